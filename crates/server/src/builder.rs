@@ -3,7 +3,7 @@ use std::net::SocketAddr;
 use std::time::Duration;
 
 use axum::response::Response;
-use axum::routing::{post, IntoMakeService};
+use axum::routing::{get, post, IntoMakeService};
 use axum::{Extension, Router};
 use hyper::server::conn::AddrIncoming;
 use hyper::{header, Method, Request, Server};
@@ -82,6 +82,17 @@ impl<TJsonRpcHandler: RpcHandler, THttpApiHandler: Clone + Send + Sync + 'static
         }
     }
 
+    /// Sets the path at which connections are upgraded to WebSockets, over which JSON-RPC
+    /// requests are served by the same object as over HTTP
+    pub fn json_rpc_ws_route(self, path: &str) -> Self {
+        Self {
+            routes: self
+                .routes
+                .route(path, get(rpc_handler::handle_socket_upgrade::<TJsonRpcHandler>)),
+            ..self
+        }
+    }
+
     /// Sets additional configuration for the [`StarknetDevnetServer`]
     pub fn set_config(self, config: ServerConfig) -> Self {
         Self { config: Some(config), ..self }
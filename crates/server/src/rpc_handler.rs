@@ -1,15 +1,22 @@
 use std::fmt::{self};
 
 use axum::extract::rejection::JsonRejection;
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
 use axum::extract::Extension;
+use axum::response::IntoResponse;
 use axum::Json;
-use futures::{future, FutureExt};
+use futures::channel::mpsc;
+use futures::{future, SinkExt, StreamExt};
 use rpc_core::error::RpcError;
-use rpc_core::request::{Request, RpcCall, RpcMethodCall};
+use rpc_core::request::{Id, Request, RpcCall, RpcMethodCall, RpcNotification, Version};
 use rpc_core::response::{Response, ResponseResult, RpcResponse};
 use serde::de::DeserializeOwned;
 use tracing::{error, trace, warn};
 
+/// Pushes messages, e.g. notifications of subscriptions, to the client of a WebSocket connection.
+/// Sending fails once the connection is closed.
+pub type NotificationSender = mpsc::UnboundedSender<serde_json::Value>;
+
 /// Helper trait that is used to execute starknet rpc calls
 #[async_trait::async_trait]
 pub trait RpcHandler: Clone + Send + Sync + 'static {
@@ -54,21 +61,119 @@ pub trait RpcHandler: Clone + Send + Sync + 'static {
             }
         }
     }
+
+    /// Invoked for every `RpcMethodCall` received over a WebSocket. Unlike over HTTP, messages can
+    /// be pushed to the client at any time with `notifications`, as long as the socket is open.
+    ///
+    /// **Note**: by default, the call is handled like one received over HTTP
+    async fn on_websocket_call(
+        &self,
+        call: RpcMethodCall,
+        _notifications: NotificationSender,
+    ) -> RpcResponse {
+        self.on_call(call).await
+    }
+
+    /// Returns whether the call only reads the state. In a batch, consecutive read-only calls are
+    /// executed concurrently, while any other call is executed on its own, after the calls
+    /// preceding it.
+    ///
+    /// **Note**: by default, no call is considered read-only, so batches are executed in order
+    fn is_read_only(&self, _call: &RpcMethodCall) -> bool {
+        false
+    }
 }
 
-/// Handles incoming JSON-RPC Request
+/// Handles incoming JSON-RPC Request, which is either a single call or a batch of calls. If no
+/// response is expected, because only notifications were received, the response body is empty.
 pub async fn handle<THandler: RpcHandler>(
-    request: Result<Json<Request>, JsonRejection>,
+    request: Result<Json<serde_json::Value>, JsonRejection>,
     Extension(handler): Extension<THandler>,
-) -> Json<Response> {
-    match request {
-        Ok(req) => handle_request(req.0, handler)
-            .await
-            .unwrap_or_else(|| Response::error(RpcError::invalid_request()))
-            .into(),
+) -> axum::response::Response {
+    let response = match request {
+        Ok(Json(request)) => handle_json_request(request, handler, None).await,
         Err(err) => {
             warn!(target: "rpc", ?err, "invalid request");
-            Response::error(RpcError::invalid_request()).into()
+            Some(Response::error(rejection_to_rpc_error(err)))
+        }
+    };
+
+    match response {
+        Some(response) => Json(response).into_response(),
+        None => ().into_response(),
+    }
+}
+
+/// Malformed JSON is a parse error, anything else, e.g. a missing content type, an invalid request
+fn rejection_to_rpc_error(rejection: JsonRejection) -> RpcError {
+    match rejection {
+        JsonRejection::JsonSyntaxError(_) => RpcError::parse_error(),
+        _ => RpcError::invalid_request(),
+    }
+}
+
+/// Upgrades the connection to a WebSocket, over which JSON-RPC requests are served
+pub async fn handle_socket_upgrade<THandler: RpcHandler>(
+    upgrade: WebSocketUpgrade,
+    Extension(handler): Extension<THandler>,
+) -> axum::response::Response {
+    upgrade.on_upgrade(move |socket| handle_socket(socket, handler))
+}
+
+/// Responds to the requests received over the socket and forwards the notifications of the
+/// handler to it, until the socket is closed
+async fn handle_socket<THandler: RpcHandler>(socket: WebSocket, handler: THandler) {
+    let (mut socket_sender, socket_receiver) = socket.split();
+    let mut socket_receiver = socket_receiver.fuse();
+    let (notification_sender, mut notification_receiver) = mpsc::unbounded();
+
+    loop {
+        let outgoing_message = futures::select! {
+            message = socket_receiver.next() => match message {
+                Some(Ok(Message::Text(text))) => {
+                    handle_socket_message(&text, handler.clone(), notification_sender.clone())
+                        .await
+                }
+                // pings are answered by axum, binary messages are not JSON-RPC requests
+                Some(Ok(Message::Binary(_) | Message::Ping(_) | Message::Pong(_))) => None,
+                Some(Ok(Message::Close(_)) | Err(_)) | None => break,
+            },
+            notification = notification_receiver.next() => {
+                notification.map(|notification| notification.to_string())
+            }
+        };
+
+        if let Some(outgoing_message) = outgoing_message {
+            if let Err(err) = socket_sender.send(Message::Text(outgoing_message)).await {
+                warn!(target: "rpc", ?err, "failed to send message over websocket");
+                break;
+            }
+        }
+    }
+
+    trace!(target: "rpc", "websocket closed");
+}
+
+/// Handles a JSON-RPC request received over a WebSocket and returns the serialized response, if
+/// any is expected
+async fn handle_socket_message<THandler: RpcHandler>(
+    text: &str,
+    handler: THandler,
+    notifications: NotificationSender,
+) -> Option<String> {
+    let response = match serde_json::from_str::<serde_json::Value>(text) {
+        Ok(request) => handle_json_request(request, handler, Some(notifications)).await?,
+        Err(err) => {
+            warn!(target: "rpc", ?err, "invalid websocket request");
+            Response::error(RpcError::parse_error())
+        }
+    };
+
+    match serde_json::to_string(&response) {
+        Ok(serialized_response) => Some(serialized_response),
+        Err(err) => {
+            error!(target: "rpc", ?err, "failed to serialize response");
+            None
         }
     }
 }
@@ -81,34 +186,101 @@ pub async fn handle_request<THandler: RpcHandler>(
     req: Request,
     handler: THandler,
 ) -> Option<Response> {
-    /// processes batch calls
-    fn responses_as_batch(outs: Vec<Option<RpcResponse>>) -> Option<Response> {
-        let batch: Vec<_> = outs.into_iter().flatten().collect();
-        (!batch.is_empty()).then_some(Response::Batch(batch))
+    match req {
+        Request::Single(call) => handle_call(call, handler, None).await.map(Response::Single),
+        Request::Batch(calls) => handle_batch(calls, handler, None).await,
     }
+}
 
+/// Handle a JSON-RPC request, which has not been deserialized yet; if it was received over a
+/// WebSocket, `notifications` pushes messages to it. The entries of a batch are deserialized one
+/// by one, so that an invalid entry only results in an error response of its own.
+async fn handle_json_request<THandler: RpcHandler>(
+    req: serde_json::Value,
+    handler: THandler,
+    notifications: Option<NotificationSender>,
+) -> Option<Response> {
     match req {
-        Request::Single(call) => handle_call(call, handler).await.map(Response::Single),
-        Request::Batch(calls) => {
-            future::join_all(calls.into_iter().map(move |call| handle_call(call, handler.clone())))
-                .map(responses_as_batch)
-                .await
+        serde_json::Value::Array(calls) if calls.is_empty() => {
+            Some(Response::error(RpcError::invalid_request()))
+        }
+        serde_json::Value::Array(calls) => {
+            let calls = calls.into_iter().map(deserialize_call).collect();
+            handle_batch(calls, handler, notifications).await
+        }
+        call => {
+            handle_call(deserialize_call(call), handler, notifications).await.map(Response::Single)
+        }
+    }
+}
+
+/// Calls which can not be deserialized at all, e.g. a number, are answered as invalid requests
+fn deserialize_call(call: serde_json::Value) -> RpcCall {
+    serde_json::from_value(call).unwrap_or(RpcCall::Invalid { id: Id::Null })
+}
+
+/// Handle the calls of a batch and return their responses in the same order. Consecutive read-only
+/// calls are handled concurrently. Notifications are executed, but have no response, so if the
+/// batch consists of notifications only, there is no response at all.
+async fn handle_batch<THandler: RpcHandler>(
+    calls: Vec<RpcCall>,
+    handler: THandler,
+    notifications: Option<NotificationSender>,
+) -> Option<Response> {
+    let mut responses = Vec::with_capacity(calls.len());
+    let mut concurrent_calls = Vec::new();
+
+    for call in calls {
+        let is_read_only = match &call {
+            RpcCall::MethodCall(method_call) => handler.is_read_only(method_call),
+            // a notification is executed like a method call, so it is subject to the same order
+            RpcCall::Notification(notification) => {
+                handler.is_read_only(&notification_to_method_call(notification.clone()))
+            }
+            RpcCall::Invalid { .. } => true,
+        };
+        if is_read_only {
+            concurrent_calls.push(call);
+            continue;
         }
+
+        responses.extend(
+            handle_concurrently(concurrent_calls.drain(..), &handler, &notifications).await,
+        );
+        responses.push(handle_call(call, handler.clone(), notifications.clone()).await);
     }
+    responses.extend(handle_concurrently(concurrent_calls, &handler, &notifications).await);
+
+    let batch: Vec<_> = responses.into_iter().flatten().collect();
+    (!batch.is_empty()).then_some(Response::Batch(batch))
+}
+
+async fn handle_concurrently<THandler: RpcHandler>(
+    calls: impl IntoIterator<Item = RpcCall>,
+    handler: &THandler,
+    notifications: &Option<NotificationSender>,
+) -> Vec<Option<RpcResponse>> {
+    future::join_all(
+        calls.into_iter().map(|call| handle_call(call, handler.clone(), notifications.clone())),
+    )
+    .await
 }
 
-/// handle a single RPC method call
+/// handle a single RPC method call or notification; only method calls have a response
 async fn handle_call<THandler: RpcHandler>(
     call: RpcCall,
     handler: THandler,
+    notifications: Option<NotificationSender>,
 ) -> Option<RpcResponse> {
     match call {
         RpcCall::MethodCall(call) => {
             trace!(target: "rpc", id = ?call.id , method = ?call.method,  "handling call");
-            Some(handler.on_call(call).await)
+            Some(dispatch_call(call, handler, notifications).await)
         }
         RpcCall::Notification(notification) => {
-            trace!(target: "rpc", method = ?notification.method, "received rpc notification");
+            trace!(target: "rpc", method = ?notification.method, "handling rpc notification");
+            // the method is executed for its effect, but the client does not expect its result
+            dispatch_call(notification_to_method_call(notification), handler, notifications).await;
             None
         }
         RpcCall::Invalid { id } => {
@@ -117,3 +289,24 @@ async fn handle_call<THandler: RpcHandler>(
         }
     }
 }
+
+async fn dispatch_call<THandler: RpcHandler>(
+    call: RpcMethodCall,
+    handler: THandler,
+    notifications: Option<NotificationSender>,
+) -> RpcResponse {
+    match notifications {
+        Some(notifications) => handler.on_websocket_call(call, notifications).await,
+        None => handler.on_call(call).await,
+    }
+}
+
+/// A notification is a method call without an id, to which no response is sent
+fn notification_to_method_call(notification: RpcNotification) -> RpcMethodCall {
+    RpcMethodCall {
+        jsonrpc: Version::V2,
+        method: notification.method,
+        params: notification.params,
+        id: Id::Null,
+    }
+}
@@ -1,10 +1,10 @@
 use axum::extract::Query;
 use axum::{Extension, Json};
-use starknet_types::contract_address::ContractAddress;
 use starknet_types::traits::ToDecimalString;
 
+use crate::api::http::endpoints::mint_token::get_balance;
 use crate::api::http::error::HttpApiError;
-use crate::api::http::models::{Balance, SerializableAccount};
+use crate::api::http::models::{Balance, BalanceQuery, SerializableAccount};
 use crate::api::http::{HttpApiHandler, HttpApiResult};
 
 pub(crate) async fn get_predeployed_accounts(
@@ -29,7 +29,12 @@ pub(crate) async fn get_predeployed_accounts(
 }
 
 pub(crate) async fn get_account_balance(
-    Query(_contract_address): Query<ContractAddress>,
+    Query(query): Query<BalanceQuery>,
+    Extension(state): Extension<HttpApiHandler>,
 ) -> HttpApiResult<Json<Balance>> {
-    Err(HttpApiError::GeneralError)
+    let starknet = state.api.starknet.read().await;
+    let amount = get_balance(&starknet, query.address, query.unit)
+        .map_err(|_| HttpApiError::GeneralError)?;
+
+    Ok(Json(Balance { amount: amount.to_str_radix(10), unit: query.unit }))
 }
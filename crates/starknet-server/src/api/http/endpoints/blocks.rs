@@ -1,15 +1,28 @@
-use axum::Json;
+use axum::{Extension, Json};
 
 use crate::api::http::error::HttpApiError;
 use crate::api::http::models::{AbortedBlocks, AbortingBlocks, CreatedBlock};
-use crate::api::http::HttpApiResult;
+use crate::api::http::{HttpApiHandler, HttpApiResult};
 
-pub(crate) async fn create_block() -> HttpApiResult<Json<CreatedBlock>> {
-    Err(HttpApiError::GeneralError)
+pub(crate) async fn create_block(
+    Extension(state): Extension<HttpApiHandler>,
+) -> HttpApiResult<Json<CreatedBlock>> {
+    let mut starknet = state.api.starknet.write().await;
+    let block_hash = starknet
+        .create_block()
+        .map_err(|err| HttpApiError::CreateBlockError { msg: err.to_string() })?;
+
+    Ok(Json(CreatedBlock { block_hash }))
 }
 
 pub(crate) async fn abort_blocks(
-    Json(_data): Json<AbortingBlocks>,
+    Json(data): Json<AbortingBlocks>,
+    Extension(state): Extension<HttpApiHandler>,
 ) -> HttpApiResult<Json<AbortedBlocks>> {
-    Err(HttpApiError::GeneralError)
+    let mut starknet = state.api.starknet.write().await;
+    let aborted = starknet
+        .abort_blocks(data.starting_block_hash)
+        .map_err(|err| HttpApiError::BlockAbortError { msg: err.to_string() })?;
+
+    Ok(Json(AbortedBlocks { aborted }))
 }
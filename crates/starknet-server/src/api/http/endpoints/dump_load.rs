@@ -13,7 +13,7 @@ pub(crate) async fn dump(
         None => {
             // path not present
             starknet
-                .dump_transactions()
+                .dump_state()
                 .map_err(|err| HttpApiError::DumpError { msg: err.to_string() })?;
             Ok(())
         }
@@ -21,13 +21,13 @@ pub(crate) async fn dump(
             if !path.is_empty() {
                 // path is present and it's not empty
                 starknet
-                    .dump_transactions_custom_path(Some(path))
+                    .dump_state_custom_path(Some(path))
                     .map_err(|err| HttpApiError::DumpError { msg: err.to_string() })?;
                 Ok(())
             } else {
                 // path is present but it's empty
                 starknet
-                    .dump_transactions()
+                    .dump_state()
                     .map_err(|err| HttpApiError::DumpError { msg: err.to_string() })?;
                 Ok(())
             }
@@ -45,10 +45,9 @@ pub(crate) async fn load(
     }
 
     let mut starknet = state.api.starknet.write().await;
-    let transactions = starknet
-        .load_transactions_custom_path(Some(path.path))
-        .map_err(|_| HttpApiError::LoadError)?;
-    starknet.re_execute(transactions).map_err(|_| HttpApiError::ReExecutionError)?;
+    starknet
+        .load_state_custom_path(Some(path.path))
+        .map_err(|err| HttpApiError::LoadError { msg: err.to_string() })?;
 
     Ok(())
 }
@@ -1,10 +1,11 @@
 use axum::{Extension, Json};
-use starknet_core::constants::ERC20_CONTRACT_ADDRESS;
+use starknet_core::constants::{ERC20_CONTRACT_ADDRESS, STRK_ERC20_CONTRACT_ADDRESS};
 use starknet_core::starknet::Starknet;
 use starknet_rs_core::types::{BlockId, BlockTag};
 use starknet_types::contract_address::ContractAddress;
 use starknet_types::felt::Felt;
 use starknet_types::num_bigint::BigUint;
+use starknet_types::rpc::estimate_message_fee::FeeUnit;
 
 use crate::api::http::error::HttpApiError;
 use crate::api::http::models::{FeeToken, MintTokensRequest, MintTokensResponse};
@@ -15,9 +16,17 @@ pub(crate) async fn get_fee_token() -> HttpApiResult<Json<FeeToken>> {
     Err(HttpApiError::GeneralError)
 }
 
-/// get the balance of the `address`
-fn get_balance(starknet: &Starknet, address: ContractAddress) -> Result<BigUint, ApiError> {
-    let erc20_address = Felt::from_prefixed_hex_str(ERC20_CONTRACT_ADDRESS).unwrap();
+/// get the balance of the `address` in the fee token denominated by `unit`
+pub(crate) fn get_balance(
+    starknet: &Starknet,
+    address: ContractAddress,
+    unit: FeeUnit,
+) -> Result<BigUint, ApiError> {
+    let erc20_address = match unit {
+        FeeUnit::Wei => ERC20_CONTRACT_ADDRESS,
+        FeeUnit::Fri => STRK_ERC20_CONTRACT_ADDRESS,
+    };
+    let erc20_address = Felt::from_prefixed_hex_str(erc20_address).unwrap();
     let balance_selector =
         starknet_rs_core::utils::get_selector_from_name("balanceOf").unwrap().into();
     let new_balance_raw = starknet.call(
@@ -47,16 +56,16 @@ pub(crate) async fn mint(
     let mut starknet = state.api.starknet.write().await;
 
     let tx_hash = starknet
-        .mint(request.address, request.amount)
+        .mint(request.address, request.amount, request.unit)
         .await
         .map_err(|err| HttpApiError::MintingError { msg: err.to_string() })?;
 
-    let new_balance = get_balance(&starknet, request.address)
+    let new_balance = get_balance(&starknet, request.address, request.unit)
         .map_err(|err| HttpApiError::MintingError { msg: err.to_string() })?;
 
     Ok(Json(MintTokensResponse {
         new_balance: new_balance.to_str_radix(10),
-        unit: "WEI".to_string(),
+        unit: request.unit,
         tx_hash,
     }))
 }
@@ -1,8 +1,8 @@
-use axum::Json;
+use axum::{Extension, Json};
 
 use super::error::HttpApiError;
 use super::models::ForkStatus;
-use super::HttpApiResult;
+use super::{HttpApiHandler, HttpApiResult};
 
 /// Dumping and loading
 pub(crate) mod dump_load;
@@ -13,6 +13,9 @@ pub(crate) mod postman;
 /// Blocks
 pub(crate) mod blocks;
 
+/// Snapshots
+pub(crate) mod snapshots;
+
 /// Time
 pub(crate) mod time;
 
@@ -22,17 +25,29 @@ pub(crate) mod accounts;
 /// Mint token - Local faucet
 pub(crate) mod mint_token;
 
+/// Transactions
+pub(crate) mod transactions;
+
 /// Is alive
 pub(crate) async fn is_alive() -> HttpApiResult<String> {
     Ok("Alive!!!".to_string())
 }
 
 /// Restart
-pub(crate) async fn restart() -> HttpApiResult<()> {
-    Err(HttpApiError::GeneralError)
+pub(crate) async fn restart(Extension(state): Extension<HttpApiHandler>) -> HttpApiResult<()> {
+    let mut starknet = state.api.starknet.write().await;
+    starknet.restart().map_err(|err| HttpApiError::RestartError { msg: err.to_string() })
 }
 
 /// Fork
-pub(crate) async fn get_fork_status() -> HttpApiResult<Json<ForkStatus>> {
-    Err(HttpApiError::GeneralError)
+pub(crate) async fn get_fork_status(
+    Extension(state): Extension<HttpApiHandler>,
+) -> HttpApiResult<Json<ForkStatus>> {
+    let starknet = state.api.starknet.read().await;
+    let fork_config = &starknet.config.fork_config;
+
+    Ok(Json(ForkStatus {
+        url: fork_config.url.as_ref().map(|url| url.to_string()),
+        block: fork_config.block,
+    }))
 }
@@ -1,30 +1,68 @@
-use axum::Json;
-use starknet_types::felt::TransactionHash;
+use axum::{Extension, Json};
+use starknet_types::rpc::messaging::MessageToL2;
+use starknet_types::rpc::transaction_receipt::MessageToL1;
+use url::Url;
 
 use crate::api::http::error::HttpApiError;
 use crate::api::http::models::{
-    MessageFromL2, MessageHash, MessageToL2, PostmanLoadL1MessagingContract,
+    FlushedMessages, MessageHash, MessagingContractAddress, PostmanLoadL1MessagingContract,
+    TransactionHashOutput,
 };
-use crate::api::http::HttpApiResult;
+use crate::api::http::{HttpApiHandler, HttpApiResult};
 
 pub(crate) async fn postman_load(
-    Json(_l1_contract): Json<PostmanLoadL1MessagingContract>,
-) -> HttpApiResult<()> {
-    Err(HttpApiError::GeneralError)
+    Json(l1_contract): Json<PostmanLoadL1MessagingContract>,
+    Extension(state): Extension<HttpApiHandler>,
+) -> HttpApiResult<Json<MessagingContractAddress>> {
+    let l1_node_url = Url::parse(&l1_contract.network_url).map_err(|err| {
+        HttpApiError::MessagingError { msg: format!("Invalid network URL: {err}") }
+    })?;
+
+    let mut starknet = state.api.starknet.write().await;
+    let messaging_contract_address = starknet
+        .configure_messaging(l1_node_url, l1_contract.address)
+        .map_err(|err| HttpApiError::MessagingError { msg: err.to_string() })?;
+
+    Ok(Json(MessagingContractAddress { messaging_contract_address }))
 }
 
-pub(crate) async fn postman_flush() -> HttpApiResult<()> {
-    Err(HttpApiError::GeneralError)
+pub(crate) async fn postman_flush(
+    Extension(state): Extension<HttpApiHandler>,
+) -> HttpApiResult<Json<FlushedMessages>> {
+    let mut starknet = state.api.starknet.write().await;
+    let flushed_messages = starknet
+        .flush_messages()
+        .map_err(|err| HttpApiError::MessagingError { msg: err.to_string() })?;
+    let l1_provider = starknet.messaging_l1_node_url().map(Url::to_string).unwrap_or_default();
+
+    Ok(Json(FlushedMessages {
+        messages_to_l1: flushed_messages.messages_to_l1,
+        messages_to_l2: flushed_messages.messages_to_l2,
+        generated_l2_transactions: flushed_messages.generated_l2_transactions,
+        l1_provider,
+    }))
 }
 
 pub(crate) async fn postman_send_message_to_l2(
-    Json(_data): Json<MessageToL2>,
-) -> HttpApiResult<Json<TransactionHash>> {
-    Err(HttpApiError::GeneralError)
+    Json(message): Json<MessageToL2>,
+    Extension(state): Extension<HttpApiHandler>,
+) -> HttpApiResult<Json<TransactionHashOutput>> {
+    let mut starknet = state.api.starknet.write().await;
+    let transaction_hash = starknet
+        .send_message_to_l2(&message)
+        .map_err(|err| HttpApiError::MessagingError { msg: err.to_string() })?;
+
+    Ok(Json(TransactionHashOutput { transaction_hash }))
 }
 
 pub(crate) async fn postman_consume_message_from_l2(
-    Json(_data): Json<MessageFromL2>,
+    Json(message): Json<MessageToL1>,
+    Extension(state): Extension<HttpApiHandler>,
 ) -> HttpApiResult<Json<MessageHash>> {
-    Err(HttpApiError::GeneralError)
+    let mut starknet = state.api.starknet.write().await;
+    let message_hash = starknet
+        .consume_message_from_l2(&message)
+        .map_err(|err| HttpApiError::MessagingError { msg: err.to_string() })?;
+
+    Ok(Json(MessageHash { message_hash }))
 }
@@ -0,0 +1,24 @@
+use axum::{Extension, Json};
+
+use crate::api::http::error::HttpApiError;
+use crate::api::http::models::{CreatedSnapshot, RevertingSnapshot};
+use crate::api::http::{HttpApiHandler, HttpApiResult};
+
+pub(crate) async fn snapshot(
+    Extension(state): Extension<HttpApiHandler>,
+) -> HttpApiResult<Json<CreatedSnapshot>> {
+    let mut starknet = state.api.starknet.write().await;
+    let snapshot_id = starknet.snapshot();
+
+    Ok(Json(CreatedSnapshot { snapshot_id }))
+}
+
+pub(crate) async fn revert(
+    Json(data): Json<RevertingSnapshot>,
+    Extension(state): Extension<HttpApiHandler>,
+) -> HttpApiResult<()> {
+    let mut starknet = state.api.starknet.write().await;
+    starknet
+        .revert_to_snapshot(data.snapshot_id)
+        .map_err(|err| HttpApiError::SnapshotRevertError { msg: err.to_string() })
+}
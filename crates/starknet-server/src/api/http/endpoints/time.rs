@@ -1,13 +1,29 @@
-use axum::Json;
+use axum::{Extension, Json};
 
 use crate::api::http::error::HttpApiError;
-use crate::api::http::models::Time;
-use crate::api::http::HttpApiResult;
+use crate::api::http::models::{IncreaseTime, IncreaseTimeResponse, SetTime, SetTimeResponse};
+use crate::api::http::{HttpApiHandler, HttpApiResult};
 
-pub(crate) async fn set_time(Json(_data): Json<Time>) -> HttpApiResult<()> {
-    Err(HttpApiError::GeneralError)
+pub(crate) async fn set_time(
+    Json(data): Json<SetTime>,
+    Extension(state): Extension<HttpApiHandler>,
+) -> HttpApiResult<Json<SetTimeResponse>> {
+    let mut starknet = state.api.starknet.write().await;
+    let block_hash = starknet
+        .set_time(data.time, data.generate_block.unwrap_or(false))
+        .map_err(|err| HttpApiError::TimeError { msg: err.to_string() })?;
+
+    Ok(Json(SetTimeResponse { block_timestamp: data.time, block_hash }))
 }
 
-pub(crate) async fn increase_time(Json(_data): Json<Time>) -> HttpApiResult<()> {
-    Err(HttpApiError::GeneralError)
+pub(crate) async fn increase_time(
+    Json(data): Json<IncreaseTime>,
+    Extension(state): Extension<HttpApiHandler>,
+) -> HttpApiResult<Json<IncreaseTimeResponse>> {
+    let mut starknet = state.api.starknet.write().await;
+    starknet
+        .increase_time(data.time)
+        .map_err(|err| HttpApiError::TimeError { msg: err.to_string() })?;
+
+    Ok(Json(IncreaseTimeResponse { timestamp_increased_by: data.time }))
 }
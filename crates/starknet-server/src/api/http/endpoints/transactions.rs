@@ -0,0 +1,21 @@
+use axum::{Extension, Json};
+
+use crate::api::http::models::RejectedTransaction;
+use crate::api::http::{HttpApiHandler, HttpApiResult};
+
+pub(crate) async fn get_rejected_transactions(
+    Extension(state): Extension<HttpApiHandler>,
+) -> HttpApiResult<Json<Vec<RejectedTransaction>>> {
+    let starknet = state.api.starknet.read().await;
+    let rejected_transactions = starknet
+        .get_rejected_transactions()
+        .into_iter()
+        .map(|transaction| RejectedTransaction {
+            transaction_hash: *transaction.inner.get_transaction_hash(),
+            transaction: transaction.inner.clone(),
+            failure_reason: transaction.get_rejection_reason().unwrap_or_default().to_string(),
+        })
+        .collect();
+
+    Ok(Json(rejected_transactions))
+}
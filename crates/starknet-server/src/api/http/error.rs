@@ -14,10 +14,20 @@ pub enum HttpApiError {
     FileNotFound,
     #[error("The dump operation failed")]
     DumpError { msg: String },
-    #[error("The load operation failed")]
-    LoadError,
-    #[error("The re-execution operation failed")]
-    ReExecutionError,
+    #[error("The load operation failed: {msg}")]
+    LoadError { msg: String },
+    #[error("The restart operation failed: {msg}")]
+    RestartError { msg: String },
+    #[error("Block creation failed: {msg}")]
+    CreateBlockError { msg: String },
+    #[error("Block abortion failed: {msg}")]
+    BlockAbortError { msg: String },
+    #[error("Snapshot revert failed: {msg}")]
+    SnapshotRevertError { msg: String },
+    #[error("Time manipulation failed: {msg}")]
+    TimeError { msg: String },
+    #[error("Messaging failed: {msg}")]
+    MessagingError { msg: String },
 }
 
 impl IntoResponse for HttpApiError {
@@ -30,13 +40,24 @@ impl IntoResponse for HttpApiError {
                 (StatusCode::BAD_REQUEST, String::from("file does not exist"))
             }
             err @ HttpApiError::DumpError { msg: _ } => (StatusCode::BAD_REQUEST, err.to_string()),
-            HttpApiError::LoadError => {
-                (StatusCode::BAD_REQUEST, String::from("load operation failed"))
+            err @ HttpApiError::LoadError { msg: _ } => (StatusCode::BAD_REQUEST, err.to_string()),
+            err @ HttpApiError::MintingError { msg: _ } => {
+                (StatusCode::BAD_REQUEST, err.to_string())
             }
-            HttpApiError::ReExecutionError => {
-                (StatusCode::BAD_REQUEST, String::from("re-execution operation failed"))
+            err @ HttpApiError::RestartError { msg: _ } => {
+                (StatusCode::INTERNAL_SERVER_ERROR, err.to_string())
             }
-            err @ HttpApiError::MintingError { msg: _ } => {
+            err @ HttpApiError::CreateBlockError { msg: _ } => {
+                (StatusCode::INTERNAL_SERVER_ERROR, err.to_string())
+            }
+            err @ HttpApiError::BlockAbortError { msg: _ } => {
+                (StatusCode::BAD_REQUEST, err.to_string())
+            }
+            err @ HttpApiError::SnapshotRevertError { msg: _ } => {
+                (StatusCode::BAD_REQUEST, err.to_string())
+            }
+            err @ HttpApiError::TimeError { msg: _ } => (StatusCode::BAD_REQUEST, err.to_string()),
+            err @ HttpApiError::MessagingError { msg: _ } => {
                 (StatusCode::BAD_REQUEST, err.to_string())
             }
         };
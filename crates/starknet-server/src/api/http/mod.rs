@@ -1,7 +1,7 @@
 pub(crate) mod endpoints;
 pub(crate) mod error;
 #[allow(unused)]
-mod models;
+pub(crate) mod models;
 
 use self::error::HttpApiError;
 use super::Api;
@@ -1,66 +1,107 @@
+use primitive_types::{H160, H256};
 use serde::{Deserialize, Serialize};
+use starknet_core::starknet::SnapshotId;
 use starknet_types::contract_address::ContractAddress;
-use starknet_types::felt::{BlockHash, Calldata, EntryPointSelector, Felt, Nonce, TransactionHash};
-use starknet_types::starknet_api::transaction::Fee;
-
-#[derive(Deserialize, Debug)]
-pub(crate) struct DumpPath {
+use starknet_types::felt::{BlockHash, Felt, TransactionHash};
+use starknet_types::rpc::estimate_message_fee::FeeUnit;
+use starknet_types::rpc::messaging::MessageToL2;
+use starknet_types::rpc::transaction_receipt::MessageToL1;
+use starknet_types::rpc::transactions::Transaction;
+
+#[derive(Deserialize, Debug, Default)]
+pub struct DumpPath {
     pub path: Option<String>,
 }
 
 #[derive(Deserialize, Debug)]
-pub(crate) struct LoadPath {
+pub struct LoadPath {
     pub path: String,
 }
 
 #[derive(Deserialize, Debug)]
-pub(crate) struct PostmanLoadL1MessagingContract {
+pub struct PostmanLoadL1MessagingContract {
     #[serde(rename = "networkUrl")]
-    network_url: String,
-    address: ContractAddress,
+    pub(crate) network_url: String,
+    pub(crate) address: Option<H160>,
 }
 
-#[derive(Deserialize)]
-pub(crate) struct MessageToL2 {
-    l2_contract_address: ContractAddress,
-    entry_point_selector: EntryPointSelector,
-    l1_contract_addresss: ContractAddress,
-    payload: Calldata,
-    paid_fee_on_l1: Fee,
-    nonce: Nonce,
+#[derive(Serialize)]
+pub(crate) struct MessagingContractAddress {
+    pub(crate) messaging_contract_address: H160,
 }
 
-#[derive(Deserialize)]
-pub(crate) struct MessageFromL2 {
-    l2_contract_address: ContractAddress,
-    l1_contract_addresss: ContractAddress,
-    payload: Calldata,
+#[derive(Serialize)]
+pub(crate) struct FlushedMessages {
+    pub(crate) messages_to_l1: Vec<MessageToL1>,
+    pub(crate) messages_to_l2: Vec<MessageToL2>,
+    pub(crate) generated_l2_transactions: Vec<TransactionHash>,
+    pub(crate) l1_provider: String,
+}
+
+#[derive(Serialize)]
+pub(crate) struct TransactionHashOutput {
+    pub(crate) transaction_hash: TransactionHash,
+}
+
+#[derive(Serialize)]
+pub(crate) struct RejectedTransaction {
+    pub(crate) transaction_hash: TransactionHash,
+    pub(crate) transaction: Transaction,
+    pub(crate) failure_reason: String,
 }
 
 #[derive(Serialize)]
 pub(crate) struct MessageHash {
-    message_hash: Felt,
+    pub(crate) message_hash: H256,
 }
 
 #[derive(Serialize)]
 pub(crate) struct CreatedBlock {
-    block_hash: BlockHash,
+    pub(crate) block_hash: BlockHash,
 }
 
-#[derive(Deserialize)]
-pub(crate) struct AbortingBlocks {
+#[derive(Debug, Deserialize)]
+pub struct AbortingBlocks {
     #[serde(rename = "startingBlockHash")]
-    starting_block_hash: BlockHash,
+    pub(crate) starting_block_hash: BlockHash,
 }
 
 #[derive(Serialize)]
 pub(crate) struct AbortedBlocks {
-    aborted: Vec<BlockHash>,
+    pub(crate) aborted: Vec<BlockHash>,
+}
+
+#[derive(Serialize)]
+pub(crate) struct CreatedSnapshot {
+    pub(crate) snapshot_id: SnapshotId,
 }
 
-#[derive(Deserialize)]
-pub(crate) struct Time {
-    time: u64,
+#[derive(Debug, Deserialize)]
+pub struct RevertingSnapshot {
+    pub(crate) snapshot_id: SnapshotId,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetTime {
+    pub(crate) time: u64,
+    pub(crate) generate_block: Option<bool>,
+}
+
+#[derive(Serialize)]
+pub(crate) struct SetTimeResponse {
+    pub(crate) block_timestamp: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) block_hash: Option<BlockHash>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct IncreaseTime {
+    pub(crate) time: u64,
+}
+
+#[derive(Serialize)]
+pub(crate) struct IncreaseTimeResponse {
+    pub(crate) timestamp_increased_by: u64,
 }
 
 #[derive(Serialize)]
@@ -73,8 +114,16 @@ pub(crate) struct SerializableAccount {
 
 #[derive(Serialize)]
 pub(crate) struct Balance {
-    amount: u128,
-    unit: String,
+    /// decimal repr
+    pub(crate) amount: String,
+    pub(crate) unit: FeeUnit,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BalanceQuery {
+    pub(crate) address: ContractAddress,
+    #[serde(default)]
+    pub(crate) unit: FeeUnit,
 }
 
 #[derive(Serialize)]
@@ -84,21 +133,25 @@ pub(crate) struct FeeToken {
 }
 
 #[derive(Debug, Deserialize)]
-pub(crate) struct MintTokensRequest {
+pub struct MintTokensRequest {
     pub(crate) address: ContractAddress,
     pub(crate) amount: u128,
+    #[serde(default)]
+    pub(crate) unit: FeeUnit,
 }
 
 #[derive(Serialize)]
 pub(crate) struct MintTokensResponse {
     /// decimal repr
     pub(crate) new_balance: String,
-    pub(crate) unit: String,
+    pub(crate) unit: FeeUnit,
     pub(crate) tx_hash: TransactionHash,
 }
 
 #[derive(Serialize)]
 pub(crate) struct ForkStatus {
-    url: String,
-    block: u128,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) block: Option<u64>,
 }
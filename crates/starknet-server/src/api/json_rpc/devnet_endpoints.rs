@@ -0,0 +1,268 @@
+use server::rpc_core::error::RpcError;
+use starknet_core::error::Error;
+use starknet_types::rpc::messaging::MessageToL2;
+use starknet_types::rpc::transaction_receipt::MessageToL1;
+use starknet_types::traits::ToDecimalString;
+use url::Url;
+
+use super::error::ApiError;
+use crate::api::http::endpoints::mint_token::get_balance;
+use crate::api::http::models::{
+    AbortedBlocks, AbortingBlocks, Balance, BalanceQuery, CreatedBlock, CreatedSnapshot, DumpPath,
+    FlushedMessages, ForkStatus, IncreaseTime, IncreaseTimeResponse, LoadPath, MessageHash,
+    MessagingContractAddress, MintTokensRequest, MintTokensResponse,
+    PostmanLoadL1MessagingContract, RejectedTransaction, RevertingSnapshot, SerializableAccount,
+    SetTime, SetTimeResponse, TransactionHashOutput,
+};
+use crate::api::json_rpc::error::RpcResult;
+use crate::api::json_rpc::JsonRpcHandler;
+
+/// here are the implementations of the Devnet-specific JSON-RPC methods, which provide the
+/// functionality of the HTTP endpoints of the same name
+impl JsonRpcHandler {
+    /// devnet_mint
+    pub(crate) async fn mint(&self, request: MintTokensRequest) -> RpcResult<MintTokensResponse> {
+        let mut starknet = self.api.starknet.write().await;
+
+        let tx_hash = starknet
+            .mint(request.address, request.amount, request.unit)
+            .await
+            .map_err(|err| ApiError::MintingError { msg: err.to_string() })?;
+
+        let new_balance = get_balance(&starknet, request.address, request.unit)
+            .map_err(|err| ApiError::MintingError { msg: err.to_string() })?;
+
+        Ok(MintTokensResponse {
+            new_balance: new_balance.to_str_radix(10),
+            unit: request.unit,
+            tx_hash,
+        })
+    }
+
+    /// devnet_getAccountBalance
+    pub(crate) async fn get_account_balance(&self, query: BalanceQuery) -> RpcResult<Balance> {
+        let starknet = self.api.starknet.read().await;
+        let amount = get_balance(&starknet, query.address, query.unit)
+            .map_err(|err| ApiError::BalanceError { msg: err.to_string() })?;
+
+        Ok(Balance { amount: amount.to_str_radix(10), unit: query.unit })
+    }
+
+    /// devnet_getPredeployedAccounts
+    pub(crate) async fn get_predeployed_accounts(&self) -> RpcResult<Vec<SerializableAccount>> {
+        let predeployed_accounts = self
+            .api
+            .starknet
+            .read()
+            .await
+            .get_predeployed_accounts()
+            .into_iter()
+            .map(|acc| SerializableAccount {
+                initial_balance: acc.initial_balance.to_decimal_string(),
+                address: acc.account_address,
+                public_key: acc.public_key,
+                private_key: acc.private_key,
+            })
+            .collect();
+
+        Ok(predeployed_accounts)
+    }
+
+    /// devnet_dump
+    pub(crate) async fn dump(&self, path: DumpPath) -> RpcResult<()> {
+        let starknet = self.api.starknet.read().await;
+        match path.path {
+            Some(path) if !path.is_empty() => starknet.dump_state_custom_path(Some(path)),
+            _ => starknet.dump_state(),
+        }
+        .map_err(|err| ApiError::DumpError { msg: err.to_string() })
+    }
+
+    /// devnet_load
+    pub(crate) async fn load(&self, path: LoadPath) -> RpcResult<()> {
+        if path.path.is_empty() || !std::path::Path::new(&path.path).exists() {
+            return Err(ApiError::LoadError { msg: format!("file {} does not exist", path.path) });
+        }
+
+        let mut starknet = self.api.starknet.write().await;
+        starknet
+            .load_state_custom_path(Some(path.path))
+            .map_err(|err| ApiError::LoadError { msg: err.to_string() })
+    }
+
+    /// devnet_restart
+    pub(crate) async fn restart(&self) -> RpcResult<()> {
+        self.api
+            .starknet
+            .write()
+            .await
+            .restart()
+            .map_err(|err| ApiError::RestartError { msg: err.to_string() })
+    }
+
+    /// devnet_createBlock
+    pub(crate) async fn create_block(&self) -> RpcResult<CreatedBlock> {
+        let block_hash = self
+            .api
+            .starknet
+            .write()
+            .await
+            .create_block()
+            .map_err(|err| ApiError::BlockCreationError { msg: err.to_string() })?;
+
+        Ok(CreatedBlock { block_hash })
+    }
+
+    /// devnet_abortBlocks
+    pub(crate) async fn abort_blocks(&self, data: AbortingBlocks) -> RpcResult<AbortedBlocks> {
+        let aborted = self
+            .api
+            .starknet
+            .write()
+            .await
+            .abort_blocks(data.starting_block_hash)
+            .map_err(|err| ApiError::BlockAbortionError { msg: err.to_string() })?;
+
+        Ok(AbortedBlocks { aborted })
+    }
+
+    /// devnet_snapshot
+    pub(crate) async fn snapshot(&self) -> RpcResult<CreatedSnapshot> {
+        let snapshot_id = self.api.starknet.write().await.snapshot();
+
+        Ok(CreatedSnapshot { snapshot_id })
+    }
+
+    /// devnet_revert
+    pub(crate) async fn revert(&self, data: RevertingSnapshot) -> RpcResult<()> {
+        self.api.starknet.write().await.revert_to_snapshot(data.snapshot_id).map_err(
+            |err| match err {
+                Error::NoSnapshot { snapshot_id } => ApiError::SnapshotNotFound { snapshot_id },
+                other_error => ApiError::RevertError { msg: other_error.to_string() },
+            },
+        )
+    }
+
+    /// devnet_setTime
+    pub(crate) async fn set_time(&self, data: SetTime) -> RpcResult<SetTimeResponse> {
+        let block_hash = self
+            .api
+            .starknet
+            .write()
+            .await
+            .set_time(data.time, data.generate_block.unwrap_or(false))
+            .map_err(|err| ApiError::TimeError { msg: err.to_string() })?;
+
+        Ok(SetTimeResponse { block_timestamp: data.time, block_hash })
+    }
+
+    /// devnet_increaseTime
+    pub(crate) async fn increase_time(
+        &self,
+        data: IncreaseTime,
+    ) -> RpcResult<IncreaseTimeResponse> {
+        self.api
+            .starknet
+            .write()
+            .await
+            .increase_time(data.time)
+            .map_err(|err| ApiError::TimeError { msg: err.to_string() })?;
+
+        Ok(IncreaseTimeResponse { timestamp_increased_by: data.time })
+    }
+
+    /// devnet_postmanLoad
+    pub(crate) async fn postman_load(
+        &self,
+        l1_contract: PostmanLoadL1MessagingContract,
+    ) -> RpcResult<MessagingContractAddress> {
+        let l1_node_url = Url::parse(&l1_contract.network_url).map_err(|err| {
+            ApiError::RpcError(RpcError::invalid_params(format!("Invalid network URL: {err}")))
+        })?;
+
+        let messaging_contract_address = self
+            .api
+            .starknet
+            .write()
+            .await
+            .configure_messaging(l1_node_url, l1_contract.address)
+            .map_err(|err| ApiError::PostmanError { msg: err.to_string() })?;
+
+        Ok(MessagingContractAddress { messaging_contract_address })
+    }
+
+    /// devnet_postmanFlush
+    pub(crate) async fn postman_flush(&self) -> RpcResult<FlushedMessages> {
+        let mut starknet = self.api.starknet.write().await;
+        let flushed_messages = starknet
+            .flush_messages()
+            .map_err(|err| ApiError::PostmanError { msg: err.to_string() })?;
+        let l1_provider = starknet.messaging_l1_node_url().map(Url::to_string).unwrap_or_default();
+
+        Ok(FlushedMessages {
+            messages_to_l1: flushed_messages.messages_to_l1,
+            messages_to_l2: flushed_messages.messages_to_l2,
+            generated_l2_transactions: flushed_messages.generated_l2_transactions,
+            l1_provider,
+        })
+    }
+
+    /// devnet_postmanSendMessageToL2
+    pub(crate) async fn postman_send_message_to_l2(
+        &self,
+        message: MessageToL2,
+    ) -> RpcResult<TransactionHashOutput> {
+        let transaction_hash = self
+            .api
+            .starknet
+            .write()
+            .await
+            .send_message_to_l2(&message)
+            .map_err(|err| ApiError::PostmanError { msg: err.to_string() })?;
+
+        Ok(TransactionHashOutput { transaction_hash })
+    }
+
+    /// devnet_postmanConsumeMessageFromL2
+    pub(crate) async fn postman_consume_message_from_l2(
+        &self,
+        message: MessageToL1,
+    ) -> RpcResult<MessageHash> {
+        let message_hash = self
+            .api
+            .starknet
+            .write()
+            .await
+            .consume_message_from_l2(&message)
+            .map_err(|err| ApiError::PostmanError { msg: err.to_string() })?;
+
+        Ok(MessageHash { message_hash })
+    }
+
+    /// devnet_getRejectedTransactions
+    pub(crate) async fn get_rejected_transactions(&self) -> RpcResult<Vec<RejectedTransaction>> {
+        let starknet = self.api.starknet.read().await;
+        let rejected_transactions = starknet
+            .get_rejected_transactions()
+            .into_iter()
+            .map(|transaction| RejectedTransaction {
+                transaction_hash: *transaction.inner.get_transaction_hash(),
+                transaction: transaction.inner.clone(),
+                failure_reason: transaction.get_rejection_reason().unwrap_or_default().to_string(),
+            })
+            .collect();
+
+        Ok(rejected_transactions)
+    }
+
+    /// devnet_getForkStatus
+    pub(crate) async fn get_fork_status(&self) -> RpcResult<ForkStatus> {
+        let starknet = self.api.starknet.read().await;
+        let fork_config = &starknet.config.fork_config;
+
+        Ok(ForkStatus {
+            url: fork_config.url.as_ref().map(|url| url.to_string()),
+            block: fork_config.block,
+        })
+    }
+}
@@ -4,10 +4,11 @@ use starknet_types::contract_address::ContractAddress;
 use starknet_types::felt::{ClassHash, Felt, TransactionHash};
 use starknet_types::rpc::block::{Block, BlockHeader, BlockId};
 use starknet_types::rpc::estimate_message_fee::FeeEstimateWrapper;
+use starknet_types::rpc::storage_proof::{ContractStorageKeys, StorageProof};
 use starknet_types::rpc::transaction_receipt::TransactionReceipt;
 use starknet_types::rpc::transactions::{
-    BroadcastedTransaction, EventFilter, EventsChunk, FunctionCall, SimulatedTransaction,
-    SimulationFlag, Transaction,
+    BlockTransactionTrace, BroadcastedTransaction, EventFilter, EventsChunk, FunctionCall,
+    SimulatedTransaction, SimulationFlag, Transaction, TransactionStatus, TransactionTrace,
 };
 use starknet_types::starknet_api::block::BlockNumber;
 use starknet_types::traits::ToHexString;
@@ -175,6 +176,19 @@ impl JsonRpcHandler {
         }
     }
 
+    /// starknet_getTransactionStatus
+    pub(crate) async fn get_transaction_status_by_hash(
+        &self,
+        transaction_hash: TransactionHash,
+    ) -> RpcResult<TransactionStatus> {
+        self.api.starknet.read().await.get_transaction_status(transaction_hash).map_err(|err| {
+            match err {
+                Error::NoTransaction => ApiError::TransactionNotFound,
+                unknown_error => ApiError::StarknetDevnetError(unknown_error),
+            }
+        })
+    }
+
     /// starknet_getClass
     pub(crate) async fn get_class(
         &self,
@@ -386,4 +400,61 @@ impl JsonRpcHandler {
             Err(err) => Err(ApiError::ContractError { msg: err.to_string() }),
         }
     }
+
+    /// starknet_getStorageProof
+    pub(crate) async fn get_storage_proof(
+        &self,
+        block_id: BlockId,
+        class_hashes: Vec<ClassHash>,
+        contract_addresses: Vec<ContractAddress>,
+        contracts_storage_keys: Vec<ContractStorageKeys>,
+    ) -> RpcResult<StorageProof> {
+        self.api
+            .starknet
+            .read()
+            .await
+            .get_storage_proof(
+                block_id.into(),
+                &class_hashes,
+                &contract_addresses,
+                &contracts_storage_keys,
+            )
+            .map_err(|err| match err {
+                Error::NoBlock => ApiError::BlockNotFound,
+                Error::NoStateAtBlock { block_number: _ } | Error::UnsupportedAction { .. } => {
+                    ApiError::StorageProofNotSupported
+                }
+                unknown_error => ApiError::StarknetDevnetError(unknown_error),
+            })
+    }
+
+    /// starknet_traceTransaction
+    pub(crate) async fn get_trace_transaction(
+        &self,
+        transaction_hash: TransactionHash,
+    ) -> RpcResult<TransactionTrace> {
+        self.api.starknet.read().await.get_transaction_trace(transaction_hash).map_err(|err| {
+            match err {
+                Error::NoTransaction => ApiError::TransactionNotFound,
+                Error::NoTransactionTrace => ApiError::NoTraceAvailable,
+                Error::UnsupportedAction { msg } => ApiError::UnsupportedAction { msg },
+                unknown_error => ApiError::StarknetDevnetError(unknown_error),
+            }
+        })
+    }
+
+    /// starknet_traceBlockTransactions
+    pub(crate) async fn get_trace_block_transactions(
+        &self,
+        block_id: BlockId,
+    ) -> RpcResult<Vec<BlockTransactionTrace>> {
+        self.api.starknet.read().await.get_transaction_traces_from_block(block_id.into()).map_err(
+            |err| match err {
+                Error::NoBlock => ApiError::BlockNotFound,
+                Error::NoTransactionTrace => ApiError::NoTraceAvailable,
+                Error::UnsupportedAction { msg } => ApiError::UnsupportedAction { msg },
+                unknown_error => ApiError::StarknetDevnetError(unknown_error),
+            },
+        )
+    }
 }
@@ -1,9 +1,14 @@
 use server::rpc_core::error::RpcError;
+use starknet_core::starknet::SnapshotId;
 use starknet_types;
 use thiserror::Error;
 use tracing::error;
 
-use super::WILDCARD_RPC_ERROR_CODE;
+use super::{
+    BALANCE_ERROR_CODE, BLOCK_ABORTION_ERROR_CODE, BLOCK_CREATION_ERROR_CODE, DUMP_ERROR_CODE,
+    LOAD_ERROR_CODE, MINTING_ERROR_CODE, POSTMAN_ERROR_CODE, RESTART_ERROR_CODE,
+    SNAPSHOT_ERROR_CODE, TIME_ERROR_CODE, WILDCARD_RPC_ERROR_CODE,
+};
 
 #[allow(unused)]
 #[derive(Error, Debug)]
@@ -50,6 +55,35 @@ pub enum ApiError {
     InsufficientAccountBalance,
     #[error("Account validation failed")]
     ValidationFailure,
+    #[error("Storage proofs are not supported for the requested block")]
+    StorageProofNotSupported,
+    #[error("Invalid subscription id")]
+    InvalidSubscriptionId,
+    #[error("No trace available for transaction")]
+    NoTraceAvailable,
+    /// Errors of the `devnet_*` methods, with codes outside of those of the specification
+    #[error("Minting error: {msg}")]
+    MintingError { msg: String },
+    #[error("The dump operation failed: {msg}")]
+    DumpError { msg: String },
+    #[error("The load operation failed: {msg}")]
+    LoadError { msg: String },
+    #[error("Snapshot with id {snapshot_id} does not exist")]
+    SnapshotNotFound { snapshot_id: SnapshotId },
+    #[error("Reverting to the snapshot failed: {msg}")]
+    RevertError { msg: String },
+    #[error("Time manipulation failed: {msg}")]
+    TimeError { msg: String },
+    #[error("The restart failed: {msg}")]
+    RestartError { msg: String },
+    #[error("Block creation failed: {msg}")]
+    BlockCreationError { msg: String },
+    #[error("Aborting blocks failed: {msg}")]
+    BlockAbortionError { msg: String },
+    #[error("The postman operation failed: {msg}")]
+    PostmanError { msg: String },
+    #[error("Balance query failed: {msg}")]
+    BalanceError { msg: String },
 }
 
 impl ApiError {
@@ -151,6 +185,76 @@ impl ApiError {
                 message: err.to_string().into(),
                 data: None,
             },
+            err @ ApiError::StorageProofNotSupported => RpcError {
+                code: server::rpc_core::error::ErrorCode::ServerError(42),
+                message: err.to_string().into(),
+                data: None,
+            },
+            err @ ApiError::InvalidSubscriptionId => RpcError {
+                code: server::rpc_core::error::ErrorCode::ServerError(66),
+                message: err.to_string().into(),
+                data: None,
+            },
+            err @ ApiError::NoTraceAvailable => RpcError {
+                code: server::rpc_core::error::ErrorCode::ServerError(10),
+                message: err.to_string().into(),
+                data: None,
+            },
+            err @ ApiError::MintingError { .. } => RpcError {
+                code: server::rpc_core::error::ErrorCode::ServerError(MINTING_ERROR_CODE),
+                message: err.to_string().into(),
+                data: None,
+            },
+            err @ ApiError::DumpError { .. } => RpcError {
+                code: server::rpc_core::error::ErrorCode::ServerError(DUMP_ERROR_CODE),
+                message: err.to_string().into(),
+                data: None,
+            },
+            err @ ApiError::LoadError { .. } => RpcError {
+                code: server::rpc_core::error::ErrorCode::ServerError(LOAD_ERROR_CODE),
+                message: err.to_string().into(),
+                data: None,
+            },
+            err @ ApiError::SnapshotNotFound { .. } => RpcError {
+                code: server::rpc_core::error::ErrorCode::ServerError(SNAPSHOT_ERROR_CODE),
+                message: err.to_string().into(),
+                data: None,
+            },
+            err @ ApiError::RevertError { .. } => RpcError {
+                code: server::rpc_core::error::ErrorCode::ServerError(SNAPSHOT_ERROR_CODE),
+                message: err.to_string().into(),
+                data: None,
+            },
+            err @ ApiError::TimeError { .. } => RpcError {
+                code: server::rpc_core::error::ErrorCode::ServerError(TIME_ERROR_CODE),
+                message: err.to_string().into(),
+                data: None,
+            },
+            err @ ApiError::RestartError { .. } => RpcError {
+                code: server::rpc_core::error::ErrorCode::ServerError(RESTART_ERROR_CODE),
+                message: err.to_string().into(),
+                data: None,
+            },
+            err @ ApiError::BlockCreationError { .. } => RpcError {
+                code: server::rpc_core::error::ErrorCode::ServerError(BLOCK_CREATION_ERROR_CODE),
+                message: err.to_string().into(),
+                data: None,
+            },
+            err @ ApiError::BlockAbortionError { .. } => RpcError {
+                code: server::rpc_core::error::ErrorCode::ServerError(BLOCK_ABORTION_ERROR_CODE),
+                message: err.to_string().into(),
+                data: None,
+            },
+            err @ ApiError::PostmanError { .. } => RpcError {
+                code: server::rpc_core::error::ErrorCode::ServerError(POSTMAN_ERROR_CODE),
+                message: err.to_string().into(),
+                data: None,
+            },
+            err @ ApiError::BalanceError { .. } => RpcError {
+                code: server::rpc_core::error::ErrorCode::ServerError(BALANCE_ERROR_CODE),
+                message: err.to_string().into(),
+                data: None,
+            },
             ApiError::StarknetDevnetError(
                 starknet_core::error::Error::TransactionValidationError(validation_error),
             ) => {
@@ -185,6 +289,132 @@ mod tests {
         error_expected_code_and_message(ApiError::ContractNotFound, 20, "Contract not found");
     }
 
+    #[test]
+    fn storage_proof_not_supported_error() {
+        error_expected_code_and_message(
+            ApiError::StorageProofNotSupported,
+            42,
+            "Storage proofs are not supported for the requested block",
+        );
+    }
+
+    #[test]
+    fn invalid_subscription_id_error() {
+        error_expected_code_and_message(
+            ApiError::InvalidSubscriptionId,
+            66,
+            "Invalid subscription id",
+        );
+    }
+
+    #[test]
+    fn no_trace_available_error() {
+        error_expected_code_and_message(
+            ApiError::NoTraceAvailable,
+            10,
+            "No trace available for transaction",
+        );
+    }
+
+    #[test]
+    fn minting_error() {
+        error_expected_code_and_message(
+            ApiError::MintingError { msg: "reason".into() },
+            100,
+            "Minting error: reason",
+        );
+    }
+
+    #[test]
+    fn dump_error() {
+        error_expected_code_and_message(
+            ApiError::DumpError { msg: "reason".into() },
+            101,
+            "The dump operation failed: reason",
+        );
+    }
+
+    #[test]
+    fn load_error() {
+        error_expected_code_and_message(
+            ApiError::LoadError { msg: "reason".into() },
+            102,
+            "The load operation failed: reason",
+        );
+    }
+
+    #[test]
+    fn snapshot_not_found_error() {
+        error_expected_code_and_message(
+            ApiError::SnapshotNotFound { snapshot_id: 1 },
+            103,
+            "Snapshot with id 1 does not exist",
+        );
+    }
+
+    #[test]
+    fn time_error() {
+        error_expected_code_and_message(
+            ApiError::TimeError { msg: "reason".into() },
+            104,
+            "Time manipulation failed: reason",
+        );
+    }
+
+    #[test]
+    fn revert_error() {
+        error_expected_code_and_message(
+            ApiError::RevertError { msg: "reason".into() },
+            103,
+            "Reverting to the snapshot failed: reason",
+        );
+    }
+
+    #[test]
+    fn restart_error() {
+        error_expected_code_and_message(
+            ApiError::RestartError { msg: "reason".into() },
+            105,
+            "The restart failed: reason",
+        );
+    }
+
+    #[test]
+    fn block_creation_error() {
+        error_expected_code_and_message(
+            ApiError::BlockCreationError { msg: "reason".into() },
+            106,
+            "Block creation failed: reason",
+        );
+    }
+
+    #[test]
+    fn block_abortion_error() {
+        error_expected_code_and_message(
+            ApiError::BlockAbortionError { msg: "reason".into() },
+            107,
+            "Aborting blocks failed: reason",
+        );
+    }
+
+    #[test]
+    fn postman_error() {
+        error_expected_code_and_message(
+            ApiError::PostmanError { msg: "reason".into() },
+            108,
+            "The postman operation failed: reason",
+        );
+    }
+
+    #[test]
+    fn balance_error() {
+        error_expected_code_and_message(
+            ApiError::BalanceError { msg: "reason".into() },
+            109,
+            "Balance query failed: reason",
+        );
+    }
+
     #[test]
     fn block_not_found_error() {
         error_expected_code_and_message(ApiError::BlockNotFound, 24, "Block not found");
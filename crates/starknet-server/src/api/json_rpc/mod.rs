@@ -1,6 +1,8 @@
+mod devnet_endpoints;
 mod endpoints;
 pub mod error;
 mod models;
+pub(crate) mod subscriptions;
 mod write_endpoints;
 
 use error::RpcResult;
@@ -8,11 +10,14 @@ use models::{
     BlockAndClassHashInput, BlockAndContractAddressInput, BlockAndIndexInput, CallInput,
     EstimateFeeInput, EventsInput, GetStorageInput, TransactionHashInput,
 };
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use server::rpc_core::error::RpcError;
-use server::rpc_core::response::ResponseResult;
-use server::rpc_handler::RpcHandler;
+use server::rpc_core::request::RpcMethodCall;
+use server::rpc_core::response::{ResponseResult, RpcResponse};
+use server::rpc_handler::{NotificationSender, RpcHandler};
 use starknet_types::rpc::estimate_message_fee::EstimateMessageFeeRequestWrapper;
+use starknet_types::rpc::messaging::MessageToL2;
+use starknet_types::rpc::transaction_receipt::MessageToL1;
 use tracing::{error, info, trace};
 
 use self::models::{
@@ -20,7 +25,13 @@ use self::models::{
     BroadcastedInvokeTransactionInput,
 };
 use super::Api;
-use crate::api::json_rpc::models::SimulateTransactionsInput;
+use crate::api::http::models::{
+    AbortingBlocks, BalanceQuery, DumpPath, IncreaseTime, LoadPath, MintTokensRequest,
+    PostmanLoadL1MessagingContract, RevertingSnapshot, SetTime,
+};
+use crate::api::json_rpc::models::{
+    GetStorageProofInput, SimulateTransactionsInput, SubscribeEventsInput, SubscriptionIdInput,
+};
 use crate::api::serde_helpers::empty_params;
 
 /// Helper trait to easily convert results to rpc results
@@ -31,6 +42,18 @@ pub(crate) trait ToRpcResponseResult {
 /// Used when there is no defined code to use
 pub const WILDCARD_RPC_ERROR_CODE: i64 = -1;
 
+// codes of the failures of the Devnet-specific methods, outside of the range of the specification
+pub const MINTING_ERROR_CODE: i64 = 100;
+pub const DUMP_ERROR_CODE: i64 = 101;
+pub const LOAD_ERROR_CODE: i64 = 102;
+pub const SNAPSHOT_ERROR_CODE: i64 = 103;
+pub const TIME_ERROR_CODE: i64 = 104;
+pub const RESTART_ERROR_CODE: i64 = 105;
+pub const BLOCK_CREATION_ERROR_CODE: i64 = 106;
+pub const BLOCK_ABORTION_ERROR_CODE: i64 = 107;
+pub const POSTMAN_ERROR_CODE: i64 = 108;
+pub const BALANCE_ERROR_CODE: i64 = 109;
+
 /// Converts a serializable value into a `ResponseResult`
 pub fn to_rpc_result<T: Serialize>(val: T) -> ResponseResult {
     match serde_json::to_value(val) {
@@ -61,11 +84,79 @@ pub struct JsonRpcHandler {
 
 #[async_trait::async_trait]
 impl RpcHandler for JsonRpcHandler {
-    type Request = StarknetRequest;
+    type Request = JsonRpcRequest;
 
     async fn on_request(&self, request: Self::Request) -> ResponseResult {
         info!(target: "rpc", "received method in on_request {}", request);
-        self.execute(request).await
+        match request {
+            JsonRpcRequest::StarknetSpecRequest(request) => self.execute(request).await,
+            JsonRpcRequest::DevnetSpecRequest(request) => self.execute_devnet(request).await,
+        }
+    }
+
+    /// Besides the methods available over HTTP, serves subscriptions
+    async fn on_websocket_call(
+        &self,
+        call: RpcMethodCall,
+        notifications: NotificationSender,
+    ) -> RpcResponse {
+        let params: serde_json::Value = call.params.clone().into();
+        let subscription_call = serde_json::json!({
+            "method": &call.method,
+            "params": params
+        });
+
+        match serde_json::from_value::<SubscriptionRequest>(subscription_call) {
+            Ok(request) => {
+                info!(target: "rpc", "received method in on_websocket_call {}", call.method);
+                let result = self.execute_subscription(request, notifications).await;
+                RpcResponse::new(call.id, result)
+            }
+            // not a subscription method, so it is served as over HTTP
+            Err(err) if err.to_string().contains("unknown variant") => self.on_call(call).await,
+            Err(err) => {
+                error!(target: "rpc", method = ?call.method, ?err, "failed to deserialize method");
+                RpcResponse::new(call.id, RpcError::invalid_params(err.to_string()))
+            }
+        }
+    }
+
+    /// Only the listed getters are read-only; any other method, including ones added later, is
+    /// executed on its own
+    fn is_read_only(&self, call: &RpcMethodCall) -> bool {
+        matches!(
+            call.method.as_str(),
+            "starknet_getBlockWithTxHashes"
+                | "starknet_getBlockWithTxs"
+                | "starknet_getStateUpdate"
+                | "starknet_getStorageAt"
+                | "starknet_getStorageProof"
+                | "starknet_getTransactionByHash"
+                | "starknet_getTransactionByBlockIdAndIndex"
+                | "starknet_getTransactionReceipt"
+                | "starknet_getTransactionStatus"
+                | "starknet_getClass"
+                | "starknet_getClassHashAt"
+                | "starknet_getClassAt"
+                | "starknet_getBlockTransactionCount"
+                | "starknet_call"
+                | "starknet_estimateFee"
+                | "starknet_estimateMessageFee"
+                | "starknet_simulateTransactions"
+                | "starknet_traceTransaction"
+                | "starknet_traceBlockTransactions"
+                | "starknet_blockNumber"
+                | "starknet_blockHashAndNumber"
+                | "starknet_chainId"
+                | "starknet_pendingTransactions"
+                | "starknet_syncing"
+                | "starknet_getEvents"
+                | "starknet_getNonce"
+                | "devnet_getAccountBalance"
+                | "devnet_getForkStatus"
+                | "devnet_getPredeployedAccounts"
+                | "devnet_getRejectedTransactions"
+        )
     }
 }
 
@@ -156,6 +247,218 @@ impl JsonRpcHandler {
                 .simulate_transactions(block_id, transactions, simulation_flags)
                 .await
                 .to_rpc_result(),
+            StarknetRequest::StorageProof(GetStorageProofInput {
+                block_id,
+                class_hashes,
+                contract_addresses,
+                contracts_storage_keys,
+            }) => self
+                .get_storage_proof(
+                    block_id,
+                    class_hashes,
+                    contract_addresses,
+                    contracts_storage_keys,
+                )
+                .await
+                .to_rpc_result(),
+            StarknetRequest::TransactionStatusByHash(TransactionHashInput { transaction_hash }) => {
+                self.get_transaction_status_by_hash(transaction_hash).await.to_rpc_result()
+            }
+            StarknetRequest::TraceTransaction(TransactionHashInput { transaction_hash }) => {
+                self.get_trace_transaction(transaction_hash).await.to_rpc_result()
+            }
+            StarknetRequest::BlockTransactionTraces(BlockIdInput { block_id }) => {
+                self.get_trace_block_transactions(block_id).await.to_rpc_result()
+            }
+        }
+    }
+}
+
+impl JsonRpcHandler {
+    async fn execute_subscription(
+        &self,
+        request: SubscriptionRequest,
+        notifications: NotificationSender,
+    ) -> ResponseResult {
+        match request {
+            SubscriptionRequest::NewHeads => {
+                self.subscribe_new_heads(notifications).await.to_rpc_result()
+            }
+            SubscriptionRequest::Events(SubscribeEventsInput { address, keys }) => {
+                self.subscribe_events(address, keys, notifications).await.to_rpc_result()
+            }
+            SubscriptionRequest::TransactionStatus(TransactionHashInput { transaction_hash }) => {
+                self.subscribe_transaction_status(transaction_hash, notifications)
+                    .await
+                    .to_rpc_result()
+            }
+            SubscriptionRequest::Unsubscribe(SubscriptionIdInput { subscription_id }) => {
+                self.unsubscribe(subscription_id, notifications).await.to_rpc_result()
+            }
+        }
+    }
+}
+
+impl JsonRpcHandler {
+    /// Serves the Devnet-specific methods
+    async fn execute_devnet(&self, request: DevnetRequest) -> ResponseResult {
+        match request {
+            DevnetRequest::Mint(request) => self.mint(request).await.to_rpc_result(),
+            DevnetRequest::AccountBalance(query) => {
+                self.get_account_balance(query).await.to_rpc_result()
+            }
+            DevnetRequest::PredeployedAccounts => {
+                self.get_predeployed_accounts().await.to_rpc_result()
+            }
+            DevnetRequest::Dump(path) => self.dump(path.unwrap_or_default()).await.to_rpc_result(),
+            DevnetRequest::Load(path) => self.load(path).await.to_rpc_result(),
+            DevnetRequest::Restart => self.restart().await.to_rpc_result(),
+            DevnetRequest::CreateBlock => self.create_block().await.to_rpc_result(),
+            DevnetRequest::AbortBlocks(data) => self.abort_blocks(data).await.to_rpc_result(),
+            DevnetRequest::Snapshot => self.snapshot().await.to_rpc_result(),
+            DevnetRequest::Revert(data) => self.revert(data).await.to_rpc_result(),
+            DevnetRequest::SetTime(data) => self.set_time(data).await.to_rpc_result(),
+            DevnetRequest::IncreaseTime(data) => self.increase_time(data).await.to_rpc_result(),
+            DevnetRequest::PostmanLoad(data) => self.postman_load(data).await.to_rpc_result(),
+            DevnetRequest::PostmanFlush => self.postman_flush().await.to_rpc_result(),
+            DevnetRequest::PostmanSendMessageToL2(message) => {
+                self.postman_send_message_to_l2(message).await.to_rpc_result()
+            }
+            DevnetRequest::PostmanConsumeMessageFromL2(message) => {
+                self.postman_consume_message_from_l2(message).await.to_rpc_result()
+            }
+            DevnetRequest::RejectedTransactions => {
+                self.get_rejected_transactions().await.to_rpc_result()
+            }
+            DevnetRequest::ForkStatus => self.get_fork_status().await.to_rpc_result(),
+        }
+    }
+}
+
+/// Methods which are only available over WebSocket, as their notifications are pushed to the
+/// socket over which they were subscribed
+#[derive(Clone, Debug, Deserialize)]
+#[serde(tag = "method", content = "params")]
+pub enum SubscriptionRequest {
+    #[serde(rename = "starknet_subscribeNewHeads", with = "empty_params")]
+    NewHeads,
+    #[serde(rename = "starknet_subscribeEvents")]
+    Events(SubscribeEventsInput),
+    #[serde(rename = "starknet_subscribeTransactionStatus")]
+    TransactionStatus(TransactionHashInput),
+    #[serde(rename = "starknet_unsubscribe")]
+    Unsubscribe(SubscriptionIdInput),
+}
+
+/// Methods of the Starknet JSON-RPC specification and the Devnet-specific methods, which are
+/// told apart by the `devnet_` prefix of their names
+#[derive(Debug)]
+pub enum JsonRpcRequest {
+    StarknetSpecRequest(StarknetRequest),
+    DevnetSpecRequest(DevnetRequest),
+}
+
+impl<'de> Deserialize<'de> for JsonRpcRequest {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let call = serde_json::Value::deserialize(deserializer)?;
+        let is_devnet_method = call
+            .get("method")
+            .and_then(serde_json::Value::as_str)
+            .map_or(false, |method| method.starts_with("devnet_"));
+
+        // the errors of the inner requests are kept, so that unknown methods are recognized
+        if is_devnet_method {
+            serde_json::from_value(call)
+                .map(JsonRpcRequest::DevnetSpecRequest)
+                .map_err(serde::de::Error::custom)
+        } else {
+            serde_json::from_value(call)
+                .map(JsonRpcRequest::StarknetSpecRequest)
+                .map_err(serde::de::Error::custom)
+        }
+    }
+}
+
+impl std::fmt::Display for JsonRpcRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JsonRpcRequest::StarknetSpecRequest(request) => request.fmt(f),
+            JsonRpcRequest::DevnetSpecRequest(request) => request.fmt(f),
+        }
+    }
+}
+
+/// Devnet-specific methods, providing the functionality of the HTTP endpoints of the same name
+#[derive(Debug, Deserialize)]
+#[serde(tag = "method", content = "params")]
+pub enum DevnetRequest {
+    #[serde(rename = "devnet_mint")]
+    Mint(MintTokensRequest),
+    #[serde(rename = "devnet_getAccountBalance")]
+    AccountBalance(BalanceQuery),
+    #[serde(rename = "devnet_getPredeployedAccounts", with = "empty_params")]
+    PredeployedAccounts,
+    /// Without params, dumps to the path Devnet was configured with
+    #[serde(rename = "devnet_dump")]
+    Dump(Option<DumpPath>),
+    #[serde(rename = "devnet_load")]
+    Load(LoadPath),
+    #[serde(rename = "devnet_restart", with = "empty_params")]
+    Restart,
+    #[serde(rename = "devnet_createBlock", with = "empty_params")]
+    CreateBlock,
+    #[serde(rename = "devnet_abortBlocks")]
+    AbortBlocks(AbortingBlocks),
+    #[serde(rename = "devnet_snapshot", with = "empty_params")]
+    Snapshot,
+    #[serde(rename = "devnet_revert")]
+    Revert(RevertingSnapshot),
+    #[serde(rename = "devnet_setTime")]
+    SetTime(SetTime),
+    #[serde(rename = "devnet_increaseTime")]
+    IncreaseTime(IncreaseTime),
+    #[serde(rename = "devnet_postmanLoad")]
+    PostmanLoad(PostmanLoadL1MessagingContract),
+    #[serde(rename = "devnet_postmanFlush", with = "empty_params")]
+    PostmanFlush,
+    #[serde(rename = "devnet_postmanSendMessageToL2")]
+    PostmanSendMessageToL2(MessageToL2),
+    #[serde(rename = "devnet_postmanConsumeMessageFromL2")]
+    PostmanConsumeMessageFromL2(MessageToL1),
+    #[serde(rename = "devnet_getRejectedTransactions", with = "empty_params")]
+    RejectedTransactions,
+    #[serde(rename = "devnet_getForkStatus", with = "empty_params")]
+    ForkStatus,
+}
+
+impl std::fmt::Display for DevnetRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DevnetRequest::Mint(_) => write!(f, "devnet_mint"),
+            DevnetRequest::AccountBalance(_) => write!(f, "devnet_getAccountBalance"),
+            DevnetRequest::PredeployedAccounts => write!(f, "devnet_getPredeployedAccounts"),
+            DevnetRequest::Dump(_) => write!(f, "devnet_dump"),
+            DevnetRequest::Load(_) => write!(f, "devnet_load"),
+            DevnetRequest::Restart => write!(f, "devnet_restart"),
+            DevnetRequest::CreateBlock => write!(f, "devnet_createBlock"),
+            DevnetRequest::AbortBlocks(_) => write!(f, "devnet_abortBlocks"),
+            DevnetRequest::Snapshot => write!(f, "devnet_snapshot"),
+            DevnetRequest::Revert(_) => write!(f, "devnet_revert"),
+            DevnetRequest::SetTime(_) => write!(f, "devnet_setTime"),
+            DevnetRequest::IncreaseTime(_) => write!(f, "devnet_increaseTime"),
+            DevnetRequest::PostmanLoad(_) => write!(f, "devnet_postmanLoad"),
+            DevnetRequest::PostmanFlush => write!(f, "devnet_postmanFlush"),
+            DevnetRequest::PostmanSendMessageToL2(_) => {
+                write!(f, "devnet_postmanSendMessageToL2")
+            }
+            DevnetRequest::PostmanConsumeMessageFromL2(_) => {
+                write!(f, "devnet_postmanConsumeMessageFromL2")
+            }
+            DevnetRequest::RejectedTransactions => write!(f, "devnet_getRejectedTransactions"),
+            DevnetRequest::ForkStatus => write!(f, "devnet_getForkStatus"),
         }
     }
 }
@@ -213,6 +516,14 @@ pub enum StarknetRequest {
     EstimateMessageFee(EstimateMessageFeeRequestWrapper),
     #[serde(rename = "starknet_simulateTransactions")]
     SimulateTransactions(SimulateTransactionsInput),
+    #[serde(rename = "starknet_getStorageProof")]
+    StorageProof(GetStorageProofInput),
+    #[serde(rename = "starknet_getTransactionStatus")]
+    TransactionStatusByHash(TransactionHashInput),
+    #[serde(rename = "starknet_traceTransaction")]
+    TraceTransaction(TransactionHashInput),
+    #[serde(rename = "starknet_traceBlockTransactions")]
+    BlockTransactionTraces(BlockIdInput),
 }
 
 impl std::fmt::Display for StarknetRequest {
@@ -255,6 +566,14 @@ impl std::fmt::Display for StarknetRequest {
             StarknetRequest::AddInvokeTransaction(_) => write!(f, "starknet_addInvokeTransaction"),
             StarknetRequest::EstimateMessageFee(_) => write!(f, "starknet_estimateMessageFee"),
             StarknetRequest::SimulateTransactions(_) => write!(f, "starknet_simulateTransactions"),
+            StarknetRequest::StorageProof(_) => write!(f, "starknet_getStorageProof"),
+            StarknetRequest::TransactionStatusByHash(_) => {
+                write!(f, "starknet_getTransactionStatus")
+            }
+            StarknetRequest::TraceTransaction(_) => write!(f, "starknet_traceTransaction"),
+            StarknetRequest::BlockTransactionTraces(_) => {
+                write!(f, "starknet_traceBlockTransactions")
+            }
         }
     }
 }
@@ -263,7 +582,7 @@ impl std::fmt::Display for StarknetRequest {
 mod requests_tests {
     use starknet_types::felt::Felt;
 
-    use super::StarknetRequest;
+    use super::{DevnetRequest, JsonRpcRequest, StarknetRequest, SubscriptionRequest};
 
     #[test]
     fn deserialize_get_block_with_transaction_hashes_request() {
@@ -479,6 +798,36 @@ mod requests_tests {
         assert_deserialization_fails(json_str.replace(r#""class_hash":"#, "").as_str());
     }
 
+    #[test]
+    fn deserialize_add_invoke_transaction_v3_request() {
+        let json_str = r#"{
+            "method":"starknet_addInvokeTransaction",
+            "params":{
+                "invoke_transaction":{
+                    "type":"INVOKE",
+                    "version": "0x3",
+                    "signature": ["0xFF", "0xAA"],
+                    "nonce": "0x0",
+                    "resource_bounds": {
+                        "l1_gas": {"max_amount": "0x186a0", "max_price_per_unit": "0x5af3107a4000"},
+                        "l2_gas": {"max_amount": "0x0", "max_price_per_unit": "0x0"}
+                    },
+                    "tip": "0x0",
+                    "paymaster_data": [],
+                    "nonce_data_availability_mode": "L1",
+                    "fee_data_availability_mode": "L1",
+                    "sender_address": "0x01",
+                    "calldata": ["0x01"],
+                    "account_deployment_data": []
+                }
+            }
+        }"#;
+
+        assert_deserialization_succeeds(json_str);
+        assert_deserialization_fails(json_str.replace(r#""resource_bounds":"#, "").as_str());
+        assert_deserialization_fails(json_str.replace(r#""L1","#, r#""L3","#).as_str());
+    }
+
     #[test]
     fn deserialize_add_declare_transaction_request() {
         let json_str = r#"{
@@ -512,6 +861,119 @@ mod requests_tests {
         assert_deserialization_succeeds(json_str);
     }
 
+    #[test]
+    fn deserialize_get_storage_proof_request() {
+        let json_str = r#"{"method":"starknet_getStorageProof","params":{"block_id":"latest","contract_addresses":["0x1"],"contracts_storage_keys":[{"contract_address":"0x1","storage_keys":["0x2"]}]}}"#;
+        assert_deserialization_succeeds(json_str);
+
+        // all but the block id can be omitted
+        assert_deserialization_succeeds(
+            r#"{"method":"starknet_getStorageProof","params":{"block_id":"latest"}}"#,
+        );
+        assert_deserialization_fails(&json_str.replace("\"0x2\"", "\"2\""));
+    }
+
+    #[test]
+    fn deserialize_get_transaction_status_request() {
+        let json_str =
+            r#"{"method":"starknet_getTransactionStatus","params":{"transaction_hash":"0x1"}}"#;
+        assert_deserialization_succeeds(json_str);
+        assert_deserialization_fails(&json_str.replace("transaction_hash", "hash"));
+    }
+
+    #[test]
+    fn deserialize_trace_requests() {
+        assert_deserialization_succeeds(
+            r#"{"method":"starknet_traceTransaction","params":{"transaction_hash":"0x1"}}"#,
+        );
+        assert_deserialization_succeeds(
+            r#"{"method":"starknet_traceBlockTransactions","params":{"block_id":{"block_number":1}}}"#,
+        );
+        assert_deserialization_fails(
+            r#"{"method":"starknet_traceBlockTransactions","params":{"transaction_hash":"0x1"}}"#,
+        );
+    }
+
+    #[test]
+    fn deserialize_subscription_requests() {
+        for json_str in [
+            r#"{"method":"starknet_subscribeNewHeads","params":[]}"#,
+            r#"{"method":"starknet_subscribeEvents","params":{"address":"0x1","keys":[["0x2"]]}}"#,
+            r#"{"method":"starknet_subscribeEvents","params":{}}"#,
+            r#"{"method":"starknet_subscribeTransactionStatus","params":{"transaction_hash":"0x1"}}"#,
+            r#"{"method":"starknet_unsubscribe","params":{"subscription_id":0}}"#,
+        ] {
+            serde_json::from_str::<SubscriptionRequest>(json_str).unwrap();
+        }
+
+        // subscriptions are only available over WebSocket
+        assert_deserialization_fails(r#"{"method":"starknet_subscribeNewHeads","params":[]}"#);
+    }
+
+    #[test]
+    fn deserialize_devnet_requests_by_method_prefix() {
+        let json_str = r#"{"method":"devnet_mint","params":{"address":"0x1","amount":10}}"#;
+        assert!(matches!(
+            serde_json::from_str::<JsonRpcRequest>(json_str).unwrap(),
+            JsonRpcRequest::DevnetSpecRequest(DevnetRequest::Mint(_))
+        ));
+
+        let json_str = r#"{"method":"starknet_chainId","params":null}"#;
+        assert!(matches!(
+            serde_json::from_str::<JsonRpcRequest>(json_str).unwrap(),
+            JsonRpcRequest::StarknetSpecRequest(StarknetRequest::ChainId)
+        ));
+    }
+
+    #[test]
+    fn deserialize_devnet_dump_request_with_and_without_path() {
+        for json_str in [
+            r#"{"method":"devnet_dump","params":null}"#,
+            r#"{"method":"devnet_dump","params":{}}"#,
+            r#"{"method":"devnet_dump","params":{"path":"dump.json"}}"#,
+        ] {
+            assert!(matches!(
+                serde_json::from_str::<JsonRpcRequest>(json_str).unwrap(),
+                JsonRpcRequest::DevnetSpecRequest(DevnetRequest::Dump(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn deserialize_devnet_requests_without_params() {
+        for method in [
+            "devnet_getPredeployedAccounts",
+            "devnet_restart",
+            "devnet_createBlock",
+            "devnet_snapshot",
+            "devnet_postmanFlush",
+            "devnet_getRejectedTransactions",
+            "devnet_getForkStatus",
+        ] {
+            let json_str = format!(r#"{{"method":"{method}","params":null}}"#);
+            let request = serde_json::from_str::<JsonRpcRequest>(&json_str).unwrap();
+            assert_eq!(request.to_string(), method);
+        }
+    }
+
+    #[test]
+    fn unknown_methods_of_both_namespaces_are_reported_as_unknown_variants() {
+        for json_str in [
+            r#"{"method":"devnet_unknown","params":null}"#,
+            r#"{"method":"starknet_unknown","params":null}"#,
+        ] {
+            let err = serde_json::from_str::<JsonRpcRequest>(json_str).unwrap_err();
+            assert!(err.to_string().contains("unknown variant"));
+        }
+    }
+
+    #[test]
+    fn deserialize_devnet_request_with_invalid_params() {
+        let json_str = r#"{"method":"devnet_setTime","params":{"time":"now"}}"#;
+        let err = serde_json::from_str::<JsonRpcRequest>(json_str).unwrap_err();
+        assert!(!err.to_string().contains("unknown variant"));
+    }
+
     fn assert_deserialization_succeeds(json_str: &str) {
         serde_json::from_str::<StarknetRequest>(json_str).unwrap();
     }
@@ -1,15 +1,16 @@
 use serde::{Deserialize, Serialize};
 use starknet_types::contract_address::ContractAddress;
-use starknet_types::felt::{BlockHash, ClassHash, TransactionHash};
+use starknet_types::felt::{BlockHash, ClassHash, Felt, TransactionHash};
 use starknet_types::rpc::block::{BlockId, SyncStatus};
-use starknet_types::rpc::transactions::broadcasted_deploy_account_transaction::BroadcastedDeployAccountTransaction;
-use starknet_types::rpc::transactions::broadcasted_invoke_transaction::BroadcastedInvokeTransaction;
+use starknet_types::rpc::storage_proof::ContractStorageKeys;
 use starknet_types::rpc::transactions::{
-    BroadcastedDeclareTransaction, BroadcastedTransaction, EventFilter, FunctionCall,
-    SimulationFlag,
+    BroadcastedDeclareTransaction, BroadcastedDeployAccountTransaction,
+    BroadcastedInvokeTransaction, BroadcastedTransaction, EventFilter, FunctionCall,
+    SimulationFlag, TransactionStatus,
 };
 use starknet_types::starknet_api::block::BlockNumber;
 
+use super::subscriptions::SubscriptionId;
 use crate::api::models::PatriciaKeyHex;
 
 #[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
@@ -116,6 +117,35 @@ pub struct SimulateTransactionsInput {
     pub simulation_flags: Vec<SimulationFlag>,
 }
 
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+pub struct GetStorageProofInput {
+    pub block_id: BlockId,
+    #[serde(default)]
+    pub class_hashes: Vec<ClassHash>,
+    #[serde(default)]
+    pub contract_addresses: Vec<ContractAddress>,
+    #[serde(default)]
+    pub contracts_storage_keys: Vec<ContractStorageKeys>,
+}
+
+/// The address and keys to match events by, as in [`EventFilter`]
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+pub struct SubscribeEventsInput {
+    pub address: Option<ContractAddress>,
+    pub keys: Option<Vec<Vec<Felt>>>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+pub struct SubscriptionIdInput {
+    pub subscription_id: SubscriptionId,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct TransactionStatusOutput {
+    pub transaction_hash: TransactionHash,
+    pub status: TransactionStatus,
+}
+
 #[cfg(test)]
 mod tests {
     use starknet_types::contract_address::ContractAddress;
@@ -0,0 +1,332 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+use serde_json::json;
+use server::rpc_handler::NotificationSender;
+use starknet_core::starknet::{ChainUpdate, Starknet};
+use starknet_rs_core::types::BlockId;
+use starknet_types::contract_address::ContractAddress;
+use starknet_types::felt::{Felt, TransactionHash};
+use starknet_types::rpc::block::BlockHeader;
+use starknet_types::rpc::transactions::{FinalityStatus, TransactionStatus};
+use starknet_types::starknet_api::block::BlockNumber;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{error, warn};
+
+use super::error::{ApiError, RpcResult};
+use super::models::TransactionStatusOutput;
+use super::JsonRpcHandler;
+use crate::api::Api;
+
+pub type SubscriptionId = u64;
+
+#[derive(Debug, Clone)]
+enum Subscription {
+    NewHeads,
+    Events { address: Option<ContractAddress>, keys: Option<Vec<Vec<Felt>>> },
+    TransactionStatus { transaction_hash: TransactionHash },
+}
+
+struct Subscriber {
+    subscription: Subscription,
+    notifications: NotificationSender,
+}
+
+/// Subscriptions of all WebSocket clients, which are notified whenever a block is sealed or the
+/// status of a transaction changes otherwise
+#[derive(Default)]
+pub struct Subscriptions {
+    next_id: SubscriptionId,
+    subscribers: HashMap<SubscriptionId, Subscriber>,
+}
+
+impl Subscriptions {
+    fn subscribe(
+        &mut self,
+        subscription: Subscription,
+        notifications: NotificationSender,
+    ) -> SubscriptionId {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.subscribers.insert(id, Subscriber { subscription, notifications });
+
+        id
+    }
+
+    /// Removes the subscription only if it was created over the same socket; returns whether it
+    /// was removed
+    fn unsubscribe(&mut self, id: SubscriptionId, notifications: &NotificationSender) -> bool {
+        match self.subscribers.get(&id) {
+            Some(subscriber) if subscriber.notifications.same_receiver(notifications) => {
+                self.subscribers.remove(&id);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Notifies the subscribers of the block and of its events and transactions. Subscriptions of
+    /// closed sockets are removed.
+    fn notify_new_block(&mut self, starknet: &Starknet, block_number: BlockNumber) {
+        let block_id = BlockId::Number(block_number.0);
+        // the block might have been aborted in the meantime
+        let block = match starknet.get_block(block_id) {
+            Ok(block) => block,
+            Err(_) => return,
+        };
+
+        self.subscribers.retain(|id, subscriber| {
+            let notifications = match &subscriber.subscription {
+                Subscription::NewHeads => {
+                    vec![notification(NEW_HEADS_NOTIFICATION, *id, BlockHeader::from(&block))]
+                }
+                Subscription::Events { address, keys } => {
+                    match starknet.get_events(
+                        Some(block_id),
+                        Some(block_id),
+                        *address,
+                        keys.clone(),
+                        0,
+                        None,
+                    ) {
+                        Ok((events, _)) => events
+                            .into_iter()
+                            .map(|event| notification(EVENTS_NOTIFICATION, *id, event))
+                            .collect(),
+                        Err(err) => {
+                            error!("Failed to get events of block {}: {}", block_number, err);
+                            vec![]
+                        }
+                    }
+                }
+                Subscription::TransactionStatus { transaction_hash } => {
+                    if block.get_transactions().contains(transaction_hash) {
+                        transaction_status_notification(starknet, *id, *transaction_hash)
+                            .into_iter()
+                            .collect()
+                    } else {
+                        vec![]
+                    }
+                }
+            };
+
+            // sending fails only if the socket is closed
+            notifications
+                .into_iter()
+                .all(|notification| subscriber.notifications.unbounded_send(notification).is_ok())
+        });
+    }
+
+    /// Notifies the subscribers of the status of the transaction, e.g. once it is rejected, its
+    /// block is aborted or it is discarded by reverting to a snapshot. Subscriptions of closed
+    /// sockets are removed.
+    fn notify_transaction_status(
+        &mut self,
+        starknet: &Starknet,
+        transaction_hash: TransactionHash,
+    ) {
+        self.subscribers.retain(|id, subscriber| match &subscriber.subscription {
+            Subscription::TransactionStatus { transaction_hash: subscribed_hash }
+                if *subscribed_hash == transaction_hash =>
+            {
+                let notification = transaction_status_notification(starknet, *id, transaction_hash)
+                    .unwrap_or_else(|| discarded_transaction_notification(*id, transaction_hash));
+                // sending fails only if the socket is closed
+                subscriber.notifications.unbounded_send(notification).is_ok()
+            }
+            _ => true,
+        });
+    }
+}
+
+const NEW_HEADS_NOTIFICATION: &str = "starknet_subscriptionNewHeads";
+const EVENTS_NOTIFICATION: &str = "starknet_subscriptionEvents";
+const TRANSACTION_STATUS_NOTIFICATION: &str = "starknet_subscriptionTransactionStatus";
+
+fn notification(method: &str, id: SubscriptionId, result: impl Serialize) -> serde_json::Value {
+    json!({
+        "jsonrpc": "2.0",
+        "method": method,
+        "params": {
+            "subscription_id": id,
+            "result": result,
+        }
+    })
+}
+
+fn transaction_status_notification(
+    starknet: &Starknet,
+    id: SubscriptionId,
+    transaction_hash: TransactionHash,
+) -> Option<serde_json::Value> {
+    let status = starknet.get_transaction_status(transaction_hash).ok()?;
+
+    Some(notification(
+        TRANSACTION_STATUS_NOTIFICATION,
+        id,
+        TransactionStatusOutput { transaction_hash, status },
+    ))
+}
+
+/// The status of a transaction which is no longer known, because it was discarded
+fn discarded_transaction_notification(
+    id: SubscriptionId,
+    transaction_hash: TransactionHash,
+) -> serde_json::Value {
+    let status = TransactionStatus {
+        finality_status: FinalityStatus::Rejected,
+        execution_status: None,
+        failure_reason: Some("Transaction was discarded".to_string()),
+    };
+
+    notification(
+        TRANSACTION_STATUS_NOTIFICATION,
+        id,
+        TransactionStatusOutput { transaction_hash, status },
+    )
+}
+
+/// Notifies the subscribers of each update of the chain, until Devnet is stopped
+pub(crate) async fn notify_subscribers(api: Api) {
+    let mut updates = api.starknet.read().await.subscribe_to_updates();
+
+    loop {
+        match updates.recv().await {
+            Ok(ChainUpdate::NewBlock(block_number)) => {
+                let starknet = api.starknet.read().await;
+                api.subscriptions.lock().await.notify_new_block(&starknet, block_number);
+            }
+            Ok(ChainUpdate::TransactionStatus(transaction_hash)) => {
+                let starknet = api.starknet.read().await;
+                api.subscriptions
+                    .lock()
+                    .await
+                    .notify_transaction_status(&starknet, transaction_hash);
+            }
+            Err(RecvError::Lagged(skipped)) => {
+                warn!("Subscribers were not notified of {} updates", skipped);
+            }
+            Err(RecvError::Closed) => break,
+        }
+    }
+}
+
+/// here are the definitions of the JSON-RPC subscription endpoints, which are only available over
+/// WebSocket
+impl JsonRpcHandler {
+    /// starknet_subscribeNewHeads
+    pub(crate) async fn subscribe_new_heads(
+        &self,
+        notifications: NotificationSender,
+    ) -> RpcResult<SubscriptionId> {
+        Ok(self.api.subscriptions.lock().await.subscribe(Subscription::NewHeads, notifications))
+    }
+
+    /// starknet_subscribeEvents
+    pub(crate) async fn subscribe_events(
+        &self,
+        address: Option<ContractAddress>,
+        keys: Option<Vec<Vec<Felt>>>,
+        notifications: NotificationSender,
+    ) -> RpcResult<SubscriptionId> {
+        Ok(self
+            .api
+            .subscriptions
+            .lock()
+            .await
+            .subscribe(Subscription::Events { address, keys }, notifications))
+    }
+
+    /// starknet_subscribeTransactionStatus; the current status of a known transaction is sent
+    /// right after the response
+    pub(crate) async fn subscribe_transaction_status(
+        &self,
+        transaction_hash: TransactionHash,
+        notifications: NotificationSender,
+    ) -> RpcResult<SubscriptionId> {
+        let starknet = self.api.starknet.read().await;
+        let subscription = Subscription::TransactionStatus { transaction_hash };
+        let id = self.api.subscriptions.lock().await.subscribe(subscription, notifications.clone());
+
+        let current_status = transaction_status_notification(&starknet, id, transaction_hash);
+        if let Some(notification) = current_status {
+            // sending fails only if the socket is already closed
+            let _ = notifications.unbounded_send(notification);
+        }
+
+        Ok(id)
+    }
+
+    /// starknet_unsubscribe
+    pub(crate) async fn unsubscribe(
+        &self,
+        subscription_id: SubscriptionId,
+        notifications: NotificationSender,
+    ) -> RpcResult<bool> {
+        if self.api.subscriptions.lock().await.unsubscribe(subscription_id, &notifications) {
+            Ok(true)
+        } else {
+            Err(ApiError::InvalidSubscriptionId)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::channel::mpsc;
+    use starknet_core::starknet::{Starknet, StarknetConfig};
+    use starknet_types::felt::Felt;
+    use starknet_types::starknet_api::block::BlockNumber;
+
+    use super::{
+        Subscription, Subscriptions, NEW_HEADS_NOTIFICATION, TRANSACTION_STATUS_NOTIFICATION,
+    };
+
+    #[test]
+    fn subscribers_of_closed_sockets_are_removed() {
+        let mut starknet = Starknet::new(&StarknetConfig::default()).unwrap();
+        starknet.create_block().unwrap();
+
+        let mut subscriptions = Subscriptions::default();
+        let (open_sender, mut open_receiver) = mpsc::unbounded();
+        let (closed_sender, closed_receiver) = mpsc::unbounded();
+        drop(closed_receiver);
+        let id = subscriptions.subscribe(Subscription::NewHeads, open_sender.clone());
+        subscriptions.subscribe(Subscription::NewHeads, closed_sender);
+
+        subscriptions.notify_new_block(&starknet, BlockNumber(0));
+
+        assert_eq!(subscriptions.subscribers.len(), 1);
+        let notification = open_receiver.try_next().unwrap().unwrap();
+        assert_eq!(notification["method"], NEW_HEADS_NOTIFICATION);
+        assert_eq!(notification["params"]["subscription_id"], id);
+    }
+
+    #[test]
+    fn discarded_transaction_is_notified_as_rejected() {
+        let starknet = Starknet::new(&StarknetConfig::default()).unwrap();
+        let mut subscriptions = Subscriptions::default();
+        let (sender, mut receiver) = mpsc::unbounded();
+        let transaction_hash = Felt::from(1);
+        subscriptions.subscribe(Subscription::TransactionStatus { transaction_hash }, sender);
+
+        // e.g. the transaction was discarded by reverting to a snapshot
+        subscriptions.notify_transaction_status(&starknet, transaction_hash);
+
+        let notification = receiver.try_next().unwrap().unwrap();
+        assert_eq!(notification["method"], TRANSACTION_STATUS_NOTIFICATION);
+        assert_eq!(notification["params"]["result"]["status"]["finality_status"], "REJECTED");
+    }
+
+    #[test]
+    fn only_subscriptions_of_the_same_socket_can_be_cancelled() {
+        let mut subscriptions = Subscriptions::default();
+        let (sender, _receiver) = mpsc::unbounded();
+        let (other_sender, _other_receiver) = mpsc::unbounded();
+        let id = subscriptions.subscribe(Subscription::NewHeads, sender.clone());
+
+        assert!(!subscriptions.unsubscribe(id, &other_sender));
+        assert!(subscriptions.unsubscribe(id, &sender));
+        assert!(!subscriptions.unsubscribe(id, &sender));
+    }
+}
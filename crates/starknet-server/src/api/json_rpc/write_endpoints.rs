@@ -1,6 +1,7 @@
-use starknet_types::rpc::transactions::broadcasted_deploy_account_transaction::BroadcastedDeployAccountTransaction;
-use starknet_types::rpc::transactions::broadcasted_invoke_transaction::BroadcastedInvokeTransaction;
-use starknet_types::rpc::transactions::BroadcastedDeclareTransaction;
+use starknet_types::rpc::transactions::{
+    BroadcastedDeclareTransaction, BroadcastedDeployAccountTransaction,
+    BroadcastedInvokeTransaction,
+};
 
 use super::error::ApiError;
 use super::models::{
@@ -27,6 +28,12 @@ impl JsonRpcHandler {
                 .write()
                 .await
                 .add_declare_transaction_v2(*broadcasted_declare_txn)?,
+            BroadcastedDeclareTransaction::V3(broadcasted_declare_txn) => self
+                .api
+                .starknet
+                .write()
+                .await
+                .add_declare_transaction_v3(*broadcasted_declare_txn)?,
         };
 
         Ok(DeclareTransactionOutput { transaction_hash, class_hash })
@@ -62,7 +69,7 @@ impl JsonRpcHandler {
 #[cfg(test)]
 mod tests {
     use starknet_types::rpc::transactions::broadcasted_declare_transaction_v1::BroadcastedDeclareTransactionV1;
-    use starknet_types::rpc::transactions::broadcasted_deploy_account_transaction::BroadcastedDeployAccountTransaction;
+    use starknet_types::rpc::transactions::BroadcastedDeployAccountTransaction;
 
     #[test]
     fn check_correct_deserialization_of_deploy_account_transaction_request() {
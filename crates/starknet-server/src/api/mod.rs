@@ -6,7 +6,9 @@ pub(crate) mod serde_helpers;
 use std::sync::Arc;
 
 use starknet_core::starknet::Starknet;
-use tokio::sync::RwLock;
+use tokio::sync::{Mutex, RwLock};
+
+use self::json_rpc::subscriptions::Subscriptions;
 
 /// Data that can be shared between threads with read write lock access
 /// Whatever needs to be accessed as information outside of Starknet could be added to this struct
@@ -14,10 +16,15 @@ use tokio::sync::RwLock;
 pub struct Api {
     // maybe the config should be added here next to the starknet instance
     pub starknet: Arc<RwLock<Starknet>>,
+    /// Subscriptions of WebSocket clients; if both are locked, `starknet` is locked first
+    pub subscriptions: Arc<Mutex<Subscriptions>>,
 }
 
 impl Api {
     pub fn new(starknet: Starknet) -> Self {
-        Self { starknet: Arc::new(RwLock::new(starknet)) }
+        Self {
+            starknet: Arc::new(RwLock::new(starknet)),
+            subscriptions: Arc::new(Mutex::new(Subscriptions::default())),
+        }
     }
 }
@@ -4,12 +4,13 @@ use std::net::{IpAddr, Ipv4Addr};
 use clap::Parser;
 use starknet_core::constants::{
     DEVNET_DEFAULT_GAS_PRICE, DEVNET_DEFAULT_INITIAL_BALANCE, DEVNET_DEFAULT_PORT,
-    DEVNET_DEFAULT_TIMEOUT, DEVNET_DEFAULT_TOTAL_ACCOUNTS,
+    DEVNET_DEFAULT_STRK_GAS_PRICE, DEVNET_DEFAULT_TIMEOUT, DEVNET_DEFAULT_TOTAL_ACCOUNTS,
 };
-use starknet_core::starknet::{DumpMode, StarknetConfig};
+use starknet_core::starknet::{DumpMode, ForkConfig, StarknetConfig};
 use starknet_types::chain_id::ChainId;
 use starknet_types::num_bigint::BigUint;
 use strum::IntoEnumIterator;
+use url::Url;
 
 use crate::ip_addr_wrapper::IpAddrWrapper;
 
@@ -68,6 +69,13 @@ pub(crate) struct Args {
     #[arg(help = "Specify the gas price in wei per gas unit;")]
     gas_price: u64,
 
+    // Gas price in fri
+    #[arg(long = "strk-gas-price")]
+    #[arg(value_name = "STRK_GAS_PRICE")]
+    #[arg(default_value_t = DEVNET_DEFAULT_STRK_GAS_PRICE)]
+    #[arg(help = "Specify the gas price in fri per gas unit, paid by V3 transactions;")]
+    strk_gas_price: u64,
+
     // Chain id as string
     #[arg(long = "chain-id")]
     #[arg(value_name = "CHAIN_ID")]
@@ -86,6 +94,42 @@ pub(crate) struct Args {
     #[arg(value_name = "DUMP_PATH")]
     #[arg(help = "Specify the path to dump to;")]
     dump_path: Option<String>,
+
+    // Directory of the Sierra compilation cache
+    #[arg(long = "compilation-cache-dir")]
+    #[arg(value_name = "DIR")]
+    #[arg(help = "Specify the directory in which Sierra classes compiled to CASM are cached, so \
+                  that a class is compiled only once, even across restarts and Devnet instances;")]
+    compilation_cache_dir: Option<String>,
+
+    // Generate blocks only on request
+    #[arg(long = "blocks-on-demand")]
+    #[arg(help = "Introduces block generation on demand via /create_block; transactions are \
+                  accumulated in the pending block until then;")]
+    blocks_on_demand: bool,
+
+    // Interval of periodic block generation in seconds
+    #[arg(long = "block-time")]
+    #[arg(value_name = "SECONDS")]
+    #[arg(value_parser = clap::value_parser!(u64).range(1..))]
+    #[arg(conflicts_with = "blocks_on_demand")]
+    #[arg(help = "Specify the time in seconds after which the pending block is periodically \
+                  turned into a new block, even if it has no transactions;")]
+    block_time: Option<u64>,
+
+    // Url of the network to fork from
+    #[arg(long = "fork-network")]
+    #[arg(value_name = "URL")]
+    #[arg(help = "Specify the URL of a Starknet JSON-RPC node to fork from;")]
+    fork_network: Option<Url>,
+
+    // Number of the block to fork from
+    #[arg(long = "fork-block")]
+    #[arg(value_name = "BLOCK_NUMBER")]
+    #[arg(requires = "fork_network")]
+    #[arg(help = "Specify the number of the block to fork at; defaults to the latest block of \
+                  the forked network;")]
+    fork_block: Option<u64>,
 }
 
 impl Args {
@@ -105,6 +149,7 @@ impl Args {
             port: self.port,
             timeout: self.timeout,
             gas_price: self.gas_price,
+            strk_gas_price: self.strk_gas_price,
             chain_id: match self.chain_id.as_str() {
                 "MAINNET" => ChainId::MainNet,
                 "TESTNET" => ChainId::TestNet,
@@ -113,6 +158,10 @@ impl Args {
             },
             dump_on: self.parse_dump_on(),
             dump_path: self.dump_path.clone(),
+            blocks_on_demand: self.blocks_on_demand,
+            block_time: self.block_time,
+            fork_config: ForkConfig { url: self.fork_network.clone(), block: self.fork_block },
+            compilation_cache_dir: self.compilation_cache_dir.clone(),
         }
     }
 
@@ -171,6 +220,78 @@ mod tests {
         assert_eq!(args.host, IpAddrWrapper::LOCALHOST);
     }
 
+    #[test]
+    fn blocks_on_demand_disabled_by_default() {
+        let args = Args::parse_from(["--"]);
+        assert!(!args.to_starknet_config().blocks_on_demand);
+
+        let args = Args::parse_from(["--", "--blocks-on-demand"]);
+        assert!(args.to_starknet_config().blocks_on_demand);
+    }
+
+    #[test]
+    fn block_time_parsed() {
+        let args = Args::parse_from(["--", "--block-time", "10"]);
+        assert_eq!(args.to_starknet_config().block_time, Some(10));
+    }
+
+    #[test]
+    fn invalid_block_time() {
+        for invalid_block_time in ["0", "-1", "abc"] {
+            match Args::try_parse_from(["--", "--block-time", invalid_block_time]) {
+                Err(_) => (),
+                Ok(parsed) => panic!("Should have failed; got: {parsed:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn block_time_conflicts_with_blocks_on_demand() {
+        match Args::try_parse_from(["--", "--block-time", "10", "--blocks-on-demand"]) {
+            Err(_) => (),
+            Ok(parsed) => panic!("Should have failed; got: {parsed:?}"),
+        }
+    }
+
+    #[test]
+    fn fork_config_parsed() {
+        let args = Args::parse_from([
+            "--",
+            "--fork-network",
+            "http://localhost:1234/rpc",
+            "--fork-block",
+            "42",
+        ]);
+        let fork_config = args.to_starknet_config().fork_config;
+        assert_eq!(fork_config.url.unwrap().as_str(), "http://localhost:1234/rpc");
+        assert_eq!(fork_config.block, Some(42));
+    }
+
+    #[test]
+    fn invalid_fork_network() {
+        match Args::try_parse_from(["--", "--fork-network", "not a url"]) {
+            Err(_) => (),
+            Ok(parsed) => panic!("Should have failed; got: {parsed:?}"),
+        }
+    }
+
+    #[test]
+    fn fork_block_requires_fork_network() {
+        match Args::try_parse_from(["--", "--fork-block", "42"]) {
+            Err(_) => (),
+            Ok(parsed) => panic!("Should have failed; got: {parsed:?}"),
+        }
+    }
+
+    #[test]
+    fn compilation_cache_disabled_by_default() {
+        let args = Args::parse_from(["--"]);
+        assert_eq!(args.compilation_cache_dir, None);
+
+        let args = Args::parse_from(["--", "--compilation-cache-dir", "/tmp/devnet-casm"]);
+        assert_eq!(args.compilation_cache_dir.as_deref(), Some("/tmp/devnet-casm"));
+    }
+
     #[test]
     fn invalid_hostname() {
         match Args::try_parse_from(["--", "--host", "invalid"]) {
@@ -1,19 +1,22 @@
 use std::net::SocketAddr;
+use std::time::Duration;
 
 use ::server::ServerConfig;
 use anyhow::Ok;
+use api::json_rpc::subscriptions::notify_subscribers;
 use api::Api;
 use clap::Parser;
 use cli::Args;
 use starknet_core::account::Account;
 use starknet_core::constants::{
-    ERC20_CONTRACT_ADDRESS, ERC20_CONTRACT_CLASS_HASH, UDC_CONTRACT_ADDRESS,
-    UDC_CONTRACT_CLASS_HASH,
+    ERC20_CONTRACT_ADDRESS, ERC20_CONTRACT_CLASS_HASH, STRK_ERC20_CONTRACT_ADDRESS,
+    UDC_CONTRACT_ADDRESS, UDC_CONTRACT_CLASS_HASH,
 };
 use starknet_core::starknet::{DumpMode, Starknet};
 use starknet_types::felt::Felt;
 use starknet_types::traits::{ToDecimalString, ToHexString};
-use tracing::info;
+use tokio::time::MissedTickBehavior;
+use tracing::{error, info};
 use tracing_subscriber::EnvFilter;
 
 mod api;
@@ -47,14 +50,18 @@ fn log_predeployed_accounts(predeployed_accounts: &Vec<Account>, seed: u32, init
 
     if !predeployed_accounts.is_empty() {
         println!();
-        println!("Initial balance of each account: {} WEI", initial_balance.to_decimal_string());
+        println!(
+            "Initial balance of each account: {} WEI and FRI",
+            initial_balance.to_decimal_string()
+        );
         println!("Seed to replicate this account sequence: {seed}");
     }
 }
 
 fn print_predeployed_contracts() {
     println!("Predeployed FeeToken");
-    println!("Address: {ERC20_CONTRACT_ADDRESS}");
+    println!("ETH Address: {ERC20_CONTRACT_ADDRESS}");
+    println!("STRK Address: {STRK_ERC20_CONTRACT_ADDRESS}");
     println!("Class Hash: {ERC20_CONTRACT_CLASS_HASH}");
     println!();
     println!("Predeployed UDC");
@@ -71,7 +78,16 @@ async fn main() -> Result<(), anyhow::Error> {
     let starknet_config = args.to_starknet_config();
     let mut addr: SocketAddr = SocketAddr::new(starknet_config.host, starknet_config.port);
 
-    let api = api::Api::new(Starknet::new(&starknet_config)?);
+    let starknet = Starknet::new(&starknet_config)?;
+    if let (Some(url), Some(block)) =
+        (&starknet.config.fork_config.url, starknet.config.fork_config.block)
+    {
+        info!("Forking from {} at block {}", url, block);
+    }
+    if let Some(dir) = &starknet.config.compilation_cache_dir {
+        info!("Caching compiled Sierra classes in {}", dir);
+    }
+    let api = api::Api::new(starknet);
 
     print_predeployed_contracts();
 
@@ -92,6 +108,13 @@ async fn main() -> Result<(), anyhow::Error> {
 
     info!("Starknet Devnet listening on {}", addr);
 
+    tokio::task::spawn(notify_subscribers(api.clone()));
+
+    if let Some(block_time) = starknet_config.block_time {
+        tokio::task::spawn(create_block_interval(api.clone(), block_time));
+        info!("Generating blocks every {} seconds", block_time);
+    }
+
     // spawn the server on a new task
     let serve = if starknet_config.dump_on == Some(DumpMode::OnExit) {
         tokio::task::spawn(server.with_graceful_shutdown(shutdown_signal(api.clone())))
@@ -102,9 +125,29 @@ async fn main() -> Result<(), anyhow::Error> {
     Ok(serve.await??)
 }
 
+/// Seals the pending block every `block_time` seconds. The lock on Starknet is held only while
+/// the block is being created.
+async fn create_block_interval(api: Api, block_time: u64) {
+    let mut interval = tokio::time::interval(Duration::from_secs(block_time));
+    // a tick delayed by waiting for the lock postpones the following ones, instead of being
+    // followed by a burst of empty blocks
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    // the first tick completes immediately
+    interval.tick().await;
+
+    loop {
+        interval.tick().await;
+
+        let mut starknet = api.starknet.write().await;
+        if let Err(err) = starknet.create_block() {
+            error!("Failed to create block: {}", err);
+        }
+    }
+}
+
 pub async fn shutdown_signal(api: Api) {
     tokio::signal::ctrl_c().await.expect("Failed to install CTRL+C signal handler");
 
     let starknet = api.starknet.read().await;
-    starknet.dump_transactions().expect("Failed to dump starknet transactions");
+    starknet.dump_state().expect("Failed to dump starknet state");
 }
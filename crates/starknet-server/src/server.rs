@@ -23,6 +23,7 @@ pub fn serve_http_api_json_rpc(
         .set_config(config)
         .json_rpc_route("/")
         .json_rpc_route("/rpc")
+        .json_rpc_ws_route("/ws")
         .http_api_route("/is_alive", get(http::is_alive))
         .http_api_route("/dump", post(http::dump_load::dump))
         .http_api_route("/load", post(http::dump_load::load))
@@ -38,6 +39,8 @@ pub fn serve_http_api_json_rpc(
         )
         .http_api_route("/create_block", post(http::blocks::create_block))
         .http_api_route("/abort_blocks", post(http::blocks::abort_blocks))
+        .http_api_route("/snapshot", post(http::snapshots::snapshot))
+        .http_api_route("/revert", post(http::snapshots::revert))
         .http_api_route("/restart", post(http::restart))
         .http_api_route("/set_time", post(http::time::set_time))
         .http_api_route("/increase_time", post(http::time::increase_time))
@@ -45,6 +48,10 @@ pub fn serve_http_api_json_rpc(
         .http_api_route("/account_balance", get(http::accounts::get_account_balance))
         .http_api_route("/fee_token", get(http::mint_token::get_fee_token))
         .http_api_route("/mint", post(http::mint_token::mint))
+        .http_api_route(
+            "/rejected_transactions",
+            get(http::transactions::get_rejected_transactions),
+        )
         .http_api_route("/fork_status", get(http::get_fork_status))
         .build(starknet_config)
 }
@@ -11,6 +11,7 @@ pub const CHAIN_ID_CLI_PARAM: &str = "TESTNET";
 
 // URL paths
 pub const RPC_PATH: &str = "/rpc";
+pub const WS_PATH: &str = "/ws";
 pub const HEALTHCHECK_PATH: &str = "/is_alive";
 
 // predeployed account info with seed=42
@@ -18,7 +18,7 @@ use url::Url;
 
 use super::constants::{
     ACCOUNTS, CHAIN_ID_CLI_PARAM, HEALTHCHECK_PATH, HOST, MAX_PORT, MIN_PORT,
-    PREDEPLOYED_ACCOUNT_INITIAL_BALANCE, RPC_PATH, SEED,
+    PREDEPLOYED_ACCOUNT_INITIAL_BALANCE, RPC_PATH, SEED, WS_PATH,
 };
 use crate::common::utils::get_json_body;
 
@@ -51,7 +51,7 @@ pub struct BackgroundDevnet {
     pub json_rpc_client: JsonRpcClient<HttpTransport>,
     pub process: Child,
     url: String,
-    rpc_url: Url,
+    pub rpc_url: Url,
 }
 
 fn get_free_port() -> Result<u16, TestError> {
@@ -160,6 +160,11 @@ impl BackgroundDevnet {
         get_json_body(resp).await
     }
 
+    /// The URL at which JSON-RPC is served over WebSocket
+    pub fn ws_url(&self) -> String {
+        format!("{}{WS_PATH}", self.url.replacen("http", "ws", 1))
+    }
+
     pub fn clone_provider(&self) -> JsonRpcClient<HttpTransport> {
         JsonRpcClient::new(HttpTransport::new(self.rpc_url.clone()))
     }
@@ -180,6 +185,18 @@ impl BackgroundDevnet {
         FieldElement::from_hex_be(resp_body["tx_hash"].as_str().unwrap()).unwrap()
     }
 
+    /// Seals the pending block and returns the hash of the newly created block
+    pub async fn create_block(&self) -> FieldElement {
+        let resp = self
+            .post_json("/create_block".into(), Body::from(json!({}).to_string()))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+        let resp_body = get_json_body(resp).await;
+
+        FieldElement::from_hex_be(resp_body["block_hash"].as_str().unwrap()).unwrap()
+    }
+
     pub async fn get(
         &self,
         path: &str,
@@ -0,0 +1,38 @@
+pub mod common;
+
+mod get_transaction_status_integration_tests {
+    use serde_json::json;
+
+    use crate::common::devnet::BackgroundDevnet;
+
+    static DUMMY_ADDRESS: u128 = 1;
+    static DUMMY_AMOUNT: u128 = 1;
+
+    #[tokio::test]
+    async fn status_of_accepted_transaction() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        let mint_tx_hash = devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+
+        let resp = devnet
+            .send_custom_rpc(
+                "starknet_getTransactionStatus",
+                json!({ "transaction_hash": format!("{mint_tx_hash:#x}") }),
+            )
+            .await;
+
+        assert_eq!(
+            resp["result"],
+            json!({ "finality_status": "ACCEPTED_ON_L2", "execution_status": "SUCCEEDED" })
+        );
+    }
+
+    #[tokio::test]
+    async fn status_of_non_existing_transaction() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let resp = devnet
+            .send_custom_rpc("starknet_getTransactionStatus", json!({ "transaction_hash": "0x0" }))
+            .await;
+        assert_eq!(resp["error"]["code"], 29);
+    }
+}
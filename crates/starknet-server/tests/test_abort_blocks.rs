@@ -0,0 +1,147 @@
+pub mod common;
+
+mod abort_blocks_tests {
+    use hyper::{Body, StatusCode};
+    use serde_json::json;
+    use starknet_core::constants::ERC20_CONTRACT_ADDRESS;
+    use starknet_rs_core::types::{
+        BlockId, BlockTag, FieldElement, FunctionCall, MaybePendingBlockWithTxHashes,
+        MaybePendingTransactionReceipt, StarknetError, TransactionFinalityStatus,
+    };
+    use starknet_rs_core::utils::get_selector_from_name;
+    use starknet_rs_providers::{
+        MaybeUnknownErrorCode, Provider, ProviderError, StarknetErrorWithMessage,
+    };
+
+    use crate::common::devnet::BackgroundDevnet;
+    use crate::common::utils::get_json_body;
+
+    static DUMMY_ADDRESS: u128 = 1;
+    static DUMMY_AMOUNT: u128 = 1;
+
+    async fn get_balance(devnet: &BackgroundDevnet, address: FieldElement) -> FieldElement {
+        let balance = devnet
+            .json_rpc_client
+            .call(
+                FunctionCall {
+                    contract_address: FieldElement::from_hex_be(ERC20_CONTRACT_ADDRESS).unwrap(),
+                    entry_point_selector: get_selector_from_name("balanceOf").unwrap(),
+                    calldata: vec![address],
+                },
+                BlockId::Tag(BlockTag::Latest),
+            )
+            .await
+            .unwrap();
+
+        balance[0]
+    }
+
+    async fn get_block_hash(devnet: &BackgroundDevnet, block_number: u64) -> FieldElement {
+        match devnet.json_rpc_client.get_block_with_tx_hashes(BlockId::Number(block_number)).await {
+            Ok(MaybePendingBlockWithTxHashes::Block(block)) => block.block_hash,
+            other => panic!("Unexpected block: {other:?}"),
+        }
+    }
+
+    async fn abort_blocks(
+        devnet: &BackgroundDevnet,
+        starting_block_hash: FieldElement,
+    ) -> hyper::Response<Body> {
+        let req_body = Body::from(json!({ "startingBlockHash": starting_block_hash }).to_string());
+        devnet.post_json("/abort_blocks".into(), req_body).await.unwrap()
+    }
+
+    fn assert_block_not_found<T: std::fmt::Debug>(result: Result<T, ProviderError>) {
+        match result {
+            Err(ProviderError::StarknetError(StarknetErrorWithMessage {
+                code: MaybeUnknownErrorCode::Known(StarknetError::BlockNotFound),
+                ..
+            })) => (),
+            other => panic!("Unexpected result: {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn abort_blocks_reverts_blocks_and_state() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        let address = FieldElement::from(DUMMY_ADDRESS);
+
+        devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+        let aborted_mint_hashes = vec![
+            devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await,
+            devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await,
+        ];
+        assert_eq!(get_balance(&devnet, address).await, FieldElement::from(3 * DUMMY_AMOUNT));
+
+        let first_block_hash = get_block_hash(&devnet, 0).await;
+        let second_block_hash = get_block_hash(&devnet, 1).await;
+        let third_block_hash = get_block_hash(&devnet, 2).await;
+
+        let abort_resp = abort_blocks(&devnet, second_block_hash).await;
+        assert_eq!(abort_resp.status(), StatusCode::OK, "Checking status of {abort_resp:?}");
+        let abort_resp_body = get_json_body(abort_resp).await;
+        let aborted: Vec<FieldElement> = abort_resp_body["aborted"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|hash| FieldElement::from_hex_be(hash.as_str().unwrap()).unwrap())
+            .collect();
+        assert_eq!(aborted, vec![second_block_hash, third_block_hash]);
+
+        assert_block_not_found(
+            devnet.json_rpc_client.get_block_with_tx_hashes(BlockId::Hash(second_block_hash)).await,
+        );
+        assert_block_not_found(
+            devnet.json_rpc_client.get_block_with_tx_hashes(BlockId::Number(2)).await,
+        );
+        assert_eq!(devnet.json_rpc_client.block_number().await.unwrap(), 0);
+        assert_eq!(get_block_hash(&devnet, 0).await, first_block_hash);
+        assert_eq!(get_balance(&devnet, address).await, FieldElement::from(DUMMY_AMOUNT));
+
+        // transactions of aborted blocks are kept, but no longer accepted
+        for tx_hash in aborted_mint_hashes {
+            match devnet.json_rpc_client.get_transaction_receipt(tx_hash).await {
+                Ok(MaybePendingTransactionReceipt::Receipt(receipt)) => {
+                    panic!("Unexpected receipt of an aborted transaction: {receipt:?}")
+                }
+                Ok(MaybePendingTransactionReceipt::PendingReceipt(_)) | Err(_) => (),
+            }
+        }
+
+        // new blocks continue after the last non-aborted block
+        let new_mint_hash = devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+        assert_eq!(devnet.json_rpc_client.block_number().await.unwrap(), 1);
+        let new_receipt =
+            devnet.json_rpc_client.get_transaction_receipt(new_mint_hash).await.unwrap();
+        assert_eq!(new_receipt.finality_status(), &TransactionFinalityStatus::AcceptedOnL2);
+        assert_ne!(get_block_hash(&devnet, 1).await, second_block_hash);
+        assert_eq!(get_balance(&devnet, address).await, FieldElement::from(2 * DUMMY_AMOUNT));
+    }
+
+    #[tokio::test]
+    async fn abort_all_blocks() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+        let first_block_hash = get_block_hash(&devnet, 0).await;
+
+        let abort_resp = abort_blocks(&devnet, first_block_hash).await;
+        assert_eq!(abort_resp.status(), StatusCode::OK, "Checking status of {abort_resp:?}");
+
+        assert_block_not_found(
+            devnet.json_rpc_client.get_block_with_tx_hashes(BlockId::Tag(BlockTag::Latest)).await,
+        );
+        assert_eq!(
+            get_balance(&devnet, FieldElement::from(DUMMY_ADDRESS)).await,
+            FieldElement::ZERO
+        );
+    }
+
+    #[tokio::test]
+    async fn abort_nonexistent_block() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let abort_resp = abort_blocks(&devnet, FieldElement::from(42_u32)).await;
+        assert_eq!(abort_resp.status(), StatusCode::BAD_REQUEST);
+    }
+}
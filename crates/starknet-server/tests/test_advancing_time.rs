@@ -0,0 +1,107 @@
+pub mod common;
+
+mod advancing_time_tests {
+    use std::time::SystemTime;
+
+    use hyper::{Body, StatusCode};
+    use serde_json::json;
+    use starknet_rs_core::types::{BlockId, BlockTag, FieldElement, MaybePendingBlockWithTxHashes};
+    use starknet_rs_providers::Provider;
+
+    use crate::common::devnet::BackgroundDevnet;
+    use crate::common::utils::get_json_body;
+
+    static DUMMY_ADDRESS: u128 = 1;
+    static DUMMY_AMOUNT: u128 = 1;
+
+    /// An arbitrary timestamp far in the past, so it cannot be mistaken for the system time
+    static PAST_TIMESTAMP: u64 = 1_000_000;
+
+    fn get_unix_timestamp_as_seconds() -> u64 {
+        SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .expect("should get current UNIX timestamp")
+            .as_secs()
+    }
+
+    async fn get_block_timestamp(devnet: &BackgroundDevnet, block_id: BlockId) -> u64 {
+        match devnet.json_rpc_client.get_block_with_tx_hashes(block_id).await {
+            Ok(MaybePendingBlockWithTxHashes::Block(block)) => block.timestamp,
+            Ok(MaybePendingBlockWithTxHashes::PendingBlock(block)) => block.timestamp,
+            other => panic!("Unexpected block: {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn set_time_and_generate_block() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let set_time_body =
+            Body::from(json!({ "time": PAST_TIMESTAMP, "generate_block": true }).to_string());
+        let resp = devnet.post_json("/set_time".into(), set_time_body).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+        let resp_body = get_json_body(resp).await;
+        assert_eq!(resp_body["block_timestamp"], PAST_TIMESTAMP);
+
+        let block_hash = FieldElement::from_hex_be(resp_body["block_hash"].as_str().unwrap());
+        assert_eq!(
+            get_block_timestamp(&devnet, BlockId::Hash(block_hash.unwrap())).await,
+            PAST_TIMESTAMP
+        );
+        assert_eq!(devnet.json_rpc_client.block_number().await.unwrap(), 0);
+
+        // following blocks continue from the set time, not from the system time
+        devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+        let next_block_timestamp =
+            get_block_timestamp(&devnet, BlockId::Tag(BlockTag::Latest)).await;
+        assert!(next_block_timestamp >= PAST_TIMESTAMP);
+        assert!(next_block_timestamp < get_unix_timestamp_as_seconds());
+    }
+
+    #[tokio::test]
+    async fn set_time_without_generating_block() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let set_time_body = Body::from(json!({ "time": PAST_TIMESTAMP }).to_string());
+        let resp = devnet.post_json("/set_time".into(), set_time_body).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+        let resp_body = get_json_body(resp).await;
+        assert!(resp_body.get("block_hash").is_none());
+
+        // the first transaction is put in a block with the set timestamp
+        devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+        assert_eq!(get_block_timestamp(&devnet, BlockId::Number(0)).await, PAST_TIMESTAMP);
+    }
+
+    #[tokio::test]
+    async fn increase_time() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let increase = 3600;
+        let lower_bound = get_unix_timestamp_as_seconds() + increase;
+
+        let increase_time_body = Body::from(json!({ "time": increase }).to_string());
+        let resp = devnet.post_json("/increase_time".into(), increase_time_body).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+        assert_eq!(get_json_body(resp).await["timestamp_increased_by"], increase);
+
+        devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+        let first_block_timestamp = get_block_timestamp(&devnet, BlockId::Number(0)).await;
+        assert!(first_block_timestamp >= lower_bound);
+
+        // the shift persists for later blocks as well
+        devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+        let second_block_timestamp = get_block_timestamp(&devnet, BlockId::Number(1)).await;
+        assert!(second_block_timestamp >= lower_bound);
+        assert!(second_block_timestamp <= get_unix_timestamp_as_seconds() + increase);
+    }
+
+    #[tokio::test]
+    async fn increase_time_by_too_much() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let increase_time_body = Body::from(json!({ "time": u64::MAX }).to_string());
+        let resp = devnet.post_json("/increase_time".into(), increase_time_body).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+}
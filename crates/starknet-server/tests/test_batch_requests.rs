@@ -0,0 +1,159 @@
+pub mod common;
+
+mod batch_requests_tests {
+    use hyper::body::to_bytes;
+    use serde_json::json;
+    use starknet_core::constants::ERC20_CONTRACT_ADDRESS;
+    use starknet_rs_accounts::{Account, Call, ExecutionEncoding, SingleOwnerAccount};
+    use starknet_rs_core::types::FieldElement;
+    use starknet_rs_core::utils::get_selector_from_name;
+
+    use crate::common::constants::{CHAIN_ID, RPC_PATH};
+    use crate::common::devnet::BackgroundDevnet;
+    use crate::common::utils::{get_json_body, iter_to_hex_felt, to_hex_felt, to_num_as_hex};
+
+    async fn send_batch(devnet: &BackgroundDevnet, batch: serde_json::Value) -> serde_json::Value {
+        let resp = devnet.post_json(RPC_PATH.into(), batch.to_string().into()).await.unwrap();
+        get_json_body(resp).await
+    }
+
+    #[tokio::test]
+    async fn responses_are_in_the_order_of_the_calls() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let resp = send_batch(
+            &devnet,
+            json!([
+                { "jsonrpc": "2.0", "id": 1, "method": "starknet_chainId", "params": [] },
+                { "jsonrpc": "2.0", "id": "2", "method": "starknet_syncing" },
+                { "jsonrpc": "2.0", "id": 3, "method": "starknet_getNonce", "params": {} },
+            ]),
+        )
+        .await;
+
+        let responses = resp.as_array().unwrap();
+        assert_eq!(responses.len(), 3);
+        assert_eq!(responses[0]["id"], 1);
+        assert!(responses[0]["result"].is_string());
+        assert_eq!(responses[1]["id"], "2");
+        assert_eq!(responses[1]["result"], false);
+        assert_eq!(responses[2]["id"], 3);
+        assert_eq!(responses[2]["error"]["code"], -32602);
+    }
+
+    #[tokio::test]
+    async fn invalid_entries_fail_on_their_own() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let resp = send_batch(
+            &devnet,
+            json!([
+                1,
+                { "jsonrpc": "2.0", "id": 2, "method": "starknet_unknownMethod", "params": [] },
+                { "jsonrpc": "2.0", "method": "starknet_chainId", "params": [] },
+                { "jsonrpc": "2.0", "id": 4, "method": "starknet_chainId", "params": [] },
+            ]),
+        )
+        .await;
+
+        // the notification without an id has no response
+        let responses = resp.as_array().unwrap();
+        assert_eq!(responses.len(), 3);
+        assert_eq!(responses[0]["id"], serde_json::Value::Null);
+        assert_eq!(responses[0]["error"]["code"], -32600);
+        assert_eq!(responses[1]["id"], 2);
+        assert_eq!(responses[1]["error"]["code"], -32601);
+        assert_eq!(responses[2]["id"], 4);
+        assert!(responses[2]["result"].is_string());
+    }
+
+    #[tokio::test]
+    async fn empty_batch_is_an_invalid_request() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let resp = send_batch(&devnet, json!([])).await;
+        assert_eq!(resp["error"]["code"], -32600);
+    }
+
+    #[tokio::test]
+    async fn batch_of_notifications_has_empty_response() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let batch = json!([
+            { "jsonrpc": "2.0", "method": "starknet_chainId", "params": [] },
+            { "jsonrpc": "2.0", "method": "starknet_syncing", "params": [] },
+        ]);
+        let resp = devnet.post_json(RPC_PATH.into(), batch.to_string().into()).await.unwrap();
+
+        assert!(resp.status().is_success());
+        assert!(to_bytes(resp.into_body()).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn notification_is_executed_before_the_following_calls() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        let (signer, account_address) = devnet.get_first_predeployed_account().await;
+        let account = SingleOwnerAccount::new(
+            devnet.clone_provider(),
+            signer,
+            account_address,
+            CHAIN_ID,
+            ExecutionEncoding::Legacy,
+        );
+
+        let max_fee = FieldElement::from(1e18 as u128);
+        let nonce = FieldElement::ZERO;
+        let invoke_request = account
+            .execute(vec![Call {
+                to: FieldElement::from_hex_be(ERC20_CONTRACT_ADDRESS).unwrap(),
+                selector: get_selector_from_name("transfer").unwrap(),
+                calldata: vec![FieldElement::ONE, FieldElement::ONE, FieldElement::ZERO],
+            }])
+            .max_fee(max_fee)
+            .nonce(nonce)
+            .prepared()
+            .unwrap()
+            .get_invoke_request()
+            .await
+            .unwrap();
+
+        let invoke_transaction = json!({
+            "type": "INVOKE",
+            "max_fee": to_hex_felt(&max_fee),
+            "version": "0x1",
+            "signature": iter_to_hex_felt(&invoke_request.signature),
+            "nonce": to_num_as_hex(&nonce),
+            "calldata": iter_to_hex_felt(&invoke_request.calldata),
+            "sender_address": to_hex_felt(&account_address),
+        });
+        let nonce_params =
+            json!({ "block_id": "latest", "contract_address": to_hex_felt(&account_address) });
+        let resp = send_batch(
+            &devnet,
+            json!([
+                {
+                    "jsonrpc": "2.0",
+                    "method": "starknet_addInvokeTransaction",
+                    "params": { "invoke_transaction": invoke_transaction },
+                },
+                { "jsonrpc": "2.0", "id": 1, "method": "starknet_getNonce", "params": nonce_params },
+            ]),
+        )
+        .await;
+
+        // only the call has a response, in which the nonce is incremented by the notification
+        let responses = resp.as_array().unwrap();
+        assert_eq!(responses.len(), 1);
+        assert_eq!(responses[0]["id"], 1);
+        assert_eq!(responses[0]["result"], "0x1");
+    }
+
+    #[tokio::test]
+    async fn malformed_json_is_a_parse_error() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let body = r#"[{ "jsonrpc": "2.0", "id": 1, "method": "starknet_chainId""#;
+        let resp = devnet.post_json(RPC_PATH.into(), body.into()).await.unwrap();
+        assert_eq!(get_json_body(resp).await["error"]["code"], -32700);
+    }
+}
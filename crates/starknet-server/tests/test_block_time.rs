@@ -0,0 +1,78 @@
+pub mod common;
+
+mod block_time_tests {
+    use std::time::{Duration, Instant};
+
+    use starknet_rs_core::types::{BlockId, BlockTag};
+    use starknet_rs_providers::Provider;
+
+    use crate::common::devnet::BackgroundDevnet;
+
+    static DUMMY_ADDRESS: u128 = 1;
+    static DUMMY_AMOUNT: u128 = 1;
+
+    /// Polls the latest block number until it satisfies `condition`, failing after `timeout`
+    async fn wait_for_block_number(
+        devnet: &BackgroundDevnet,
+        timeout: Duration,
+        condition: impl Fn(u64) -> bool,
+    ) -> u64 {
+        let deadline = Instant::now() + timeout;
+        loop {
+            // there is no latest block before the first one is generated
+            if let Ok(block_number) = devnet.json_rpc_client.block_number().await {
+                if condition(block_number) {
+                    return block_number;
+                }
+            }
+
+            assert!(Instant::now() < deadline, "Expected block not generated in {timeout:?}");
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn blocks_are_generated_periodically() {
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&["--block-time", "1"])
+            .await
+            .expect("Could not start Devnet");
+
+        // empty blocks are generated as well
+        wait_for_block_number(&devnet, Duration::from_secs(10), |number| number >= 2).await;
+
+        let latest_block = devnet
+            .json_rpc_client
+            .get_block_with_tx_hashes(BlockId::Tag(BlockTag::Latest))
+            .await
+            .unwrap();
+        assert!(latest_block.transactions().is_empty());
+    }
+
+    #[tokio::test]
+    async fn transactions_are_included_in_next_generated_block() {
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&["--block-time", "2"])
+            .await
+            .expect("Could not start Devnet");
+
+        wait_for_block_number(&devnet, Duration::from_secs(10), |_| true).await;
+        let tx_hash = devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+        let block_number_after_mint = devnet.json_rpc_client.block_number().await.unwrap();
+
+        let block_number = wait_for_block_number(&devnet, Duration::from_secs(10), |number| {
+            number > block_number_after_mint
+        })
+        .await;
+        assert!(block_number > block_number_after_mint);
+
+        // the transaction is contained in exactly one of the generated blocks
+        let mut containing_blocks = 0;
+        for number in 0..=block_number {
+            let block =
+                devnet.json_rpc_client.get_block_with_tx_hashes(BlockId::Number(number)).await;
+            if block.unwrap().transactions().contains(&tx_hash) {
+                containing_blocks += 1;
+            }
+        }
+        assert_eq!(containing_blocks, 1);
+    }
+}
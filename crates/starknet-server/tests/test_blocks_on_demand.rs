@@ -0,0 +1,106 @@
+pub mod common;
+
+mod blocks_on_demand_tests {
+    use starknet_rs_core::types::{
+        BlockId, BlockTag, MaybePendingStateUpdate, MaybePendingTransactionReceipt, StarknetError,
+        TransactionReceipt,
+    };
+    use starknet_rs_providers::{
+        MaybeUnknownErrorCode, Provider, ProviderError, StarknetErrorWithMessage,
+    };
+
+    use crate::common::devnet::BackgroundDevnet;
+
+    static DUMMY_ADDRESS: u128 = 1;
+    static DUMMY_AMOUNT: u128 = 1;
+
+    #[tokio::test]
+    async fn blocks_on_demand_states_and_blocks() {
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&["--blocks-on-demand"])
+            .await
+            .expect("Could not start Devnet");
+
+        let tx_count = 5;
+        let mut tx_hashes = Vec::new();
+        for _ in 0..tx_count {
+            let tx_hash = devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+            tx_hashes.push(tx_hash);
+        }
+
+        // transactions are visible in the pending block, but no block has been created yet
+        let pending_block = devnet
+            .json_rpc_client
+            .get_block_with_tx_hashes(BlockId::Tag(BlockTag::Pending))
+            .await
+            .unwrap();
+        assert_eq!(pending_block.transactions(), tx_hashes.as_slice());
+
+        match devnet.json_rpc_client.get_block_with_tx_hashes(BlockId::Number(0)).await {
+            Err(ProviderError::StarknetError(StarknetErrorWithMessage {
+                code: MaybeUnknownErrorCode::Known(StarknetError::BlockNotFound),
+                ..
+            })) => (),
+            other => panic!("Unexpected result: {other:?}"),
+        }
+
+        let block_hash = devnet.create_block().await;
+
+        let latest_block = devnet
+            .json_rpc_client
+            .get_block_with_tx_hashes(BlockId::Tag(BlockTag::Latest))
+            .await
+            .unwrap();
+        assert_eq!(latest_block.transactions(), tx_hashes.as_slice());
+
+        let block_by_hash =
+            devnet.json_rpc_client.get_block_with_tx_hashes(BlockId::Hash(block_hash)).await;
+        assert_eq!(block_by_hash.unwrap().transactions(), tx_hashes.as_slice());
+        assert_eq!(devnet.json_rpc_client.block_number().await.unwrap(), 0);
+
+        // all transactions end up in the same block
+        for tx_hash in tx_hashes {
+            let receipt = devnet.json_rpc_client.get_transaction_receipt(tx_hash).await.unwrap();
+            match receipt {
+                MaybePendingTransactionReceipt::Receipt(TransactionReceipt::Invoke(receipt)) => {
+                    assert_eq!(receipt.block_hash, block_hash)
+                }
+                other => panic!("Unexpected receipt: {other:?}"),
+            }
+        }
+
+        // the state diff of the block contains the combined changes
+        let state_update =
+            devnet.json_rpc_client.get_state_update(BlockId::Hash(block_hash)).await.unwrap();
+        match state_update {
+            MaybePendingStateUpdate::Update(state_update) => {
+                assert!(!state_update.state_diff.storage_diffs.is_empty())
+            }
+            other => panic!("Unexpected state update: {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn create_block_without_transactions() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        devnet.create_block().await;
+        devnet.create_block().await;
+
+        let latest_block = devnet
+            .json_rpc_client
+            .get_block_with_tx_hashes(BlockId::Tag(BlockTag::Latest))
+            .await
+            .unwrap();
+        assert!(latest_block.transactions().is_empty());
+        assert_eq!(devnet.json_rpc_client.block_number().await.unwrap(), 1);
+
+        let tx_hash = devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+        let latest_block = devnet
+            .json_rpc_client
+            .get_block_with_tx_hashes(BlockId::Tag(BlockTag::Latest))
+            .await
+            .unwrap();
+        assert_eq!(latest_block.transactions(), &[tx_hash]);
+        assert_eq!(devnet.json_rpc_client.block_number().await.unwrap(), 2);
+    }
+}
@@ -0,0 +1,110 @@
+pub mod common;
+
+mod devnet_rpc_tests {
+    use serde_json::json;
+    use starknet_rs_providers::Provider;
+
+    use crate::common::constants::PREDEPLOYED_ACCOUNT_INITIAL_BALANCE;
+    use crate::common::devnet::BackgroundDevnet;
+    use crate::common::utils::get_json_body;
+
+    #[tokio::test]
+    async fn mint_and_get_balance() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        let address = "0x123";
+
+        let minted = &devnet
+            .send_custom_rpc("devnet_mint", json!({ "address": address, "amount": 1000 }))
+            .await["result"];
+        assert_eq!(minted["new_balance"], "1000");
+        assert_eq!(minted["unit"], "WEI");
+        assert!(minted["tx_hash"].is_string());
+
+        let balance = &devnet
+            .send_custom_rpc("devnet_getAccountBalance", json!({ "address": address }))
+            .await["result"];
+        assert_eq!(balance, &json!({ "amount": "1000", "unit": "WEI" }));
+
+        let balance = &devnet
+            .send_custom_rpc(
+                "devnet_getAccountBalance",
+                json!({ "address": address, "unit": "FRI" }),
+            )
+            .await["result"];
+        assert_eq!(balance, &json!({ "amount": "0", "unit": "FRI" }));
+    }
+
+    #[tokio::test]
+    async fn predeployed_accounts_are_the_same_as_over_http() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let accounts =
+            &devnet.send_custom_rpc("devnet_getPredeployedAccounts", json!(null)).await["result"];
+        let http_accounts =
+            get_json_body(devnet.get("/predeployed_accounts", None).await.unwrap()).await;
+        assert_eq!(accounts, &http_accounts);
+        assert_eq!(accounts[0]["initial_balance"], PREDEPLOYED_ACCOUNT_INITIAL_BALANCE.to_string());
+    }
+
+    #[tokio::test]
+    async fn create_block_and_restart() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let created = &devnet.send_custom_rpc("devnet_createBlock", json!(null)).await["result"];
+        assert!(created["block_hash"].is_string());
+        assert_eq!(devnet.json_rpc_client.block_number().await.unwrap(), 1);
+
+        let restarted = devnet.send_custom_rpc("devnet_restart", json!(null)).await;
+        assert_eq!(restarted["result"], json!(null));
+        assert_eq!(devnet.json_rpc_client.block_number().await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn failing_devnet_method_returns_error() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let error = &devnet
+            .send_custom_rpc("devnet_load", json!({ "path": "nonexistent_dump" }))
+            .await["error"];
+        assert_eq!(error["code"], 102);
+        assert_eq!(
+            error["message"],
+            "The load operation failed: file nonexistent_dump does not exist"
+        );
+    }
+
+    #[tokio::test]
+    async fn failing_block_and_postman_methods_return_their_own_error_codes() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let error = &devnet
+            .send_custom_rpc("devnet_abortBlocks", json!({ "startingBlockHash": "0x1" }))
+            .await["error"];
+        assert_eq!(error["code"], 107);
+        assert_eq!(error["message"], "Aborting blocks failed: No block found");
+
+        let error = &devnet.send_custom_rpc("devnet_postmanFlush", json!(null)).await["error"];
+        assert_eq!(error["code"], 108);
+        assert_eq!(
+            error["message"],
+            "The postman operation failed: Messaging error: Messaging contract is not loaded"
+        );
+    }
+
+    #[tokio::test]
+    async fn devnet_method_with_invalid_params_is_rejected() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let error =
+            &devnet.send_custom_rpc("devnet_mint", json!({ "amount": 1000 })).await["error"];
+        assert_eq!(error["code"], -32602);
+    }
+
+    #[tokio::test]
+    async fn unknown_devnet_method_is_not_found() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let error = &devnet.send_custom_rpc("devnet_unknownMethod", json!(null)).await["error"];
+        assert_eq!(error["code"], -32601);
+    }
+}
@@ -21,7 +21,9 @@ mod dump_and_load_tests {
     use starknet_rs_accounts::{Account, ExecutionEncoding, SingleOwnerAccount};
     use starknet_rs_contract::ContractFactory;
     use starknet_rs_core::chain_id;
-    use starknet_rs_core::types::{BlockId, BlockTag, FieldElement, FunctionCall};
+    use starknet_rs_core::types::{
+        BlockId, BlockTag, FieldElement, FunctionCall, MaybePendingBlockWithTxHashes,
+    };
 
     use crate::common::utils::get_events_contract_in_sierra_and_compiled_class_hash;
 
@@ -351,7 +353,7 @@ mod dump_and_load_tests {
         devnet_dump.post_json("/dump".into(), dump_body_custom_path).await.unwrap();
         assert!(Path::new(dump_file_name_custom_path).exists());
 
-        // load and re-execute from "dump_endpoint" file and check if transaction and state of the
+        // load from "dump_endpoint" file and check if transaction and state of the
         // blockchain is valid
         let devnet_load = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
         let load_body = Body::from(
@@ -392,4 +394,43 @@ mod dump_and_load_tests {
         remove_file(dump_file_name);
         remove_file(dump_file_name_custom_path);
     }
+
+    #[tokio::test]
+    async fn blocks_are_loaded_with_the_same_hashes_and_timestamps() {
+        let dump_file_name = "dump_blocks";
+        let devnet_dump =
+            BackgroundDevnet::spawn_with_additional_args(&["--dump-path", dump_file_name])
+                .await
+                .expect("Could not start Devnet");
+        devnet_dump.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+        devnet_dump.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+        devnet_dump.post_json("/dump".into(), Body::from(json!({}).to_string())).await.unwrap();
+
+        // loading on request replaces the blocks created so far
+        let devnet_load = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        devnet_load.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+        let load_body = Body::from(json!({ "path": dump_file_name }).to_string());
+        devnet_load.post_json("/load".into(), load_body).await.unwrap();
+
+        for block_number in [0, 1] {
+            let block_id = BlockId::Number(block_number);
+            match (
+                devnet_dump.json_rpc_client.get_block_with_tx_hashes(block_id).await.unwrap(),
+                devnet_load.json_rpc_client.get_block_with_tx_hashes(block_id).await.unwrap(),
+            ) {
+                (
+                    MaybePendingBlockWithTxHashes::Block(dumped_block),
+                    MaybePendingBlockWithTxHashes::Block(loaded_block),
+                ) => {
+                    assert_eq!(loaded_block.block_hash, dumped_block.block_hash);
+                    assert_eq!(loaded_block.parent_hash, dumped_block.parent_hash);
+                    assert_eq!(loaded_block.timestamp, dumped_block.timestamp);
+                    assert_eq!(loaded_block.transactions, dumped_block.transactions);
+                }
+                blocks => panic!("Unexpected blocks: {blocks:?}"),
+            }
+        }
+
+        remove_file(dump_file_name);
+    }
 }
@@ -0,0 +1,90 @@
+pub mod common;
+
+mod fork_tests {
+    use serde_json::json;
+    use starknet_core::constants::ERC20_CONTRACT_ADDRESS;
+    use starknet_rs_core::types::{BlockId, BlockTag, FieldElement, FunctionCall};
+    use starknet_rs_core::utils::get_selector_from_name;
+    use starknet_rs_providers::Provider;
+
+    use crate::common::devnet::BackgroundDevnet;
+    use crate::common::utils::get_json_body;
+
+    static DUMMY_ADDRESS: u128 = 1;
+    static DUMMY_AMOUNT: u128 = 42;
+
+    async fn get_balance(devnet: &BackgroundDevnet, address: FieldElement) -> FieldElement {
+        let balance = devnet
+            .json_rpc_client
+            .call(
+                FunctionCall {
+                    contract_address: FieldElement::from_hex_be(ERC20_CONTRACT_ADDRESS).unwrap(),
+                    entry_point_selector: get_selector_from_name("balanceOf").unwrap(),
+                    calldata: vec![address],
+                },
+                BlockId::Tag(BlockTag::Latest),
+            )
+            .await
+            .unwrap();
+
+        balance[0]
+    }
+
+    async fn spawn_fork(origin: &BackgroundDevnet, fork_block: Option<u64>) -> BackgroundDevnet {
+        let origin_url = origin.rpc_url.to_string();
+        let fork_block = fork_block.map(|block| block.to_string());
+
+        let mut args = vec!["--fork-network", origin_url.as_str()];
+        if let Some(fork_block) = fork_block.as_ref() {
+            args.extend(["--fork-block", fork_block.as_str()]);
+        }
+
+        BackgroundDevnet::spawn_with_additional_args(&args)
+            .await
+            .expect("Could not start forked Devnet")
+    }
+
+    #[tokio::test]
+    async fn forked_devnet_reads_state_of_origin() {
+        let origin = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        let address = FieldElement::from(DUMMY_ADDRESS);
+        origin.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+
+        let fork = spawn_fork(&origin, None).await;
+        assert_eq!(get_balance(&fork, address).await, FieldElement::from(DUMMY_AMOUNT));
+
+        // changes of the fork are local
+        fork.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+        assert_eq!(get_balance(&fork, address).await, FieldElement::from(2 * DUMMY_AMOUNT));
+        assert_eq!(get_balance(&origin, address).await, FieldElement::from(DUMMY_AMOUNT));
+
+        // new blocks are generated on top of the fork block
+        assert_eq!(fork.json_rpc_client.block_number().await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn forked_devnet_ignores_origin_changes_after_fork_block() {
+        let origin = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        let address = FieldElement::from(DUMMY_ADDRESS);
+        origin.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+        origin.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+
+        let fork = spawn_fork(&origin, Some(0)).await;
+        assert_eq!(get_balance(&fork, address).await, FieldElement::from(DUMMY_AMOUNT));
+
+        origin.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+        assert_eq!(get_balance(&fork, address).await, FieldElement::from(DUMMY_AMOUNT));
+    }
+
+    #[tokio::test]
+    async fn fork_status() {
+        let origin = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        let origin_status = get_json_body(origin.get("/fork_status", None).await.unwrap()).await;
+        assert_eq!(origin_status, json!({}));
+
+        origin.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+        let fork = spawn_fork(&origin, None).await;
+        let fork_status = get_json_body(fork.get("/fork_status", None).await.unwrap()).await;
+        assert_eq!(fork_status, json!({ "url": origin.rpc_url.to_string(), "block": 0 }));
+    }
+}
@@ -0,0 +1,272 @@
+pub mod common;
+
+mod messaging_tests {
+    use std::sync::Arc;
+
+    use hyper::{Body, StatusCode};
+    use serde_json::json;
+    use starknet_core::utils::exported_test_utils::dummy_cairo_l1l2_contract;
+    use starknet_rs_accounts::{Account, Call, ExecutionEncoding, SingleOwnerAccount};
+    use starknet_rs_contract::ContractFactory;
+    use starknet_rs_core::types::contract::legacy::LegacyContractClass;
+    use starknet_rs_core::types::{
+        BlockId, BlockTag, FieldElement, FunctionCall, MaybePendingTransactionReceipt,
+        TransactionReceipt,
+    };
+    use starknet_rs_core::utils::{
+        get_selector_from_name, get_udc_deployed_address, UdcUniqueness,
+    };
+    use starknet_rs_providers::Provider;
+
+    use crate::common::constants::CHAIN_ID;
+    use crate::common::devnet::BackgroundDevnet;
+    use crate::common::utils::{assert_tx_successful, get_json_body, remove_file, to_hex_felt};
+
+    /// Address at which no L1 node is expected to be listening
+    const UNREACHABLE_L1_NODE_URL: &str = "http://127.0.0.1:1";
+    const DUMMY_MESSAGING_CONTRACT_ADDRESS: &str = "0x5fbdb2315678afecb367f032d93f642f64180aa3";
+    /// The only L1 address the deposit handler of the L1-L2 test contract accepts messages from
+    const WHITELISTED_L1_ADDRESS: &str = "0x8359e4b0152ed5a731162d3c7b0d8d56edb165a0";
+    const DUMMY_USER: u128 = 1;
+
+    /// Declares and deploys the L1-L2 test contract, returning its address
+    async fn deploy_l1l2_contract(devnet: &BackgroundDevnet) -> FieldElement {
+        let (signer, account_address) = devnet.get_first_predeployed_account().await;
+        let account = Arc::new(SingleOwnerAccount::new(
+            devnet.clone_provider(),
+            signer,
+            account_address,
+            CHAIN_ID,
+            ExecutionEncoding::Legacy,
+        ));
+
+        let contract_artifact: Arc<LegacyContractClass> =
+            Arc::new(serde_json::from_value(dummy_cairo_l1l2_contract().inner).unwrap());
+        let class_hash = contract_artifact.class_hash().unwrap();
+        account
+            .declare_legacy(contract_artifact)
+            .nonce(FieldElement::ZERO)
+            .max_fee(FieldElement::from(1e18 as u128))
+            .send()
+            .await
+            .unwrap();
+
+        let salt = FieldElement::from_hex_be("0x123").unwrap();
+        ContractFactory::new(class_hash, account)
+            .deploy(vec![], salt, false)
+            .nonce(FieldElement::ONE)
+            .max_fee(FieldElement::from(1e18 as u128))
+            .send()
+            .await
+            .unwrap();
+
+        get_udc_deployed_address(salt, class_hash, &UdcUniqueness::NotUnique, &[])
+    }
+
+    /// Sends a message to the deposit handler of the L1-L2 test contract, returning the hash of
+    /// the resulting L1 handler transaction
+    async fn deposit(
+        devnet: &BackgroundDevnet,
+        contract_address: FieldElement,
+        amount: u128,
+    ) -> FieldElement {
+        let req_body = Body::from(
+            json!({
+                "l1_contract_address": WHITELISTED_L1_ADDRESS,
+                "l2_contract_address": to_hex_felt(&contract_address),
+                "entry_point_selector": to_hex_felt(&get_selector_from_name("deposit").unwrap()),
+                "payload": [to_hex_felt(&DUMMY_USER), to_hex_felt(&amount)],
+                "paid_fee_on_l1": "0x1",
+                "nonce": "0x0",
+            })
+            .to_string(),
+        );
+        let resp = devnet.post_json("/postman/send_message_to_l2".into(), req_body).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+        let resp_body = get_json_body(resp).await;
+
+        FieldElement::from_hex_be(resp_body["transaction_hash"].as_str().unwrap()).unwrap()
+    }
+
+    async fn get_balance(
+        devnet: &BackgroundDevnet,
+        contract_address: FieldElement,
+    ) -> FieldElement {
+        devnet
+            .json_rpc_client
+            .call(
+                FunctionCall {
+                    contract_address,
+                    entry_point_selector: get_selector_from_name("get_balance").unwrap(),
+                    calldata: vec![DUMMY_USER.into()],
+                },
+                BlockId::Tag(BlockTag::Latest),
+            )
+            .await
+            .unwrap()[0]
+    }
+
+    #[tokio::test]
+    async fn flush_without_loaded_messaging_contract() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let resp = devnet
+            .post_json("/postman/flush".into(), Body::from(json!({}).to_string()))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        let resp_body = get_json_body(resp).await;
+        assert!(resp_body["error"].as_str().unwrap().contains("not loaded"));
+    }
+
+    #[tokio::test]
+    async fn deploy_messaging_contract_on_unreachable_l1_node() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let req_body = Body::from(json!({ "networkUrl": UNREACHABLE_L1_NODE_URL }).to_string());
+        let resp =
+            devnet.post_json("/postman/load_l1_messaging_contract".into(), req_body).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn load_messaging_contract_of_unreachable_l1_node() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let req_body = Body::from(
+            json!({
+                "networkUrl": UNREACHABLE_L1_NODE_URL,
+                "address": DUMMY_MESSAGING_CONTRACT_ADDRESS
+            })
+            .to_string(),
+        );
+        let resp =
+            devnet.post_json("/postman/load_l1_messaging_contract".into(), req_body).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        // the messaging contract is still not loaded
+        let resp = devnet
+            .post_json("/postman/flush".into(), Body::from(json!({}).to_string()))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn send_message_to_l2_executes_l1_handler() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        let contract_address = deploy_l1l2_contract(&devnet).await;
+
+        let transaction_hash = deposit(&devnet, contract_address, 10).await;
+        assert_tx_successful(&transaction_hash, &devnet.json_rpc_client).await;
+        match devnet.json_rpc_client.get_transaction_by_hash(transaction_hash).await.unwrap() {
+            starknet_rs_core::types::Transaction::L1Handler(transaction) => {
+                assert_eq!(transaction.contract_address, contract_address)
+            }
+            transaction => panic!("Unexpected transaction: {transaction:?}"),
+        }
+
+        assert_eq!(get_balance(&devnet, contract_address).await, FieldElement::from(10_u32));
+    }
+
+    #[tokio::test]
+    async fn send_message_to_nonexistent_contract() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let req_body = Body::from(
+            json!({
+                "l1_contract_address": WHITELISTED_L1_ADDRESS,
+                "l2_contract_address": "0x42",
+                "entry_point_selector": to_hex_felt(&get_selector_from_name("deposit").unwrap()),
+                "payload": ["0x1", "0x1"],
+                "paid_fee_on_l1": "0x1",
+                "nonce": "0x0",
+            })
+            .to_string(),
+        );
+        let resp = devnet.post_json("/postman/send_message_to_l2".into(), req_body).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn l1_handler_transaction_is_dumped_and_loaded() {
+        let dump_file_name = "messaging_dump";
+        let devnet_dump =
+            BackgroundDevnet::spawn_with_additional_args(&["--dump-path", dump_file_name])
+                .await
+                .expect("Could not start Devnet");
+        let contract_address = deploy_l1l2_contract(&devnet_dump).await;
+        let transaction_hash = deposit(&devnet_dump, contract_address, 10).await;
+        devnet_dump.post_json("/dump".into(), Body::from(json!({}).to_string())).await.unwrap();
+
+        let devnet_load = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        let load_body = Body::from(json!({ "path": dump_file_name }).to_string());
+        let resp = devnet_load.post_json("/load".into(), load_body).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        assert_tx_successful(&transaction_hash, &devnet_load.json_rpc_client).await;
+        assert_eq!(get_balance(&devnet_load, contract_address).await, FieldElement::from(10_u32));
+
+        remove_file(dump_file_name);
+    }
+
+    #[tokio::test]
+    async fn message_sent_to_l1_is_consumed_once() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        let contract_address = deploy_l1l2_contract(&devnet).await;
+        deposit(&devnet, contract_address, 10).await;
+
+        let (signer, account_address) = devnet.get_first_predeployed_account().await;
+        let account = SingleOwnerAccount::new(
+            devnet.clone_provider(),
+            signer,
+            account_address,
+            CHAIN_ID,
+            ExecutionEncoding::Legacy,
+        );
+        let withdraw_amount = 3_u32;
+        let withdrawal = account
+            .execute(vec![Call {
+                to: contract_address,
+                selector: get_selector_from_name("withdraw").unwrap(),
+                calldata: vec![DUMMY_USER.into(), withdraw_amount.into()],
+            }])
+            .max_fee(FieldElement::from(1e18 as u128))
+            .send()
+            .await
+            .unwrap();
+
+        match devnet
+            .json_rpc_client
+            .get_transaction_receipt(withdrawal.transaction_hash)
+            .await
+            .unwrap()
+        {
+            MaybePendingTransactionReceipt::Receipt(TransactionReceipt::Invoke(receipt)) => {
+                assert_eq!(receipt.messages_sent.len(), 1);
+                assert_eq!(receipt.messages_sent[0].from_address, contract_address);
+            }
+            receipt => panic!("Unexpected receipt: {receipt:?}"),
+        }
+
+        // the withdraw message of the L1-L2 test contract is [0, user, amount]
+        let message = json!({
+            "from_address": to_hex_felt(&contract_address),
+            "to_address": WHITELISTED_L1_ADDRESS,
+            "payload": ["0x0", to_hex_felt(&DUMMY_USER), to_hex_felt(&withdraw_amount)],
+        });
+        let resp = devnet
+            .post_json("/postman/consume_message_from_l2".into(), Body::from(message.to_string()))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let resp_body = get_json_body(resp).await;
+        assert!(resp_body["message_hash"].as_str().unwrap().starts_with("0x"));
+
+        let resp = devnet
+            .post_json("/postman/consume_message_from_l2".into(), Body::from(message.to_string()))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+}
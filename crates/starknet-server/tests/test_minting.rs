@@ -57,6 +57,38 @@ mod minting_tests {
         .await
     }
 
+    #[tokio::test]
+    async fn increase_balance_in_fri() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        let req_body = Body::from(
+            json!({
+                "address": DUMMY_ADDRESS,
+                "amount": DUMMY_AMOUNT,
+                "unit": "FRI"
+            })
+            .to_string(),
+        );
+
+        let resp = devnet.post_json("/mint".into(), req_body).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+        let resp_body = get_json_body(resp).await;
+        assert_eq!(resp_body["new_balance"], DUMMY_AMOUNT.to_string());
+        assert_eq!(resp_body["unit"], "FRI");
+
+        // minting STRK leaves the ETH balance untouched
+        for (unit, expected_amount) in [("WEI", 0), ("FRI", DUMMY_AMOUNT)] {
+            let resp = devnet
+                .get("/account_balance", Some(format!("address={DUMMY_ADDRESS}&unit={unit}")))
+                .await
+                .unwrap();
+            assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+            assert_eq!(
+                get_json_body(resp).await,
+                json!({ "amount": expected_amount.to_string(), "unit": unit })
+            );
+        }
+    }
+
     async fn reject_bad_request(
         devnet: &BackgroundDevnet,
         json_body: serde_json::Value,
@@ -81,6 +113,21 @@ mod minting_tests {
         .await;
     }
 
+    #[tokio::test]
+    async fn reject_unknown_unit() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        reject_bad_request(
+            &devnet,
+            json!({
+                "address": DUMMY_ADDRESS,
+                "amount": DUMMY_AMOUNT,
+                "unit": "GWEI"
+            }),
+            StatusCode::UNPROCESSABLE_ENTITY,
+        )
+        .await;
+    }
+
     #[tokio::test]
     async fn reject_missing_address() {
         let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
@@ -0,0 +1,85 @@
+pub mod common;
+
+mod rejected_transactions_tests {
+    use serde_json::json;
+    use starknet_rs_core::types::{BroadcastedDeclareTransaction, BroadcastedDeclareTransactionV1};
+    use starknet_rs_providers::Provider;
+
+    use crate::common::devnet::BackgroundDevnet;
+    use crate::common::utils::get_json_body;
+
+    /// Sends a declaration whose max fee is too low and returns the hash of the rejected
+    /// transaction
+    async fn send_rejected_declaration(devnet: &BackgroundDevnet) -> serde_json::Value {
+        let json_string = std::fs::read_to_string(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/test_data/rpc/declare_v1.json"
+        ))
+        .unwrap();
+        let declare_txn_v1: BroadcastedDeclareTransactionV1 =
+            serde_json::from_str(&json_string).unwrap();
+
+        devnet
+            .json_rpc_client
+            .add_declare_transaction(BroadcastedDeclareTransaction::V1(declare_txn_v1))
+            .await
+            .unwrap_err();
+
+        let rejected_transactions =
+            get_json_body(devnet.get("/rejected_transactions", None).await.unwrap()).await;
+        let rejected_transactions = rejected_transactions.as_array().unwrap();
+        assert_eq!(rejected_transactions.len(), 1);
+        assert!(!rejected_transactions[0]["failure_reason"].as_str().unwrap().is_empty());
+        assert_eq!(rejected_transactions[0]["transaction"]["type"], "DECLARE");
+
+        rejected_transactions[0]["transaction_hash"].clone()
+    }
+
+    #[tokio::test]
+    async fn no_transactions_are_rejected_initially() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let rejected_transactions =
+            get_json_body(devnet.get("/rejected_transactions", None).await.unwrap()).await;
+        assert_eq!(rejected_transactions, json!([]));
+    }
+
+    #[tokio::test]
+    async fn rejected_transaction_has_status_with_failure_reason() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        let transaction_hash = send_rejected_declaration(&devnet).await;
+
+        let status = &devnet
+            .send_custom_rpc(
+                "starknet_getTransactionStatus",
+                json!({ "transaction_hash": transaction_hash }),
+            )
+            .await["result"];
+        assert_eq!(status["finality_status"], "REJECTED");
+        assert!(status.get("execution_status").is_none());
+        assert!(status["failure_reason"].is_string());
+    }
+
+    #[tokio::test]
+    async fn rejected_transaction_can_be_retrieved_by_hash() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        let transaction_hash = send_rejected_declaration(&devnet).await;
+
+        let transaction = &devnet
+            .send_custom_rpc(
+                "starknet_getTransactionByHash",
+                json!({ "transaction_hash": transaction_hash }),
+            )
+            .await["result"];
+        assert_eq!(transaction["transaction_hash"], transaction_hash);
+
+        // a rejected transaction is not part of the chain, so it has no receipt
+        let receipt_error = &devnet
+            .send_custom_rpc(
+                "starknet_getTransactionReceipt",
+                json!({ "transaction_hash": transaction_hash }),
+            )
+            .await["error"];
+        assert_eq!(receipt_error["code"], 29);
+    }
+}
@@ -0,0 +1,93 @@
+pub mod common;
+
+mod restart_tests {
+    use hyper::{Body, StatusCode};
+    use serde_json::json;
+    use starknet_core::constants::ERC20_CONTRACT_ADDRESS;
+    use starknet_rs_core::types::{BlockId, BlockTag, FieldElement, FunctionCall, StarknetError};
+    use starknet_rs_core::utils::get_selector_from_name;
+    use starknet_rs_providers::{
+        MaybeUnknownErrorCode, Provider, ProviderError, StarknetErrorWithMessage,
+    };
+
+    use crate::common::constants::{
+        PREDEPLOYED_ACCOUNT_ADDRESS, PREDEPLOYED_ACCOUNT_INITIAL_BALANCE,
+    };
+    use crate::common::devnet::BackgroundDevnet;
+
+    static DUMMY_AMOUNT: u128 = 42;
+
+    async fn get_balance(devnet: &BackgroundDevnet, address: FieldElement) -> FieldElement {
+        let balance = devnet
+            .json_rpc_client
+            .call(
+                FunctionCall {
+                    contract_address: FieldElement::from_hex_be(ERC20_CONTRACT_ADDRESS).unwrap(),
+                    entry_point_selector: get_selector_from_name("balanceOf").unwrap(),
+                    calldata: vec![address],
+                },
+                BlockId::Tag(BlockTag::Latest),
+            )
+            .await
+            .unwrap();
+
+        balance[0]
+    }
+
+    #[tokio::test]
+    async fn restart_clears_transactions_and_blocks() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        let account_address = FieldElement::from_hex_be(PREDEPLOYED_ACCOUNT_ADDRESS).unwrap();
+
+        let mint_tx_hash = devnet.mint(account_address, DUMMY_AMOUNT).await;
+        assert_eq!(
+            get_balance(&devnet, account_address).await,
+            FieldElement::from(PREDEPLOYED_ACCOUNT_INITIAL_BALANCE + DUMMY_AMOUNT)
+        );
+
+        let restart_resp =
+            devnet.post_json("/restart".into(), Body::from(json!({}).to_string())).await.unwrap();
+        assert_eq!(restart_resp.status(), StatusCode::OK, "Checking status of {restart_resp:?}");
+
+        match devnet.json_rpc_client.get_transaction_by_hash(mint_tx_hash).await {
+            Err(ProviderError::StarknetError(StarknetErrorWithMessage {
+                code: MaybeUnknownErrorCode::Known(StarknetError::TransactionHashNotFound),
+                ..
+            })) => (),
+            other => panic!("Unexpected result: {other:?}"),
+        }
+
+        match devnet.json_rpc_client.get_block_with_tx_hashes(BlockId::Number(0)).await {
+            Err(ProviderError::StarknetError(StarknetErrorWithMessage {
+                code: MaybeUnknownErrorCode::Known(StarknetError::BlockNotFound),
+                ..
+            })) => (),
+            other => panic!("Unexpected result: {other:?}"),
+        }
+
+        assert_eq!(
+            get_balance(&devnet, account_address).await,
+            FieldElement::from(PREDEPLOYED_ACCOUNT_INITIAL_BALANCE)
+        );
+    }
+
+    #[tokio::test]
+    async fn restart_keeps_devnet_usable() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        let account_address = FieldElement::from_hex_be(PREDEPLOYED_ACCOUNT_ADDRESS).unwrap();
+
+        devnet.mint(account_address, DUMMY_AMOUNT).await;
+
+        let restart_resp =
+            devnet.post_json("/restart".into(), Body::from(json!({}).to_string())).await.unwrap();
+        assert_eq!(restart_resp.status(), StatusCode::OK, "Checking status of {restart_resp:?}");
+
+        // the chargeable account nonce is reset as well, so minting works again
+        devnet.mint(account_address, DUMMY_AMOUNT).await;
+        assert_eq!(
+            get_balance(&devnet, account_address).await,
+            FieldElement::from(PREDEPLOYED_ACCOUNT_INITIAL_BALANCE + DUMMY_AMOUNT)
+        );
+        assert_eq!(devnet.json_rpc_client.block_number().await.unwrap(), 0);
+    }
+}
@@ -0,0 +1,100 @@
+pub mod common;
+
+mod snapshots_tests {
+    use hyper::{Body, StatusCode};
+    use serde_json::json;
+    use starknet_core::constants::ERC20_CONTRACT_ADDRESS;
+    use starknet_rs_core::types::{BlockId, BlockTag, FieldElement, FunctionCall};
+    use starknet_rs_core::utils::get_selector_from_name;
+    use starknet_rs_providers::Provider;
+
+    use crate::common::devnet::BackgroundDevnet;
+    use crate::common::utils::get_json_body;
+
+    static DUMMY_ADDRESS: u128 = 1;
+    static DUMMY_AMOUNT: u128 = 1;
+
+    async fn get_balance(devnet: &BackgroundDevnet) -> FieldElement {
+        let balance = devnet
+            .json_rpc_client
+            .call(
+                FunctionCall {
+                    contract_address: FieldElement::from_hex_be(ERC20_CONTRACT_ADDRESS).unwrap(),
+                    entry_point_selector: get_selector_from_name("balanceOf").unwrap(),
+                    calldata: vec![FieldElement::from(DUMMY_ADDRESS)],
+                },
+                BlockId::Tag(BlockTag::Latest),
+            )
+            .await
+            .unwrap();
+
+        balance[0]
+    }
+
+    async fn snapshot(devnet: &BackgroundDevnet) -> u64 {
+        let resp =
+            devnet.post_json("/snapshot".into(), Body::from(json!({}).to_string())).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+
+        get_json_body(resp).await["snapshot_id"].as_u64().unwrap()
+    }
+
+    async fn revert(devnet: &BackgroundDevnet, snapshot_id: u64) -> hyper::Response<Body> {
+        let req_body = Body::from(json!({ "snapshot_id": snapshot_id }).to_string());
+        devnet.post_json("/revert".into(), req_body).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn revert_discards_later_blocks_and_transactions() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+        let snapshot_id = snapshot(&devnet).await;
+
+        let discarded_mint_hash = devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+        assert_eq!(get_balance(&devnet).await, FieldElement::from(2 * DUMMY_AMOUNT));
+        assert_eq!(devnet.json_rpc_client.block_number().await.unwrap(), 1);
+
+        let revert_resp = revert(&devnet, snapshot_id).await;
+        assert_eq!(revert_resp.status(), StatusCode::OK, "Checking status of {revert_resp:?}");
+        assert_eq!(get_balance(&devnet).await, FieldElement::from(DUMMY_AMOUNT));
+        assert_eq!(devnet.json_rpc_client.block_number().await.unwrap(), 0);
+        assert!(devnet.json_rpc_client.get_transaction_by_hash(discarded_mint_hash).await.is_err());
+
+        // the same snapshot can be reverted to again
+        devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+        let revert_resp = revert(&devnet, snapshot_id).await;
+        assert_eq!(revert_resp.status(), StatusCode::OK, "Checking status of {revert_resp:?}");
+        assert_eq!(get_balance(&devnet).await, FieldElement::from(DUMMY_AMOUNT));
+    }
+
+    #[tokio::test]
+    async fn revert_to_nonexistent_snapshot() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let revert_resp = revert(&devnet, 42).await;
+        assert_eq!(revert_resp.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn snapshot_and_revert_over_json_rpc() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+        let snapshot = &devnet.send_custom_rpc("devnet_snapshot", json!(null)).await["result"];
+        let snapshot_id = snapshot["snapshot_id"].as_u64().unwrap();
+
+        devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+        let reverted =
+            devnet.send_custom_rpc("devnet_revert", json!({ "snapshot_id": snapshot_id })).await;
+        assert_eq!(reverted["result"], json!(null));
+        assert_eq!(get_balance(&devnet).await, FieldElement::from(DUMMY_AMOUNT));
+        assert_eq!(devnet.json_rpc_client.block_number().await.unwrap(), 0);
+
+        let error = &devnet
+            .send_custom_rpc("devnet_revert", json!({ "snapshot_id": snapshot_id + 1 }))
+            .await["error"];
+        assert_eq!(error["code"], 103);
+        assert!(error["message"].as_str().unwrap().contains("does not exist"));
+    }
+}
@@ -0,0 +1,45 @@
+pub mod common;
+
+mod state_roots_tests {
+    use starknet_rs_core::types::{
+        BlockId, FieldElement, MaybePendingBlockWithTxHashes, MaybePendingStateUpdate,
+    };
+    use starknet_rs_providers::Provider;
+
+    use crate::common::devnet::BackgroundDevnet;
+
+    static DUMMY_ADDRESS: u128 = 1;
+    static DUMMY_AMOUNT: u128 = 1;
+
+    async fn get_block_root(devnet: &BackgroundDevnet, block_number: u64) -> FieldElement {
+        match devnet
+            .json_rpc_client
+            .get_block_with_tx_hashes(BlockId::Number(block_number))
+            .await
+            .unwrap()
+        {
+            MaybePendingBlockWithTxHashes::Block(block) => block.new_root,
+            block => panic!("Unexpected block: {block:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn state_update_roots_match_block_roots() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+        devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+
+        let first_root = get_block_root(&devnet, 0).await;
+        let second_root = get_block_root(&devnet, 1).await;
+        assert_ne!(first_root, FieldElement::ZERO);
+        assert_ne!(first_root, second_root);
+
+        match devnet.json_rpc_client.get_state_update(BlockId::Number(1)).await.unwrap() {
+            MaybePendingStateUpdate::Update(state_update) => {
+                assert_eq!(state_update.old_root, first_root);
+                assert_eq!(state_update.new_root, second_root);
+            }
+            state_update => panic!("Unexpected state update: {state_update:?}"),
+        }
+    }
+}
@@ -0,0 +1,66 @@
+pub mod common;
+
+mod storage_proof_tests {
+    use serde_json::json;
+    use starknet_rs_core::types::{BlockId, BlockTag, FieldElement, MaybePendingBlockWithTxHashes};
+    use starknet_rs_providers::Provider;
+
+    use crate::common::constants::PREDEPLOYED_ACCOUNT_ADDRESS;
+    use crate::common::devnet::BackgroundDevnet;
+
+    static DUMMY_ADDRESS: u128 = 1;
+    static DUMMY_AMOUNT: u128 = 1;
+
+    fn to_felt(value: &serde_json::Value) -> FieldElement {
+        FieldElement::from_hex_be(value.as_str().unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn proofs_are_against_roots_of_latest_block() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+
+        let latest_block = match devnet
+            .json_rpc_client
+            .get_block_with_tx_hashes(BlockId::Tag(BlockTag::Latest))
+            .await
+            .unwrap()
+        {
+            MaybePendingBlockWithTxHashes::Block(block) => block,
+            block => panic!("Unexpected block: {block:?}"),
+        };
+
+        let resp = &devnet
+            .send_custom_rpc(
+                "starknet_getStorageProof",
+                json!({
+                    "block_id": "latest",
+                    "contract_addresses": [PREDEPLOYED_ACCOUNT_ADDRESS],
+                    "contracts_storage_keys": [{
+                        "contract_address": PREDEPLOYED_ACCOUNT_ADDRESS,
+                        "storage_keys": ["0x1"]
+                    }]
+                }),
+            )
+            .await["result"];
+
+        // only Cairo 0 classes are declared, so the state root is the root of the contracts trie
+        let contracts_tree_root = &resp["global_roots"]["contracts_tree_root"];
+        assert_eq!(to_felt(contracts_tree_root), latest_block.new_root);
+        assert_eq!(to_felt(&resp["global_roots"]["block_hash"]), latest_block.block_hash);
+
+        assert_eq!(&resp["contracts_proof"]["nodes"][0]["node_hash"], contracts_tree_root);
+        assert_eq!(resp["contracts_proof"]["contract_leaves_data"].as_array().unwrap().len(), 1);
+        assert_eq!(resp["contracts_storage_proofs"].as_array().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn proofs_are_not_supported_for_pending_block() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let resp = devnet
+            .send_custom_rpc("starknet_getStorageProof", json!({ "block_id": "pending" }))
+            .await;
+        assert_eq!(resp["error"]["code"], 42);
+    }
+}
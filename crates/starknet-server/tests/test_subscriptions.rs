@@ -0,0 +1,160 @@
+pub mod common;
+
+mod subscriptions_tests {
+    use std::time::Duration;
+
+    use futures::{SinkExt, StreamExt};
+    use hyper::{Body, StatusCode};
+    use serde_json::json;
+    use starknet_core::constants::ERC20_CONTRACT_ADDRESS;
+    use starknet_rs_core::types::FieldElement;
+    use tokio::net::TcpStream;
+    use tokio_tungstenite::tungstenite::Message;
+    use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
+
+    use crate::common::devnet::BackgroundDevnet;
+    use crate::common::utils::get_json_body;
+
+    type Socket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+    static DUMMY_ADDRESS: u128 = 1;
+    static DUMMY_AMOUNT: u128 = 1;
+
+    async fn send_request(socket: &mut Socket, method: &str, params: serde_json::Value) {
+        let request = json!({ "jsonrpc": "2.0", "id": 0, "method": method, "params": params });
+        socket.send(Message::Text(request.to_string())).await.unwrap();
+    }
+
+    async fn receive(socket: &mut Socket) -> serde_json::Value {
+        let message = tokio::time::timeout(Duration::from_secs(5), socket.next())
+            .await
+            .expect("No message received")
+            .unwrap()
+            .unwrap();
+
+        serde_json::from_str(message.to_text().unwrap()).unwrap()
+    }
+
+    async fn subscribe(socket: &mut Socket, method: &str, params: serde_json::Value) -> u64 {
+        send_request(socket, method, params).await;
+        receive(socket).await["result"].as_u64().unwrap()
+    }
+
+    #[tokio::test]
+    async fn regular_methods_are_served_over_websocket() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        let (mut socket, _) = connect_async(devnet.ws_url()).await.unwrap();
+
+        send_request(&mut socket, "starknet_chainId", json!([])).await;
+        let response = receive(&mut socket).await;
+        assert!(response["result"].is_string(), "Unexpected response: {response}");
+    }
+
+    #[tokio::test]
+    async fn new_heads_are_pushed_when_blocks_are_created() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        let (mut socket, _) = connect_async(devnet.ws_url()).await.unwrap();
+        let subscription_id = subscribe(&mut socket, "starknet_subscribeNewHeads", json!([])).await;
+
+        devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+        let block_hash = devnet.create_block().await;
+
+        let first_notification = receive(&mut socket).await;
+        assert_eq!(first_notification["method"], "starknet_subscriptionNewHeads");
+        assert_eq!(first_notification["params"]["subscription_id"], subscription_id);
+        assert_eq!(first_notification["params"]["result"]["block_number"], 0);
+
+        let second_notification = receive(&mut socket).await;
+        let second_block_hash = &second_notification["params"]["result"]["block_hash"];
+        assert_eq!(
+            FieldElement::from_hex_be(second_block_hash.as_str().unwrap()).unwrap(),
+            block_hash
+        );
+    }
+
+    #[tokio::test]
+    async fn events_matching_the_filter_are_pushed() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        let (mut socket, _) = connect_async(devnet.ws_url()).await.unwrap();
+        subscribe(&mut socket, "starknet_subscribeEvents", json!({ "address": "0x1" })).await;
+        let subscription_id = subscribe(
+            &mut socket,
+            "starknet_subscribeEvents",
+            json!({ "address": ERC20_CONTRACT_ADDRESS }),
+        )
+        .await;
+
+        let tx_hash = devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+
+        // the fee token emits the event of the minting transfer, no contract at 0x1 emits events
+        let notification = receive(&mut socket).await;
+        assert_eq!(notification["method"], "starknet_subscriptionEvents");
+        assert_eq!(notification["params"]["subscription_id"], subscription_id);
+        let event_tx_hash = &notification["params"]["result"]["transaction_hash"];
+        assert_eq!(FieldElement::from_hex_be(event_tx_hash.as_str().unwrap()).unwrap(), tx_hash);
+    }
+
+    #[tokio::test]
+    async fn transaction_status_is_pushed_on_subscription() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        let (mut socket, _) = connect_async(devnet.ws_url()).await.unwrap();
+        let tx_hash = devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+
+        let subscription_id = subscribe(
+            &mut socket,
+            "starknet_subscribeTransactionStatus",
+            json!({ "transaction_hash": format!("{tx_hash:#x}") }),
+        )
+        .await;
+
+        let notification = receive(&mut socket).await;
+        assert_eq!(notification["method"], "starknet_subscriptionTransactionStatus");
+        assert_eq!(notification["params"]["subscription_id"], subscription_id);
+        assert_eq!(
+            notification["params"]["result"]["status"],
+            json!({ "finality_status": "ACCEPTED_ON_L2", "execution_status": "SUCCEEDED" })
+        );
+    }
+
+    #[tokio::test]
+    async fn status_of_transaction_discarded_by_revert_is_pushed() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        let (mut socket, _) = connect_async(devnet.ws_url()).await.unwrap();
+        devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+        let resp =
+            devnet.post_json("/snapshot".into(), Body::from(json!({}).to_string())).await.unwrap();
+        let snapshot_id = get_json_body(resp).await["snapshot_id"].clone();
+
+        let tx_hash = devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+        subscribe(
+            &mut socket,
+            "starknet_subscribeTransactionStatus",
+            json!({ "transaction_hash": format!("{tx_hash:#x}") }),
+        )
+        .await;
+        // the current status is pushed on subscription
+        receive(&mut socket).await;
+
+        let req_body = Body::from(json!({ "snapshot_id": snapshot_id }).to_string());
+        let resp = devnet.post_json("/revert".into(), req_body).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let notification = receive(&mut socket).await;
+        assert_eq!(notification["method"], "starknet_subscriptionTransactionStatus");
+        assert_eq!(notification["params"]["result"]["status"]["finality_status"], "REJECTED");
+    }
+
+    #[tokio::test]
+    async fn unsubscribing_twice_fails() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        let (mut socket, _) = connect_async(devnet.ws_url()).await.unwrap();
+        let subscription_id = subscribe(&mut socket, "starknet_subscribeNewHeads", json!([])).await;
+
+        let params = json!({ "subscription_id": subscription_id });
+        send_request(&mut socket, "starknet_unsubscribe", params.clone()).await;
+        assert_eq!(receive(&mut socket).await["result"], true);
+
+        send_request(&mut socket, "starknet_unsubscribe", params).await;
+        assert_eq!(receive(&mut socket).await["error"]["code"], 66);
+    }
+}
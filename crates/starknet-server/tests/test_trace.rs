@@ -0,0 +1,88 @@
+pub mod common;
+
+mod trace_tests {
+    use hyper::{Body, StatusCode};
+    use serde_json::json;
+    use starknet_core::constants::ERC20_CONTRACT_ADDRESS;
+    use starknet_rs_core::types::FieldElement;
+
+    use crate::common::devnet::BackgroundDevnet;
+    use crate::common::utils::remove_file;
+
+    static DUMMY_ADDRESS: u128 = 1;
+    static DUMMY_AMOUNT: u128 = 1;
+
+    fn to_felt(value: &serde_json::Value) -> FieldElement {
+        FieldElement::from_hex_be(value.as_str().unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn trace_of_minting_contains_fee_token_invocation() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        let mint_tx_hash = devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+
+        let resp = &devnet
+            .send_custom_rpc(
+                "starknet_traceTransaction",
+                json!({ "transaction_hash": format!("{mint_tx_hash:#x}") }),
+            )
+            .await["result"];
+
+        // the chargeable account calls the fee token, which mints the tokens
+        let execution_invocation = &resp["execution_invocation"];
+        let fee_token_invocation = &execution_invocation["calls"][0];
+        assert_eq!(
+            to_felt(&fee_token_invocation["contract_address"]),
+            FieldElement::from_hex_be(ERC20_CONTRACT_ADDRESS).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn traces_of_block_are_returned_with_transaction_hashes() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        let mint_tx_hash = devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+
+        let resp = &devnet
+            .send_custom_rpc("starknet_traceBlockTransactions", json!({ "block_id": "latest" }))
+            .await["result"];
+
+        let traces = resp.as_array().unwrap();
+        assert_eq!(traces.len(), 1);
+        assert_eq!(to_felt(&traces[0]["transaction_hash"]), mint_tx_hash);
+        assert!(traces[0]["trace_root"]["validate_invocation"].is_object());
+    }
+
+    #[tokio::test]
+    async fn trace_of_unknown_transaction_is_not_found() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let resp = devnet
+            .send_custom_rpc("starknet_traceTransaction", json!({ "transaction_hash": "0x1" }))
+            .await;
+        assert_eq!(resp["error"]["code"], 29);
+    }
+
+    #[tokio::test]
+    async fn trace_is_preserved_in_dump() {
+        let dump_file_name = "trace_dump";
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        let mint_tx_hash = devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+        let trace_request = json!({ "transaction_hash": format!("{mint_tx_hash:#x}") });
+        let trace = devnet
+            .send_custom_rpc("starknet_traceTransaction", trace_request.clone())
+            .await["result"]
+            .clone();
+
+        let dump_body = Body::from(json!({ "path": dump_file_name }).to_string());
+        devnet.post_json("/dump".into(), dump_body).await.unwrap();
+        let load_body = Body::from(json!({ "path": dump_file_name }).to_string());
+        let resp = devnet.post_json("/load".into(), load_body).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let loaded_trace =
+            &devnet.send_custom_rpc("starknet_traceTransaction", trace_request).await["result"];
+        assert_eq!(loaded_trace, &trace);
+
+        remove_file(dump_file_name);
+    }
+}
@@ -0,0 +1,265 @@
+#!/usr/bin/env python3
+"""
+Generates MockStarknetMessaging.json, the mock Starknet messaging contract which Devnet deploys on
+the L1 node when no messaging contract address is provided to the postman.
+
+The contract is written directly in EVM assembly, so that it is reproducible with the Python
+standard library alone. It implements the part of the Starknet core contract's messaging used
+with Devnet:
+- sendMessageToL2(uint256 toAddress, uint256 selector, uint256[] payload) payable
+      returns (bytes32 msgHash, uint256 nonce)
+  emits LogMessageToL2(address indexed fromAddress, uint256 indexed toAddress,
+      uint256 indexed selector, uint256[] payload, uint256 nonce, uint256 fee)
+- addMessageHashesFromL2(uint256[] msgHashes), called by Devnet on flush
+- consumeMessageFromL2(uint256 fromAddress, uint256[] payload) returns (bytes32 msgHash),
+  reverts if the message was not sent from L2
+- l2ToL1Messages(bytes32 msgHash) view returns (uint256 count)
+
+Storage: slot 0 holds the nonce of the next message to L2; the count of consumable messages from
+L2 is stored at keccak256(msgHash . 1), as Solidity stores `mapping(bytes32 => uint256)` at slot 1.
+
+Usage: python3 generate_mock_messaging.py > MockStarknetMessaging.json
+"""
+
+import json
+
+# Keccak-256 (the pre-standard variant used by Ethereum; hashlib's sha3_256 differs in padding)
+ROUND_CONSTANTS = [
+    0x0000000000000001, 0x0000000000008082, 0x800000000000808A, 0x8000000080008000,
+    0x000000000000808B, 0x0000000080000001, 0x8000000080008081, 0x8000000000008009,
+    0x000000000000008A, 0x0000000000000088, 0x0000000080008009, 0x000000008000000A,
+    0x000000008000808B, 0x800000000000008B, 0x8000000000008089, 0x8000000000008003,
+    0x8000000000008002, 0x8000000000000080, 0x000000000000800A, 0x800000008000000A,
+    0x8000000080008081, 0x8000000000008080, 0x0000000080000001, 0x8000000080008008,
+]
+ROTATIONS = [
+    [0, 36, 3, 41, 18], [1, 44, 10, 45, 2], [62, 6, 43, 15, 61], [28, 55, 25, 21, 56],
+    [27, 20, 39, 8, 14],
+]
+MASK = (1 << 64) - 1
+
+
+def rotl(value, shift):
+    return ((value << shift) | (value >> (64 - shift))) & MASK if shift else value
+
+
+def keccak_f(state):
+    for round_constant in ROUND_CONSTANTS:
+        c = [state[x][0] ^ state[x][1] ^ state[x][2] ^ state[x][3] ^ state[x][4] for x in range(5)]
+        d = [c[(x - 1) % 5] ^ rotl(c[(x + 1) % 5], 1) for x in range(5)]
+        state = [[state[x][y] ^ d[x] for y in range(5)] for x in range(5)]
+        b = [[0] * 5 for _ in range(5)]
+        for x in range(5):
+            for y in range(5):
+                b[y][(2 * x + 3 * y) % 5] = rotl(state[x][y], ROTATIONS[x][y])
+        state = [
+            [b[x][y] ^ (~b[(x + 1) % 5][y] & b[(x + 2) % 5][y]) for y in range(5)]
+            for x in range(5)
+        ]
+        state[0][0] ^= round_constant
+    return state
+
+
+def keccak256(data):
+    rate = 136
+    padded = bytearray(data) + b"\x01" + b"\x00" * ((-len(data) - 1) % rate)
+    padded[-1] |= 0x80
+    state = [[0] * 5 for _ in range(5)]
+    for offset in range(0, len(padded), rate):
+        block = padded[offset:offset + rate]
+        for i in range(rate // 8):
+            state[i % 5][i // 5] ^= int.from_bytes(block[8 * i:8 * i + 8], "little")
+        state = keccak_f(state)
+    return b"".join(state[i % 5][i // 5].to_bytes(8, "little") for i in range(4))
+
+
+def selector(signature):
+    return int.from_bytes(keccak256(signature.encode())[:4], "big")
+
+
+OPCODES = {
+    "STOP": 0x00, "ADD": 0x01, "SUB": 0x03, "LT": 0x10, "GT": 0x11, "EQ": 0x14,
+    "ISZERO": 0x15, "SHL": 0x1B, "SHR": 0x1C, "SHA3": 0x20, "CALLER": 0x33, "CALLVALUE": 0x34,
+    "CALLDATALOAD": 0x35, "CALLDATACOPY": 0x37, "CODECOPY": 0x39, "POP": 0x50, "MSTORE": 0x52,
+    "SLOAD": 0x54, "SSTORE": 0x55, "JUMP": 0x56, "JUMPI": 0x57, "JUMPDEST": 0x5B,
+    "DUP1": 0x80, "DUP2": 0x81, "DUP3": 0x82, "DUP4": 0x83, "DUP5": 0x84,
+    "SWAP1": 0x90, "SWAP2": 0x91, "LOG4": 0xA4, "RETURN": 0xF3, "REVERT": 0xFD,
+}
+
+SEND_MESSAGE_TO_L2 = "sendMessageToL2(uint256,uint256,uint256[])"
+ADD_MESSAGE_HASHES_FROM_L2 = "addMessageHashesFromL2(uint256[])"
+CONSUME_MESSAGE_FROM_L2 = "consumeMessageFromL2(uint256,uint256[])"
+L2_TO_L1_MESSAGES = "l2ToL1Messages(bytes32)"
+LOG_MESSAGE_TO_L2 = "LogMessageToL2(address,uint256,uint256,uint256[],uint256,uint256)"
+
+# Instructions are opcode names, ("PUSH", value, size), ("LABEL", name) or ("PUSHLABEL", name).
+# Comments show the stack after the instruction, with its top on the right.
+RUNTIME = [
+    # dispatch on the selector
+    ("PUSH", 0, 1), "CALLDATALOAD", ("PUSH", 0xE0, 1), "SHR",  # selector
+    "DUP1", ("PUSH", selector(SEND_MESSAGE_TO_L2), 4), "EQ", ("PUSHLABEL", "send"), "JUMPI",
+    "DUP1", ("PUSH", selector(ADD_MESSAGE_HASHES_FROM_L2), 4), "EQ", ("PUSHLABEL", "add"), "JUMPI",
+    "DUP1", ("PUSH", selector(CONSUME_MESSAGE_FROM_L2), 4), "EQ", ("PUSHLABEL", "consume"), "JUMPI",
+    "DUP1", ("PUSH", selector(L2_TO_L1_MESSAGES), 4), "EQ", ("PUSHLABEL", "get"), "JUMPI",
+    ("PUSH", 0, 1), "DUP1", "REVERT",
+
+    # sendMessageToL2: log data is [payload offset, nonce, fee, payload length, payload]
+    ("LABEL", "send"),
+    ("PUSH", 0x60, 1), ("PUSH", 0, 1), "MSTORE",
+    ("PUSH", 0, 1), "SLOAD",  # nonce
+    "DUP1", ("PUSH", 0x20, 1), "MSTORE",
+    "CALLVALUE", ("PUSH", 0x40, 1), "MSTORE",
+    ("PUSH", 0x44, 1), "CALLDATALOAD", ("PUSH", 4, 1), "ADD",  # nonce lenpos
+    "DUP1", "CALLDATALOAD",  # nonce lenpos len
+    "DUP1", ("PUSH", 0x60, 1), "MSTORE",
+    ("PUSH", 5, 1), "SHL",  # nonce lenpos bytes
+    "DUP1", "DUP3", ("PUSH", 0x20, 1), "ADD", ("PUSH", 0x80, 1), "CALLDATACOPY",
+    ("PUSH", 0x24, 1), "CALLDATALOAD",  # ... selector
+    ("PUSH", 4, 1), "CALLDATALOAD",  # ... selector toAddress
+    "CALLER",  # ... selector toAddress fromAddress
+    ("PUSH", int.from_bytes(keccak256(LOG_MESSAGE_TO_L2.encode()), "big"), 32),
+    "DUP5", ("PUSH", 0x80, 1), "ADD", ("PUSH", 0, 1), "LOG4",  # nonce lenpos bytes
+    # msgHash = keccak256(fromAddress . toAddress . nonce . selector . payload length . payload)
+    "CALLER", ("PUSH", 0, 1), "MSTORE",
+    ("PUSH", 4, 1), "CALLDATALOAD", ("PUSH", 0x20, 1), "MSTORE",
+    "DUP3", ("PUSH", 0x40, 1), "MSTORE",
+    ("PUSH", 0x24, 1), "CALLDATALOAD", ("PUSH", 0x60, 1), "MSTORE",
+    "DUP2", "CALLDATALOAD", ("PUSH", 0x80, 1), "MSTORE",
+    "DUP1", "DUP3", ("PUSH", 0x20, 1), "ADD", ("PUSH", 0xA0, 1), "CALLDATACOPY",
+    "DUP1", ("PUSH", 0xA0, 1), "ADD", ("PUSH", 0, 1), "SHA3",  # nonce lenpos bytes msgHash
+    "DUP4", ("PUSH", 1, 1), "ADD", ("PUSH", 0, 1), "SSTORE",
+    ("PUSH", 0, 1), "MSTORE",  # nonce lenpos bytes
+    "POP", "POP", ("PUSH", 0x20, 1), "MSTORE",
+    ("PUSH", 0x40, 1), ("PUSH", 0, 1), "RETURN",
+
+    # addMessageHashesFromL2: increments the count of each hash
+    ("LABEL", "add"),
+    ("PUSH", 4, 1), "CALLDATALOAD", ("PUSH", 4, 1), "ADD",  # lenpos
+    "DUP1", "CALLDATALOAD", ("PUSH", 5, 1), "SHL",  # lenpos bytes
+    "DUP2", ("PUSH", 0x20, 1), "ADD",  # lenpos bytes start
+    "SWAP2", "POP",  # start bytes
+    "DUP2", "ADD",  # position end
+    ("LABEL", "add_loop"),
+    "DUP2", "DUP2", "GT", "ISZERO", ("PUSHLABEL", "add_done"), "JUMPI",
+    "DUP2", "CALLDATALOAD", ("PUSH", 0, 1), "MSTORE",
+    ("PUSH", 1, 1), ("PUSH", 0x20, 1), "MSTORE",
+    ("PUSH", 0x40, 1), ("PUSH", 0, 1), "SHA3",  # position end slot
+    "DUP1", "SLOAD", ("PUSH", 1, 1), "ADD", "SWAP1", "SSTORE",
+    "SWAP1", ("PUSH", 0x20, 1), "ADD", "SWAP1",
+    ("PUSHLABEL", "add_loop"), "JUMP",
+    ("LABEL", "add_done"),
+    "STOP",
+
+    # consumeMessageFromL2: msgHash = keccak256(fromAddress . toAddress . payload length . payload)
+    ("LABEL", "consume"),
+    ("PUSH", 4, 1), "CALLDATALOAD", ("PUSH", 0, 1), "MSTORE",
+    "CALLER", ("PUSH", 0x20, 1), "MSTORE",
+    ("PUSH", 0x24, 1), "CALLDATALOAD", ("PUSH", 4, 1), "ADD",  # lenpos
+    "DUP1", "CALLDATALOAD",  # lenpos len
+    "DUP1", ("PUSH", 0x40, 1), "MSTORE",
+    ("PUSH", 5, 1), "SHL",  # lenpos bytes
+    "DUP1", "DUP3", ("PUSH", 0x20, 1), "ADD", ("PUSH", 0x60, 1), "CALLDATACOPY",
+    ("PUSH", 0x60, 1), "ADD", ("PUSH", 0, 1), "SHA3",  # lenpos msgHash
+    "SWAP1", "POP",  # msgHash
+    "DUP1", ("PUSH", 0, 1), "MSTORE", ("PUSH", 1, 1), ("PUSH", 0x20, 1), "MSTORE",
+    ("PUSH", 0x40, 1), ("PUSH", 0, 1), "SHA3",  # msgHash slot
+    "DUP1", "SLOAD",  # msgHash slot count
+    "DUP1", ("PUSHLABEL", "consume_valid"), "JUMPI",
+    ("PUSH", 0, 1), "DUP1", "REVERT",
+    ("LABEL", "consume_valid"),
+    ("PUSH", 1, 1), "SWAP1", "SUB",  # msgHash slot count-1
+    "SWAP1", "SSTORE",  # msgHash
+    ("PUSH", 0, 1), "MSTORE",
+    ("PUSH", 0x20, 1), ("PUSH", 0, 1), "RETURN",
+
+    # l2ToL1Messages
+    ("LABEL", "get"),
+    ("PUSH", 4, 1), "CALLDATALOAD", ("PUSH", 0, 1), "MSTORE",
+    ("PUSH", 1, 1), ("PUSH", 0x20, 1), "MSTORE",
+    ("PUSH", 0x40, 1), ("PUSH", 0, 1), "SHA3", "SLOAD",
+    ("PUSH", 0, 1), "MSTORE",
+    ("PUSH", 0x20, 1), ("PUSH", 0, 1), "RETURN",
+]
+
+
+def assemble(instructions):
+    def size(instruction):
+        if isinstance(instruction, str):
+            return 1
+        if instruction[0] == "PUSH":
+            return 1 + instruction[2]
+        return 3 if instruction[0] == "PUSHLABEL" else 1
+
+    labels, position = {}, 0
+    for instruction in instructions:
+        if isinstance(instruction, tuple) and instruction[0] == "LABEL":
+            labels[instruction[1]] = position
+        position += size(instruction)
+
+    code = bytearray()
+    for instruction in instructions:
+        if isinstance(instruction, str):
+            code.append(OPCODES[instruction])
+        elif instruction[0] == "PUSH":
+            code.append(0x5F + instruction[2])
+            code += instruction[1].to_bytes(instruction[2], "big")
+        elif instruction[0] == "PUSHLABEL":
+            code.append(0x61)
+            code += labels[instruction[1]].to_bytes(2, "big")
+        else:
+            code.append(OPCODES["JUMPDEST"])
+    return bytes(code)
+
+
+def constructor(runtime):
+    """Init code which returns the runtime code, which follows right after it"""
+    init_code_size = 13
+    return assemble([
+        ("PUSH", len(runtime), 2), "DUP1", ("PUSH", init_code_size, 2), ("PUSH", 0, 1),
+        "CODECOPY", ("PUSH", 0, 1), "RETURN",
+    ]) + runtime
+
+
+def abi():
+    def param(name, solidity_type, indexed=None):
+        param = {"name": name, "type": solidity_type, "internalType": solidity_type}
+        if indexed is not None:
+            param["indexed"] = indexed
+        return param
+
+    return [
+        {
+            "type": "function", "name": "sendMessageToL2", "stateMutability": "payable",
+            "inputs": [
+                param("toAddress", "uint256"), param("selector", "uint256"),
+                param("payload", "uint256[]"),
+            ],
+            "outputs": [param("", "bytes32"), param("", "uint256")],
+        },
+        {
+            "type": "function", "name": "addMessageHashesFromL2", "stateMutability": "nonpayable",
+            "inputs": [param("msgHashes", "uint256[]")], "outputs": [],
+        },
+        {
+            "type": "function", "name": "consumeMessageFromL2", "stateMutability": "nonpayable",
+            "inputs": [param("fromAddress", "uint256"), param("payload", "uint256[]")],
+            "outputs": [param("", "bytes32")],
+        },
+        {
+            "type": "function", "name": "l2ToL1Messages", "stateMutability": "view",
+            "inputs": [param("msgHash", "bytes32")], "outputs": [param("", "uint256")],
+        },
+        {
+            "type": "event", "name": "LogMessageToL2", "anonymous": False,
+            "inputs": [
+                param("fromAddress", "address", True), param("toAddress", "uint256", True),
+                param("selector", "uint256", True), param("payload", "uint256[]", False),
+                param("nonce", "uint256", False), param("fee", "uint256", False),
+            ],
+        },
+    ]
+
+
+if __name__ == "__main__":
+    artifact = {"abi": abi(), "bytecode": "0x" + constructor(assemble(RUNTIME)).hex()}
+    print(json.dumps(artifact, indent=2))
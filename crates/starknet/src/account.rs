@@ -10,6 +10,7 @@ use starknet_types::contract_storage_key::ContractStorageKey;
 use starknet_types::error::Error;
 use starknet_types::felt::{Balance, ClassHash, Felt, Key};
 use starknet_types::num_bigint::BigUint;
+use starknet_types::rpc::estimate_message_fee::FeeUnit;
 
 use crate::constants::{
     CHARGEABLE_ACCOUNT_ADDRESS, CHARGEABLE_ACCOUNT_PRIVATE_KEY, CHARGEABLE_ACCOUNT_PUBLIC_KEY,
@@ -30,14 +31,16 @@ pub struct Account {
     pub initial_balance: Balance,
     pub(crate) class_hash: ClassHash,
     pub(crate) contract_class: ContractClass,
-    pub(crate) fee_token_address: ContractAddress,
+    pub(crate) eth_fee_token_address: ContractAddress,
+    pub(crate) strk_fee_token_address: ContractAddress,
 }
 
 impl Account {
     pub(crate) fn new_chargeable(
         class_hash: ClassHash,
         contract_class: ContractClass,
-        fee_token_address: ContractAddress,
+        eth_fee_token_address: ContractAddress,
+        strk_fee_token_address: ContractAddress,
     ) -> Self {
         // insanely big - should practically never run out of funds
         let initial_balance = BigUint::from(u128::MAX);
@@ -52,7 +55,8 @@ impl Account {
             initial_balance: Felt::from_prefixed_hex_str(&initial_balance_hex).unwrap(),
             class_hash,
             contract_class,
-            fee_token_address,
+            eth_fee_token_address,
+            strk_fee_token_address,
         }
     }
 
@@ -62,7 +66,8 @@ impl Account {
         private_key: Key,
         class_hash: ClassHash,
         contract_class: ContractClass,
-        fee_token_address: ContractAddress,
+        eth_fee_token_address: ContractAddress,
+        strk_fee_token_address: ContractAddress,
     ) -> DevnetResult<Self> {
         Ok(Self {
             initial_balance,
@@ -71,7 +76,8 @@ impl Account {
             class_hash,
             contract_class,
             account_address: Account::compute_account_address(&public_key)?,
-            fee_token_address,
+            eth_fee_token_address,
+            strk_fee_token_address,
         })
     }
 
@@ -87,10 +93,17 @@ impl Account {
         Ok(ContractAddress::from(account_address))
     }
 
-    fn balance_storage_key(&self) -> DevnetResult<ContractStorageKey> {
+    fn fee_token_address(&self, unit: FeeUnit) -> ContractAddress {
+        match unit {
+            FeeUnit::Wei => self.eth_fee_token_address,
+            FeeUnit::Fri => self.strk_fee_token_address,
+        }
+    }
+
+    fn balance_storage_key(&self, unit: FeeUnit) -> DevnetResult<ContractStorageKey> {
         let storage_var_address =
             get_storage_var_address("ERC20_balances", &[Felt::from(self.account_address)])?;
-        Ok(ContractStorageKey::new(self.fee_token_address, storage_var_address))
+        Ok(ContractStorageKey::new(self.fee_token_address(unit), storage_var_address))
     }
 }
 
@@ -118,18 +131,17 @@ impl Deployed for Account {
 }
 
 impl Accounted for Account {
+    /// Funds the account with the initial balance in both fee tokens
     fn set_initial_balance(&self, state: &mut impl StateChanger) -> DevnetResult<()> {
-        let storage_var_address =
-            get_storage_var_address("ERC20_balances", &[Felt::from(self.account_address)])?;
-        let storage_key = ContractStorageKey::new(self.fee_token_address, storage_var_address);
-
-        state.change_storage(storage_key, self.initial_balance)?;
+        for unit in [FeeUnit::Wei, FeeUnit::Fri] {
+            state.change_storage(self.balance_storage_key(unit)?, self.initial_balance)?;
+        }
 
         Ok(())
     }
 
-    fn get_balance(&self, state: &mut impl StateExtractor) -> DevnetResult<Balance> {
-        state.get_storage(self.balance_storage_key()?)
+    fn get_balance(&self, state: &mut impl StateExtractor, unit: FeeUnit) -> DevnetResult<Balance> {
+        state.get_storage(self.balance_storage_key(unit)?)
     }
 }
 
@@ -138,6 +150,7 @@ mod tests {
     use starknet_types::contract_address::ContractAddress;
     use starknet_types::contract_storage_key::ContractStorageKey;
     use starknet_types::felt::Felt;
+    use starknet_types::rpc::estimate_message_fee::FeeUnit;
 
     use super::Account;
     use crate::constants::ERC20_CONTRACT_CLASS_HASH;
@@ -192,25 +205,33 @@ mod tests {
     #[test]
     fn correct_balance_storage_key() {
         let default_felt = Felt::default();
-        let fee_token_address =
+        let eth_fee_token_address =
             ContractAddress::new(Felt::from_prefixed_hex_str("0xFEEE").unwrap()).unwrap();
+        let strk_fee_token_address =
+            ContractAddress::new(Felt::from_prefixed_hex_str("0xFEEF").unwrap()).unwrap();
         let mut account = Account::new(
             default_felt,
             default_felt,
             default_felt,
             default_felt,
             dummy_cairo_0_contract_class().into(),
-            fee_token_address,
+            eth_fee_token_address,
+            strk_fee_token_address,
         )
         .unwrap();
         let account_address = ContractAddress::new(Felt::from(111)).unwrap();
         account.account_address = account_address;
 
-        let expected_balance_storage_key = ContractStorageKey::new(
-            fee_token_address,
-            get_storage_var_address("ERC20_balances", &[Felt::from(account_address)]).unwrap(),
+        let balance_storage_var_address =
+            get_storage_var_address("ERC20_balances", &[Felt::from(account_address)]).unwrap();
+        assert_eq!(
+            ContractStorageKey::new(eth_fee_token_address, balance_storage_var_address),
+            account.balance_storage_key(FeeUnit::Wei).unwrap()
+        );
+        assert_eq!(
+            ContractStorageKey::new(strk_fee_token_address, balance_storage_var_address),
+            account.balance_storage_key(FeeUnit::Fri).unwrap()
         );
-        assert_eq!(expected_balance_storage_key, account.balance_storage_key().unwrap());
     }
 
     #[test]
@@ -224,7 +245,7 @@ mod tests {
         let (account, mut state) = setup();
 
         account.deploy(&mut state).unwrap();
-        let balance = account.get_balance(&mut state).unwrap();
+        let balance = account.get_balance(&mut state, FeeUnit::Wei).unwrap();
         assert_eq!(balance, Felt::from(0));
     }
 
@@ -235,9 +256,10 @@ mod tests {
         account.initial_balance = expected_balance;
         account.deploy(&mut state).unwrap();
         account.set_initial_balance(&mut state).unwrap();
-        let generated_balance = account.get_balance(&mut state).unwrap();
 
-        assert_eq!(expected_balance, generated_balance);
+        for unit in [FeeUnit::Wei, FeeUnit::Fri] {
+            assert_eq!(expected_balance, account.get_balance(&mut state, unit).unwrap());
+        }
     }
 
     #[test]
@@ -256,15 +278,18 @@ mod tests {
 
     fn setup() -> (Account, StarknetState) {
         let mut state = StarknetState::default();
-        let fee_token_address = dummy_contract_address();
-
-        // deploy the erc20 contract
-        state
-            .deploy_contract(
-                fee_token_address,
-                Felt::from_prefixed_hex_str(ERC20_CONTRACT_CLASS_HASH).unwrap(),
-            )
-            .unwrap();
+        let eth_fee_token_address = dummy_contract_address();
+        let strk_fee_token_address = ContractAddress::new(Felt::from(0xFEEF)).unwrap();
+
+        // deploy the erc20 contracts
+        for fee_token_address in [eth_fee_token_address, strk_fee_token_address] {
+            state
+                .deploy_contract(
+                    fee_token_address,
+                    Felt::from_prefixed_hex_str(ERC20_CONTRACT_CLASS_HASH).unwrap(),
+                )
+                .unwrap();
+        }
 
         (
             Account::new(
@@ -273,7 +298,8 @@ mod tests {
                 Felt::from(11),
                 dummy_felt(),
                 dummy_cairo_0_contract_class().into(),
-                fee_token_address,
+                eth_fee_token_address,
+                strk_fee_token_address,
             )
             .unwrap(),
             state,
@@ -1,26 +1,35 @@
-use std::collections::{BTreeMap, HashMap};
+use std::collections::BTreeMap;
 
-use starknet_api::block::{BlockHeader, BlockNumber, BlockStatus, BlockTimestamp};
-use starknet_api::hash::{pedersen_hash_array, StarkFelt};
+use serde::{Deserialize, Serialize};
+use starknet_api::block::{
+    BlockHeader, BlockNumber, BlockStatus, BlockTimestamp, GasPrice, GlobalRoot,
+};
+use starknet_api::hash::{pedersen_hash, pedersen_hash_array, StarkFelt};
 use starknet_api::stark_felt;
-use starknet_rs_core::types::BlockId;
+use starknet_rs_core::types::{BlockId, BlockTag};
 use starknet_types::contract_address::ContractAddress;
+use starknet_types::emitted_event::Event;
 use starknet_types::felt::{BlockHash, Felt, TransactionHash};
-use starknet_types::rpc::block::BlockHeader as TypesBlockHeader;
+use starknet_types::rpc::block::{BlockHeader as TypesBlockHeader, ResourcePrice};
 use starknet_types::traits::HashProducer;
 
 use crate::error::{DevnetResult, Error};
+use crate::state::patricia_trie::{PatriciaTrie, TrieHashFunction, BLOCK_TRIE_HEIGHT};
 use crate::state::state_diff::StateDiff;
 use crate::state::StarknetState;
 use crate::traits::HashIdentified;
+use crate::transactions::StarknetTransaction;
 
+/// Sealed blocks are kept in persistent maps, so that snapshots of the chain share them instead of
+/// copying them
+#[derive(Clone)]
 pub(crate) struct StarknetBlocks {
-    pub(crate) hash_to_num: HashMap<BlockHash, BlockNumber>,
-    pub(crate) num_to_block: HashMap<BlockNumber, StarknetBlock>,
+    pub(crate) hash_to_num: im::HashMap<BlockHash, BlockNumber>,
+    pub(crate) num_to_block: im::HashMap<BlockNumber, StarknetBlock>,
     pub(crate) pending_block: StarknetBlock,
     pub(crate) last_block_hash: Option<BlockHash>,
-    pub(crate) num_to_state_diff: HashMap<BlockNumber, StateDiff>,
-    pub(crate) num_to_state: HashMap<BlockNumber, StarknetState>,
+    pub(crate) num_to_state_diff: im::HashMap<BlockNumber, StateDiff>,
+    pub(crate) num_to_state: im::HashMap<BlockNumber, StarknetState>,
 }
 
 impl HashIdentified for StarknetBlocks {
@@ -38,12 +47,12 @@ impl HashIdentified for StarknetBlocks {
 impl Default for StarknetBlocks {
     fn default() -> Self {
         Self {
-            hash_to_num: HashMap::new(),
-            num_to_block: HashMap::new(),
+            hash_to_num: im::HashMap::new(),
+            num_to_block: im::HashMap::new(),
             pending_block: StarknetBlock::create_pending_block(),
             last_block_hash: None,
-            num_to_state_diff: HashMap::new(),
-            num_to_state: HashMap::new(),
+            num_to_state_diff: im::HashMap::new(),
+            num_to_state: im::HashMap::new(),
         }
     }
 }
@@ -65,6 +74,17 @@ impl StarknetBlocks {
         self.last_block_hash = Some(hash);
     }
 
+    /// Removes the block with the given number together with its state diff and state. The last
+    /// block hash is not updated.
+    pub(crate) fn remove(&mut self, block_number: &BlockNumber) -> Option<StarknetBlock> {
+        let block = self.num_to_block.remove(block_number)?;
+        self.hash_to_num.remove(&block.block_hash());
+        self.num_to_state_diff.remove(block_number);
+        self.num_to_state.remove(block_number);
+
+        Some(block)
+    }
+
     pub fn save_state_at(&mut self, block_number: BlockNumber, state: StarknetState) {
         self.num_to_state.insert(block_number, state);
     }
@@ -73,7 +93,13 @@ impl StarknetBlocks {
         match block_id {
             BlockId::Hash(hash) => self.get_by_hash(Felt::from(hash)),
             BlockId::Number(block_number) => self.num_to_block.get(&BlockNumber(block_number)),
-            // latest and pending for now will return the latest one
+            // the pending block is returned only if it has transactions, which happens when blocks
+            // are created on demand; otherwise pending is the same as latest
+            BlockId::Tag(BlockTag::Pending)
+                if !self.pending_block.get_transactions().is_empty() =>
+            {
+                Some(&self.pending_block)
+            }
             BlockId::Tag(_) => {
                 if let Some(hash) = self.last_block_hash {
                     self.get_by_hash(hash)
@@ -141,11 +167,17 @@ impl StarknetBlocks {
     }
 }
 
-#[derive(Clone, Eq, PartialEq)]
+#[derive(Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub struct StarknetBlock {
+    /// The gas price of the header is the price in WEI
     pub(crate) header: BlockHeader,
+    pub(crate) l1_gas_price_in_fri: GasPrice,
     transaction_hashes: Vec<TransactionHash>,
     pub(crate) status: BlockStatus,
+    /// Roots of the tries of the transactions and of the events of the block; part of its hash
+    transaction_commitment: Felt,
+    event_count: u64,
+    event_commitment: Felt,
 }
 
 impl From<&StarknetBlock> for TypesBlockHeader {
@@ -157,6 +189,7 @@ impl From<&StarknetBlock> for TypesBlockHeader {
             sequencer_address: value.sequencer_address(),
             new_root: value.new_root(),
             timestamp: value.timestamp(),
+            l1_gas_price: value.l1_gas_price(),
         }
     }
 }
@@ -194,10 +227,41 @@ impl StarknetBlock {
         self.header.state_root.0.into()
     }
 
+    pub fn l1_gas_price(&self) -> ResourcePrice {
+        ResourcePrice {
+            price_in_wei: self.header.gas_price.0,
+            price_in_fri: self.l1_gas_price_in_fri.0,
+        }
+    }
+
     pub(crate) fn set_block_hash(&mut self, block_hash: BlockHash) {
         self.header.block_hash = block_hash.into();
     }
 
+    pub(crate) fn set_state_root(&mut self, state_root: Felt) {
+        self.header.state_root = GlobalRoot(state_root.into());
+    }
+
+    /// Commits to `transactions`, which are expected to be the transactions of the block in order,
+    /// and to the events emitted by them
+    pub(crate) fn set_commitments(&mut self, transactions: &[&StarknetTransaction]) {
+        let mut transaction_leaves = vec![];
+        let mut event_leaves = vec![];
+        for (index, transaction) in transactions.iter().enumerate() {
+            transaction_leaves.push((Felt::from(index as u128), transaction_leaf(transaction)));
+            for event in transaction.events.iter() {
+                event_leaves.push((Felt::from(event_leaves.len() as u128), event_leaf(event)));
+            }
+        }
+
+        self.transaction_commitment =
+            PatriciaTrie::new(BLOCK_TRIE_HEIGHT, transaction_leaves, TrieHashFunction::Pedersen)
+                .root();
+        self.event_count = event_leaves.len() as u64;
+        self.event_commitment =
+            PatriciaTrie::new(BLOCK_TRIE_HEIGHT, event_leaves, TrieHashFunction::Pedersen).root();
+    }
+
     pub fn block_number(&self) -> BlockNumber {
         self.header.block_number
     }
@@ -205,12 +269,40 @@ impl StarknetBlock {
     pub(crate) fn create_pending_block() -> Self {
         Self {
             header: BlockHeader::default(),
+            l1_gas_price_in_fri: GasPrice::default(),
             status: BlockStatus::Pending,
             transaction_hashes: Vec::new(),
+            transaction_commitment: Felt::default(),
+            event_count: 0,
+            event_commitment: Felt::default(),
         }
     }
 }
 
+/// Hash of the transaction together with its signature
+fn transaction_leaf(transaction: &StarknetTransaction) -> Felt {
+    let signature: Vec<StarkFelt> =
+        transaction.inner.get_signature().iter().map(StarkFelt::from).collect();
+
+    pedersen_hash(
+        &transaction.inner.get_transaction_hash().into(),
+        &pedersen_hash_array(&signature),
+    )
+    .into()
+}
+
+fn event_leaf(event: &Event) -> Felt {
+    let keys: Vec<StarkFelt> = event.keys.iter().map(StarkFelt::from).collect();
+    let data: Vec<StarkFelt> = event.data.iter().map(StarkFelt::from).collect();
+
+    pedersen_hash_array(&[
+        Felt::from(event.from_address).into(),
+        pedersen_hash_array(&keys),
+        pedersen_hash_array(&data),
+    ])
+    .into()
+}
+
 impl HashProducer for StarknetBlock {
     type Error = Error;
     fn generate_hash(&self) -> DevnetResult<BlockHash> {
@@ -220,10 +312,10 @@ impl HashProducer for StarknetBlock {
             *self.header.sequencer.0.key(),          // sequencer_address
             stark_felt!(self.header.timestamp.0),    // block_timestamp
             stark_felt!(self.transaction_hashes.len() as u64), // transaction_count
-            stark_felt!(0_u8),                       // transaction_commitment
-            stark_felt!(0_u8),                       // event_count
-            stark_felt!(0_u8),                       // event_commitment
-            stark_felt!(0_u8),                       // protocol_version
+            self.transaction_commitment.into(),      // transaction_commitment
+            stark_felt!(self.event_count),           // event_count
+            self.event_commitment.into(),            // event_commitment
+            stark_felt!(0_u8),                       // protocol_version, always zero
             stark_felt!(0_u8),                       // extra_data
             stark_felt!(self.header.parent_hash.0),  // parent_block_hash
         ]);
@@ -234,14 +326,109 @@ impl HashProducer for StarknetBlock {
 
 #[cfg(test)]
 mod tests {
-    use starknet_api::block::{BlockHash, BlockHeader, BlockNumber, BlockStatus};
+    use serde::Deserialize;
+    use starknet_api::block::{BlockHash, BlockHeader, BlockNumber, BlockStatus, BlockTimestamp};
     use starknet_rs_core::types::{BlockId, BlockTag};
+    use starknet_types::contract_address::ContractAddress;
+    use starknet_types::emitted_event::Event;
     use starknet_types::felt::Felt;
+    use starknet_types::rpc::transactions::invoke_transaction_v1::InvokeTransactionV1;
+    use starknet_types::rpc::transactions::{InvokeTransaction, L1HandlerTransaction, Transaction};
     use starknet_types::traits::HashProducer;
 
     use super::{StarknetBlock, StarknetBlocks};
     use crate::state::state_diff::StateDiff;
     use crate::traits::HashIdentified;
+    use crate::transactions::StarknetTransaction;
+
+    #[test]
+    fn commitments_to_transactions_and_events_are_part_of_block_hash() {
+        let mut block = StarknetBlock::create_pending_block();
+        let transaction_hash = Felt::from(1);
+        block.add_transaction(transaction_hash);
+        let hash_without_commitments = block.generate_hash().unwrap();
+
+        let mut transaction = StarknetTransaction::create_rejected(
+            &Transaction::L1Handler(L1HandlerTransaction {
+                transaction_hash,
+                ..Default::default()
+            }),
+            "",
+        );
+        transaction.events = vec![Event {
+            from_address: ContractAddress::zero(),
+            keys: vec![Felt::from(2)],
+            data: vec![],
+        }];
+        block.set_commitments(&[&transaction]);
+
+        assert_ne!(block.transaction_commitment, Felt::default());
+        assert_eq!(block.event_count, 1);
+        assert_ne!(block.event_commitment, Felt::default());
+        assert_ne!(block.generate_hash().unwrap(), hash_without_commitments);
+    }
+
+    /// Block 183862 of mainnet, with the signatures and events of its transactions
+    #[derive(Deserialize)]
+    struct MainnetBlock {
+        block_number: u64,
+        block_hash: Felt,
+        parent_hash: Felt,
+        state_root: Felt,
+        sequencer: ContractAddress,
+        timestamp: u64,
+        transaction_commitment: Felt,
+        event_commitment: Felt,
+        transactions: Vec<MainnetTransaction>,
+    }
+
+    #[derive(Deserialize)]
+    struct MainnetTransaction {
+        transaction_hash: Felt,
+        signature: Vec<Felt>,
+        events: Vec<Event>,
+    }
+
+    #[test]
+    fn commitments_and_hash_of_mainnet_block_match() {
+        let json_str = std::fs::read_to_string(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/test_artifacts/mainnet_block_183862.json"
+        ))
+        .unwrap();
+        let mainnet_block: MainnetBlock = serde_json::from_str(&json_str).unwrap();
+
+        let mut block = StarknetBlock::create_pending_block();
+        block.header.block_number = BlockNumber(mainnet_block.block_number);
+        block.header.parent_hash = mainnet_block.parent_hash.into();
+        block.set_state_root(mainnet_block.state_root);
+        block.header.sequencer = mainnet_block.sequencer.try_into().unwrap();
+        block.header.timestamp = BlockTimestamp(mainnet_block.timestamp);
+
+        let transactions: Vec<StarknetTransaction> = mainnet_block
+            .transactions
+            .into_iter()
+            .map(|mainnet_transaction| {
+                block.add_transaction(mainnet_transaction.transaction_hash);
+                let mut transaction = StarknetTransaction::create_rejected(
+                    &Transaction::Invoke(InvokeTransaction::Version1(InvokeTransactionV1 {
+                        transaction_hash: mainnet_transaction.transaction_hash,
+                        signature: mainnet_transaction.signature,
+                        ..Default::default()
+                    })),
+                    "",
+                );
+                transaction.events = mainnet_transaction.events;
+                transaction
+            })
+            .collect();
+        block.set_commitments(&transactions.iter().collect::<Vec<_>>());
+
+        assert_eq!(block.transaction_commitment, mainnet_block.transaction_commitment);
+        assert_eq!(block.event_count, 1561);
+        assert_eq!(block.event_commitment, mainnet_block.event_commitment);
+        assert_eq!(block.generate_hash().unwrap(), mainnet_block.block_hash);
+    }
 
     #[test]
     fn get_blocks_return_in_correct_order() {
@@ -624,6 +811,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn get_by_block_id_returns_pending_block_with_transactions() {
+        let mut blocks = StarknetBlocks::default();
+        let mut block_to_insert = StarknetBlock::create_pending_block();
+        block_to_insert.header.block_hash = block_to_insert.generate_hash().unwrap().into();
+        blocks.insert(block_to_insert, StateDiff::default());
+
+        blocks.pending_block.header.block_number = BlockNumber(1);
+        blocks.pending_block.add_transaction(Felt::from(1));
+
+        let pending_block = blocks.get_by_block_id(BlockId::Tag(BlockTag::Pending)).unwrap();
+        assert_eq!(pending_block.block_number(), BlockNumber(1));
+        assert_eq!(pending_block.get_transactions(), &vec![Felt::from(1)]);
+
+        let latest_block = blocks.get_by_block_id(BlockId::Tag(BlockTag::Latest)).unwrap();
+        assert_eq!(latest_block.block_number(), BlockNumber(0));
+    }
+
     #[test]
     fn correct_block_linking_via_parent_hash() {
         let mut blocks = StarknetBlocks::default();
@@ -19,6 +19,10 @@ pub const ERC20_CONTRACT_CLASS_HASH: &str =
 pub const ERC20_CONTRACT_ADDRESS: &str =
     "0x49D36570D4E46F48E99674BD3FCC84644DDD6B96F7C741B1562B82F9E004DC7";
 
+/// Address of the STRK fee token, deployed with the same class as the ETH one
+pub const STRK_ERC20_CONTRACT_ADDRESS: &str =
+    "0x4718F5A0FC34CC1AF16A1CDEE98FFB20C31F5CD61D6AB07201858F4287C938D";
+
 pub(crate) const UDC_CONTRACT_PATH: &str =
     concat!(env!("CARGO_MANIFEST_DIR"), "/accounts_artifacts/UDC_OZ_0.5.0.json");
 
@@ -28,11 +32,16 @@ pub const UDC_CONTRACT_CLASS_HASH: &str =
 pub const UDC_CONTRACT_ADDRESS: &str =
     "0x41A78E741E5AF2FEC34B695679BC6891742439F7AFB8484ECD7766661AD02BF";
 
+/// Mock Starknet messaging contract, deployed on the L1 node if no messaging contract is provided
+pub(crate) const MOCK_STARKNET_MESSAGING_CONTRACT_PATH: &str =
+    concat!(env!("CARGO_MANIFEST_DIR"), "/l1_artifacts/MockStarknetMessaging.json");
+
 pub const DEVNET_DEFAULT_SEED: u32 = 123;
 pub const DEVNET_DEFAULT_TEST_SEED: u32 = 123;
 pub const DEVNET_DEFAULT_TOTAL_ACCOUNTS: u8 = 10;
 pub const DEVNET_DEFAULT_INITIAL_BALANCE: u128 = 1_000_000_000_000_000_000_000;
 pub const DEVNET_DEFAULT_GAS_PRICE: u64 = 100_000_000_000;
+pub const DEVNET_DEFAULT_STRK_GAS_PRICE: u64 = 100_000_000_000;
 pub const DEVNET_DEFAULT_HOST: IpAddr = IpAddr::V4(Ipv4Addr::LOCALHOST);
 pub const DEVNET_DEFAULT_PORT: u16 = 5050;
 pub const DEVNET_DEFAULT_TIMEOUT: u16 = 120;
@@ -40,6 +49,9 @@ pub const DEVNET_DEFAULT_CHAIN_ID: ChainId = ChainId::TestNet;
 
 pub const SUPPORTED_TX_VERSION: u32 = 1;
 
+/// Number of chain updates kept for subscribers that have not received them yet
+pub const UPDATES_CHANNEL_CAPACITY: usize = 1024;
+
 // chargeable account
 pub const CHARGEABLE_ACCOUNT_PUBLIC_KEY: &str =
     "0x4C37AB4F0994879337BFD4EAD0800776DB57DA382B8ED8EFAA478C5D3B942A4";
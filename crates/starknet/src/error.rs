@@ -32,12 +32,16 @@ pub enum Error {
     NoBlock,
     #[error("No state at block {block_number}")]
     NoStateAtBlock { block_number: u64 },
+    #[error("Snapshot with id {snapshot_id} does not exist")]
+    NoSnapshot { snapshot_id: u64 },
     #[error("Format error")]
     FormatError,
     #[error("Sierra compilation error")]
     SierraCompilationError,
     #[error("No transaction found")]
     NoTransaction,
+    #[error("No trace available for transaction")]
+    NoTransactionTrace,
     #[error("Invalid transaction index in a block")]
     InvalidTransactionIndexInBlock,
     #[error("{msg}")]
@@ -50,12 +54,18 @@ pub enum Error {
     DeserializationError { obj_name: String },
     #[error("Serialization error of {obj_name}")]
     SerializationError { obj_name: String },
+    #[error("The dump cannot be loaded: {msg}")]
+    IncompatibleDump { msg: String },
     #[error("Serialization not supported")]
     SerializationNotSupported,
     #[error("{reason}")]
     FeeError { reason: String },
     #[error(transparent)]
     TransactionValidationError(#[from] TransactionValidationError),
+    #[error("Forking origin error: {msg}")]
+    ForkOriginError { msg: String },
+    #[error("Messaging error: {msg}")]
+    MessagingError { msg: String },
 }
 
 #[derive(Debug, Error)]
@@ -0,0 +1,458 @@
+use std::str::FromStr;
+use std::time::Duration;
+
+use primitive_types::{H160, H256, U256};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use starknet_api::transaction::Fee;
+use starknet_types::contract_address::ContractAddress;
+use starknet_types::felt::Felt;
+use starknet_types::rpc::eth_address::EthAddressWrapper;
+use starknet_types::rpc::messaging::MessageToL2;
+use url::Url;
+
+use crate::constants::MOCK_STARKNET_MESSAGING_CONTRACT_PATH;
+use crate::error::{DevnetResult, Error};
+use crate::utils::{create_remote_node_agent, run_blocking, READ_TIMEOUT};
+
+/// Topic of `LogMessageToL2(address,uint256,uint256,uint256[],uint256,uint256)`, emitted by the
+/// messaging contract for each message sent to L2
+const LOG_MESSAGE_TO_L2_TOPIC: &str =
+    "0xdb80dd488acf86d17c747445b0eabb5d57c541d3bd7b6b87af987858e5066b2b";
+
+/// Selector of `addMessageHashesFromL2(uint256[])` of the mock messaging contract, which makes
+/// messages sent from L2 consumable on L1
+const ADD_MESSAGE_HASHES_FROM_L2_SELECTOR: [u8; 4] = [0xdb, 0x3c, 0x52, 0x70];
+
+const WORD_SIZE: usize = 32;
+
+/// How long to wait for the deployment of the messaging contract to be mined
+const DEPLOYMENT_RECEIPT_ATTEMPTS: usize = 20;
+const DEPLOYMENT_RECEIPT_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Deserialize)]
+struct Log {
+    topics: Vec<H256>,
+    data: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TransactionReceipt {
+    status: U256,
+    contract_address: Option<H160>,
+}
+
+/// Connection to an L1 node (e.g. anvil) with a mock Starknet messaging contract deployed. The
+/// first account of the node is expected to be unlocked, as it sends the transactions to L1.
+pub(crate) struct EthereumMessaging {
+    node_url: Url,
+    agent: ureq::Agent,
+    messaging_contract_address: H160,
+    sender_address: H160,
+    /// The last L1 block whose messages were fetched
+    last_fetched_block: Option<u64>,
+}
+
+impl EthereumMessaging {
+    /// Attaches to the messaging contract at `messaging_contract_address` of the node at `node_url`
+    pub(crate) fn new(node_url: Url, messaging_contract_address: H160) -> DevnetResult<Self> {
+        Self::with_read_timeout(node_url, messaging_contract_address, READ_TIMEOUT)
+    }
+
+    fn with_read_timeout(
+        node_url: Url,
+        messaging_contract_address: H160,
+        read_timeout: Duration,
+    ) -> DevnetResult<Self> {
+        let mut messaging = Self::connect(node_url, read_timeout);
+        messaging.messaging_contract_address = messaging_contract_address;
+
+        let code: String =
+            messaging.request("eth_getCode", json!([messaging_contract_address, "latest"]))?;
+        if code.trim_start_matches("0x").is_empty() {
+            return Err(Error::MessagingError {
+                msg: format!("No contract deployed at {messaging_contract_address:#x}"),
+            });
+        }
+
+        messaging.sender_address = messaging.fetch_sender_address()?;
+
+        Ok(messaging)
+    }
+
+    /// Deploys the bundled mock messaging contract on the node at `node_url` and attaches to it
+    pub(crate) fn deploy(node_url: Url) -> DevnetResult<Self> {
+        let mut messaging = Self::connect(node_url, READ_TIMEOUT);
+        messaging.sender_address = messaging.fetch_sender_address()?;
+
+        let artifact: Value = std::fs::read_to_string(MOCK_STARKNET_MESSAGING_CONTRACT_PATH)
+            .map_err(Error::from)
+            .and_then(|artifact| serde_json::from_str(&artifact).map_err(|_| Error::FormatError))?;
+        let bytecode = artifact["bytecode"].as_str().ok_or_else(|| Error::MessagingError {
+            msg: "Messaging contract artifact has no bytecode".into(),
+        })?;
+
+        let transaction_hash: H256 = messaging.request(
+            "eth_sendTransaction",
+            json!([{ "from": messaging.sender_address, "data": bytecode }]),
+        )?;
+        messaging.messaging_contract_address =
+            messaging.wait_for_deployed_contract_address(transaction_hash)?;
+
+        Ok(messaging)
+    }
+
+    fn connect(node_url: Url, read_timeout: Duration) -> Self {
+        Self {
+            node_url,
+            agent: create_remote_node_agent(read_timeout),
+            messaging_contract_address: H160::zero(),
+            sender_address: H160::zero(),
+            last_fetched_block: None,
+        }
+    }
+
+    fn fetch_sender_address(&self) -> DevnetResult<H160> {
+        let accounts: Vec<H160> = self.request("eth_accounts", json!([]))?;
+        accounts
+            .first()
+            .copied()
+            .ok_or_else(|| Error::MessagingError { msg: "L1 node has no unlocked accounts".into() })
+    }
+
+    /// Polls the receipt of the deployment transaction until it is mined
+    fn wait_for_deployed_contract_address(&self, transaction_hash: H256) -> DevnetResult<H160> {
+        for _ in 0..DEPLOYMENT_RECEIPT_ATTEMPTS {
+            let receipt: Option<TransactionReceipt> =
+                self.request("eth_getTransactionReceipt", json!([transaction_hash]))?;
+
+            match receipt {
+                Some(TransactionReceipt { status, contract_address: Some(address) })
+                    if status == U256::one() =>
+                {
+                    return Ok(address);
+                }
+                Some(_) => {
+                    return Err(Error::MessagingError {
+                        msg: format!(
+                            "Deployment of the messaging contract failed in transaction \
+                             {transaction_hash:#x}"
+                        ),
+                    });
+                }
+                None => run_blocking(|| std::thread::sleep(DEPLOYMENT_RECEIPT_POLL_INTERVAL)),
+            }
+        }
+
+        Err(Error::MessagingError {
+            msg: format!(
+                "Deployment transaction {transaction_hash:#x} of the messaging contract was not \
+                 mined"
+            ),
+        })
+    }
+
+    pub(crate) fn messaging_contract_address(&self) -> H160 {
+        self.messaging_contract_address
+    }
+
+    pub(crate) fn node_url(&self) -> &Url {
+        &self.node_url
+    }
+
+    /// Returns the messages sent to L2 in the blocks after the last fetched one, along with the
+    /// latest block they were fetched up to. They are fetched again until that block is marked as
+    /// fetched with [`EthereumMessaging::set_last_fetched_block`].
+    pub(crate) fn fetch_messages_to_l2(&self) -> DevnetResult<(Vec<MessageToL2>, u64)> {
+        let to_block: U256 = self.request("eth_blockNumber", json!([]))?;
+        let to_block = to_block.as_u64();
+        let from_block = self.last_fetched_block.map_or(0, |block| block + 1);
+        if from_block > to_block {
+            return Ok((vec![], to_block));
+        }
+
+        let logs: Vec<Log> = self.request(
+            "eth_getLogs",
+            json!([{
+                "fromBlock": format!("{from_block:#x}"),
+                "toBlock": format!("{to_block:#x}"),
+                "address": self.messaging_contract_address,
+                "topics": [LOG_MESSAGE_TO_L2_TOPIC],
+            }]),
+        )?;
+
+        let messages = logs.iter().map(parse_message_to_l2).collect::<DevnetResult<Vec<_>>>()?;
+
+        Ok((messages, to_block))
+    }
+
+    pub(crate) fn set_last_fetched_block(&mut self, block: u64) {
+        self.last_fetched_block = Some(block);
+    }
+
+    /// Registers the hashes of messages sent from L2 in the messaging contract, so that they can be
+    /// consumed on L1
+    pub(crate) fn send_message_hashes_to_l1(&self, message_hashes: &[H256]) -> DevnetResult<()> {
+        if message_hashes.is_empty() {
+            return Ok(());
+        }
+
+        let mut calldata = ADD_MESSAGE_HASHES_FROM_L2_SELECTOR.to_vec();
+        // the only argument is a dynamic array, so its data follows right after the offset
+        calldata.extend(H256::from_low_u64_be(WORD_SIZE as u64).as_bytes());
+        calldata.extend(H256::from_low_u64_be(message_hashes.len() as u64).as_bytes());
+        message_hashes.iter().for_each(|hash| calldata.extend(hash.as_bytes()));
+
+        let _transaction_hash: H256 = self.request(
+            "eth_sendTransaction",
+            json!([{
+                "from": self.sender_address,
+                "to": self.messaging_contract_address,
+                "data": format!("0x{}", hex::encode(calldata)),
+            }]),
+        )?;
+
+        Ok(())
+    }
+
+    fn request<T: DeserializeOwned>(&self, method: &str, params: Value) -> DevnetResult<T> {
+        let request_body = json!({
+            "jsonrpc": "2.0",
+            "id": 0,
+            "method": method,
+            "params": params,
+        });
+
+        let mut response: Value = run_blocking(|| {
+            self.agent
+                .post(self.node_url.as_str())
+                .send_json(request_body)
+                .and_then(|response| response.into_json().map_err(ureq::Error::from))
+        })
+        .map_err(|err| Error::MessagingError { msg: format!("{method} failed: {err}") })?;
+
+        if let Some(error) = response.get("error") {
+            return Err(Error::MessagingError { msg: format!("{method} failed: {error}") });
+        }
+
+        let result = response.get_mut("result").map(Value::take).unwrap_or_default();
+        serde_json::from_value(result).map_err(|err| Error::MessagingError {
+            msg: format!("{method} returned an invalid result: {err}"),
+        })
+    }
+}
+
+/// Parses a `LogMessageToL2` log. The sender, the recipient and the selector are indexed, so they
+/// are in the topics; the data holds the offset of the payload, the nonce, the fee and the payload.
+fn parse_message_to_l2(log: &Log) -> DevnetResult<MessageToL2> {
+    let invalid_log_error =
+        || Error::MessagingError { msg: "Invalid LogMessageToL2 log".to_string() };
+
+    let [_, from_address, to_address, selector] = log.topics.as_slice() else {
+        return Err(invalid_log_error());
+    };
+
+    let data = hex::decode(log.data.trim_start_matches("0x")).map_err(|_| invalid_log_error())?;
+    let words: Vec<H256> = data.chunks_exact(WORD_SIZE).map(H256::from_slice).collect();
+    let word_to_felt = |word: &H256| Felt::new(word.to_fixed_bytes()).map_err(Error::from);
+
+    let (Some(nonce), Some(fee), Some(payload_length)) = (words.get(1), words.get(2), words.get(3))
+    else {
+        return Err(invalid_log_error());
+    };
+    let payload = words
+        .get(4..4 + payload_length.to_low_u64_be() as usize)
+        .ok_or_else(invalid_log_error)?
+        .iter()
+        .map(word_to_felt)
+        .collect::<DevnetResult<Vec<Felt>>>()?;
+
+    Ok(MessageToL2 {
+        l1_contract_address: EthAddressWrapper::from_str(&format!(
+            "{:#x}",
+            H160::from(*from_address)
+        ))?,
+        l2_contract_address: ContractAddress::new(word_to_felt(to_address)?)?,
+        entry_point_selector: word_to_felt(selector)?,
+        payload,
+        paid_fee_on_l1: Fee(U256::from_big_endian(fee.as_bytes()).low_u128()),
+        nonce: word_to_felt(nonce)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::TcpListener;
+    use std::sync::atomic::Ordering;
+    use std::time::{Duration, Instant};
+
+    use primitive_types::{H160, H256};
+    use serde_json::{json, Value};
+    use starknet_types::felt::Felt;
+    use url::Url;
+
+    use super::{EthereumMessaging, LOG_MESSAGE_TO_L2_TOPIC};
+    use crate::constants::MOCK_STARKNET_MESSAGING_CONTRACT_PATH;
+    use crate::error::Error;
+    use crate::utils::test_utils::spawn_mock_json_rpc_server;
+
+    const MESSAGING_CONTRACT_ADDRESS: u64 = 0x42;
+    const L1_SENDER_ADDRESS: u64 = 0x8;
+
+    fn word(value: u64) -> String {
+        format!("{:x}", H256::from_low_u64_be(value))
+    }
+
+    /// Handler of [`spawn_mock_json_rpc_server`] imitating an L1 node at block 2, with a single
+    /// message sent to L2 with the payload `[3, 4]`
+    fn mock_l1_node_handler(method: &str, params: &Value) -> Value {
+        match method {
+            "eth_getCode" => json!({ "result": "0x6080" }),
+            "eth_accounts" => json!({ "result": [H160::from_low_u64_be(1)] }),
+            "eth_blockNumber" => json!({ "result": "0x2" }),
+            "eth_getLogs" => json!({
+                "result": [{
+                    "topics": [
+                        LOG_MESSAGE_TO_L2_TOPIC,
+                        format!("0x{}", word(L1_SENDER_ADDRESS)),
+                        format!("0x{}", word(0x1)),
+                        format!("0x{}", word(0x6)),
+                    ],
+                    // payload offset, nonce, fee, payload length, payload
+                    "data": format!("0x{}", [0x60, 5, 1, 2, 3, 4].map(word).concat()),
+                }]
+            }),
+            // addMessageHashesFromL2 with a single hash of 0x..07
+            "eth_sendTransaction"
+                if params[0]["data"]
+                    == format!("0xdb3c5270{}{}{}", word(0x20), word(1), word(7)) =>
+            {
+                json!({ "result": H256::zero() })
+            }
+            _ => json!({ "error": { "code": -32601, "message": "Method not found" } }),
+        }
+    }
+
+    fn attach(url: Url) -> EthereumMessaging {
+        EthereumMessaging::new(url, H160::from_low_u64_be(MESSAGING_CONTRACT_ADDRESS)).unwrap()
+    }
+
+    #[test]
+    fn messages_to_l2_are_fetched_until_marked_as_fetched() {
+        let (url, _) = spawn_mock_json_rpc_server(mock_l1_node_handler);
+        let mut messaging = attach(url);
+
+        let (messages, last_block) = messaging.fetch_messages_to_l2().unwrap();
+        assert_eq!(last_block, 2);
+        assert_eq!(messages.len(), 1);
+        let message = &messages[0];
+        assert_eq!(Felt::from(message.l1_contract_address.clone()), Felt::from(8));
+        assert_eq!(Felt::from(message.l2_contract_address), Felt::from(1));
+        assert_eq!(message.entry_point_selector, Felt::from(6));
+        assert_eq!(message.payload, vec![Felt::from(3), Felt::from(4)]);
+        assert_eq!(message.nonce, Felt::from(5));
+        assert_eq!(message.paid_fee_on_l1.0, 1);
+
+        assert_eq!(messaging.fetch_messages_to_l2().unwrap().0.len(), 1);
+
+        // no new L1 blocks since the last fetched one
+        messaging.set_last_fetched_block(last_block);
+        assert!(messaging.fetch_messages_to_l2().unwrap().0.is_empty());
+    }
+
+    #[test]
+    fn message_hashes_are_sent_to_messaging_contract() {
+        let (url, request_count) = spawn_mock_json_rpc_server(mock_l1_node_handler);
+        let messaging = attach(url);
+        let requests_on_attach = request_count.load(Ordering::SeqCst);
+
+        messaging.send_message_hashes_to_l1(&[H256::from_low_u64_be(7)]).unwrap();
+        assert!(messaging.send_message_hashes_to_l1(&[H256::from_low_u64_be(8)]).is_err());
+
+        // nothing is sent if there are no messages
+        messaging.send_message_hashes_to_l1(&[]).unwrap();
+        assert_eq!(request_count.load(Ordering::SeqCst), requests_on_attach + 2);
+    }
+
+    #[test]
+    fn mock_messaging_contract_is_deployed() {
+        let (url, _) = spawn_mock_json_rpc_server(|method, params| match method {
+            "eth_accounts" => json!({ "result": [H160::from_low_u64_be(1)] }),
+            "eth_sendTransaction" => {
+                let artifact: Value = serde_json::from_str(
+                    &std::fs::read_to_string(MOCK_STARKNET_MESSAGING_CONTRACT_PATH).unwrap(),
+                )
+                .unwrap();
+                assert_eq!(params[0]["from"], json!(H160::from_low_u64_be(1)));
+                assert_eq!(params[0]["data"], artifact["bytecode"]);
+                assert!(params[0].get("to").is_none());
+                json!({ "result": H256::from_low_u64_be(0xd) })
+            }
+            "eth_getTransactionReceipt" => {
+                assert_eq!(params[0], json!(H256::from_low_u64_be(0xd)));
+                json!({ "result": {
+                    "status": "0x1",
+                    "contractAddress": H160::from_low_u64_be(MESSAGING_CONTRACT_ADDRESS),
+                } })
+            }
+            _ => json!({ "error": { "code": -32601, "message": "Method not found" } }),
+        });
+
+        let messaging = EthereumMessaging::deploy(url).unwrap();
+        assert_eq!(
+            messaging.messaging_contract_address(),
+            H160::from_low_u64_be(MESSAGING_CONTRACT_ADDRESS)
+        );
+    }
+
+    #[test]
+    fn failed_deployment_of_mock_messaging_contract() {
+        let (url, _) = spawn_mock_json_rpc_server(|method, _| match method {
+            "eth_accounts" => json!({ "result": [H160::from_low_u64_be(1)] }),
+            "eth_sendTransaction" => json!({ "result": H256::from_low_u64_be(0xd) }),
+            "eth_getTransactionReceipt" => {
+                json!({ "result": { "status": "0x0", "contractAddress": null } })
+            }
+            _ => json!({ "error": { "code": -32601, "message": "Method not found" } }),
+        });
+
+        match EthereumMessaging::deploy(url) {
+            Err(Error::MessagingError { .. }) => (),
+            unexpected => panic!("Should have failed; got {:?}", unexpected.err()),
+        }
+    }
+
+    #[test]
+    fn unresponsive_l1_node_times_out() {
+        // accepts connections, but never responds
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap()).parse().unwrap();
+
+        let start = Instant::now();
+        match EthereumMessaging::with_read_timeout(
+            url,
+            H160::from_low_u64_be(MESSAGING_CONTRACT_ADDRESS),
+            Duration::from_millis(200),
+        ) {
+            Err(Error::MessagingError { .. }) => (),
+            unexpected => panic!("Should have failed; got {:?}", unexpected.err()),
+        }
+        assert!(start.elapsed() < Duration::from_secs(5));
+        drop(listener);
+    }
+
+    #[test]
+    fn attaching_to_address_without_contract() {
+        let (url, _) = spawn_mock_json_rpc_server(|method, _| match method {
+            "eth_getCode" => json!({ "result": "0x" }),
+            _ => json!({ "error": { "code": -32601, "message": "Method not found" } }),
+        });
+
+        match EthereumMessaging::new(url, H160::from_low_u64_be(MESSAGING_CONTRACT_ADDRESS)) {
+            Err(Error::MessagingError { .. }) => (),
+            unexpected => panic!("Should have failed; got {:?}", unexpected.err()),
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use std::collections::HashMap;
+
+use primitive_types::H256;
+use starknet_api::block::BlockNumber;
+use starknet_types::felt::TransactionHash;
+use starknet_types::rpc::messaging::MessageToL2;
+use starknet_types::rpc::transaction_receipt::MessageToL1;
+
+pub(crate) mod ethereum;
+
+use self::ethereum::EthereumMessaging;
+use crate::error::{DevnetResult, Error};
+
+/// Messages exchanged with L1 in a single flush
+#[derive(Debug, Default)]
+pub struct FlushedMessages {
+    pub messages_to_l1: Vec<MessageToL1>,
+    pub messages_to_l2: Vec<MessageToL2>,
+    /// Hashes of the L1 handler transactions executed for `messages_to_l2`
+    pub generated_l2_transactions: Vec<TransactionHash>,
+}
+
+#[derive(Default)]
+pub(crate) struct MessagingBroker {
+    /// Set once the messaging contract on L1 is loaded
+    pub(crate) ethereum: Option<EthereumMessaging>,
+    /// Messages sent to L1 in blocks with lower numbers have already been flushed
+    pub(crate) next_block_to_flush: BlockNumber,
+    /// Number of not yet consumed messages sent to L1, by message hash
+    pub(crate) l2_to_l1_messages: HashMap<H256, u64>,
+}
+
+impl MessagingBroker {
+    /// Makes the messages consumable with [`MessagingBroker::consume_message_from_l2`]
+    pub(crate) fn add_messages_to_l1(&mut self, messages: &[MessageToL1]) {
+        for message in messages {
+            *self.l2_to_l1_messages.entry(message.hash()).or_default() += 1;
+        }
+    }
+
+    /// Removes the messages which were not consumed yet, e.g. once the transactions sending them
+    /// are aborted
+    pub(crate) fn remove_messages_to_l1(&mut self, messages: &[MessageToL1]) {
+        for message in messages {
+            // a consumed message cannot be removed anymore
+            let _ = self.consume_message_from_l2(message);
+        }
+    }
+
+    /// Consumes a single occurrence of the message, as the messaging contract does on L1. Returns
+    /// the hash of the message.
+    pub(crate) fn consume_message_from_l2(&mut self, message: &MessageToL1) -> DevnetResult<H256> {
+        let message_hash = message.hash();
+
+        let count =
+            self.l2_to_l1_messages.get_mut(&message_hash).ok_or_else(|| Error::MessagingError {
+                msg: format!("Message with hash {message_hash:#x} does not exist or was consumed"),
+            })?;
+        *count -= 1;
+        if *count == 0 {
+            self.l2_to_l1_messages.remove(&message_hash);
+        }
+
+        Ok(message_hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use primitive_types::H160;
+    use starknet_api::core::EthAddress;
+    use starknet_types::contract_address::ContractAddress;
+    use starknet_types::felt::Felt;
+    use starknet_types::rpc::transaction_receipt::MessageToL1;
+
+    use super::MessagingBroker;
+    use crate::error::Error;
+
+    fn dummy_message_to_l1(payload: u128) -> MessageToL1 {
+        MessageToL1 {
+            from_address: ContractAddress::new(Felt::from(1)).unwrap(),
+            to_address: EthAddress(H160::from_low_u64_be(2)),
+            payload: vec![Felt::from(payload)],
+        }
+    }
+
+    fn assert_not_consumable(broker: &mut MessagingBroker, message: &MessageToL1) {
+        match broker.consume_message_from_l2(message) {
+            Err(Error::MessagingError { .. }) => (),
+            unexpected => panic!("Should have failed; got {unexpected:?}"),
+        }
+    }
+
+    #[test]
+    fn message_is_consumed_as_many_times_as_it_was_sent() {
+        let mut broker = MessagingBroker::default();
+        let message = dummy_message_to_l1(1);
+        broker.add_messages_to_l1(&[message.clone(), message.clone()]);
+
+        assert_eq!(broker.consume_message_from_l2(&message).unwrap(), message.hash());
+        assert_eq!(broker.consume_message_from_l2(&message).unwrap(), message.hash());
+        assert_not_consumable(&mut broker, &message);
+    }
+
+    #[test]
+    fn message_which_was_not_sent_is_not_consumable() {
+        let mut broker = MessagingBroker::default();
+        broker.add_messages_to_l1(&[dummy_message_to_l1(1)]);
+
+        assert_not_consumable(&mut broker, &dummy_message_to_l1(2));
+    }
+
+    #[test]
+    fn removed_message_is_not_consumable() {
+        let mut broker = MessagingBroker::default();
+        let message = dummy_message_to_l1(1);
+        broker.add_messages_to_l1(&[message.clone()]);
+        broker.remove_messages_to_l1(&[message.clone()]);
+
+        assert_not_consumable(&mut broker, &message);
+        // removing a message which is not present has no effect
+        broker.remove_messages_to_l1(&[message]);
+    }
+}
@@ -13,7 +13,8 @@ use crate::utils::generate_u128_random_numbers;
 pub(crate) struct PredeployedAccounts {
     seed: u32,
     initial_balance: Felt,
-    fee_token_address: ContractAddress,
+    eth_fee_token_address: ContractAddress,
+    strk_fee_token_address: ContractAddress,
     accounts: Vec<Account>,
 }
 
@@ -21,9 +22,16 @@ impl PredeployedAccounts {
     pub(crate) fn new(
         seed: u32,
         initial_balance: Felt,
-        fee_token_address: ContractAddress,
+        eth_fee_token_address: ContractAddress,
+        strk_fee_token_address: ContractAddress,
     ) -> Self {
-        Self { seed, initial_balance, fee_token_address, accounts: Vec::new() }
+        Self {
+            seed,
+            initial_balance,
+            eth_fee_token_address,
+            strk_fee_token_address,
+            accounts: Vec::new(),
+        }
     }
 }
 
@@ -68,7 +76,8 @@ impl AccountGenerator for PredeployedAccounts {
                 private_key,
                 class_hash,
                 contract_class.clone(),
-                self.fee_token_address,
+                self.eth_fee_token_address,
+                self.strk_fee_token_address,
             )?;
             self.accounts.push(account);
         }
@@ -131,8 +140,12 @@ mod tests {
 
     #[test]
     fn private_key_from_different_seeds_should_be_different() {
-        let predeployed_acc =
-            PredeployedAccounts::new(999, Felt::from(1), dummy_contract_address());
+        let predeployed_acc = PredeployedAccounts::new(
+            999,
+            Felt::from(1),
+            dummy_contract_address(),
+            dummy_contract_address(),
+        );
         let generated_private_key = predeployed_acc.generate_private_keys(1).unwrap()[0];
 
         let non_expected_result = Felt::from_prefixed_hex_str(PRIVATE_KEYS_IN_HEX[0]).unwrap();
@@ -162,8 +175,12 @@ mod tests {
 
     #[test]
     fn check_generated_predeployed_accounts_against_json_schema() {
-        let mut predeployed_acc =
-            PredeployedAccounts::new(123, Felt::from(1000), dummy_contract_address());
+        let mut predeployed_acc = PredeployedAccounts::new(
+            123,
+            Felt::from(1000),
+            dummy_contract_address(),
+            dummy_contract_address(),
+        );
         let class_hash = Felt::from_prefixed_hex_str(CAIRO_0_ACCOUNT_CONTRACT_HASH).unwrap();
         let json_str = std::fs::read_to_string(CAIRO_0_ACCOUNT_CONTRACT_PATH).unwrap();
 
@@ -195,6 +212,11 @@ mod tests {
     }
 
     fn predeployed_account_instance() -> PredeployedAccounts {
-        PredeployedAccounts::new(SEED, Felt::from(100), dummy_contract_address())
+        PredeployedAccounts::new(
+            SEED,
+            Felt::from(100),
+            dummy_contract_address(),
+            dummy_contract_address(),
+        )
     }
 }
@@ -1,7 +1,10 @@
 use blockifier::transaction::transactions::ExecutableTransaction;
 use starknet_types::felt::{ClassHash, TransactionHash};
+use starknet_types::rpc::estimate_message_fee::FeeUnit;
 use starknet_types::rpc::transactions::broadcasted_declare_transaction_v1::BroadcastedDeclareTransactionV1;
 use starknet_types::rpc::transactions::broadcasted_declare_transaction_v2::BroadcastedDeclareTransactionV2;
+use starknet_types::rpc::transactions::broadcasted_declare_transaction_v3::BroadcastedDeclareTransactionV3;
+use starknet_types::rpc::transactions::declare_transaction_v0v1::DeclareTransactionV0V1;
 use starknet_types::rpc::transactions::{DeclareTransaction, Transaction};
 
 use crate::error::{DevnetResult, Error};
@@ -17,8 +20,8 @@ pub fn add_declare_transaction_v2(
         });
     }
 
-    let blockifier_declare_transaction =
-        broadcasted_declare_transaction.create_blockifier_declare(starknet.chain_id().to_felt())?;
+    let blockifier_declare_transaction = broadcasted_declare_transaction
+        .create_blockifier_declare(starknet.chain_id().to_felt(), starknet.compilation_cache())?;
 
     let transaction_hash = blockifier_declare_transaction.tx_hash().0.into();
     let class_hash = blockifier_declare_transaction.class_hash().0.into();
@@ -38,6 +41,39 @@ pub fn add_declare_transaction_v2(
     Ok((transaction_hash, class_hash))
 }
 
+pub fn add_declare_transaction_v3(
+    starknet: &mut Starknet,
+    broadcasted_declare_transaction: BroadcastedDeclareTransactionV3,
+) -> DevnetResult<(TransactionHash, ClassHash)> {
+    if broadcasted_declare_transaction.common.get_max_fee().0 == 0 {
+        return Err(Error::FeeError {
+            reason: "For declare transaction version 3, max fee cannot be 0".to_string(),
+        });
+    }
+    starknet.check_v3_transaction(&broadcasted_declare_transaction.common)?;
+
+    let blockifier_declare_transaction = broadcasted_declare_transaction
+        .create_blockifier_declare(starknet.chain_id().to_felt(), starknet.compilation_cache())?;
+
+    let transaction_hash = blockifier_declare_transaction.tx_hash().0.into();
+    let class_hash = blockifier_declare_transaction.class_hash().0.into();
+
+    let transaction = Transaction::Declare(DeclareTransaction::Version3(
+        broadcasted_declare_transaction.create_declare(class_hash, transaction_hash),
+    ));
+
+    let block_context = starknet.block_context_for_fee_unit(FeeUnit::Fri);
+    let blockifier_execution_result =
+        blockifier::transaction::account_transaction::AccountTransaction::Declare(
+            blockifier_declare_transaction,
+        )
+        .execute(&mut starknet.state.state, &block_context, true, true);
+
+    starknet.handle_transaction_result(transaction, blockifier_execution_result)?;
+
+    Ok((transaction_hash, class_hash))
+}
+
 pub fn add_declare_transaction_v1(
     starknet: &mut Starknet,
     broadcasted_declare_transaction: BroadcastedDeclareTransactionV1,
@@ -70,16 +106,48 @@ pub fn add_declare_transaction_v1(
     Ok((transaction_hash, class_hash))
 }
 
+/// Executes a declare transaction of version 0, e.g. one of an older chain. Such transactions
+/// predate account validation and fees, so it is neither validated nor charged.
+pub fn add_declare_transaction_v0(
+    starknet: &mut Starknet,
+    declare_transaction: DeclareTransactionV0V1,
+) -> DevnetResult<(TransactionHash, ClassHash)> {
+    let transaction_hash = declare_transaction.transaction_hash;
+    let class_hash = declare_transaction.class_hash;
+    let blockifier_declare_transaction = declare_transaction.create_blockifier_declare()?;
+
+    let transaction = Transaction::Declare(DeclareTransaction::Version0(declare_transaction));
+
+    let blockifier_execution_result =
+        blockifier::transaction::account_transaction::AccountTransaction::Declare(
+            blockifier_declare_transaction,
+        )
+        .execute(&mut starknet.state.state, &starknet.block_context, false, false);
+
+    starknet.handle_transaction_result(transaction, blockifier_execution_result)?;
+
+    Ok((transaction_hash, class_hash))
+}
+
 #[cfg(test)]
 mod tests {
     use starknet_api::block::BlockNumber;
     use starknet_api::transaction::Fee;
-    use starknet_rs_core::types::{TransactionExecutionStatus, TransactionFinalityStatus};
+    use starknet_rs_core::types::{
+        BlockId, BlockTag, TransactionExecutionStatus, TransactionFinalityStatus,
+    };
     use starknet_types::contract_address::ContractAddress;
-    use starknet_types::contract_class::{Cairo0Json, ContractClass};
+    use starknet_types::contract_class::compilation_cache::CompilationCache;
+    use starknet_types::contract_class::{Cairo0ContractClass, Cairo0Json, ContractClass};
     use starknet_types::felt::Felt;
     use starknet_types::rpc::transactions::broadcasted_declare_transaction_v1::BroadcastedDeclareTransactionV1;
     use starknet_types::rpc::transactions::broadcasted_declare_transaction_v2::BroadcastedDeclareTransactionV2;
+    use starknet_types::rpc::transactions::broadcasted_declare_transaction_v3::BroadcastedDeclareTransactionV3;
+    use starknet_types::rpc::transactions::declare_transaction_v0v1::DeclareTransactionV0V1;
+    use starknet_types::rpc::transactions::{
+        BroadcastedTransactionCommonV3, DeclareTransaction, FinalityStatus, ResourceBounds,
+        ResourceBoundsMapping, Transaction,
+    };
     use starknet_types::traits::HashProducer;
 
     use crate::account::Account;
@@ -145,6 +213,22 @@ mod tests {
         }
     }
 
+    #[test]
+    fn declare_v2_transaction_failing_validation_is_recorded_as_rejected() {
+        let (mut starknet, sender) = setup(Some(1));
+        let declare_txn = dummy_broadcasted_declare_transaction_v2(&sender);
+
+        assert!(starknet.add_declare_transaction_v2(declare_txn).is_err());
+
+        let (transaction_hash, _) = starknet.transactions.iter().next().unwrap();
+        let status = starknet.get_transaction_status(*transaction_hash).unwrap();
+        assert_eq!(status.finality_status, FinalityStatus::Rejected);
+        assert_eq!(status.execution_status, None);
+        assert!(status.failure_reason.is_some());
+        assert!(starknet.pending_block().get_transactions().is_empty());
+        assert_eq!(starknet.get_rejected_transactions().len(), 1);
+    }
+
     #[test]
     fn add_declare_v2_transaction_successful_execution() {
         let (mut starknet, sender) = setup(Some(100000000));
@@ -162,10 +246,23 @@ mod tests {
         );
         // check if txn is with status accepted
         assert_eq!(tx.finality_status, Some(TransactionFinalityStatus::AcceptedOnL2));
-        assert_eq!(tx.execution_result.status(), TransactionExecutionStatus::Succeeded);
+        assert_eq!(tx.get_execution_status(), Some(TransactionExecutionStatus::Succeeded));
         assert!(starknet.state.contract_classes.get(&class_hash).is_some());
     }
 
+    #[test]
+    fn declared_cairo_1_class_is_compiled_through_the_compilation_cache() {
+        let cache_dir = tempfile::tempdir().unwrap();
+        let (mut starknet, sender) = setup(Some(100000000));
+        starknet.state.state.state.compilation_cache =
+            Some(CompilationCache::new(cache_dir.path()).unwrap());
+
+        let declare_txn = dummy_broadcasted_declare_transaction_v2(&sender);
+        let (_, class_hash) = starknet.add_declare_transaction_v2(declare_txn).unwrap();
+
+        assert!(starknet.compilation_cache().unwrap().get(class_hash).is_some());
+    }
+
     #[test]
     fn declare_v2_transaction_successful_storage_change() {
         let (mut starknet, sender) = setup(Some(100000000));
@@ -194,10 +291,46 @@ mod tests {
         assert_eq!(retrieved_class_hash, expected_class_hash);
         // check if txn is with status accepted
         assert_eq!(retrieved_txn.finality_status, Some(TransactionFinalityStatus::AcceptedOnL2));
-        assert_eq!(retrieved_txn.execution_result.status(), TransactionExecutionStatus::Succeeded);
+        assert_eq!(
+            retrieved_txn.get_execution_status(),
+            Some(TransactionExecutionStatus::Succeeded)
+        );
         assert!(starknet.state.is_contract_declared(&expected_class_hash));
     }
 
+    #[test]
+    fn add_declare_v3_transaction_successful_execution() {
+        let (mut starknet, sender) = setup(Some(100000000));
+        let declare_v2 = dummy_broadcasted_declare_transaction_v2(&sender);
+        let declare_txn = BroadcastedDeclareTransactionV3 {
+            common: BroadcastedTransactionCommonV3 {
+                version: Felt::from(3),
+                signature: vec![],
+                nonce: Felt::from(0),
+                resource_bounds: ResourceBoundsMapping {
+                    l1_gas: ResourceBounds { max_amount: 4000, max_price_per_unit: 1 },
+                    ..Default::default()
+                },
+                tip: Default::default(),
+                paymaster_data: vec![],
+                nonce_data_availability_mode: Default::default(),
+                fee_data_availability_mode: Default::default(),
+            },
+            contract_class: declare_v2.contract_class,
+            sender_address: sender,
+            compiled_class_hash: declare_v2.compiled_class_hash,
+            account_deployment_data: vec![],
+        };
+
+        let (tx_hash, class_hash) = starknet.add_declare_transaction_v3(declare_txn).unwrap();
+
+        let tx = starknet.transactions.get_by_hash_mut(&tx_hash).unwrap();
+        assert_eq!(tx.get_execution_status(), Some(TransactionExecutionStatus::Succeeded));
+        assert!(matches!(tx.inner, Transaction::Declare(DeclareTransaction::Version3(_))));
+        assert!(starknet.state.is_contract_declared(&class_hash));
+        assert!(starknet.state.contract_classes.get(&class_hash).is_some());
+    }
+
     #[test]
     fn declare_transaction_v1_with_max_fee_zero_should_return_an_error() {
         let declare_transaction = BroadcastedDeclareTransactionV1::new(
@@ -266,7 +399,7 @@ mod tests {
         assert_eq!(class_hash, declare_txn.contract_class.generate_hash().unwrap());
         // check if txn is with status accepted
         assert_eq!(tx.finality_status, Some(TransactionFinalityStatus::AcceptedOnL2));
-        assert_eq!(tx.execution_result.status(), TransactionExecutionStatus::Succeeded);
+        assert_eq!(tx.get_execution_status(), Some(TransactionExecutionStatus::Succeeded));
         // check if contract is successfully declared
         assert!(starknet.state.is_contract_declared(&class_hash));
         // check if pending block is resetted
@@ -302,12 +435,47 @@ mod tests {
 
         // check if txn is with status accepted
         assert_eq!(tx.finality_status, Some(TransactionFinalityStatus::AcceptedOnL2));
-        assert_eq!(tx.execution_result.status(), TransactionExecutionStatus::Succeeded);
+        assert_eq!(tx.get_execution_status(), Some(TransactionExecutionStatus::Succeeded));
 
         // check if contract is declared
         assert!(starknet.state.is_contract_declared(&class_hash));
     }
 
+    #[test]
+    fn declare_v0_transaction_successfully_declares_class() {
+        let (mut starknet, _) = setup(None);
+        let contract_class: Cairo0ContractClass = dummy_cairo_0_contract_class().into();
+        let expected_class_hash = contract_class.generate_hash().unwrap();
+        assert!(!starknet.state.is_contract_declared(&expected_class_hash));
+
+        // version 0 declarations were sent by the bootloader address 0x1
+        let declare_transaction = DeclareTransactionV0V1 {
+            class_hash: expected_class_hash,
+            contract_class: contract_class.clone(),
+            sender_address: ContractAddress::new(Felt::from(1)).unwrap(),
+            nonce: Felt::from(0),
+            max_fee: Fee(0),
+            version: Felt::from(0),
+            transaction_hash: Felt::from(1),
+            signature: vec![],
+        };
+
+        let (tx_hash, class_hash) =
+            starknet.add_declare_transaction_v0(declare_transaction).unwrap();
+        assert_eq!(class_hash, expected_class_hash);
+
+        let tx = starknet.transactions.get_by_hash_mut(&tx_hash).unwrap();
+        assert_eq!(tx.finality_status, Some(TransactionFinalityStatus::AcceptedOnL2));
+        assert_eq!(tx.get_execution_status(), Some(TransactionExecutionStatus::Succeeded));
+        assert!(matches!(tx.inner, Transaction::Declare(DeclareTransaction::Version0(_))));
+
+        assert!(starknet.state.is_contract_declared(&class_hash));
+        assert_eq!(
+            starknet.get_class(BlockId::Tag(BlockTag::Latest), class_hash).unwrap(),
+            ContractClass::Cairo0(contract_class)
+        );
+    }
+
     /// Initializes starknet with 1 account - account without validations
     fn setup(acc_balance: Option<u128>) -> (Starknet, ContractAddress) {
         let mut starknet = Starknet::default();
@@ -319,6 +487,8 @@ mod tests {
 
         let erc_20_contract = predeployed::create_erc20().unwrap();
         erc_20_contract.deploy(&mut starknet.state).unwrap();
+        let strk_erc_20_contract = predeployed::create_strk_erc20().unwrap();
+        strk_erc_20_contract.deploy(&mut starknet.state).unwrap();
 
         let acc = Account::new(
             Felt::from(acc_balance.unwrap_or(10000)),
@@ -327,6 +497,7 @@ mod tests {
             contract_class.generate_hash().unwrap(),
             contract_class.into(),
             erc_20_contract.get_address(),
+            strk_erc_20_contract.get_address(),
         )
         .unwrap();
 
@@ -335,8 +506,10 @@ mod tests {
 
         starknet.state.clear_dirty_state();
         starknet.block_context = Starknet::init_block_context(
+            1,
             1,
             constants::ERC20_CONTRACT_ADDRESS,
+            constants::STRK_ERC20_CONTRACT_ADDRESS,
             DEVNET_DEFAULT_CHAIN_ID,
         );
 
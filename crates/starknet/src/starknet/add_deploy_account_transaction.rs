@@ -1,8 +1,7 @@
 use blockifier::transaction::transactions::ExecutableTransaction;
 use starknet_types::contract_address::ContractAddress;
 use starknet_types::felt::TransactionHash;
-use starknet_types::rpc::transactions::broadcasted_deploy_account_transaction::BroadcastedDeployAccountTransaction;
-use starknet_types::rpc::transactions::Transaction;
+use starknet_types::rpc::transactions::{BroadcastedDeployAccountTransaction, Transaction};
 
 use super::Starknet;
 use crate::error::{DevnetResult, Error};
@@ -12,16 +11,20 @@ pub fn add_deploy_account_transaction(
     starknet: &mut Starknet,
     broadcasted_deploy_account_transaction: BroadcastedDeployAccountTransaction,
 ) -> DevnetResult<(TransactionHash, ContractAddress)> {
-    if broadcasted_deploy_account_transaction.common.max_fee.0 == 0 {
+    if broadcasted_deploy_account_transaction.get_max_fee().0 == 0 {
         return Err(Error::FeeError {
             reason: "For deploy account transaction, max fee cannot be 0".to_string(),
         });
     }
+    if let BroadcastedDeployAccountTransaction::V3(transaction) =
+        &broadcasted_deploy_account_transaction
+    {
+        starknet.check_v3_transaction(&transaction.common)?;
+    }
 
-    if !starknet.state.is_contract_declared(&broadcasted_deploy_account_transaction.class_hash) {
-        return Err(Error::StateError(crate::error::StateError::NoneClassHash(
-            broadcasted_deploy_account_transaction.class_hash,
-        )));
+    let class_hash = broadcasted_deploy_account_transaction.get_class_hash();
+    if !starknet.state.is_contract_declared(class_hash) {
+        return Err(Error::StateError(crate::error::StateError::NoneClassHash(*class_hash)));
     }
 
     let blockifier_deploy_account_transaction = broadcasted_deploy_account_transaction
@@ -34,11 +37,13 @@ pub fn add_deploy_account_transaction(
 
     let transaction = Transaction::DeployAccount(deploy_account_transaction);
 
+    let block_context =
+        starknet.block_context_for_fee_unit(broadcasted_deploy_account_transaction.get_fee_unit());
     let blockifier_execution_result =
         blockifier::transaction::account_transaction::AccountTransaction::DeployAccount(
             blockifier_deploy_account_transaction,
         )
-        .execute(&mut starknet.state.state, &starknet.block_context, true, true);
+        .execute(&mut starknet.state.state, &block_context, true, true);
 
     starknet.handle_transaction_result(transaction, blockifier_execution_result)?;
 
@@ -53,7 +58,12 @@ mod tests {
     use starknet_types::contract_class::Cairo0Json;
     use starknet_types::contract_storage_key::ContractStorageKey;
     use starknet_types::felt::{ClassHash, Felt};
-    use starknet_types::rpc::transactions::broadcasted_deploy_account_transaction::BroadcastedDeployAccountTransaction;
+    use starknet_types::rpc::transactions::broadcasted_deploy_account_transaction_v1::BroadcastedDeployAccountTransactionV1;
+    use starknet_types::rpc::transactions::broadcasted_deploy_account_transaction_v3::BroadcastedDeployAccountTransactionV3;
+    use starknet_types::rpc::transactions::{
+        BroadcastedDeployAccountTransaction, BroadcastedTransactionCommonV3,
+        DeployAccountTransaction, ResourceBounds, ResourceBoundsMapping, Transaction,
+    };
     use starknet_types::traits::HashProducer;
 
     use crate::constants::{self, DEVNET_DEFAULT_CHAIN_ID};
@@ -64,15 +74,16 @@ mod tests {
 
     #[test]
     fn account_deploy_transaction_with_max_fee_zero_should_return_an_error() {
-        let deploy_account_transaction = BroadcastedDeployAccountTransaction::new(
-            &vec![0.into(), 1.into()],
-            Fee(0),
-            &vec![0.into(), 1.into()],
-            0.into(),
-            0.into(),
-            0.into(),
-            0.into(),
-        );
+        let deploy_account_transaction =
+            BroadcastedDeployAccountTransaction::V1(BroadcastedDeployAccountTransactionV1::new(
+                &vec![0.into(), 1.into()],
+                Fee(0),
+                &vec![0.into(), 1.into()],
+                0.into(),
+                0.into(),
+                0.into(),
+                0.into(),
+            ));
 
         let result = Starknet::default().add_deploy_account_transaction(deploy_account_transaction);
 
@@ -90,15 +101,16 @@ mod tests {
         let (mut starknet, account_class_hash, _) = setup();
 
         let fee_raw: u128 = 4000;
-        let transaction = BroadcastedDeployAccountTransaction::new(
-            &vec![],
-            Fee(fee_raw),
-            &vec![],
-            Felt::from(0),
-            account_class_hash,
-            Felt::from(13),
-            Felt::from(1),
-        );
+        let transaction =
+            BroadcastedDeployAccountTransaction::V1(BroadcastedDeployAccountTransactionV1::new(
+                &vec![],
+                Fee(fee_raw),
+                &vec![],
+                Felt::from(0),
+                account_class_hash,
+                Felt::from(13),
+                Felt::from(1),
+            ));
 
         match starknet.add_deploy_account_transaction(transaction).unwrap_err() {
             Error::TransactionValidationError(
@@ -115,15 +127,16 @@ mod tests {
         let (mut starknet, account_class_hash, fee_token_address) = setup();
 
         let fee_raw: u128 = 2000;
-        let transaction = BroadcastedDeployAccountTransaction::new(
-            &vec![],
-            Fee(fee_raw),
-            &vec![],
-            Felt::from(0),
-            account_class_hash,
-            Felt::from(13),
-            Felt::from(1),
-        );
+        let transaction =
+            BroadcastedDeployAccountTransaction::V1(BroadcastedDeployAccountTransactionV1::new(
+                &vec![],
+                Fee(fee_raw),
+                &vec![],
+                Felt::from(0),
+                account_class_hash,
+                Felt::from(13),
+                Felt::from(1),
+            ));
 
         let blockifier_transaction = transaction
             .create_blockifier_deploy_account(DEVNET_DEFAULT_CHAIN_ID.to_felt())
@@ -157,15 +170,16 @@ mod tests {
     fn deploy_account_transaction_successful_execution() {
         let (mut starknet, account_class_hash, fee_token_address) = setup();
 
-        let transaction = BroadcastedDeployAccountTransaction::new(
-            &vec![],
-            Fee(4000),
-            &vec![],
-            Felt::from(0),
-            account_class_hash,
-            Felt::from(13),
-            Felt::from(1),
-        );
+        let transaction =
+            BroadcastedDeployAccountTransaction::V1(BroadcastedDeployAccountTransactionV1::new(
+                &vec![],
+                Fee(4000),
+                &vec![],
+                Felt::from(0),
+                account_class_hash,
+                Felt::from(13),
+                Felt::from(1),
+            ));
         let blockifier_transaction = transaction
             .create_blockifier_deploy_account(DEVNET_DEFAULT_CHAIN_ID.to_felt())
             .unwrap();
@@ -191,7 +205,7 @@ mod tests {
         let txn = starknet.transactions.get_by_hash_mut(&txn_hash).unwrap();
 
         assert_eq!(txn.finality_status, Some(TransactionFinalityStatus::AcceptedOnL2));
-        assert_eq!(txn.execution_result.status(), TransactionExecutionStatus::Succeeded);
+        assert_eq!(txn.get_execution_status(), Some(TransactionExecutionStatus::Succeeded));
 
         assert_eq!(get_accounts_count(&starknet), accounts_before_deployment + 1);
         let account_balance_after_deployment =
@@ -200,6 +214,59 @@ mod tests {
         assert!(account_balance_before_deployment > account_balance_after_deployment);
     }
 
+    #[test]
+    fn deploy_account_transaction_v3_successful_execution() {
+        let (mut starknet, account_class_hash, _) = setup();
+        // the fee of a V3 transaction is paid in STRK
+        let fee_token_address = ContractAddress::new(
+            Felt::from_prefixed_hex_str(constants::STRK_ERC20_CONTRACT_ADDRESS).unwrap(),
+        )
+        .unwrap();
+
+        let transaction =
+            BroadcastedDeployAccountTransaction::V3(BroadcastedDeployAccountTransactionV3 {
+                common: BroadcastedTransactionCommonV3 {
+                    version: Felt::from(3),
+                    signature: vec![],
+                    nonce: Felt::from(0),
+                    resource_bounds: ResourceBoundsMapping {
+                        l1_gas: ResourceBounds { max_amount: 4000, max_price_per_unit: 1 },
+                        ..Default::default()
+                    },
+                    tip: Default::default(),
+                    paymaster_data: vec![],
+                    nonce_data_availability_mode: Default::default(),
+                    fee_data_availability_mode: Default::default(),
+                },
+                contract_address_salt: Felt::from(13),
+                constructor_calldata: vec![],
+                class_hash: account_class_hash,
+            });
+        let blockifier_transaction = transaction
+            .create_blockifier_deploy_account(DEVNET_DEFAULT_CHAIN_ID.to_felt())
+            .unwrap();
+
+        let account_address = ContractAddress::from(blockifier_transaction.contract_address);
+        let balance_storage_var_address =
+            get_storage_var_address("ERC20_balances", &[account_address.into()]).unwrap();
+        let balance_storage_key =
+            ContractStorageKey::new(fee_token_address, balance_storage_var_address);
+        starknet.state.change_storage(balance_storage_key, Felt::from(1000000)).unwrap();
+        starknet.state.clear_dirty_state();
+
+        let (txn_hash, address) = starknet.add_deploy_account_transaction(transaction).unwrap();
+        assert_eq!(address, account_address);
+
+        let txn = starknet.transactions.get_by_hash_mut(&txn_hash).unwrap();
+        assert_eq!(txn.get_execution_status(), Some(TransactionExecutionStatus::Succeeded));
+        match &txn.inner {
+            Transaction::DeployAccount(DeployAccountTransaction::Version3(deploy_account)) => {
+                assert_eq!(deploy_account.contract_address, account_address)
+            }
+            other => panic!("Unexpected transaction: {other:?}"),
+        }
+    }
+
     /// Initializes starknet with erc20 contract, 1 declared contract class. Gas price is set to 1
     fn setup() -> (Starknet, ClassHash, ContractAddress) {
         let mut starknet = Starknet::default();
@@ -209,6 +276,8 @@ mod tests {
         );
         let erc_20_contract = predeployed::create_erc20().unwrap();
         erc_20_contract.deploy(&mut starknet.state).unwrap();
+        let strk_erc_20_contract = predeployed::create_strk_erc20().unwrap();
+        strk_erc_20_contract.deploy(&mut starknet.state).unwrap();
 
         let contract_class = Cairo0Json::raw_json_from_path(account_json_path).unwrap();
         let class_hash = contract_class.generate_hash().unwrap();
@@ -216,8 +285,10 @@ mod tests {
         starknet.state.declare_contract_class(class_hash, contract_class.into()).unwrap();
         starknet.state.clear_dirty_state();
         starknet.block_context = Starknet::init_block_context(
+            1,
             1,
             constants::ERC20_CONTRACT_ADDRESS,
+            constants::STRK_ERC20_CONTRACT_ADDRESS,
             DEVNET_DEFAULT_CHAIN_ID,
         );
 
@@ -0,0 +1,146 @@
+use blockifier::execution::entry_point::{
+    ConstructorContext, EntryPointExecutionContext, ExecutionResources,
+};
+use blockifier::execution::execution_utils::execute_deployment;
+use blockifier::state::cached_state::TransactionalState;
+use blockifier::transaction::errors::TransactionExecutionError;
+use blockifier::transaction::objects::{AccountTransactionContext, TransactionExecutionInfo};
+use starknet_types::felt::TransactionHash;
+use starknet_types::rpc::transactions::deploy_transaction::DeployTransaction;
+use starknet_types::rpc::transactions::Transaction;
+
+use super::Starknet;
+use crate::error::DevnetResult;
+
+/// Executes a deploy transaction as a system deployment: it is not sent by an account, so there
+/// is nothing to validate or charge. The class hash is set at the address of the contract and
+/// its constructor is executed. If the constructor fails, the state is left unchanged.
+pub fn add_deploy_transaction(
+    starknet: &mut Starknet,
+    transaction: DeployTransaction,
+) -> DevnetResult<TransactionHash> {
+    let transaction_hash = transaction.transaction_hash;
+    let constructor_context = ConstructorContext {
+        class_hash: starknet_api::core::ClassHash(transaction.class_hash.into()),
+        code_address: None,
+        storage_address: transaction.get_contract_address()?.try_into()?,
+        caller_address: starknet_api::core::ContractAddress::default(),
+    };
+    let constructor_calldata = starknet_api::transaction::Calldata(std::sync::Arc::new(
+        transaction.constructor_calldata.iter().map(|felt| felt.into()).collect(),
+    ));
+
+    let mut context = EntryPointExecutionContext::new(
+        starknet.block_context.clone(),
+        AccountTransactionContext::default(),
+        starknet.block_context.invoke_tx_max_n_steps as usize,
+    );
+    let mut transactional_state =
+        TransactionalState::create_transactional(&mut starknet.state.state);
+    let deployment_result = execute_deployment(
+        &mut transactional_state,
+        &mut ExecutionResources::default(),
+        &mut context,
+        constructor_context,
+        constructor_calldata,
+        blockifier::transaction::transaction_execution::Transaction::initial_gas(),
+    );
+
+    let blockifier_execution_result = match deployment_result {
+        Ok(call_info) => {
+            transactional_state.commit();
+            Ok(TransactionExecutionInfo {
+                execute_call_info: Some(call_info),
+                ..Default::default()
+            })
+        }
+        Err(err) => {
+            transactional_state.abort();
+            Err(TransactionExecutionError::EntryPointExecutionError(err))
+        }
+    };
+
+    starknet
+        .handle_transaction_result(Transaction::Deploy(transaction), blockifier_execution_result)?;
+
+    Ok(transaction_hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use starknet_rs_core::types::{
+        BlockId, BlockTag, TransactionExecutionStatus, TransactionFinalityStatus,
+    };
+    use starknet_types::contract_storage_key::ContractStorageKey;
+    use starknet_types::felt::Felt;
+    use starknet_types::rpc::transaction_receipt::TransactionReceipt;
+    use starknet_types::rpc::transactions::deploy_transaction::DeployTransaction;
+    use starknet_types::rpc::transactions::{Transaction, TransactionType};
+
+    use crate::constants::CAIRO_0_ACCOUNT_CONTRACT_HASH;
+    use crate::error::Error;
+    use crate::starknet::Starknet;
+    use crate::traits::{HashIdentified, StateExtractor};
+    use crate::utils::get_storage_var_address;
+    use crate::utils::test_utils::{dummy_felt, starknet_config_for_test};
+
+    /// Deploy transaction of the predeclared account class, whose constructor stores the public
+    /// key
+    fn deploy_account_class_transaction(class_hash: Felt) -> DeployTransaction {
+        DeployTransaction {
+            transaction_hash: Felt::from(1),
+            version: Felt::from(0),
+            class_hash,
+            contract_address_salt: Felt::from(20),
+            constructor_calldata: vec![Felt::from(42)],
+        }
+    }
+
+    #[test]
+    fn deploy_transaction_sets_class_hash_and_constructor_storage() {
+        let mut starknet = Starknet::new(&starknet_config_for_test()).unwrap();
+        let class_hash = Felt::from_prefixed_hex_str(CAIRO_0_ACCOUNT_CONTRACT_HASH).unwrap();
+        let deploy_transaction = deploy_account_class_transaction(class_hash);
+        let contract_address = deploy_transaction.get_contract_address().unwrap();
+
+        let transaction_hash = starknet.add_deploy_transaction(deploy_transaction).unwrap();
+
+        let transaction = starknet.transactions.get_by_hash(transaction_hash).unwrap();
+        assert_eq!(transaction.finality_status, Some(TransactionFinalityStatus::AcceptedOnL2));
+        assert_eq!(transaction.get_execution_status(), Some(TransactionExecutionStatus::Succeeded));
+        assert!(matches!(transaction.inner, Transaction::Deploy(_)));
+
+        assert_eq!(
+            starknet.get_class_hash_at(BlockId::Tag(BlockTag::Latest), contract_address).unwrap(),
+            class_hash
+        );
+        let public_key_storage_key = ContractStorageKey::new(
+            contract_address,
+            get_storage_var_address("Account_public_key", &[]).unwrap(),
+        );
+        assert_eq!(starknet.state.get_storage(public_key_storage_key).unwrap(), Felt::from(42));
+
+        match starknet.get_transaction_receipt_by_hash(transaction_hash).unwrap() {
+            TransactionReceipt::Deploy(receipt) => {
+                assert_eq!(receipt.contract_address, contract_address);
+                assert_eq!(receipt.common.r#type, TransactionType::Deploy);
+            }
+            other => panic!("Unexpected receipt: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn deploy_transaction_of_undeclared_class_leaves_state_unchanged() {
+        let mut starknet = Starknet::new(&starknet_config_for_test()).unwrap();
+        let deploy_transaction = deploy_account_class_transaction(dummy_felt());
+        let contract_address = deploy_transaction.get_contract_address().unwrap();
+
+        match starknet.add_deploy_transaction(deploy_transaction) {
+            Err(Error::BlockifierTransactionError(_)) => (),
+            other => panic!("Unexpected result: {other:?}"),
+        }
+
+        assert!(starknet.transactions.get_by_hash(Felt::from(1)).is_none());
+        assert!(!starknet.state.is_contract_deployed(&contract_address));
+    }
+}
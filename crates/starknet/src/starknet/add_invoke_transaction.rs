@@ -1,7 +1,8 @@
 use blockifier::transaction::transactions::ExecutableTransaction;
 use starknet_types::felt::TransactionHash;
-use starknet_types::rpc::transactions::broadcasted_invoke_transaction::BroadcastedInvokeTransaction;
-use starknet_types::rpc::transactions::{InvokeTransaction, Transaction};
+use starknet_types::rpc::transactions::{
+    BroadcastedInvokeTransaction, InvokeTransaction, InvokeTransactionV0, Transaction,
+};
 
 use super::Starknet;
 use crate::error::{self, DevnetResult};
@@ -10,25 +11,54 @@ pub fn add_invoke_transaction(
     starknet: &mut Starknet,
     broadcasted_invoke_transaction: BroadcastedInvokeTransaction,
 ) -> DevnetResult<TransactionHash> {
-    if broadcasted_invoke_transaction.common.max_fee.0 == 0 {
+    if broadcasted_invoke_transaction.get_max_fee().0 == 0 {
         return Err(error::Error::FeeError {
             reason: "For invoke transaction, max fee cannot be 0".to_string(),
         });
     }
+    if let BroadcastedInvokeTransaction::V3(transaction) = &broadcasted_invoke_transaction {
+        starknet.check_v3_transaction(&transaction.common)?;
+    }
 
     let blockifier_invoke_transaction = broadcasted_invoke_transaction
         .create_blockifier_invoke_transaction(starknet.chain_id().to_felt())?;
     let transaction_hash = blockifier_invoke_transaction.tx_hash.0.into();
 
-    let invoke_transaction =
-        broadcasted_invoke_transaction.create_invoke_transaction(transaction_hash);
-    let transaction = Transaction::Invoke(InvokeTransaction::Version1(invoke_transaction));
+    let transaction = Transaction::Invoke(
+        broadcasted_invoke_transaction.create_invoke_transaction(transaction_hash),
+    );
+
+    let block_context =
+        starknet.block_context_for_fee_unit(broadcasted_invoke_transaction.get_fee_unit());
+    let blockifier_execution_result =
+        blockifier::transaction::account_transaction::AccountTransaction::Invoke(
+            blockifier_invoke_transaction,
+        )
+        .execute(&mut starknet.state.state, &block_context, true, true);
+
+    starknet.handle_transaction_result(transaction, blockifier_execution_result)?;
+
+    Ok(transaction_hash)
+}
+
+/// Executes an invoke transaction of version 0, which calls the entry point of the contract
+/// directly, instead of through an account. It predates validation and fees, so it is neither
+/// validated nor charged.
+pub fn add_invoke_transaction_v0(
+    starknet: &mut Starknet,
+    invoke_transaction: InvokeTransactionV0,
+) -> DevnetResult<TransactionHash> {
+    let transaction_hash = invoke_transaction.transaction_hash;
+    let blockifier_invoke_transaction =
+        invoke_transaction.create_blockifier_invoke_transaction()?;
+
+    let transaction = Transaction::Invoke(InvokeTransaction::Version0(invoke_transaction));
 
     let blockifier_execution_result =
         blockifier::transaction::account_transaction::AccountTransaction::Invoke(
             blockifier_invoke_transaction,
         )
-        .execute(&mut starknet.state.state, &starknet.block_context, true, true);
+        .execute(&mut starknet.state.state, &starknet.block_context, false, false);
 
     starknet.handle_transaction_result(transaction, blockifier_execution_result)?;
 
@@ -46,7 +76,13 @@ mod tests {
     use starknet_types::contract_class::{Cairo0ContractClass, ContractClass};
     use starknet_types::contract_storage_key::ContractStorageKey;
     use starknet_types::felt::Felt;
-    use starknet_types::rpc::transactions::broadcasted_invoke_transaction::BroadcastedInvokeTransaction;
+    use starknet_types::rpc::transactions::broadcasted_invoke_transaction_v1::BroadcastedInvokeTransactionV1;
+    use starknet_types::rpc::transactions::broadcasted_invoke_transaction_v3::BroadcastedInvokeTransactionV3;
+    use starknet_types::rpc::transactions::{
+        BroadcastedInvokeTransaction, BroadcastedTransactionCommonV3, FinalityStatus,
+        InvokeTransaction, InvokeTransactionV0, ResourceBounds, ResourceBoundsMapping, Tip,
+        Transaction,
+    };
     use starknet_types::traits::HashProducer;
 
     use crate::account::Account;
@@ -73,14 +109,38 @@ mod tests {
             param,                        // calldata
         ];
 
-        BroadcastedInvokeTransaction::new(
+        BroadcastedInvokeTransaction::V1(BroadcastedInvokeTransactionV1::new(
             account_address,
             Fee(5000),
             &vec![],
             Felt::from(nonce),
             &calldata,
             Felt::from(1),
-        )
+        ))
+    }
+
+    fn test_invoke_transaction_v3(
+        account_address: ContractAddress,
+        contract_address: ContractAddress,
+        function_selector: Felt,
+        param: Felt,
+        l1_gas: ResourceBounds,
+    ) -> BroadcastedInvokeTransaction {
+        BroadcastedInvokeTransaction::V3(BroadcastedInvokeTransactionV3 {
+            common: BroadcastedTransactionCommonV3 {
+                version: Felt::from(3),
+                signature: vec![],
+                nonce: Felt::from(0),
+                resource_bounds: ResourceBoundsMapping { l1_gas, ..Default::default() },
+                tip: Default::default(),
+                paymaster_data: vec![],
+                nonce_data_availability_mode: Default::default(),
+                fee_data_availability_mode: Default::default(),
+            },
+            sender_address: account_address,
+            calldata: vec![Felt::from(contract_address), function_selector, Felt::from(1), param],
+            account_deployment_data: vec![],
+        })
     }
 
     #[test]
@@ -101,7 +161,7 @@ mod tests {
         let transaction = starknet.transactions.get_by_hash_mut(&transaction_hash).unwrap();
 
         assert_eq!(transaction.finality_status, Some(TransactionFinalityStatus::AcceptedOnL2));
-        assert_eq!(transaction.execution_result.status(), TransactionExecutionStatus::Succeeded);
+        assert_eq!(transaction.get_execution_status(), Some(TransactionExecutionStatus::Succeeded));
     }
 
     #[test]
@@ -126,7 +186,7 @@ mod tests {
         let transaction_hash = starknet.add_invoke_transaction(invoke_transaction).unwrap();
         let transaction = starknet.transactions.get_by_hash_mut(&transaction_hash).unwrap();
         assert_eq!(transaction.finality_status, Some(TransactionFinalityStatus::AcceptedOnL2));
-        assert_eq!(transaction.execution_result.status(), TransactionExecutionStatus::Succeeded);
+        assert_eq!(transaction.get_execution_status(), Some(TransactionExecutionStatus::Succeeded));
 
         // check storage
         assert_eq!(
@@ -146,7 +206,7 @@ mod tests {
         let transaction_hash = starknet.add_invoke_transaction(invoke_transaction).unwrap();
         let transaction = starknet.transactions.get_by_hash_mut(&transaction_hash).unwrap();
 
-        assert_eq!(transaction.execution_result.status(), TransactionExecutionStatus::Succeeded);
+        assert_eq!(transaction.get_execution_status(), Some(TransactionExecutionStatus::Succeeded));
         assert_eq!(transaction.finality_status, Some(TransactionFinalityStatus::AcceptedOnL2));
         assert_eq!(
             starknet.state.get_storage(balance_var_storage_address).unwrap(),
@@ -155,16 +215,142 @@ mod tests {
     }
 
     #[test]
-    fn invoke_transaction_with_max_fee_zero_should_return_error() {
-        let invoke_transaction = BroadcastedInvokeTransaction::new(
-            dummy_contract_address(),
-            Fee(0),
-            &vec![],
-            dummy_felt(),
-            &vec![],
-            Felt::from(1),
+    fn invoke_transaction_v3_successfully_changes_storage() {
+        let (
+            mut starknet,
+            account_address,
+            contract_address,
+            increase_balance_selector,
+            balance_var_storage_address,
+        ) = setup();
+
+        let invoke_transaction = test_invoke_transaction_v3(
+            account_address,
+            contract_address,
+            increase_balance_selector,
+            Felt::from(10),
+            ResourceBounds { max_amount: 5000, max_price_per_unit: 1 },
         );
 
+        let account_balance_var_address =
+            get_storage_var_address("ERC20_balances", &[account_address.into()]).unwrap();
+        let [eth_balance_storage_key, strk_balance_storage_key] =
+            [constants::ERC20_CONTRACT_ADDRESS, constants::STRK_ERC20_CONTRACT_ADDRESS].map(
+                |fee_token_address| {
+                    ContractStorageKey::new(
+                        ContractAddress::new(
+                            Felt::from_prefixed_hex_str(fee_token_address).unwrap(),
+                        )
+                        .unwrap(),
+                        account_balance_var_address,
+                    )
+                },
+            );
+
+        let transaction_hash = starknet.add_invoke_transaction(invoke_transaction).unwrap();
+        let transaction = starknet.transactions.get_by_hash_mut(&transaction_hash).unwrap();
+        assert_eq!(transaction.finality_status, Some(TransactionFinalityStatus::AcceptedOnL2));
+        assert_eq!(transaction.get_execution_status(), Some(TransactionExecutionStatus::Succeeded));
+        assert!(matches!(transaction.inner, Transaction::Invoke(InvokeTransaction::Version3(_))));
+
+        assert_eq!(
+            starknet.state.get_storage(balance_var_storage_address).unwrap(),
+            Felt::from(10)
+        );
+
+        // the fee is paid in STRK
+        assert_eq!(starknet.state.get_storage(eth_balance_storage_key).unwrap(), Felt::from(10000));
+        assert!(starknet.state.get_storage(strk_balance_storage_key).unwrap() < Felt::from(10000));
+    }
+
+    #[test]
+    fn invoke_transaction_v3_without_l1_gas_should_return_error() {
+        let (mut starknet, account_address, contract_address, increase_balance_selector, _) =
+            setup();
+
+        let invoke_transaction = test_invoke_transaction_v3(
+            account_address,
+            contract_address,
+            increase_balance_selector,
+            Felt::from(10),
+            ResourceBounds { max_amount: 0, max_price_per_unit: 1 },
+        );
+
+        match starknet.add_invoke_transaction(invoke_transaction) {
+            Err(crate::error::Error::FeeError { .. }) => (),
+            other => panic!("Unexpected result: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn invoke_transaction_v3_with_price_per_unit_below_gas_price_should_return_error() {
+        let (mut starknet, account_address, contract_address, increase_balance_selector, _) =
+            setup();
+        starknet.block_context.gas_prices.strk_l1_gas_price = 2;
+
+        let invoke_transaction = test_invoke_transaction_v3(
+            account_address,
+            contract_address,
+            increase_balance_selector,
+            Felt::from(10),
+            ResourceBounds { max_amount: 5000, max_price_per_unit: 1 },
+        );
+
+        match starknet.add_invoke_transaction(invoke_transaction) {
+            Err(crate::error::Error::FeeError { reason }) => assert_eq!(
+                reason,
+                "Max price per unit of L1 gas (1) is lower than the gas price (2)"
+            ),
+            other => panic!("Unexpected result: {other:?}"),
+        }
+        assert_eq!(starknet.transactions.iter().count(), 0);
+    }
+
+    #[test]
+    fn invoke_transaction_v3_with_tip_or_paymaster_data_should_return_error() {
+        let (mut starknet, account_address, contract_address, increase_balance_selector, _) =
+            setup();
+
+        let invoke_transaction = test_invoke_transaction_v3(
+            account_address,
+            contract_address,
+            increase_balance_selector,
+            Felt::from(10),
+            ResourceBounds { max_amount: 5000, max_price_per_unit: 1 },
+        );
+
+        let mut with_tip = invoke_transaction.clone();
+        let mut with_paymaster_data = invoke_transaction;
+        if let (
+            BroadcastedInvokeTransaction::V3(with_tip),
+            BroadcastedInvokeTransaction::V3(with_paymaster_data),
+        ) = (&mut with_tip, &mut with_paymaster_data)
+        {
+            with_tip.common.tip = Tip(1);
+            with_paymaster_data.common.paymaster_data = vec![Felt::from(1)];
+        }
+
+        for invoke_transaction in [with_tip, with_paymaster_data] {
+            match starknet.add_invoke_transaction(invoke_transaction) {
+                Err(crate::error::Error::UnsupportedAction { .. }) => (),
+                other => panic!("Unexpected result: {other:?}"),
+            }
+        }
+        assert_eq!(starknet.transactions.iter().count(), 0);
+    }
+
+    #[test]
+    fn invoke_transaction_with_max_fee_zero_should_return_error() {
+        let invoke_transaction =
+            BroadcastedInvokeTransaction::V1(BroadcastedInvokeTransactionV1::new(
+                dummy_contract_address(),
+                Fee(0),
+                &vec![],
+                dummy_felt(),
+                &vec![],
+                Felt::from(1),
+            ));
+
         let result = Starknet::default().add_invoke_transaction(invoke_transaction);
 
         assert!(result.is_err());
@@ -193,7 +379,7 @@ mod tests {
         let transaction_hash = starknet.add_invoke_transaction(invoke_transaction.clone()).unwrap();
         let transaction = starknet.transactions.get_by_hash_mut(&transaction_hash).unwrap();
         assert_eq!(transaction.finality_status, Some(TransactionFinalityStatus::AcceptedOnL2));
-        assert_eq!(transaction.execution_result.status(), TransactionExecutionStatus::Succeeded);
+        assert_eq!(transaction.get_execution_status(), Some(TransactionExecutionStatus::Succeeded));
 
         match starknet.add_invoke_transaction(invoke_transaction).unwrap_err() {
             crate::error::Error::TransactionValidationError(
@@ -203,6 +389,10 @@ mod tests {
                 panic!("Wrong error type: {:?}", err);
             }
         }
+
+        // the accepted transaction is not replaced by its rejected resubmission
+        let status = starknet.get_transaction_status(transaction_hash).unwrap();
+        assert_eq!(status.finality_status, FinalityStatus::AcceptedOnL2);
     }
 
     #[test]
@@ -221,24 +411,48 @@ mod tests {
         ];
 
         let insufficient_max_fee = 2482; // this is minimum fee (enough for passing validation), anything lower than that is bounced back
-        let invoke_transaction = BroadcastedInvokeTransaction::new(
-            account_address,
-            Fee(insufficient_max_fee),
-            &vec![],
-            initial_nonce,
-            &calldata,
-            Felt::from(1),
-        );
+        let invoke_transaction =
+            BroadcastedInvokeTransaction::V1(BroadcastedInvokeTransactionV1::new(
+                account_address,
+                Fee(insufficient_max_fee),
+                &vec![],
+                initial_nonce,
+                &calldata,
+                Felt::from(1),
+            ));
 
         let transaction_hash = starknet.add_invoke_transaction(invoke_transaction).unwrap();
         let transaction = starknet.transactions.get_by_hash_mut(&transaction_hash).unwrap();
         assert_eq!(transaction.finality_status, Some(TransactionFinalityStatus::AcceptedOnL2));
-        assert_eq!(transaction.execution_result.status(), TransactionExecutionStatus::Reverted);
+        assert_eq!(transaction.get_execution_status(), Some(TransactionExecutionStatus::Reverted));
 
         let nonce_after_reverted = starknet.state.get_nonce(&account_address).unwrap();
         assert_eq!(nonce_after_reverted, Felt::from(1));
     }
 
+    #[test]
+    fn invoke_transaction_v0_calls_contract_directly_and_changes_storage() {
+        let (mut starknet, _, contract_address, increase_balance_selector, balance_storage_key) =
+            setup();
+
+        let invoke_transaction = InvokeTransactionV0 {
+            transaction_hash: Felt::from(1),
+            version: Felt::from(0),
+            contract_address,
+            entry_point_selector: increase_balance_selector,
+            calldata: vec![Felt::from(10)],
+            ..Default::default()
+        };
+
+        let transaction_hash = starknet.add_invoke_transaction_v0(invoke_transaction).unwrap();
+        let transaction = starknet.transactions.get_by_hash_mut(&transaction_hash).unwrap();
+        assert_eq!(transaction.finality_status, Some(TransactionFinalityStatus::AcceptedOnL2));
+        assert_eq!(transaction.get_execution_status(), Some(TransactionExecutionStatus::Succeeded));
+        assert!(matches!(transaction.inner, Transaction::Invoke(InvokeTransaction::Version0(_))));
+
+        assert_eq!(starknet.state.get_storage(balance_storage_key).unwrap(), Felt::from(10));
+    }
+
     /// Initialize starknet object with: erc20 contract, account contract and  simple contract that
     /// has a function increase_balance
     fn setup() -> (Starknet, ContractAddress, ContractAddress, Felt, ContractStorageKey) {
@@ -247,6 +461,8 @@ mod tests {
         // deploy erc20 contract
         let erc_20_contract = predeployed::create_erc20().unwrap();
         erc_20_contract.deploy(&mut starknet.state).unwrap();
+        let strk_erc_20_contract = predeployed::create_strk_erc20().unwrap();
+        strk_erc_20_contract.deploy(&mut starknet.state).unwrap();
 
         // deploy account contract
         let account_without_validations_contract_class = cairo_0_account_without_validations();
@@ -260,6 +476,7 @@ mod tests {
             account_without_validations_class_hash,
             ContractClass::Cairo0(account_without_validations_contract_class),
             erc_20_contract.get_address(),
+            strk_erc_20_contract.get_address(),
         )
         .unwrap();
 
@@ -301,8 +518,10 @@ mod tests {
 
         starknet.state.clear_dirty_state();
         starknet.block_context = Starknet::init_block_context(
+            1,
             1,
             constants::ERC20_CONTRACT_ADDRESS,
+            constants::STRK_ERC20_CONTRACT_ADDRESS,
             DEVNET_DEFAULT_CHAIN_ID,
         );
 
@@ -0,0 +1,134 @@
+use blockifier::transaction::transactions::ExecutableTransaction;
+use starknet_types::felt::TransactionHash;
+use starknet_types::rpc::transactions::{L1HandlerTransaction, Transaction};
+
+use super::Starknet;
+use crate::error::DevnetResult;
+
+pub fn add_l1_handler_transaction(
+    starknet: &mut Starknet,
+    transaction: L1HandlerTransaction,
+) -> DevnetResult<TransactionHash> {
+    let transaction_hash = transaction.transaction_hash;
+    let blockifier_l1_handler_transaction = transaction.create_blockifier_transaction()?;
+
+    // fee is paid on L1, so it is not charged on L2
+    let blockifier_execution_result =
+        blockifier::transaction::transaction_execution::Transaction::L1HandlerTransaction(
+            blockifier_l1_handler_transaction,
+        )
+        .execute(&mut starknet.state.state, &starknet.block_context, false, true);
+
+    starknet.handle_transaction_result(
+        Transaction::L1Handler(transaction),
+        blockifier_execution_result,
+    )?;
+
+    Ok(transaction_hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use starknet_api::transaction::Fee;
+    use starknet_rs_core::types::{TransactionExecutionStatus, TransactionFinalityStatus};
+    use starknet_rs_core::utils::get_selector_from_name;
+    use starknet_types::contract_address::ContractAddress;
+    use starknet_types::contract_class::Cairo0ContractClass;
+    use starknet_types::contract_storage_key::ContractStorageKey;
+    use starknet_types::felt::Felt;
+    use starknet_types::rpc::transaction_receipt::TransactionReceipt;
+    use starknet_types::rpc::transactions::{L1HandlerTransaction, TransactionType};
+    use starknet_types::traits::HashProducer;
+
+    use crate::constants::{self, DEVNET_DEFAULT_CHAIN_ID};
+    use crate::starknet::Starknet;
+    use crate::traits::{HashIdentifiedMut, StateChanger, StateExtractor};
+    use crate::utils::exported_test_utils::dummy_cairo_l1l2_contract;
+    use crate::utils::get_storage_var_address;
+
+    /// The only L1 address the deposit handler of the L1-L2 test contract accepts messages from
+    const WHITELISTED_L1_ADDRESS: &str = "0x8359E4B0152ed5A731162D3c7B0D8D56edB165A0";
+
+    fn deposit_transaction(
+        contract_address: ContractAddress,
+        user: Felt,
+        amount: Felt,
+        nonce: u128,
+    ) -> L1HandlerTransaction {
+        L1HandlerTransaction::new(
+            contract_address,
+            get_selector_from_name("deposit").unwrap().into(),
+            vec![Felt::from_prefixed_hex_str(WHITELISTED_L1_ADDRESS).unwrap(), user, amount],
+            Felt::from(nonce),
+            Fee(1),
+            DEVNET_DEFAULT_CHAIN_ID.to_felt(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn l1_handler_transaction_successfully_changes_storage() {
+        let (mut starknet, contract_address) = setup();
+        let user = Felt::from(1);
+        let balance_storage_key = ContractStorageKey::new(
+            contract_address,
+            get_storage_var_address("balance", &[user]).unwrap(),
+        );
+
+        let transaction = deposit_transaction(contract_address, user, Felt::from(10), 0);
+        let transaction_hash = starknet.add_l1_handler_transaction(transaction.clone()).unwrap();
+        assert_eq!(transaction_hash, transaction.transaction_hash);
+
+        let transaction = starknet.transactions.get_by_hash_mut(&transaction_hash).unwrap();
+        assert_eq!(transaction.finality_status, Some(TransactionFinalityStatus::AcceptedOnL2));
+        assert_eq!(transaction.get_execution_status(), Some(TransactionExecutionStatus::Succeeded));
+        match transaction.get_receipt().unwrap() {
+            TransactionReceipt::Common(receipt) => {
+                assert_eq!(receipt.r#type, TransactionType::L1Handler)
+            }
+            receipt => panic!("Unexpected receipt: {receipt:?}"),
+        }
+
+        assert_eq!(starknet.state.get_storage(balance_storage_key).unwrap(), Felt::from(10));
+
+        let transaction = deposit_transaction(contract_address, user, Felt::from(15), 1);
+        starknet.add_l1_handler_transaction(transaction).unwrap();
+        assert_eq!(starknet.state.get_storage(balance_storage_key).unwrap(), Felt::from(25));
+    }
+
+    #[test]
+    fn l1_handler_transaction_of_nonexistent_contract_is_not_accepted() {
+        let (mut starknet, _) = setup();
+        let nonexistent_address = ContractAddress::new(Felt::from(42)).unwrap();
+
+        let transaction = deposit_transaction(nonexistent_address, Felt::from(1), Felt::from(1), 0);
+        let transaction_hash = transaction.transaction_hash;
+
+        assert!(starknet.add_l1_handler_transaction(transaction).is_err());
+        assert!(starknet.transactions.get_by_hash_mut(&transaction_hash).is_none());
+    }
+
+    /// Initialize starknet object with the L1-L2 test contract deployed
+    fn setup() -> (Starknet, ContractAddress) {
+        let mut starknet = Starknet::default();
+
+        let contract_class: Cairo0ContractClass = dummy_cairo_l1l2_contract().into();
+        let class_hash = contract_class.generate_hash().unwrap();
+        let contract_address = ContractAddress::new(Felt::from(5)).unwrap();
+
+        starknet.state.declare_contract_class(class_hash, contract_class.into()).unwrap();
+        starknet.state.deploy_contract(contract_address, class_hash).unwrap();
+
+        starknet.state.clear_dirty_state();
+        starknet.block_context = Starknet::init_block_context(
+            1,
+            1,
+            constants::ERC20_CONTRACT_ADDRESS,
+            constants::STRK_ERC20_CONTRACT_ADDRESS,
+            DEVNET_DEFAULT_CHAIN_ID,
+        );
+        starknet.restart_pending_block().unwrap();
+
+        (starknet, contract_address)
+    }
+}
@@ -1,186 +1,370 @@
-use std::fs::{self, File, OpenOptions};
-use std::io::{Read, Seek, SeekFrom, Write};
+use std::collections::HashMap;
+use std::fs::{self, File};
 use std::path::Path;
+use std::sync::Arc;
 
-use starknet_types::rpc::transactions::broadcasted_declare_transaction_v1::BroadcastedDeclareTransactionV1;
-use starknet_types::rpc::transactions::broadcasted_declare_transaction_v2::BroadcastedDeclareTransactionV2;
-use starknet_types::rpc::transactions::broadcasted_deploy_account_transaction::BroadcastedDeployAccountTransaction;
-use starknet_types::rpc::transactions::broadcasted_invoke_transaction::BroadcastedInvokeTransaction;
-use starknet_types::rpc::transactions::{DeclareTransaction, InvokeTransaction, Transaction};
+use primitive_types::H256;
+use serde::{Deserialize, Serialize};
+use starknet_in_rust::SierraContractClass;
+use starknet_types::contract_address::ContractAddress;
+use starknet_types::contract_class::{
+    Cairo0ContractClass, Cairo0Json, ContractClass, DeprecatedContractClass,
+};
+use starknet_types::contract_storage_key::ContractStorageKey;
+use starknet_types::felt::{ClassHash, CompiledClassHash, Felt, TransactionHash};
+use starknet_types::patricia_key::StorageKey;
+use starknet_types::rpc::transactions::{
+    DeclareTransactionTrace, DeployAccountTransactionTrace, InvokeTransactionTrace,
+    L1HandlerTransactionTrace, TransactionTrace,
+};
 
-use super::{DumpMode, Starknet};
+use super::{Starknet, StarknetConfig};
+use crate::blocks::StarknetBlock;
 use crate::error::{DevnetResult, Error};
+use crate::state::origin::OriginReader;
+use crate::state::state_diff::StateDiff;
+use crate::state::DevnetState;
+use crate::traits::{HashIdentifiedMut, StateChanger};
+use crate::transactions::StarknetTransactions;
 
-impl Starknet {
-    pub fn re_execute(&mut self, transactions: Vec<Transaction>) -> DevnetResult<()> {
-        for transaction in transactions.iter() {
-            match transaction {
-                Transaction::Declare(DeclareTransaction::Version0(_)) => {
-                    return Err(Error::SerializationNotSupported);
-                }
-                Transaction::Declare(DeclareTransaction::Version1(tx)) => {
-                    let declare_tx = BroadcastedDeclareTransactionV1::new(
-                        tx.sender_address,
-                        tx.max_fee,
-                        &tx.signature,
-                        tx.nonce,
-                        &tx.contract_class,
-                        tx.version,
-                    );
-                    self.add_declare_transaction_v1(declare_tx)?;
-                }
-                Transaction::Declare(DeclareTransaction::Version2(tx)) => {
-                    let declare_tx = BroadcastedDeclareTransactionV2::new(
-                        &tx.contract_class,
-                        tx.compiled_class_hash,
-                        tx.sender_address,
-                        tx.max_fee,
-                        &tx.signature,
-                        tx.nonce,
-                        tx.version,
-                    );
-                    self.add_declare_transaction_v2(declare_tx)?;
-                }
-                Transaction::DeployAccount(tx) => {
-                    let deploy_account_tx = BroadcastedDeployAccountTransaction::new(
-                        &tx.constructor_calldata,
-                        tx.max_fee,
-                        &tx.signature,
-                        tx.nonce,
-                        tx.class_hash,
-                        tx.contract_address_salt,
-                        tx.version,
-                    );
-                    self.add_deploy_account_transaction(deploy_account_tx)?;
-                }
-                Transaction::Deploy(_) => return Err(Error::SerializationNotSupported),
-                Transaction::Invoke(InvokeTransaction::Version0(_)) => {
-                    return Err(Error::SerializationNotSupported);
-                }
-                Transaction::Invoke(InvokeTransaction::Version1(tx)) => {
-                    let invoke_tx = BroadcastedInvokeTransaction::new(
-                        tx.sender_address,
-                        tx.max_fee,
-                        &tx.signature,
-                        tx.nonce,
-                        &tx.calldata,
-                        tx.version,
-                    );
-                    self.add_invoke_transaction(invoke_tx)?;
-                }
-                Transaction::L1Handler(_) => return Err(Error::SerializationNotSupported),
-            };
+/// Contract class in a form which can be deserialized back into the same variant
+#[derive(Serialize, Deserialize)]
+enum DumpedContractClass {
+    Cairo0Json(Cairo0Json),
+    Cairo0Rpc(DeprecatedContractClass),
+    Cairo1(SierraContractClass),
+}
+
+impl TryFrom<&ContractClass> for DumpedContractClass {
+    type Error = Error;
+
+    fn try_from(value: &ContractClass) -> DevnetResult<Self> {
+        match value {
+            ContractClass::Cairo0(Cairo0ContractClass::RawJson(class)) => {
+                Ok(Self::Cairo0Json(class.clone()))
+            }
+            ContractClass::Cairo0(Cairo0ContractClass::Rpc(class)) => {
+                Ok(Self::Cairo0Rpc(class.clone()))
+            }
+            ContractClass::Cairo0(Cairo0ContractClass::SIR(_)) => {
+                Err(Error::SerializationNotSupported)
+            }
+            ContractClass::Cairo1(class) => Ok(Self::Cairo1(class.clone())),
         }
+    }
+}
 
-        Ok(())
+impl From<DumpedContractClass> for ContractClass {
+    fn from(value: DumpedContractClass) -> Self {
+        match value {
+            DumpedContractClass::Cairo0Json(class) => class.into(),
+            DumpedContractClass::Cairo0Rpc(class) => class.into(),
+            DumpedContractClass::Cairo1(class) => class.into(),
+        }
     }
+}
 
-    /// attach starknet transaction to end of existing file
-    pub fn dump_transaction(&self, transaction: &Transaction) -> DevnetResult<()> {
-        match &self.config.dump_path {
-            Some(path) => {
-                let file_path = Path::new(path);
-                if file_path.exists() {
-                    // attach to file
-                    let transaction_dump = serde_json::to_string(transaction).map_err(|_| {
-                        Error::SerializationError { obj_name: "Vec<Transaction>".to_string() }
-                    })?;
-                    let mut file = OpenOptions::new()
-                        .append(true)
-                        .read(true)
-                        .open(file_path)
-                        .map_err(Error::IoError)?;
-                    let mut buffer = [0; 1];
-                    file.seek(SeekFrom::End(-1))?;
-                    file.read_exact(&mut buffer)?;
-                    if String::from_utf8_lossy(&buffer).into_owned() == "]" {
-                        // if the last character is "]", remove it and add transaction at the end
-                        let length = file.seek(SeekFrom::End(0)).map_err(Error::IoError)?;
-                        file.set_len(length - 1).map_err(Error::IoError)?; // remove last "]" with set_len
-                        file.write_all(format!(", {transaction_dump}]").as_bytes())
-                            .map_err(Error::IoError)?;
-                    } else {
-                        // if the last character is not "]" it means that it's a wrongly formatted
-                        // file
-                        return Err(Error::FormatError);
-                    }
-                } else {
-                    // create file
-                    let transactions = vec![transaction];
-                    let transactions_dump = serde_json::to_string(&transactions).map_err(|_| {
-                        Error::SerializationError { obj_name: "Vec<Transaction>".to_string() }
-                    })?;
-                    fs::write(Path::new(&path), transactions_dump)?;
-                }
+fn dump_contract_classes(
+    contract_classes: &im::HashMap<ClassHash, ContractClass>,
+) -> DevnetResult<HashMap<ClassHash, DumpedContractClass>> {
+    contract_classes
+        .iter()
+        .map(|(class_hash, contract_class)| Ok((*class_hash, contract_class.try_into()?)))
+        .collect()
+}
 
-                Ok(())
-            }
-            None => Err(Error::FormatError),
+fn load_contract_classes(
+    contract_classes: HashMap<ClassHash, DumpedContractClass>,
+) -> im::HashMap<ClassHash, ContractClass> {
+    contract_classes
+        .into_iter()
+        .map(|(class_hash, contract_class)| (class_hash, contract_class.into()))
+        .collect()
+}
+
+/// Transaction trace in a form which can be deserialized back into the same variant
+#[derive(Serialize, Deserialize)]
+enum DumpedTransactionTrace {
+    Invoke(InvokeTransactionTrace),
+    Declare(DeclareTransactionTrace),
+    DeployAccount(DeployAccountTransactionTrace),
+    L1Handler(L1HandlerTransactionTrace),
+}
+
+impl From<TransactionTrace> for DumpedTransactionTrace {
+    fn from(value: TransactionTrace) -> Self {
+        match value {
+            TransactionTrace::Invoke(trace) => Self::Invoke(trace),
+            TransactionTrace::Declare(trace) => Self::Declare(trace),
+            TransactionTrace::DeployAccount(trace) => Self::DeployAccount(trace),
+            TransactionTrace::L1Handler(trace) => Self::L1Handler(trace),
         }
     }
+}
 
-    pub fn dump_transactions(&self) -> DevnetResult<()> {
-        self.dump_transactions_custom_path(None)
+impl From<DumpedTransactionTrace> for TransactionTrace {
+    fn from(value: DumpedTransactionTrace) -> Self {
+        match value {
+            DumpedTransactionTrace::Invoke(trace) => Self::Invoke(trace),
+            DumpedTransactionTrace::Declare(trace) => Self::Declare(trace),
+            DumpedTransactionTrace::DeployAccount(trace) => Self::DeployAccount(trace),
+            DumpedTransactionTrace::L1Handler(trace) => Self::L1Handler(trace),
+        }
     }
+}
 
-    /// save starknet transactions to file
-    pub fn dump_transactions_custom_path(&self, custom_path: Option<String>) -> DevnetResult<()> {
+/// The configuration which determines the genesis state and the chain. The dumped chain is
+/// restored on top of the genesis state created with the same predeployed accounts. The origin of
+/// a fork cannot be recreated, and the hashes and fees of the dumped transactions depend on the
+/// chain ID and gas prices, so the loading instance has to be configured with the same ones.
+#[derive(Serialize, Deserialize)]
+struct DumpedGenesisConfig {
+    seed: u32,
+    total_accounts: u8,
+    predeployed_accounts_initial_balance: Felt,
+    fork_url: Option<String>,
+    fork_block: Option<u64>,
+    chain_id: Felt,
+    gas_price: u64,
+    strk_gas_price: u64,
+}
+
+impl DumpedGenesisConfig {
+    fn new(config: &StarknetConfig) -> Self {
+        Self {
+            seed: config.seed,
+            total_accounts: config.total_accounts,
+            predeployed_accounts_initial_balance: config.predeployed_accounts_initial_balance,
+            fork_url: config.fork_config.url.as_ref().map(|url| url.to_string()),
+            fork_block: config.fork_config.block,
+            chain_id: config.chain_id.to_felt(),
+            gas_price: config.gas_price,
+            strk_gas_price: config.strk_gas_price,
+        }
+    }
+
+    /// Checks that the chain can be restored by an instance with the configuration `config`
+    fn check_compatibility(&self, config: &StarknetConfig) -> DevnetResult<()> {
+        let fork_url = config.fork_config.url.as_ref().map(|url| url.to_string());
+        let msg = if self.fork_url != fork_url || self.fork_block != config.fork_config.block {
+            "it was created with a different forking configuration"
+        } else if self.chain_id != config.chain_id.to_felt() {
+            "it was created with a different chain ID"
+        } else if self.gas_price != config.gas_price || self.strk_gas_price != config.strk_gas_price
+        {
+            "it was created with different gas prices"
+        } else {
+            return Ok(());
+        };
+
+        Err(Error::IncompatibleDump { msg: msg.into() })
+    }
+}
+
+/// The persistent part of [`DevnetState`]. The origin of a fork is not dumped, it is taken from
+/// the configuration of the loading instance.
+#[derive(Serialize, Deserialize)]
+struct DumpedDevnetState {
+    address_to_class_hash: HashMap<ContractAddress, ClassHash>,
+    address_to_nonce: HashMap<ContractAddress, Felt>,
+    address_to_storage: HashMap<ContractAddress, HashMap<StorageKey, Felt>>,
+    class_hash_to_compiled_class: HashMap<ClassHash, DumpedContractClass>,
+    class_hash_to_compiled_class_hash: HashMap<ClassHash, CompiledClassHash>,
+}
+
+impl DumpedDevnetState {
+    fn new(state: &DevnetState) -> DevnetResult<Self> {
+        let mut address_to_storage: HashMap<ContractAddress, HashMap<StorageKey, Felt>> =
+            HashMap::new();
+        for (storage_key, value) in state.address_to_storage.iter() {
+            address_to_storage
+                .entry(*storage_key.get_contract_address())
+                .or_default()
+                .insert(*storage_key.get_storage_key(), *value);
+        }
+
+        Ok(Self {
+            address_to_class_hash: state.address_to_class_hash.clone().into_iter().collect(),
+            address_to_nonce: state.address_to_nonce.clone().into_iter().collect(),
+            address_to_storage,
+            class_hash_to_compiled_class: dump_contract_classes(
+                &state.class_hash_to_compiled_class,
+            )?,
+            class_hash_to_compiled_class_hash: state
+                .class_hash_to_compiled_class_hash
+                .clone()
+                .into_iter()
+                .collect(),
+        })
+    }
+
+    fn into_devnet_state(self, origin: Option<OriginReader>) -> DevnetState {
+        let address_to_storage = self
+            .address_to_storage
+            .into_iter()
+            .flat_map(|(address, storage)| {
+                storage.into_iter().map(move |(storage_key, value)| {
+                    (ContractStorageKey::new(address, storage_key), value)
+                })
+            })
+            .collect();
+
+        DevnetState {
+            address_to_class_hash: self.address_to_class_hash.into_iter().collect(),
+            address_to_nonce: self.address_to_nonce.into_iter().collect(),
+            address_to_storage,
+            class_hash_to_compiled_class: load_contract_classes(self.class_hash_to_compiled_class),
+            class_hash_to_compiled_class_hash: self
+                .class_hash_to_compiled_class_hash
+                .into_iter()
+                .collect(),
+            origin,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct DumpedBlock {
+    block: StarknetBlock,
+    state_diff: StateDiff,
+}
+
+/// Snapshot of the chain. Transactions are generic, so that they can be dumped by reference.
+#[derive(Serialize, Deserialize)]
+struct StarknetDump<T> {
+    genesis: DumpedGenesisConfig,
+    state: DumpedDevnetState,
+    contract_classes: HashMap<ClassHash, DumpedContractClass>,
+    /// Sealed blocks in ascending order
+    blocks: Vec<DumpedBlock>,
+    pending_block: StarknetBlock,
+    pending_state_diff: StateDiff,
+    transactions: T,
+    /// Traces of the executed transactions, since their execution infos are not dumped
+    traces: HashMap<TransactionHash, DumpedTransactionTrace>,
+    /// Number of not yet consumed messages sent to L1, by message hash
+    l2_to_l1_messages: HashMap<H256, u64>,
+    timestamp_shift_in_secs: i64,
+}
+
+impl Starknet {
+    pub fn dump_state(&self) -> DevnetResult<()> {
+        self.dump_state_custom_path(None)
+    }
+
+    /// save the state of starknet, with all blocks and transactions, to file
+    pub fn dump_state_custom_path(&self, custom_path: Option<String>) -> DevnetResult<()> {
         let dump_path = if custom_path.is_some() { &custom_path } else { &self.config.dump_path };
         match dump_path {
             Some(path) => {
-                let transactions = &self
-                    .transactions
-                    .iter()
-                    .map(|x| x.1.inner.clone())
-                    .collect::<Vec<Transaction>>();
-
-                // dump only if there are transactions to dump
-                if !transactions.is_empty() {
-                    let transactions_dump = serde_json::to_string(transactions).map_err(|_| {
-                        Error::SerializationError { obj_name: "Vec<Transaction>".to_string() }
-                    })?;
-                    fs::write(Path::new(&path), transactions_dump)?;
-
-                    return Ok(());
+                // dump only if there are accepted transactions
+                if self.transactions.iter().all(|(_, tx)| tx.get_rejection_reason().is_some()) {
+                    return Err(Error::NoTransaction);
                 }
 
-                Err(Error::NoTransaction)
+                let dump = self.create_dump()?;
+                let serialized_dump = serde_json::to_string(&dump).map_err(|_| {
+                    Error::SerializationError { obj_name: "StarknetDump".to_string() }
+                })?;
+                fs::write(Path::new(&path), serialized_dump)?;
+
+                Ok(())
             }
             None => Err(Error::FormatError),
         }
     }
 
-    pub fn load_transactions(&self) -> DevnetResult<Vec<Transaction>> {
-        self.load_transactions_custom_path(None)
+    fn create_dump(&self) -> DevnetResult<StarknetDump<&StarknetTransactions>> {
+        let mut block_numbers: Vec<_> = self.blocks.num_to_block.keys().copied().collect();
+        block_numbers.sort();
+        let blocks = block_numbers
+            .into_iter()
+            .map(|block_number| {
+                let block = self.blocks.num_to_block.get(&block_number);
+                let state_diff = self.blocks.num_to_state_diff.get(&block_number);
+                match (block, state_diff) {
+                    (Some(block), Some(state_diff)) => {
+                        Ok(DumpedBlock { block: block.clone(), state_diff: state_diff.clone() })
+                    }
+                    _ => Err(Error::NoBlock),
+                }
+            })
+            .collect::<DevnetResult<Vec<_>>>()?;
+
+        // the traces were stored when the transactions were executed
+        let traces = self
+            .transactions
+            .iter()
+            .filter_map(|(transaction_hash, transaction)| {
+                let trace = transaction.trace.as_deref()?;
+                Some((*transaction_hash, trace.clone().into()))
+            })
+            .collect();
+
+        Ok(StarknetDump {
+            genesis: DumpedGenesisConfig::new(&self.config),
+            state: DumpedDevnetState::new(&self.state.state.state)?,
+            contract_classes: dump_contract_classes(&self.state.contract_classes)?,
+            blocks,
+            pending_block: self.blocks.pending_block.clone(),
+            pending_state_diff: self.pending_state_diff.clone(),
+            transactions: &self.transactions,
+            traces,
+            l2_to_l1_messages: self.messaging.l2_to_l1_messages.clone(),
+            timestamp_shift_in_secs: self.timestamp_shift_in_secs,
+        })
+    }
+
+    /// Replaces the state of starknet with the one from the file. Blocks, transactions and
+    /// snapshots created so far are discarded.
+    pub fn load_state_custom_path(&mut self, custom_path: Option<String>) -> DevnetResult<()> {
+        let dump = self.read_dump(custom_path)?;
+        self.load_dump(dump)
+    }
+
+    /// Loads the state from the configured dump path into a newly created instance
+    pub(crate) fn load_state_on_startup(&mut self) -> DevnetResult<()> {
+        let dump = self.read_dump(None)?;
+        self.load_dump(dump)
     }
 
-    // load starknet transactions from file
-    pub fn load_transactions_custom_path(
+    /// Replaces the instance with the dumped chain, restored on top of the genesis state it was
+    /// created on. If the dump cannot be restored, the instance is left unchanged.
+    fn load_dump(&mut self, dump: StarknetDump<StarknetTransactions>) -> DevnetResult<()> {
+        let genesis = &dump.genesis;
+        genesis.check_compatibility(&self.config)?;
+
+        let mut loaded = self.create_genesis_instance_with(StarknetConfig {
+            seed: genesis.seed,
+            total_accounts: genesis.total_accounts,
+            predeployed_accounts_initial_balance: genesis.predeployed_accounts_initial_balance,
+            ..self.config.clone()
+        })?;
+        loaded.restore_dump(dump)?;
+        *self = loaded;
+
+        Ok(())
+    }
+
+    // read starknet dump from file
+    fn read_dump(
         &self,
         custom_path: Option<String>,
-    ) -> DevnetResult<Vec<Transaction>> {
+    ) -> DevnetResult<StarknetDump<StarknetTransactions>> {
         let dump_path = if custom_path.is_some() { &custom_path } else { &self.config.dump_path };
         match dump_path {
             Some(path) => {
                 let file_path = Path::new(path);
 
                 // load only if the file exists, if config.dump_path is set but the file doesn't
-                // exist it means that it's first execution and in that case return an empty vector,
-                // in case of load from HTTP endpoint return FileNotFound error
+                // exist it means that it's first execution, in case of load from HTTP endpoint
+                // return FileNotFound error
                 if file_path.exists() {
                     let file = File::open(file_path).map_err(Error::IoError)?;
-                    let transactions: Vec<Transaction> =
-                        serde_json::from_reader(file).map_err(|_| Error::DeserializationError {
-                            obj_name: "Vec<Transaction>".to_string(),
-                        })?;
-
-                    // to avoid doublets in transaction mode during load, we need to remove the file
-                    // because they will be re-executed and saved again
-                    if self.config.dump_on == Some(DumpMode::OnTransaction) {
-                        fs::remove_file(file_path).map_err(Error::IoError)?;
-                    }
-
-                    Ok(transactions)
+                    serde_json::from_reader(file).map_err(|_| Error::DeserializationError {
+                        obj_name: "StarknetDump".to_string(),
+                    })
                 } else {
                     Err(Error::FileNotFound)
                 }
@@ -188,4 +372,355 @@ impl Starknet {
             None => Err(Error::FormatError),
         }
     }
+
+    /// Restores the dumped chain on top of a genesis instance, without executing transactions
+    fn restore_dump(&mut self, dump: StarknetDump<StarknetTransactions>) -> DevnetResult<()> {
+        self.state.contract_classes = load_contract_classes(dump.contract_classes);
+
+        // states at past blocks are not dumped, so they are rebuilt from the state diffs
+        for DumpedBlock { block, state_diff } in dump.blocks {
+            self.state.apply_state_difference(state_diff.clone())?;
+            self.state.state.state.commit_state_diff(&state_diff)?;
+            let block_number = block.block_number();
+            self.blocks.insert(block, state_diff);
+            self.blocks.save_state_at(block_number, self.state.clone());
+        }
+
+        let origin = self.state.state.state.origin.take();
+        // the tries are as of the last block; pending changes are committed with the next block
+        let tries = std::mem::take(&mut self.state.state.state.tries);
+        let compilation_cache = self.state.state.state.compilation_cache.take();
+        self.state.state.state =
+            DevnetState { tries, compilation_cache, ..dump.state.into_devnet_state(origin) };
+        self.state.clear_dirty_state();
+
+        self.blocks.pending_block = dump.pending_block;
+        self.pending_state_diff = dump.pending_state_diff;
+        self.block_context.block_number = self.blocks.pending_block.block_number();
+        self.block_context.block_timestamp = self.blocks.pending_block.timestamp();
+        self.timestamp_shift_in_secs = dump.timestamp_shift_in_secs;
+
+        self.transactions = dump.transactions;
+        for (transaction_hash, trace) in dump.traces {
+            if let Some(transaction) = self.transactions.get_by_hash_mut(&transaction_hash) {
+                transaction.trace = Some(Arc::new(trace.into()));
+            }
+        }
+        self.messaging.l2_to_l1_messages = dump.l2_to_l1_messages;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use blockifier::transaction::objects::TransactionExecutionInfo;
+    use primitive_types::H160;
+    use starknet_api::core::EthAddress;
+    use starknet_api::transaction::Fee;
+    use starknet_rs_core::types::{BlockId, BlockTag, TransactionFinalityStatus};
+    use starknet_rs_core::utils::get_selector_from_name;
+    use starknet_types::chain_id::ChainId;
+    use starknet_types::contract_address::ContractAddress;
+    use starknet_types::contract_class::Cairo0ContractClass;
+    use starknet_types::contract_storage_key::ContractStorageKey;
+    use starknet_types::felt::Felt;
+    use starknet_types::rpc::transaction_receipt::MessageToL1;
+    use starknet_types::rpc::transactions::deploy_transaction::DeployTransaction;
+    use starknet_types::rpc::transactions::{
+        DeclareTransaction, InvokeTransaction, InvokeTransactionV0, L1HandlerTransaction,
+        Transaction,
+    };
+    use starknet_types::traits::HashProducer;
+
+    use super::StarknetDump;
+    use crate::constants::{self, DEVNET_DEFAULT_CHAIN_ID};
+    use crate::error::Error;
+    use crate::starknet::{Starknet, StarknetConfig};
+    use crate::traits::{HashIdentified, HashIdentifiedMut, StateChanger, StateExtractor};
+    use crate::transactions::{StarknetTransaction, StarknetTransactions};
+    use crate::utils::exported_test_utils::dummy_cairo_l1l2_contract;
+    use crate::utils::get_storage_var_address;
+    use crate::utils::test_utils::{
+        dummy_contract_address, dummy_declare_transaction_v1, dummy_felt, starknet_config_for_test,
+    };
+
+    /// The only L1 address the deposit handler of the L1-L2 test contract accepts messages from
+    const WHITELISTED_L1_ADDRESS: &str = "0x8359E4B0152ed5A731162D3c7B0D8D56edB165A0";
+
+    #[test]
+    fn chain_is_restored_without_execution() {
+        let (mut starknet, contract_address) = setup();
+        let user = Felt::from(1);
+        let transaction = L1HandlerTransaction::new(
+            contract_address,
+            get_selector_from_name("deposit").unwrap().into(),
+            vec![
+                Felt::from_prefixed_hex_str(WHITELISTED_L1_ADDRESS).unwrap(),
+                user,
+                Felt::from(10),
+            ],
+            Felt::from(0),
+            Fee(1),
+            DEVNET_DEFAULT_CHAIN_ID.to_felt(),
+        )
+        .unwrap();
+        let transaction_hash = starknet.add_l1_handler_transaction(transaction).unwrap();
+
+        let serialized_dump = serde_json::to_string(&starknet.create_dump().unwrap()).unwrap();
+        let dump: StarknetDump<StarknetTransactions> =
+            serde_json::from_str(&serialized_dump).unwrap();
+        let mut loaded_starknet = Starknet::default();
+        loaded_starknet.restore_dump(dump).unwrap();
+
+        let balance_storage_key = ContractStorageKey::new(
+            contract_address,
+            get_storage_var_address("balance", &[user]).unwrap(),
+        );
+        assert_eq!(loaded_starknet.state.get_storage(balance_storage_key).unwrap(), Felt::from(10));
+
+        let latest_block = starknet.blocks.get_by_block_id(BlockId::Tag(BlockTag::Latest)).unwrap();
+        let loaded_latest_block =
+            loaded_starknet.blocks.get_by_block_id(BlockId::Tag(BlockTag::Latest)).unwrap();
+        assert_eq!(loaded_latest_block.block_hash(), latest_block.block_hash());
+        assert_eq!(loaded_latest_block.timestamp(), latest_block.timestamp());
+        assert!(loaded_starknet.blocks.num_to_state.contains_key(&latest_block.block_number()));
+        assert_eq!(loaded_starknet.block_context.block_number, starknet.block_context.block_number);
+
+        let transaction = starknet.transactions.get_by_hash(transaction_hash).unwrap();
+        let loaded_transaction =
+            loaded_starknet.transactions.get_by_hash(transaction_hash).unwrap();
+        assert!(loaded_transaction.execution_info.is_none());
+        assert_eq!(
+            serde_json::to_value(loaded_starknet.get_transaction_trace(transaction_hash).unwrap())
+                .unwrap(),
+            serde_json::to_value(starknet.get_transaction_trace(transaction_hash).unwrap())
+                .unwrap()
+        );
+        assert_eq!(
+            loaded_transaction.finality_status,
+            Some(TransactionFinalityStatus::AcceptedOnL2)
+        );
+        assert_eq!(loaded_transaction.block_hash, transaction.block_hash);
+        assert_eq!(loaded_transaction.get_events(), transaction.get_events());
+    }
+
+    #[test]
+    fn legacy_transactions_are_restored_as_their_own_versions() {
+        let mut starknet = Starknet::default();
+
+        let mut declare_v0 = dummy_declare_transaction_v1();
+        declare_v0.version = Felt::from(0);
+        declare_v0.transaction_hash = Felt::from(1);
+        let mut declare_v1 = dummy_declare_transaction_v1();
+        declare_v1.transaction_hash = Felt::from(2);
+        let transactions = [
+            Transaction::Declare(DeclareTransaction::Version0(declare_v0)),
+            Transaction::Declare(DeclareTransaction::Version1(declare_v1)),
+            Transaction::Invoke(InvokeTransaction::Version0(InvokeTransactionV0 {
+                transaction_hash: Felt::from(3),
+                contract_address: dummy_contract_address(),
+                ..Default::default()
+            })),
+            Transaction::Deploy(DeployTransaction {
+                transaction_hash: Felt::from(4),
+                class_hash: dummy_felt(),
+                ..Default::default()
+            }),
+        ];
+
+        for transaction in transactions.iter() {
+            // the trace of an invoke transaction needs either an execution or a revert reason
+            let execution_info = TransactionExecutionInfo {
+                revert_error: Some("reverted".into()),
+                ..Default::default()
+            };
+            starknet
+                .handle_accepted_transaction(
+                    transaction.get_transaction_hash(),
+                    transaction,
+                    execution_info,
+                )
+                .unwrap();
+        }
+
+        let serialized_dump = serde_json::to_string(&starknet.create_dump().unwrap()).unwrap();
+        let dump: StarknetDump<StarknetTransactions> =
+            serde_json::from_str(&serialized_dump).unwrap();
+        let mut loaded_starknet = Starknet::default();
+        loaded_starknet.restore_dump(dump).unwrap();
+
+        for transaction in transactions {
+            let transaction_hash = *transaction.get_transaction_hash();
+            let loaded_transaction = loaded_starknet.transactions.get_by_hash(transaction_hash);
+            assert_eq!(loaded_transaction.unwrap().inner, transaction);
+
+            let loaded_trace = loaded_starknet.get_transaction_trace(transaction_hash);
+            match transaction {
+                // deploy transactions are not traced
+                Transaction::Deploy(_) => {
+                    assert!(matches!(loaded_trace, Err(Error::NoTransactionTrace)))
+                }
+                _ => assert_eq!(
+                    serde_json::to_value(loaded_trace.unwrap()).unwrap(),
+                    serde_json::to_value(starknet.get_transaction_trace(transaction_hash).unwrap())
+                        .unwrap()
+                ),
+            }
+        }
+    }
+
+    #[test]
+    fn consumed_messages_to_l1_stay_consumed() {
+        let mut starknet = Starknet::default();
+        let message = MessageToL1 {
+            from_address: dummy_contract_address(),
+            to_address: EthAddress(H160::from_low_u64_be(1)),
+            payload: vec![dummy_felt()],
+        };
+
+        // the message is sent twice and consumed once
+        let mut transaction = dummy_declare_transaction_v1();
+        transaction.transaction_hash = Felt::from(1);
+        let mut starknet_transaction = StarknetTransaction::create_accepted(
+            &Transaction::Declare(DeclareTransaction::Version1(transaction)),
+            TransactionExecutionInfo::default(),
+        );
+        starknet_transaction.messages_sent = vec![message.clone(), message.clone()];
+        starknet.transactions.insert(&Felt::from(1), starknet_transaction);
+        starknet.messaging.add_messages_to_l1(&[message.clone(), message.clone()]);
+        starknet.messaging.consume_message_from_l2(&message).unwrap();
+
+        let mut loaded_starknet = Starknet::default();
+        loaded_starknet.restore_dump(dump_and_read(&starknet)).unwrap();
+
+        assert!(loaded_starknet.messaging.consume_message_from_l2(&message).is_ok());
+        assert!(loaded_starknet.messaging.consume_message_from_l2(&message).is_err());
+    }
+
+    #[test]
+    fn chain_is_restored_on_top_of_its_genesis() {
+        let config = StarknetConfig { seed: 1, total_accounts: 1, ..starknet_config_for_test() };
+        let mut starknet = Starknet::new(&config).unwrap();
+        starknet.create_block().unwrap();
+
+        let mut loading_starknet = Starknet::new(&StarknetConfig {
+            seed: 2,
+            total_accounts: 2,
+            ..starknet_config_for_test()
+        })
+        .unwrap();
+        loading_starknet.load_dump(dump_and_read(&starknet)).unwrap();
+
+        assert_eq!(loading_starknet.config.seed, 1);
+        assert_eq!(loading_starknet.genesis_state_root, starknet.genesis_state_root);
+        let accounts = loading_starknet.get_predeployed_accounts();
+        assert_eq!(accounts.len(), 1);
+        assert_eq!(
+            accounts[0].account_address,
+            starknet.get_predeployed_accounts()[0].account_address
+        );
+    }
+
+    #[test]
+    fn chain_of_another_fork_is_not_loaded() {
+        let mut starknet = Starknet::new(&starknet_config_for_test()).unwrap();
+        starknet.create_block().unwrap();
+
+        let mut dump = dump_and_read(&starknet);
+        dump.genesis.fork_url = Some("http://localhost:1234/".into());
+        dump.genesis.fork_block = Some(1);
+
+        match starknet.load_dump(dump) {
+            Err(Error::IncompatibleDump { .. }) => (),
+            other => panic!("Unexpected result: {other:?}"),
+        }
+        // the instance is left unchanged
+        assert!(starknet.get_block(BlockId::Number(0)).is_ok());
+    }
+
+    #[test]
+    fn chain_with_another_chain_id_or_gas_prices_is_not_loaded() {
+        let mut starknet = Starknet::new(&starknet_config_for_test()).unwrap();
+        starknet.create_block().unwrap();
+
+        let mut dump = dump_and_read(&starknet);
+        dump.genesis.chain_id = ChainId::MainNet.to_felt();
+        match starknet.load_dump(dump) {
+            Err(Error::IncompatibleDump { msg }) => assert!(msg.contains("chain ID")),
+            other => panic!("Unexpected result: {other:?}"),
+        }
+
+        let mut dump = dump_and_read(&starknet);
+        dump.genesis.gas_price += 1;
+        match starknet.load_dump(dump) {
+            Err(Error::IncompatibleDump { msg }) => assert!(msg.contains("gas prices")),
+            other => panic!("Unexpected result: {other:?}"),
+        }
+
+        let mut dump = dump_and_read(&starknet);
+        dump.genesis.strk_gas_price += 1;
+        assert!(matches!(starknet.load_dump(dump), Err(Error::IncompatibleDump { .. })));
+    }
+
+    #[test]
+    fn stored_traces_are_dumped_without_execution_infos() {
+        let (mut starknet, contract_address) = setup();
+        let transaction = L1HandlerTransaction::new(
+            contract_address,
+            get_selector_from_name("deposit").unwrap().into(),
+            vec![
+                Felt::from_prefixed_hex_str(WHITELISTED_L1_ADDRESS).unwrap(),
+                Felt::from(1),
+                Felt::from(10),
+            ],
+            Felt::from(0),
+            Fee(1),
+            DEVNET_DEFAULT_CHAIN_ID.to_felt(),
+        )
+        .unwrap();
+        let transaction_hash = starknet.add_l1_handler_transaction(transaction).unwrap();
+        let trace = serde_json::to_value(starknet.get_transaction_trace(transaction_hash).unwrap())
+            .unwrap();
+
+        // the trace is not created from the execution info when dumping
+        starknet.transactions.get_by_hash_mut(&transaction_hash).unwrap().execution_info = None;
+
+        let mut loaded_starknet = Starknet::default();
+        loaded_starknet.restore_dump(dump_and_read(&starknet)).unwrap();
+        assert_eq!(
+            serde_json::to_value(loaded_starknet.get_transaction_trace(transaction_hash).unwrap())
+                .unwrap(),
+            trace
+        );
+    }
+
+    /// Serializes the dump of the instance and deserializes it, as if it was written to a file
+    fn dump_and_read(starknet: &Starknet) -> StarknetDump<StarknetTransactions> {
+        let serialized_dump = serde_json::to_string(&starknet.create_dump().unwrap()).unwrap();
+        serde_json::from_str(&serialized_dump).unwrap()
+    }
+
+    /// Initialize starknet object with the L1-L2 test contract deployed
+    fn setup() -> (Starknet, ContractAddress) {
+        let mut starknet = Starknet::default();
+
+        let contract_class: Cairo0ContractClass = dummy_cairo_l1l2_contract().into();
+        let class_hash = contract_class.generate_hash().unwrap();
+        let contract_address = ContractAddress::new(Felt::from(5)).unwrap();
+
+        starknet.state.declare_contract_class(class_hash, contract_class.into()).unwrap();
+        starknet.state.deploy_contract(contract_address, class_hash).unwrap();
+        starknet.state.clear_dirty_state();
+        starknet.block_context = Starknet::init_block_context(
+            1,
+            1,
+            constants::ERC20_CONTRACT_ADDRESS,
+            constants::STRK_ERC20_CONTRACT_ADDRESS,
+            DEVNET_DEFAULT_CHAIN_ID,
+        );
+        starknet.restart_pending_block().unwrap();
+
+        (starknet, contract_address)
+    }
 }
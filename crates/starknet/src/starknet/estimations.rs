@@ -4,7 +4,7 @@ use blockifier::transaction::transactions::ExecutableTransaction;
 use starknet_rs_core::types::{BlockId, MsgFromL1};
 use starknet_types::contract_address::ContractAddress;
 use starknet_types::rpc::estimate_message_fee::{
-    EstimateMessageFeeRequestWrapper, FeeEstimateWrapper,
+    EstimateMessageFeeRequestWrapper, FeeEstimateWrapper, FeeUnit,
 };
 use starknet_types::rpc::transactions::BroadcastedTransaction;
 
@@ -24,18 +24,24 @@ pub fn estimate_fee(
 
     let transactions = transactions
         .iter()
-        .map(|txn| Ok(txn.to_blockifier_account_transaction(chain_id)?))
-        .collect::<DevnetResult<Vec<AccountTransaction>>>()?;
+        .map(|txn| {
+            Ok((
+                txn.to_blockifier_account_transaction(chain_id, starknet.compilation_cache())?,
+                txn.get_fee_unit(),
+            ))
+        })
+        .collect::<DevnetResult<Vec<(AccountTransaction, FeeUnit)>>>()?;
 
     transactions
         .into_iter()
-        .map(|transaction| {
+        .map(|(transaction, unit)| {
             estimate_transaction_fee(
                 &mut state,
-                &starknet.block_context,
+                &starknet.block_context_for_fee_unit(unit),
                 blockifier::transaction::transaction_execution::Transaction::AccountTransaction(
                     transaction,
                 ),
+                unit,
                 charge_fee,
                 validate,
             )
@@ -66,15 +72,19 @@ pub fn estimate_message_fee(
         blockifier::transaction::transaction_execution::Transaction::L1HandlerTransaction(
             l1_transaction,
         ),
+        FeeUnit::Wei,
         None,
         None,
     )
 }
 
+/// Estimates the fee of `transaction` in `unit`, which is expected to be the unit of the fee token
+/// of `block_context`
 fn estimate_transaction_fee(
     state: &mut StarknetState,
     block_context: &blockifier::block_context::BlockContext,
     transaction: blockifier::transaction::transaction_execution::Transaction,
+    unit: FeeUnit,
     charge_fee: Option<bool>,
     validate: Option<bool>,
 ) -> DevnetResult<FeeEstimateWrapper> {
@@ -103,5 +113,5 @@ fn estimate_transaction_fee(
 
     let gas_price = block_context.gas_prices.eth_l1_gas_price as u64;
 
-    Ok(FeeEstimateWrapper::new(total_l1_gas_usage, gas_price, total_l1_gas_usage * gas_price))
+    Ok(FeeEstimateWrapper::new(total_l1_gas_usage, gas_price, total_l1_gas_usage * gas_price, unit))
 }
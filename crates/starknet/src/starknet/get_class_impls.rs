@@ -13,7 +13,7 @@ pub fn get_class_hash_at_impl(
     contract_address: ContractAddress,
 ) -> DevnetResult<ClassHash> {
     let state = starknet.get_state_at(&block_id)?;
-    let class_hash = state.state.state.class_hash_at(&contract_address);
+    let class_hash = state.state.state.class_hash_at(&contract_address)?;
 
     if class_hash == Felt::default() {
         return Err(Error::ContractNotFound);
@@ -65,6 +65,8 @@ mod tests {
 
         let erc_20_contract = predeployed::create_erc20().unwrap();
         erc_20_contract.deploy(&mut starknet.state).unwrap();
+        let strk_erc_20_contract = predeployed::create_strk_erc20().unwrap();
+        strk_erc_20_contract.deploy(&mut starknet.state).unwrap();
 
         let acc = Account::new(
             Felt::from(acc_balance.unwrap_or(100)),
@@ -73,6 +75,7 @@ mod tests {
             contract_class.generate_hash().unwrap(),
             contract_class.into(),
             erc_20_contract.get_address(),
+            strk_erc_20_contract.get_address(),
         )
         .unwrap();
 
@@ -81,8 +84,10 @@ mod tests {
 
         starknet.state.clear_dirty_state();
         starknet.block_context = Starknet::init_block_context(
+            1,
             1,
             constants::ERC20_CONTRACT_ADDRESS,
+            constants::STRK_ERC20_CONTRACT_ADDRESS,
             DEVNET_DEFAULT_CHAIN_ID,
         );
 
@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::fmt;
 use std::net::IpAddr;
 use std::time::SystemTime;
@@ -10,44 +11,56 @@ use blockifier::transaction::transactions::ExecutableTransaction;
 use starknet_api::block::{BlockNumber, BlockStatus, BlockTimestamp, GasPrice};
 use starknet_api::transaction::Fee;
 use starknet_in_rust::definitions::constants::DEFAULT_CAIRO_RESOURCE_FEE_WEIGHTS;
-use starknet_rs_core::types::{BlockId, MsgFromL1, TransactionFinalityStatus};
+use starknet_rs_core::types::{BlockId, BlockTag, MsgFromL1, TransactionFinalityStatus};
 use starknet_rs_core::utils::get_selector_from_name;
 use starknet_rs_ff::FieldElement;
 use starknet_rs_signers::Signer;
 use starknet_types::chain_id::ChainId;
 use starknet_types::contract_address::ContractAddress;
+use starknet_types::contract_class::compilation_cache::CompilationCache;
 use starknet_types::contract_class::{Cairo0Json, ContractClass};
 use starknet_types::contract_storage_key::ContractStorageKey;
 use starknet_types::emitted_event::EmittedEvent;
-use starknet_types::felt::{ClassHash, Felt, TransactionHash};
+use starknet_types::felt::{BlockHash, ClassHash, Felt, TransactionHash};
 use starknet_types::patricia_key::PatriciaKey;
 use starknet_types::rpc::block::{Block, BlockHeader};
-use starknet_types::rpc::estimate_message_fee::FeeEstimateWrapper;
+use starknet_types::rpc::estimate_message_fee::{FeeEstimateWrapper, FeeUnit};
+use starknet_types::rpc::storage_proof::{ContractStorageKeys, StorageProof};
 use starknet_types::rpc::transaction_receipt::TransactionReceipt;
 use starknet_types::rpc::transactions::broadcasted_declare_transaction_v1::BroadcastedDeclareTransactionV1;
 use starknet_types::rpc::transactions::broadcasted_declare_transaction_v2::BroadcastedDeclareTransactionV2;
-use starknet_types::rpc::transactions::broadcasted_deploy_account_transaction::BroadcastedDeployAccountTransaction;
-use starknet_types::rpc::transactions::broadcasted_invoke_transaction::BroadcastedInvokeTransaction;
+use starknet_types::rpc::transactions::broadcasted_declare_transaction_v3::BroadcastedDeclareTransactionV3;
+use starknet_types::rpc::transactions::broadcasted_invoke_transaction_v1::BroadcastedInvokeTransactionV1;
+use starknet_types::rpc::transactions::declare_transaction_v0v1::DeclareTransactionV0V1;
+use starknet_types::rpc::transactions::deploy_transaction::DeployTransaction;
 use starknet_types::rpc::transactions::{
-    BroadcastedTransaction, BroadcastedTransactionCommon, DeclareTransaction,
-    DeclareTransactionTrace, DeployAccountTransactionTrace, ExecutionInvocation,
-    FunctionInvocation, InvokeTransactionTrace, SimulatedTransaction, SimulationFlag, Transaction,
-    TransactionTrace, Transactions,
+    BlockTransactionTrace, BroadcastedDeployAccountTransaction, BroadcastedInvokeTransaction,
+    BroadcastedTransaction, BroadcastedTransactionCommon, BroadcastedTransactionCommonV3,
+    DeclareTransaction, FinalityStatus, InvokeTransactionV0, L1HandlerTransaction,
+    SimulatedTransaction, SimulationFlag, Transaction, TransactionStatus, TransactionTrace,
+    TransactionType, Transactions,
 };
 use starknet_types::traits::HashProducer;
 use strum_macros::EnumIter;
+use tokio::sync::broadcast;
 use tracing::{error, warn};
+use url::Url;
 
 use self::predeployed::initialize_erc20;
+use self::snapshot::Snapshots;
+pub use self::snapshot::SnapshotId;
 use crate::account::Account;
 use crate::blocks::{StarknetBlock, StarknetBlocks};
 use crate::constants::{
     CAIRO_0_ACCOUNT_CONTRACT_PATH, CHARGEABLE_ACCOUNT_ADDRESS, CHARGEABLE_ACCOUNT_PRIVATE_KEY,
     DEVNET_DEFAULT_CHAIN_ID, DEVNET_DEFAULT_HOST, ERC20_CONTRACT_ADDRESS,
+    STRK_ERC20_CONTRACT_ADDRESS, UPDATES_CHANNEL_CAPACITY,
 };
 use crate::error::{DevnetResult, Error, TransactionValidationError};
+use crate::messaging::MessagingBroker;
 use crate::predeployed_accounts::PredeployedAccounts;
 use crate::raw_execution::{Call, RawExecution};
+use crate::state::origin::OriginReader;
 use crate::state::state_diff::StateDiff;
 use crate::state::state_update::StateUpdate;
 use crate::state::StarknetState;
@@ -59,13 +72,19 @@ use crate::transactions::{StarknetTransaction, StarknetTransactions};
 
 mod add_declare_transaction;
 mod add_deploy_account_transaction;
+mod add_deploy_transaction;
 mod add_invoke_transaction;
+mod add_l1_handler_transaction;
 mod dump;
 mod estimations;
 mod events;
 mod get_class_impls;
+mod postman;
 mod predeployed;
+mod snapshot;
 mod state_update;
+mod storage_proof;
+mod transaction_trace;
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, EnumIter)]
 pub enum DumpMode {
@@ -82,6 +101,23 @@ impl fmt::Display for DumpMode {
     }
 }
 
+/// Change of the chain, of which subscribers are notified
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ChainUpdate {
+    /// A block was sealed
+    NewBlock(BlockNumber),
+    /// The status of a transaction changed without a block being sealed, i.e. the transaction was
+    /// rejected or its block was aborted
+    TransactionStatus(TransactionHash),
+}
+
+/// Network to fork from. If `block` is not provided, the latest block of the network is used.
+#[derive(Clone, Debug, Default)]
+pub struct ForkConfig {
+    pub url: Option<Url>,
+    pub block: Option<u64>,
+}
+
 #[derive(Clone, Debug)]
 pub struct StarknetConfig {
     pub seed: u32,
@@ -91,9 +127,15 @@ pub struct StarknetConfig {
     pub port: u16,
     pub timeout: u16,
     pub gas_price: u64,
+    pub strk_gas_price: u64,
     pub chain_id: ChainId,
     pub dump_on: Option<DumpMode>,
     pub dump_path: Option<String>,
+    pub blocks_on_demand: bool,
+    pub block_time: Option<u64>,
+    pub fork_config: ForkConfig,
+    /// Directory in which Sierra classes compiled to CASM are cached, if any
+    pub compilation_cache_dir: Option<String>,
 }
 
 impl Default for StarknetConfig {
@@ -106,9 +148,14 @@ impl Default for StarknetConfig {
             port: u16::default(),
             timeout: u16::default(),
             gas_price: Default::default(),
+            strk_gas_price: Default::default(),
             chain_id: DEVNET_DEFAULT_CHAIN_ID,
             dump_on: None,
             dump_path: None,
+            blocks_on_demand: false,
+            block_time: None,
+            fork_config: ForkConfig::default(),
+            compilation_cache_dir: None,
         }
     }
 }
@@ -118,6 +165,16 @@ pub struct Starknet {
     predeployed_accounts: PredeployedAccounts,
     pub(in crate::starknet) block_context: BlockContext,
     blocks: StarknetBlocks,
+    /// Root of the state on startup, on top of which the first block is created
+    genesis_state_root: Felt,
+    /// State changes of the transactions in the pending block, accumulated until it is sealed
+    pending_state_diff: StateDiff,
+    /// Seconds added to the system time when assigning timestamps to new blocks
+    timestamp_shift_in_secs: i64,
+    messaging: MessagingBroker,
+    snapshots: Snapshots,
+    /// Sends each sealed block and each transaction status change to the subscribers
+    updates: broadcast::Sender<ChainUpdate>,
     pub transactions: StarknetTransactions,
     pub config: StarknetConfig,
 }
@@ -126,13 +183,21 @@ impl Default for Starknet {
     fn default() -> Self {
         Self {
             block_context: Self::init_block_context(
+                0,
                 0,
                 ERC20_CONTRACT_ADDRESS,
+                STRK_ERC20_CONTRACT_ADDRESS,
                 DEVNET_DEFAULT_CHAIN_ID,
             ),
             state: Default::default(),
             predeployed_accounts: Default::default(),
             blocks: Default::default(),
+            genesis_state_root: Default::default(),
+            pending_state_diff: Default::default(),
+            timestamp_shift_in_secs: Default::default(),
+            messaging: Default::default(),
+            snapshots: Default::default(),
+            updates: broadcast::channel(UPDATES_CHANNEL_CAPACITY).0,
             transactions: Default::default(),
             config: Default::default(),
         }
@@ -141,20 +206,39 @@ impl Default for Starknet {
 
 impl Starknet {
     pub fn new(config: &StarknetConfig) -> DevnetResult<Self> {
-        let mut state = StarknetState::default();
-        // deploy udc and erc20 contracts
-        let erc20_fee_contract = predeployed::create_erc20()?;
+        let origin = match &config.fork_config.url {
+            Some(url) => Some(OriginReader::new(url.clone(), config.fork_config.block)?),
+            None => None,
+        };
+        let fork_block = origin.as_ref().map(OriginReader::block_number);
+
+        let compilation_cache =
+            config.compilation_cache_dir.as_ref().map(CompilationCache::new).transpose()?;
+
+        let mut state = StarknetState::new(origin, compilation_cache);
+        // deploy udc and erc20 contracts of both fee tokens
+        let eth_erc20_fee_contract = predeployed::create_erc20()?;
+        let strk_erc20_fee_contract = predeployed::create_strk_erc20()?;
         let udc_contract = predeployed::create_udc()?;
 
-        erc20_fee_contract.deploy(&mut state)?;
-        initialize_erc20(&mut state)?;
+        eth_erc20_fee_contract.deploy(&mut state)?;
+        initialize_erc20(&mut state, eth_erc20_fee_contract.get_address(), "ether", "ETH")?;
+
+        strk_erc20_fee_contract.deploy(&mut state)?;
+        initialize_erc20(
+            &mut state,
+            strk_erc20_fee_contract.get_address(),
+            "starknet token",
+            "STRK",
+        )?;
 
         udc_contract.deploy(&mut state)?;
 
         let mut predeployed_accounts = PredeployedAccounts::new(
             config.seed,
             config.predeployed_accounts_initial_balance,
-            erc20_fee_contract.get_address(),
+            eth_erc20_fee_contract.get_address(),
+            strk_erc20_fee_contract.get_address(),
         );
         let account_contract_class = Cairo0Json::raw_json_from_path(CAIRO_0_ACCOUNT_CONTRACT_PATH)?;
         let class_hash = account_contract_class.generate_hash()?;
@@ -172,7 +256,8 @@ impl Starknet {
         let chargeable_account = Account::new_chargeable(
             class_hash,
             account_contract_class.into(),
-            erc20_fee_contract.get_address(),
+            eth_erc20_fee_contract.get_address(),
+            strk_erc20_fee_contract.get_address(),
         );
         chargeable_account.deploy(&mut state)?;
         chargeable_account.set_initial_balance(&mut state)?;
@@ -181,26 +266,39 @@ impl Starknet {
         state.clear_dirty_state();
 
         let mut this = Self {
+            genesis_state_root: state.state.state.rebuild_tries()?,
             state,
             predeployed_accounts,
             block_context: Self::init_block_context(
                 config.gas_price,
+                config.strk_gas_price,
                 ERC20_CONTRACT_ADDRESS,
+                STRK_ERC20_CONTRACT_ADDRESS,
                 config.chain_id,
             ),
             blocks: StarknetBlocks::default(),
+            pending_state_diff: StateDiff::default(),
+            timestamp_shift_in_secs: 0,
+            messaging: MessagingBroker::default(),
+            snapshots: Snapshots::default(),
+            updates: broadcast::channel(UPDATES_CHANNEL_CAPACITY).0,
             transactions: StarknetTransactions::default(),
             config: config.clone(),
         };
 
+        // new blocks are generated on top of the fork block
+        if let Some(fork_block) = fork_block {
+            this.config.fork_config.block = Some(fork_block);
+            this.block_context.block_number = BlockNumber(fork_block + 1);
+        }
+
         this.restart_pending_block()?;
 
-        // Load starknet transactions
+        // Load starknet state
         if this.config.dump_path.is_some() {
-            // Try to load transactions from dump_path, if there is no file skip this step
-            match this.load_transactions() {
-                Ok(txs) => this.re_execute(txs)?,
-                Err(Error::FileNotFound) => {}
+            // Try to load the state from dump_path, if there is no file skip this step
+            match this.load_state_on_startup() {
+                Ok(()) | Err(Error::FileNotFound) => {}
                 Err(err) => return Err(err),
             };
         }
@@ -208,6 +306,43 @@ impl Starknet {
         Ok(this)
     }
 
+    /// Resets the instance to the state it had right after startup: same predeployed contracts
+    /// and accounts, no blocks, no transactions and no snapshots. The dump file, if configured, is
+    /// not loaded.
+    pub fn restart(&mut self) -> DevnetResult<()> {
+        *self = self.create_genesis_instance()?;
+
+        Ok(())
+    }
+
+    /// Creates a new instance with the same configuration, without loading the dump file
+    fn create_genesis_instance(&self) -> DevnetResult<Starknet> {
+        self.create_genesis_instance_with(self.config.clone())
+    }
+
+    /// Creates a new instance with the given configuration, without loading the dump file
+    fn create_genesis_instance_with(&self, config: StarknetConfig) -> DevnetResult<Starknet> {
+        let genesis_config = StarknetConfig { dump_path: None, ..config.clone() };
+        let mut genesis = Starknet::new(&genesis_config)?;
+        genesis.config = config;
+        // subscribers keep receiving updates after a restart or a load
+        genesis.updates = self.updates.clone();
+        // snapshots of the replaced chain cannot be reverted to, but their ids are not reused
+        genesis.snapshots = self.snapshots.discard_all();
+
+        Ok(genesis)
+    }
+
+    /// Returns a receiver of the updates of the chain from now on
+    pub fn subscribe_to_updates(&self) -> broadcast::Receiver<ChainUpdate> {
+        self.updates.subscribe()
+    }
+
+    fn send_update(&self, update: ChainUpdate) {
+        // sending only fails if there are no subscribers
+        let _ = self.updates.send(update);
+    }
+
     pub fn get_predeployed_accounts(&self) -> Vec<Account> {
         self.predeployed_accounts.get_accounts().to_vec()
     }
@@ -215,7 +350,7 @@ impl Starknet {
     // Update block context
     // Initialize values for new pending block
     pub(crate) fn generate_pending_block(&mut self) -> DevnetResult<()> {
-        Self::update_block_context(&mut self.block_context);
+        Self::update_block_context(&mut self.block_context, self.timestamp_shift_in_secs);
         self.restart_pending_block()?;
 
         Ok(())
@@ -229,7 +364,14 @@ impl Starknet {
     ) -> DevnetResult<BlockNumber> {
         let mut new_block = self.pending_block().clone();
 
-        // set new block header
+        // set new block header; the state of the block is already applied
+        let transactions = new_block
+            .get_transactions()
+            .iter()
+            .map(|tx_hash| self.transactions.get_by_hash(*tx_hash).ok_or(Error::NoTransaction))
+            .collect::<DevnetResult<Vec<_>>>()?;
+        new_block.set_commitments(&transactions);
+        new_block.set_state_root(self.state.state.state.commit_state_diff(&state_diff)?);
         new_block.set_block_hash(new_block.generate_hash()?);
         new_block.status = BlockStatus::AcceptedOnL2;
         let new_block_number = new_block.block_number();
@@ -247,10 +389,10 @@ impl Starknet {
 
         // insert pending block in the blocks collection and connect it to the state diff
         self.blocks.insert(new_block, state_diff);
-        // save into blocks state archive
+        // save into blocks state archive; the clone shares unchanged entries with earlier blocks
+        self.blocks.save_state_at(new_block_number, self.state.clone());
 
-        let deep_cloned_state = self.state.clone();
-        self.blocks.save_state_at(new_block_number, deep_cloned_state);
+        self.send_update(ChainUpdate::NewBlock(new_block_number));
 
         Ok(new_block_number)
     }
@@ -274,13 +416,14 @@ impl Starknet {
         match transaction_result {
             Ok(tx_info) => {
                 // If transaction is not reverted
-                // then save the contract class in the state cache for Declare V1/V2 transactions
+                // then save the contract class in the state cache for declare transactions
                 if !tx_info.is_reverted() {
                     match &transaction {
-                        Transaction::Declare(DeclareTransaction::Version1(declare_v1)) => {
+                        Transaction::Declare(DeclareTransaction::Version0(declare_v0v1))
+                        | Transaction::Declare(DeclareTransaction::Version1(declare_v0v1)) => {
                             self.state.contract_classes.insert(
-                                declare_v1.class_hash,
-                                declare_v1.contract_class.clone().into(),
+                                declare_v0v1.class_hash,
+                                declare_v0v1.contract_class.clone().into(),
                             );
                         }
                         Transaction::Declare(DeclareTransaction::Version2(declare_v2)) => {
@@ -289,6 +432,12 @@ impl Starknet {
                                 declare_v2.contract_class.clone().into(),
                             );
                         }
+                        Transaction::Declare(DeclareTransaction::Version3(declare_v3)) => {
+                            self.state.contract_classes.insert(
+                                declare_v3.class_hash,
+                                declare_v3.contract_class.clone().into(),
+                            );
+                        }
                         _ => {}
                     };
                 }
@@ -296,51 +445,170 @@ impl Starknet {
             }
             Err(tx_err) => {
                 // based on this https://community.starknet.io/t/efficient-utilization-of-sequencer-capacity-in-starknet-v0-12-1/95607#the-validation-phase-in-the-gateway-5
-                // we should not save transactions that failed with one of the following errors
-                match tx_err {
+                // transactions that failed with one of the following errors are not executed
+                let validation_error = match &tx_err {
                     blockifier::transaction::errors::TransactionExecutionError::InvalidNonce { .. } =>
-                        Err(TransactionValidationError::InvalidTransactionNonce.into()),
+                        TransactionValidationError::InvalidTransactionNonce,
                     blockifier::transaction::errors::TransactionExecutionError::MaxFeeExceedsBalance { .. } =>
-                        Err(TransactionValidationError::InsufficientAccountBalance.into()),
+                        TransactionValidationError::InsufficientAccountBalance,
                     blockifier::transaction::errors::TransactionExecutionError::FeeTransferError { .. }
                     | blockifier::transaction::errors::TransactionExecutionError::MaxFeeTooLow { .. } =>
-                        Err(TransactionValidationError::InsufficientMaxFee.into()),
+                        TransactionValidationError::InsufficientMaxFee,
                     blockifier::transaction::errors::TransactionExecutionError::ValidateTransactionError(..) =>
-                        Err(TransactionValidationError::ValidationFailure.into()),
-                    _ => Err(tx_err.into())
+                        TransactionValidationError::ValidationFailure,
+                    _ => return Err(tx_err.into()),
+                };
+
+                // they are only recorded as rejected, unless a transaction with the same hash is
+                // already known, e.g. if an accepted transaction was sent again
+                if self.transactions.get(&transaction_hash).is_none() {
+                    self.transactions.insert(
+                        &transaction_hash,
+                        StarknetTransaction::create_rejected(&transaction, &tx_err.to_string()),
+                    );
+                    self.send_update(ChainUpdate::TransactionStatus(transaction_hash));
                 }
+
+                Err(validation_error.into())
             }
         }
     }
 
     /// Handles suceeded and reverted transactions.
     /// The tx is stored and potentially dumped.
-    /// A new block is generated.
+    /// A new block is generated, unless blocks are created on demand or periodically.
     pub(crate) fn handle_accepted_transaction(
         &mut self,
         transaction_hash: &TransactionHash,
         transaction: &Transaction,
         tx_info: TransactionExecutionInfo,
     ) -> DevnetResult<()> {
-        let transaction_to_add = StarknetTransaction::create_accepted(transaction, tx_info);
+        let mut transaction_to_add = StarknetTransaction::create_accepted(transaction, tx_info);
+        self.messaging.add_messages_to_l1(&transaction_to_add.get_l2_to_l1_messages());
 
         // add accepted transaction to pending block
         self.blocks.pending_block.add_transaction(*transaction_hash);
 
-        self.transactions.insert(transaction_hash, transaction_to_add);
-
         let state_difference = self.state.extract_state_diff_from_pending_state()?;
         // apply state changes from cached state
         self.state.apply_state_difference(state_difference.clone())?;
         // make cached state part of "persistent" state
         self.state.clear_dirty_state();
+        // the state difference of the pending block is sealed together with the block
+        self.pending_state_diff.extend(state_difference);
+
+        // traced once the contracts deployed by the transaction are in the state
+        transaction_to_add.trace =
+            transaction_trace::trace_of_execution(self, &transaction_to_add)?;
+        self.transactions.insert(transaction_hash, transaction_to_add);
+
+        if !self.accumulates_pending_transactions() {
+            self.create_block()?;
+        }
+
+        if self.config.dump_on == Some(DumpMode::OnTransaction) {
+            self.dump_state()?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns true if accepted transactions are kept in the pending block until it is sealed on
+    /// demand or on a time interval, instead of each transaction being put in a new block
+    fn accumulates_pending_transactions(&self) -> bool {
+        self.config.blocks_on_demand || self.config.block_time.is_some()
+    }
+
+    /// Seals the pending block with all of its transactions and the combined state difference,
+    /// and starts a new pending block. Returns the hash of the sealed block.
+    pub fn create_block(&mut self) -> DevnetResult<BlockHash> {
+        let state_diff = std::mem::take(&mut self.pending_state_diff);
         // create new block from pending one
-        self.generate_new_block(state_difference)?;
+        let new_block_number = self.generate_new_block(state_diff)?;
         // clear pending block information
         self.generate_pending_block()?;
 
+        let new_block = self.blocks.num_to_block.get(&new_block_number).ok_or(Error::NoBlock)?;
+        Ok(new_block.block_hash())
+    }
+
+    /// Aborts the block with the given hash, all blocks after it and the pending block. Their
+    /// transactions are marked as rejected and the state is reverted to the state of the block
+    /// preceding the aborted ones. Returns the hashes of the aborted blocks in ascending order.
+    pub fn abort_blocks(&mut self, starting_block_hash: BlockHash) -> DevnetResult<Vec<BlockHash>> {
+        let starting_block_number =
+            self.blocks.get_by_hash(starting_block_hash).ok_or(Error::NoBlock)?.block_number();
+        let latest_block_number = self.get_latest_block()?.block_number();
+
+        // if there is no preceding block, the state is reverted to the one on startup
+        let preceding_block = starting_block_number
+            .0
+            .checked_sub(1)
+            .and_then(|block_number| self.blocks.num_to_block.get(&BlockNumber(block_number)));
+        let (reverted_state, last_block_hash) = match preceding_block {
+            Some(block) => {
+                let state = self
+                    .blocks
+                    .num_to_state
+                    .get(&block.block_number())
+                    .ok_or(Error::NoStateAtBlock { block_number: block.block_number().0 })?;
+                (state.clone(), Some(block.block_hash()))
+            }
+            None => (self.create_genesis_instance()?.state, None),
+        };
+
+        let mut aborted_blocks = vec![];
+        let mut aborted_transactions = vec![];
+        for block_number in starting_block_number.0..=latest_block_number.0 {
+            if let Some(block) = self.blocks.remove(&BlockNumber(block_number)) {
+                aborted_transactions.extend(block.get_transactions().iter().copied());
+                aborted_blocks.push(block.block_hash());
+            }
+        }
+        aborted_transactions.extend(self.pending_block().get_transactions().iter().copied());
+
+        for transaction_hash in aborted_transactions.iter() {
+            if let Some(transaction) = self.transactions.get_by_hash_mut(transaction_hash) {
+                transaction.abort();
+                self.messaging.remove_messages_to_l1(&transaction.get_l2_to_l1_messages());
+            } else {
+                error!("Transaction is not present in the transactions collection");
+            }
+        }
+
+        self.state = reverted_state;
+        self.blocks.last_block_hash = last_block_hash;
+        self.pending_state_diff = StateDiff::default();
+        self.block_context.block_number = starting_block_number;
+        self.restart_pending_block()?;
+        // blocks replacing the aborted ones have to be flushed, even if the aborted ones were
+        self.messaging.next_block_to_flush =
+            self.messaging.next_block_to_flush.min(starting_block_number);
+
+        for transaction_hash in aborted_transactions {
+            self.send_update(ChainUpdate::TransactionStatus(transaction_hash));
+        }
+
+        // the dump file contains the aborted blocks as well, so it has to be rewritten
+        self.rewrite_dump_on_transaction()?;
+
+        Ok(aborted_blocks)
+    }
+
+    /// Rewrites the dump file if the state is dumped on each transaction, e.g. once blocks are
+    /// discarded. The file is removed if there are no accepted transactions left.
+    fn rewrite_dump_on_transaction(&self) -> DevnetResult<()> {
         if self.config.dump_on == Some(DumpMode::OnTransaction) {
-            self.dump_transaction(transaction)?;
+            match self.dump_state() {
+                Ok(()) => {}
+                Err(Error::NoTransaction) => match &self.config.dump_path {
+                    Some(dump_path) if std::path::Path::new(dump_path).exists() => {
+                        std::fs::remove_file(dump_path)?;
+                    }
+                    _ => {}
+                },
+                Err(err) => return Err(err),
+            }
         }
 
         Ok(())
@@ -348,7 +616,9 @@ impl Starknet {
 
     fn init_block_context(
         gas_price: u64,
-        fee_token_address: &str,
+        strk_gas_price: u64,
+        eth_fee_token_address: &str,
+        strk_fee_token_address: &str,
         chain_id: ChainId,
     ) -> BlockContext {
         use starknet_api::core::{ContractAddress, PatriciaKey};
@@ -360,9 +630,12 @@ impl Starknet {
         block_context.block_number = BlockNumber(0);
         block_context.block_timestamp = BlockTimestamp(0);
         block_context.gas_prices.eth_l1_gas_price = gas_price as u128;
+        block_context.gas_prices.strk_l1_gas_price = strk_gas_price as u128;
         block_context.chain_id = chain_id.into();
         block_context.fee_token_addresses.eth_fee_token_address =
-            contract_address!(fee_token_address);
+            contract_address!(eth_fee_token_address);
+        block_context.fee_token_addresses.strk_fee_token_address =
+            contract_address!(strk_fee_token_address);
         // inject cairo resource fee weights from starknet_in_rust
         block_context.vm_resource_fee_cost =
             std::sync::Arc::new(DEFAULT_CAIRO_RESOURCE_FEE_WEIGHTS.clone());
@@ -370,18 +643,116 @@ impl Starknet {
         block_context
     }
 
-    /// Should update block context with new block timestamp
-    /// and pointer to the next block number
-    fn update_block_context(block_context: &mut BlockContext) {
-        let current_timestamp_secs = SystemTime::now()
-            .duration_since(SystemTime::UNIX_EPOCH)
-            .expect("should get current UNIX timestamp")
-            .as_secs();
+    /// The block context in which a transaction paying its fee in `unit` is executed. The execution
+    /// engine charges every transaction in the ETH token, so for FRI the STRK token and its gas
+    /// price take the place of the ETH ones. This is how V3 transactions, which the engine executes
+    /// as the older versions they are emulated by, are charged in FRI: the actual fee is the L1
+    /// gas consumed times the STRK gas price, bounded by the max fee derived from the resource
+    /// bounds. The price per unit of the bounds does not set the price of the gas, which is why
+    /// [`Self::check_v3_transaction`] rejects bounds below the gas price.
+    pub(in crate::starknet) fn block_context_for_fee_unit(&self, unit: FeeUnit) -> BlockContext {
+        let mut block_context = self.block_context.clone();
+        if unit == FeeUnit::Fri {
+            block_context.fee_token_addresses.eth_fee_token_address =
+                block_context.fee_token_addresses.strk_fee_token_address;
+            block_context.gas_prices.eth_l1_gas_price = block_context.gas_prices.strk_l1_gas_price;
+        }
+
+        block_context
+    }
+
+    /// Rejects V3 transactions which could not be executed as specified, because of the emulation
+    /// by older versions: a max price per unit of L1 gas lower than the STRK gas price, since the
+    /// gas price is charged regardless, and a tip or paymaster data, which would be ignored.
+    pub(in crate::starknet) fn check_v3_transaction(
+        &self,
+        common: &BroadcastedTransactionCommonV3,
+    ) -> DevnetResult<()> {
+        let gas_price = self.block_context.gas_prices.strk_l1_gas_price;
+        if common.resource_bounds.l1_gas.max_price_per_unit < gas_price {
+            return Err(Error::FeeError {
+                reason: format!(
+                    "Max price per unit of L1 gas ({}) is lower than the gas price ({gas_price})",
+                    common.resource_bounds.l1_gas.max_price_per_unit
+                ),
+            });
+        }
+        if common.tip.0 != 0 {
+            return Err(Error::UnsupportedAction {
+                msg: "Transactions with a non-zero tip are not supported".to_string(),
+            });
+        }
+        if !common.paymaster_data.is_empty() {
+            return Err(Error::UnsupportedAction {
+                msg: "Transactions with paymaster data are not supported".to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Should update block context with new block timestamp, shifted by the provided amount of
+    /// seconds, and pointer to the next block number
+    fn update_block_context(block_context: &mut BlockContext, timestamp_shift_in_secs: i64) {
+        let current_timestamp_secs =
+            Self::get_unix_timestamp_as_seconds().saturating_add_signed(timestamp_shift_in_secs);
 
         block_context.block_number = block_context.block_number.next();
         block_context.block_timestamp = BlockTimestamp(current_timestamp_secs);
     }
 
+    fn get_unix_timestamp_as_seconds() -> u64 {
+        SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .expect("should get current UNIX timestamp")
+            .as_secs()
+    }
+
+    /// Sets the timestamp used for the pending block and the transactions executed in it
+    fn set_pending_block_timestamp(&mut self, timestamp: u64) {
+        self.block_context.block_timestamp = BlockTimestamp(timestamp);
+        self.blocks.pending_block.header.timestamp = BlockTimestamp(timestamp);
+    }
+
+    /// Sets the timestamp of the pending block. Timestamps of later blocks continue from it as
+    /// the system time passes. If `generate_block` is true, the pending block is sealed right
+    /// away and the hash of the new block is returned.
+    pub fn set_time(
+        &mut self,
+        timestamp: u64,
+        generate_block: bool,
+    ) -> DevnetResult<Option<BlockHash>> {
+        let signed_timestamp = i64::try_from(timestamp).map_err(|_| Error::UnsupportedAction {
+            msg: format!("Timestamp {timestamp} is too big"),
+        })?;
+        let current_timestamp = Self::get_unix_timestamp_as_seconds() as i64;
+
+        self.timestamp_shift_in_secs = signed_timestamp - current_timestamp;
+        self.set_pending_block_timestamp(timestamp);
+
+        if generate_block { self.create_block().map(Some) } else { Ok(None) }
+    }
+
+    /// Moves the timestamps of the pending block and of all later blocks forward by the provided
+    /// amount of seconds, relative to the system time
+    pub fn increase_time(&mut self, time_shift_in_secs: u64) -> DevnetResult<()> {
+        let too_big_error = || Error::UnsupportedAction {
+            msg: format!("Time increase of {time_shift_in_secs} seconds is too big"),
+        };
+        let signed_time_shift = i64::try_from(time_shift_in_secs).map_err(|_| too_big_error())?;
+
+        self.timestamp_shift_in_secs = self
+            .timestamp_shift_in_secs
+            .checked_add(signed_time_shift)
+            .ok_or_else(too_big_error)?;
+        self.set_pending_block_timestamp(
+            Self::get_unix_timestamp_as_seconds()
+                .saturating_add_signed(self.timestamp_shift_in_secs),
+        );
+
+        Ok(())
+    }
+
     fn pending_block(&self) -> &StarknetBlock {
         &self.blocks.pending_block
     }
@@ -392,6 +763,7 @@ impl Starknet {
 
         block.header.block_number = self.block_context.block_number;
         block.header.gas_price = GasPrice(self.block_context.gas_prices.eth_l1_gas_price);
+        block.l1_gas_price_in_fri = GasPrice(self.block_context.gas_prices.strk_l1_gas_price);
         block.header.sequencer = self.block_context.sequencer_address;
         block.header.timestamp = self.block_context.block_timestamp;
 
@@ -402,6 +774,18 @@ impl Starknet {
 
     fn get_state_at(&self, block_id: &BlockId) -> DevnetResult<&StarknetState> {
         match block_id {
+            // if transactions are accumulated in the pending block, the current state also contains
+            // its changes, so the latest state is the one saved with the last sealed block
+            BlockId::Tag(BlockTag::Latest) if self.accumulates_pending_transactions() => {
+                match self.blocks.get_by_block_id(*block_id) {
+                    Some(block) => self
+                        .blocks
+                        .num_to_state
+                        .get(&block.block_number())
+                        .ok_or(Error::NoStateAtBlock { block_number: block.block_number().0 }),
+                    None => Ok(&self.state),
+                }
+            }
             BlockId::Tag(_) => Ok(&self.state),
             _ => {
                 let block = self.blocks.get_by_block_id(*block_id).ok_or(Error::NoBlock)?;
@@ -493,6 +877,15 @@ impl Starknet {
         estimations::estimate_message_fee(self, block_id, message)
     }
 
+    /// Executes a declare transaction of version 0, e.g. when replaying the transactions of an
+    /// older chain
+    pub fn add_declare_transaction_v0(
+        &mut self,
+        declare_transaction: DeclareTransactionV0V1,
+    ) -> DevnetResult<(TransactionHash, ClassHash)> {
+        add_declare_transaction::add_declare_transaction_v0(self, declare_transaction)
+    }
+
     pub fn add_declare_transaction_v1(
         &mut self,
         declare_transaction: BroadcastedDeclareTransactionV1,
@@ -507,11 +900,23 @@ impl Starknet {
         add_declare_transaction::add_declare_transaction_v2(self, declare_transaction)
     }
 
+    pub fn add_declare_transaction_v3(
+        &mut self,
+        declare_transaction: BroadcastedDeclareTransactionV3,
+    ) -> DevnetResult<(TransactionHash, ClassHash)> {
+        add_declare_transaction::add_declare_transaction_v3(self, declare_transaction)
+    }
+
     /// returning the chain id as object
     pub fn chain_id(&self) -> ChainId {
         self.config.chain_id
     }
 
+    /// The cache through which Sierra classes are compiled to CASM, if one was configured
+    pub(crate) fn compilation_cache(&self) -> Option<&CompilationCache> {
+        self.state.state.state.compilation_cache.as_ref()
+    }
+
     pub fn add_deploy_account_transaction(
         &mut self,
         deploy_account_transaction: BroadcastedDeployAccountTransaction,
@@ -522,6 +927,14 @@ impl Starknet {
         )
     }
 
+    /// Executes a deploy transaction, e.g. when replaying the transactions of an older chain
+    pub fn add_deploy_transaction(
+        &mut self,
+        deploy_transaction: DeployTransaction,
+    ) -> DevnetResult<TransactionHash> {
+        add_deploy_transaction::add_deploy_transaction(self, deploy_transaction)
+    }
+
     pub fn add_invoke_transaction(
         &mut self,
         invoke_transaction: BroadcastedInvokeTransaction,
@@ -529,8 +942,29 @@ impl Starknet {
         add_invoke_transaction::add_invoke_transaction(self, invoke_transaction)
     }
 
-    /// Creates an invoke tx for minting, using the chargeable account.
-    pub async fn mint(&mut self, address: ContractAddress, amount: u128) -> DevnetResult<Felt> {
+    /// Executes an invoke transaction of version 0, e.g. when replaying the transactions of an
+    /// older chain
+    pub fn add_invoke_transaction_v0(
+        &mut self,
+        invoke_transaction: InvokeTransactionV0,
+    ) -> DevnetResult<TransactionHash> {
+        add_invoke_transaction::add_invoke_transaction_v0(self, invoke_transaction)
+    }
+
+    pub fn add_l1_handler_transaction(
+        &mut self,
+        l1_handler_transaction: L1HandlerTransaction,
+    ) -> DevnetResult<TransactionHash> {
+        add_l1_handler_transaction::add_l1_handler_transaction(self, l1_handler_transaction)
+    }
+
+    /// Creates an invoke tx for minting in the fee token of `unit`, using the chargeable account.
+    pub async fn mint(
+        &mut self,
+        address: ContractAddress,
+        amount: u128,
+        unit: FeeUnit,
+    ) -> DevnetResult<Felt> {
         let sufficiently_big_max_fee: u128 = self.config.gas_price as u128 * 1_000_000;
         let chargeable_address_felt = Felt::from_prefixed_hex_str(CHARGEABLE_ACCOUNT_ADDRESS)?;
         let nonce =
@@ -544,7 +978,10 @@ impl Starknet {
             FieldElement::from(0u32),   // `high` part
         ];
 
-        let erc20_address_felt = Felt::from_prefixed_hex_str(ERC20_CONTRACT_ADDRESS)?;
+        let erc20_address_felt = Felt::from_prefixed_hex_str(match unit {
+            FeeUnit::Wei => ERC20_CONTRACT_ADDRESS,
+            FeeUnit::Fri => STRK_ERC20_CONTRACT_ADDRESS,
+        })?;
         let raw_execution = RawExecution {
             calls: vec![Call {
                 to: erc20_address_felt.into(),
@@ -568,7 +1005,7 @@ impl Starknet {
         );
         let signature = signer.sign_hash(&msg_hash_felt).await?;
 
-        let invoke_tx = BroadcastedInvokeTransaction {
+        let invoke_tx = BroadcastedInvokeTransactionV1 {
             sender_address: ContractAddress::new(chargeable_address_felt)?,
             calldata: raw_execution.raw_calldata().into_iter().map(|c| c.into()).collect(),
             common: BroadcastedTransactionCommon {
@@ -580,13 +1017,29 @@ impl Starknet {
         };
 
         // apply the invoke tx
-        self.add_invoke_transaction(invoke_tx)
+        self.add_invoke_transaction(BroadcastedInvokeTransaction::V1(invoke_tx))
     }
 
     pub fn block_state_update(&self, block_id: BlockId) -> DevnetResult<StateUpdate> {
         state_update::state_update_by_block_id(self, block_id)
     }
 
+    pub fn get_storage_proof(
+        &self,
+        block_id: BlockId,
+        class_hashes: &[ClassHash],
+        contract_addresses: &[ContractAddress],
+        contracts_storage_keys: &[ContractStorageKeys],
+    ) -> DevnetResult<StorageProof> {
+        storage_proof::get_storage_proof(
+            self,
+            block_id,
+            class_hashes,
+            contract_addresses,
+            contracts_storage_keys,
+        )
+    }
+
     pub fn get_block_txs_count(&self, block_id: BlockId) -> DevnetResult<u64> {
         let block = self.blocks.get_by_block_id(block_id).ok_or(Error::NoBlock)?;
 
@@ -654,7 +1107,7 @@ impl Starknet {
     pub fn get_latest_block(&self) -> DevnetResult<StarknetBlock> {
         let block = self
             .blocks
-            .get_by_block_id(BlockId::Tag(starknet_rs_core::types::BlockTag::Latest))
+            .get_by_block_id(BlockId::Tag(BlockTag::Latest))
             .ok_or(crate::error::Error::NoBlock)?;
 
         Ok(block.clone())
@@ -679,6 +1132,40 @@ impl Starknet {
         events::get_events(self, from_block, to_block, address, keys, skip, limit)
     }
 
+    pub fn get_transaction_status(
+        &self,
+        transaction_hash: TransactionHash,
+    ) -> DevnetResult<TransactionStatus> {
+        let transaction = self.transactions.get(&transaction_hash).ok_or(Error::NoTransaction)?;
+
+        if let Some(rejection_reason) = transaction.get_rejection_reason() {
+            return Ok(TransactionStatus {
+                finality_status: FinalityStatus::Rejected,
+                execution_status: None,
+                failure_reason: Some(rejection_reason.to_string()),
+            });
+        }
+
+        // transactions of the pending block are not final yet
+        Ok(TransactionStatus {
+            finality_status: transaction
+                .finality_status
+                .map_or(FinalityStatus::Received, FinalityStatus::from),
+            execution_status: transaction.get_execution_status(),
+            failure_reason: None,
+        })
+    }
+
+    /// Returns the transactions which failed validation or were in an aborted block, in the order
+    /// in which they were received
+    pub fn get_rejected_transactions(&self) -> Vec<&StarknetTransaction> {
+        self.transactions
+            .iter()
+            .map(|(_, transaction)| transaction)
+            .filter(|transaction| transaction.get_rejection_reason().is_some())
+            .collect()
+    }
+
     pub fn get_transaction_receipt_by_hash(
         &self,
         transaction_hash: TransactionHash,
@@ -689,6 +1176,20 @@ impl Starknet {
         transaction_to_map.get_receipt()
     }
 
+    pub fn get_transaction_trace(
+        &self,
+        transaction_hash: TransactionHash,
+    ) -> DevnetResult<TransactionTrace> {
+        transaction_trace::get_transaction_trace(self, transaction_hash)
+    }
+
+    pub fn get_transaction_traces_from_block(
+        &self,
+        block_id: BlockId,
+    ) -> DevnetResult<Vec<BlockTransactionTrace>> {
+        transaction_trace::get_transaction_traces_from_block(self, block_id)
+    }
+
     pub fn simulate_transactions(
         &self,
         block_id: BlockId,
@@ -713,94 +1214,27 @@ impl Starknet {
         let mut transactions_traces: Vec<TransactionTrace> = vec![];
 
         for broadcasted_transaction in transactions.iter() {
-            let blockifier_transaction =
-                broadcasted_transaction.to_blockifier_account_transaction(chain_id)?;
+            let blockifier_transaction = broadcasted_transaction
+                .to_blockifier_account_transaction(chain_id, self.compilation_cache())?;
             let tx_execution_info = blockifier_transaction.execute(
                 &mut state.state,
-                &self.block_context,
+                &self.block_context_for_fee_unit(broadcasted_transaction.get_fee_unit()),
                 !skip_fee_charge,
                 !skip_validate,
             )?;
-            let address_to_class_hash_map = &state.state.state.address_to_class_hash;
-
-            let validate_invocation =
-                if let Some(validate_info) = tx_execution_info.validate_call_info {
-                    Some(FunctionInvocation::try_from_call_info(
-                        validate_info,
-                        address_to_class_hash_map,
-                    )?)
-                } else {
-                    None
-                };
+            let address_to_class_hash_map: HashMap<ContractAddress, ClassHash> =
+                state.state.state.address_to_class_hash.clone().into_iter().collect();
 
-            let fee_transfer_invocation =
-                if let Some(fee_transfer_info) = tx_execution_info.fee_transfer_call_info {
-                    Some(FunctionInvocation::try_from_call_info(
-                        fee_transfer_info,
-                        address_to_class_hash_map,
-                    )?)
-                } else {
-                    None
-                };
-
-            let trace = match broadcasted_transaction {
-                BroadcastedTransaction::Declare(_) => {
-                    TransactionTrace::Declare(DeclareTransactionTrace {
-                        validate_invocation,
-                        fee_transfer_invocation,
-                    })
-                }
-                BroadcastedTransaction::DeployAccount(_) => {
-                    TransactionTrace::DeployAccount(DeployAccountTransactionTrace {
-                        validate_invocation,
-                        constructor_invocation: if let Some(call_info) =
-                            tx_execution_info.execute_call_info
-                        {
-                            Some(FunctionInvocation::try_from_call_info(
-                                call_info,
-                                address_to_class_hash_map,
-                            )?)
-                        } else {
-                            None
-                        },
-                        fee_transfer_invocation,
-                    })
-                }
-                BroadcastedTransaction::Invoke(_) => {
-                    TransactionTrace::Invoke(InvokeTransactionTrace {
-                        validate_invocation,
-                        execution_invocation: match tx_execution_info.execute_call_info {
-                            Some(call_info) => match call_info.execution.failed {
-                                false => ExecutionInvocation::Succeeded(
-                                    FunctionInvocation::try_from_call_info(
-                                        call_info,
-                                        address_to_class_hash_map,
-                                    )?,
-                                ),
-                                true => ExecutionInvocation::Reverted(
-                                    starknet_types::rpc::transactions::Reversion {
-                                        revert_reason: tx_execution_info
-                                            .revert_error
-                                            .unwrap_or("Revert reason not found".into()),
-                                    },
-                                ),
-                            },
-                            None => match tx_execution_info.revert_error {
-                                Some(revert_reason) => ExecutionInvocation::Reverted(
-                                    starknet_types::rpc::transactions::Reversion { revert_reason },
-                                ),
-                                None => {
-                                    return Err(Error::UnexpectedInternalError {
-                                        msg: "Simulation contains neither call_info nor \
-                                              revert_error"
-                                            .into(),
-                                    });
-                                }
-                            },
-                        },
-                    })
-                }
+            let transaction_type = match broadcasted_transaction {
+                BroadcastedTransaction::Declare(_) => TransactionType::Declare,
+                BroadcastedTransaction::DeployAccount(_) => TransactionType::DeployAccount,
+                BroadcastedTransaction::Invoke(_) => TransactionType::Invoke,
             };
+            let trace = transaction_trace::create_trace(
+                transaction_type,
+                &tx_execution_info,
+                &address_to_class_hash_map,
+            )?;
 
             transactions_traces.push(trace);
         }
@@ -842,23 +1276,46 @@ impl Starknet {
 mod tests {
     use blockifier::state::state_api::State;
     use blockifier::transaction::errors::TransactionExecutionError;
+    use blockifier::transaction::objects::TransactionExecutionInfo;
     use starknet_api::block::{BlockHash, BlockNumber, BlockStatus, BlockTimestamp, GasPrice};
-    use starknet_rs_core::types::{BlockId, BlockTag};
+    use starknet_rs_core::types::{BlockId, BlockTag, TransactionExecutionStatus};
     use starknet_types::contract_address::ContractAddress;
     use starknet_types::felt::Felt;
+    use starknet_types::patricia_key::PatriciaKey;
+    use starknet_types::rpc::estimate_message_fee::FeeUnit;
+    use starknet_types::rpc::transactions::declare_transaction_v0v1::DeclareTransactionV0V1;
+    use starknet_types::rpc::transactions::{DeclareTransaction, FinalityStatus, Transaction};
 
-    use super::Starknet;
+    use super::{ChainUpdate, ForkConfig, Starknet, StarknetConfig};
     use crate::blocks::StarknetBlock;
     use crate::constants::{
         DEVNET_DEFAULT_CHAIN_ID, DEVNET_DEFAULT_INITIAL_BALANCE, ERC20_CONTRACT_ADDRESS,
+        STRK_ERC20_CONTRACT_ADDRESS,
     };
     use crate::error::{DevnetResult, Error};
     use crate::state::state_diff::StateDiff;
     use crate::traits::{Accounted, StateChanger, StateExtractor};
+    use crate::transactions::StarknetTransaction;
     use crate::utils::test_utils::{
-        dummy_contract_address, dummy_declare_transaction_v1, dummy_felt, starknet_config_for_test,
+        dummy_contract_address, dummy_declare_transaction_v1, dummy_felt, mock_origin_handler,
+        spawn_mock_json_rpc_server, starknet_config_for_test, MOCK_ORIGIN_BLOCK_NUMBER,
+        MOCK_ORIGIN_CLASS_HASH, MOCK_ORIGIN_CONTRACT_ADDRESS, MOCK_ORIGIN_NONCE,
+        MOCK_ORIGIN_STORAGE_VALUE,
     };
 
+    /// Adds `declaration` to the pending block and to the transactions, as if it was executed
+    fn add_executed_declaration(starknet: &mut Starknet, declaration: DeclareTransactionV0V1) {
+        let transaction_hash = declaration.transaction_hash;
+        starknet.transactions.insert(
+            &transaction_hash,
+            StarknetTransaction::create_accepted(
+                &Transaction::Declare(DeclareTransaction::Version1(declaration)),
+                TransactionExecutionInfo::default(),
+            ),
+        );
+        starknet.blocks.pending_block.add_transaction(transaction_hash);
+    }
+
     #[test]
     fn correct_initial_state_with_test_config() {
         let config = starknet_config_for_test();
@@ -867,8 +1324,10 @@ mod tests {
         let expected_balance = config.predeployed_accounts_initial_balance;
 
         for account in predeployed_accounts {
-            let account_balance = account.get_balance(&mut starknet.state).unwrap();
-            assert_eq!(expected_balance, account_balance);
+            for unit in [FeeUnit::Wei, FeeUnit::Fri] {
+                let account_balance = account.get_balance(&mut starknet.state, unit).unwrap();
+                assert_eq!(expected_balance, account_balance);
+            }
         }
     }
 
@@ -876,14 +1335,59 @@ mod tests {
     fn correct_block_context_creation() {
         let fee_token_address =
             ContractAddress::new(Felt::from_prefixed_hex_str("0xAA").unwrap()).unwrap();
-        let block_ctx = Starknet::init_block_context(10, "0xAA", DEVNET_DEFAULT_CHAIN_ID);
+        let strk_fee_token_address =
+            ContractAddress::new(Felt::from_prefixed_hex_str("0xBB").unwrap()).unwrap();
+        let block_ctx =
+            Starknet::init_block_context(10, 20, "0xAA", "0xBB", DEVNET_DEFAULT_CHAIN_ID);
         assert_eq!(block_ctx.block_number, BlockNumber(0));
         assert_eq!(block_ctx.block_timestamp, BlockTimestamp(0));
         assert_eq!(block_ctx.gas_prices.eth_l1_gas_price, 10);
+        assert_eq!(block_ctx.gas_prices.strk_l1_gas_price, 20);
         assert_eq!(
             ContractAddress::from(block_ctx.fee_token_addresses.eth_fee_token_address),
             fee_token_address
         );
+        assert_eq!(
+            ContractAddress::from(block_ctx.fee_token_addresses.strk_fee_token_address),
+            strk_fee_token_address
+        );
+    }
+
+    #[test]
+    fn compilation_cache_is_created_in_configured_directory() {
+        let parent_dir = tempfile::tempdir().unwrap();
+        let cache_dir = parent_dir.path().join("casm");
+        let config = StarknetConfig {
+            compilation_cache_dir: Some(cache_dir.to_str().unwrap().to_string()),
+            ..starknet_config_for_test()
+        };
+
+        assert!(Starknet::new(&starknet_config_for_test()).unwrap().compilation_cache().is_none());
+        assert!(Starknet::new(&config).unwrap().compilation_cache().is_some());
+        assert!(cache_dir.is_dir());
+    }
+
+    #[test]
+    fn block_context_for_fri_charges_in_strk() {
+        let config =
+            StarknetConfig { gas_price: 10, strk_gas_price: 20, ..starknet_config_for_test() };
+        let starknet = Starknet::new(&config).unwrap();
+
+        let wei_block_ctx = starknet.block_context_for_fee_unit(FeeUnit::Wei);
+        assert_eq!(wei_block_ctx.gas_prices.eth_l1_gas_price, 10);
+        assert_eq!(
+            ContractAddress::from(wei_block_ctx.fee_token_addresses.eth_fee_token_address),
+            ContractAddress::new(Felt::from_prefixed_hex_str(ERC20_CONTRACT_ADDRESS).unwrap())
+                .unwrap()
+        );
+
+        let fri_block_ctx = starknet.block_context_for_fee_unit(FeeUnit::Fri);
+        assert_eq!(fri_block_ctx.gas_prices.eth_l1_gas_price, 20);
+        assert_eq!(
+            ContractAddress::from(fri_block_ctx.fee_token_addresses.eth_fee_token_address),
+            ContractAddress::new(Felt::from_prefixed_hex_str(STRK_ERC20_CONTRACT_ADDRESS).unwrap())
+                .unwrap()
+        );
     }
 
     #[test]
@@ -903,8 +1407,8 @@ mod tests {
 
         let tx = dummy_declare_transaction_v1();
 
-        // add transaction hash to pending block
-        starknet.blocks.pending_block.add_transaction(tx.transaction_hash);
+        // add transaction to pending block
+        add_executed_declaration(&mut starknet, tx.clone());
 
         // pending block has some transactions
         assert!(!starknet.pending_block().get_transactions().is_empty());
@@ -929,6 +1433,7 @@ mod tests {
 
         let initial_block_number = starknet.block_context.block_number;
         let initial_gas_price = starknet.block_context.gas_prices.eth_l1_gas_price;
+        let initial_strk_gas_price = starknet.block_context.gas_prices.strk_l1_gas_price;
         let initial_block_timestamp = starknet.block_context.block_timestamp;
         let initial_sequencer = starknet.block_context.sequencer_address;
 
@@ -951,18 +1456,157 @@ mod tests {
         assert_eq!(starknet.pending_block().header.block_number, initial_block_number);
         assert_eq!(starknet.pending_block().header.parent_hash, BlockHash::default());
         assert_eq!(starknet.pending_block().header.gas_price, GasPrice(initial_gas_price));
+        assert_eq!(starknet.pending_block().l1_gas_price_in_fri, GasPrice(initial_strk_gas_price));
         assert_eq!(starknet.pending_block().header.sequencer, initial_sequencer);
     }
 
     #[test]
     fn correct_block_context_update() {
-        let mut block_ctx = Starknet::init_block_context(0, "0x0", DEVNET_DEFAULT_CHAIN_ID);
+        let mut block_ctx =
+            Starknet::init_block_context(0, 0, "0x0", "0x0", DEVNET_DEFAULT_CHAIN_ID);
         let initial_block_number = block_ctx.block_number;
-        Starknet::update_block_context(&mut block_ctx);
+        Starknet::update_block_context(&mut block_ctx, 0);
 
         assert_eq!(block_ctx.block_number, initial_block_number.next());
     }
 
+    #[test]
+    fn block_context_update_applies_timestamp_shift() {
+        let mut block_ctx =
+            Starknet::init_block_context(0, 0, "0x0", "0x0", DEVNET_DEFAULT_CHAIN_ID);
+        let shift_in_secs = 1000;
+        let lower_bound = Starknet::get_unix_timestamp_as_seconds() + shift_in_secs;
+        Starknet::update_block_context(&mut block_ctx, shift_in_secs as i64);
+        let upper_bound = Starknet::get_unix_timestamp_as_seconds() + shift_in_secs;
+
+        assert!(block_ctx.block_timestamp.0 >= lower_bound);
+        assert!(block_ctx.block_timestamp.0 <= upper_bound);
+    }
+
+    #[test]
+    fn set_time_with_block_generation() {
+        let config = starknet_config_for_test();
+        let mut starknet = Starknet::new(&config).unwrap();
+
+        let timestamp = 1_000_000;
+        let block_hash = starknet.set_time(timestamp, true).unwrap().unwrap();
+
+        let block = starknet.get_latest_block().unwrap();
+        assert_eq!(block.block_hash(), block_hash);
+        assert_eq!(block.header.timestamp, BlockTimestamp(timestamp));
+
+        // the following blocks are not generated with the system time
+        let pending_block_timestamp = starknet.pending_block().header.timestamp.0;
+        assert!(pending_block_timestamp >= timestamp);
+        assert!(pending_block_timestamp < Starknet::get_unix_timestamp_as_seconds());
+    }
+
+    #[test]
+    fn set_time_without_block_generation() {
+        let config = starknet_config_for_test();
+        let mut starknet = Starknet::new(&config).unwrap();
+
+        let timestamp = 1_000_000;
+        assert_eq!(starknet.set_time(timestamp, false).unwrap(), None);
+
+        starknet.get_latest_block().err().unwrap();
+        assert_eq!(starknet.pending_block().header.timestamp, BlockTimestamp(timestamp));
+        assert_eq!(starknet.block_context.block_timestamp, BlockTimestamp(timestamp));
+
+        starknet.create_block().unwrap();
+        assert_eq!(
+            starknet.get_latest_block().unwrap().header.timestamp,
+            BlockTimestamp(timestamp)
+        );
+    }
+
+    #[test]
+    fn increase_time_shifts_timestamps_of_pending_and_later_blocks() {
+        let config = starknet_config_for_test();
+        let mut starknet = Starknet::new(&config).unwrap();
+
+        let increase = 1000;
+        let lower_bound = Starknet::get_unix_timestamp_as_seconds() + increase;
+        starknet.increase_time(increase).unwrap();
+        let pending_block_timestamp = starknet.pending_block().header.timestamp;
+        assert!(pending_block_timestamp.0 >= lower_bound);
+
+        starknet.create_block().unwrap();
+        assert_eq!(starknet.get_latest_block().unwrap().header.timestamp, pending_block_timestamp);
+
+        // the shift is kept for the following blocks
+        assert!(starknet.pending_block().header.timestamp.0 >= lower_bound);
+    }
+
+    #[test]
+    fn increase_time_rejects_too_big_values() {
+        let config = starknet_config_for_test();
+        let mut starknet = Starknet::new(&config).unwrap();
+
+        match starknet.increase_time(u64::MAX) {
+            Err(Error::UnsupportedAction { .. }) => (),
+            unexpected => panic!("Should have failed; got {unexpected:?}"),
+        }
+    }
+
+    #[test]
+    fn forked_state_falls_back_to_origin() {
+        let (url, _) = spawn_mock_json_rpc_server(mock_origin_handler);
+        let config = StarknetConfig {
+            fork_config: ForkConfig { url: Some(url), block: None },
+            ..starknet_config_for_test()
+        };
+        let starknet = Starknet::new(&config).unwrap();
+
+        // the latest block of the origin is used and new blocks are generated on top of it
+        assert_eq!(starknet.config.fork_config.block, Some(MOCK_ORIGIN_BLOCK_NUMBER));
+        assert_eq!(
+            starknet.pending_block().block_number(),
+            BlockNumber(MOCK_ORIGIN_BLOCK_NUMBER + 1)
+        );
+
+        let block_id = BlockId::Tag(BlockTag::Latest);
+        let address = ContractAddress::new(Felt::from(MOCK_ORIGIN_CONTRACT_ADDRESS)).unwrap();
+        assert_eq!(
+            starknet.get_class_hash_at(block_id, address).unwrap(),
+            Felt::from(MOCK_ORIGIN_CLASS_HASH)
+        );
+        assert_eq!(
+            starknet.contract_nonce_at_block(block_id, address).unwrap(),
+            Felt::from(MOCK_ORIGIN_NONCE)
+        );
+        let storage_key = PatriciaKey::new(dummy_felt()).unwrap();
+        assert_eq!(
+            starknet.contract_storage_at_block(block_id, address, storage_key).unwrap(),
+            Felt::from(MOCK_ORIGIN_STORAGE_VALUE)
+        );
+
+        // contracts unknown to the origin are not deployed
+        match starknet.contract_nonce_at_block(block_id, dummy_contract_address()) {
+            Err(Error::ContractNotFound) => (),
+            unexpected => panic!("Should have failed; got {unexpected:?}"),
+        }
+    }
+
+    #[test]
+    fn local_state_of_fork_takes_precedence_over_origin() {
+        let (url, _) = spawn_mock_json_rpc_server(mock_origin_handler);
+        let config = StarknetConfig {
+            fork_config: ForkConfig { url: Some(url), block: Some(3) },
+            ..starknet_config_for_test()
+        };
+        let mut starknet = Starknet::new(&config).unwrap();
+        assert_eq!(starknet.pending_block().block_number(), BlockNumber(4));
+
+        let address = ContractAddress::new(Felt::from(MOCK_ORIGIN_CONTRACT_ADDRESS)).unwrap();
+        starknet.state.increment_nonce(address).unwrap();
+
+        assert_eq!(
+            starknet.contract_nonce_at_block(BlockId::Tag(BlockTag::Latest), address).unwrap(),
+            Felt::from(MOCK_ORIGIN_NONCE + 1)
+        );
+    }
+
     #[test]
     fn getting_state_of_latest_block() {
         let config = starknet_config_for_test();
@@ -1052,13 +1696,14 @@ mod tests {
     /// utility method for happy path balance retrieval
     fn get_balance_at(
         starknet: &Starknet,
+        fee_token_address: &str,
         contract_address: ContractAddress,
     ) -> DevnetResult<Vec<Felt>> {
         let entry_point_selector =
             starknet_rs_core::utils::get_selector_from_name("balanceOf").unwrap();
         starknet.call(
             BlockId::Tag(BlockTag::Latest),
-            Felt::from_prefixed_hex_str(ERC20_CONTRACT_ADDRESS)?,
+            Felt::from_prefixed_hex_str(fee_token_address)?,
             entry_point_selector.into(),
             vec![Felt::from(contract_address)],
         )
@@ -1070,12 +1715,16 @@ mod tests {
         let starknet = Starknet::new(&config).unwrap();
 
         let predeployed_account = &starknet.predeployed_accounts.get_accounts()[0];
-        let result = get_balance_at(&starknet, predeployed_account.account_address).unwrap();
-
         let balance_hex = format!("0x{:x}", DEVNET_DEFAULT_INITIAL_BALANCE);
         let balance_felt = Felt::from_prefixed_hex_str(balance_hex.as_str()).unwrap();
         let balance_uint256 = vec![balance_felt, Felt::from_prefixed_hex_str("0x0").unwrap()];
-        assert_eq!(result, balance_uint256);
+
+        for fee_token_address in [ERC20_CONTRACT_ADDRESS, STRK_ERC20_CONTRACT_ADDRESS] {
+            let result =
+                get_balance_at(&starknet, fee_token_address, predeployed_account.account_address)
+                    .unwrap();
+            assert_eq!(result, balance_uint256);
+        }
     }
 
     #[test]
@@ -1085,7 +1734,7 @@ mod tests {
 
         let undeployed_address =
             ContractAddress::new(Felt::from_prefixed_hex_str("0x1234").unwrap()).unwrap();
-        let result = get_balance_at(&starknet, undeployed_address).unwrap();
+        let result = get_balance_at(&starknet, ERC20_CONTRACT_ADDRESS, undeployed_address).unwrap();
 
         let zero = Felt::from_prefixed_hex_str("0x0").unwrap();
         let expected_balance_uint256 = vec![zero, zero];
@@ -1130,10 +1779,8 @@ mod tests {
 
         assert_eq!(num_no_transactions.unwrap(), 0);
 
-        let tx = dummy_declare_transaction_v1();
-
-        // add transaction hash to pending block
-        starknet.blocks.pending_block.add_transaction(tx.transaction_hash);
+        // add transaction to pending block
+        add_executed_declaration(&mut starknet, dummy_declare_transaction_v1());
 
         starknet.generate_new_block(StateDiff::default()).unwrap();
 
@@ -1223,4 +1870,248 @@ mod tests {
 
         assert_eq!(latest_block.unwrap().block_number(), BlockNumber(2));
     }
+
+    #[test]
+    fn transactions_of_pending_block_are_received_until_it_is_sealed() {
+        let config = StarknetConfig { blocks_on_demand: true, ..starknet_config_for_test() };
+        let mut starknet = Starknet::new(&config).unwrap();
+        let transaction_hash = Felt::from(1);
+        let mut declare_transaction = dummy_declare_transaction_v1();
+        declare_transaction.transaction_hash = transaction_hash;
+        starknet
+            .handle_accepted_transaction(
+                &transaction_hash,
+                &Transaction::Declare(DeclareTransaction::Version1(declare_transaction)),
+                TransactionExecutionInfo::default(),
+            )
+            .unwrap();
+
+        let status = starknet.get_transaction_status(transaction_hash).unwrap();
+        assert_eq!(status.finality_status, FinalityStatus::Received);
+        assert_eq!(status.execution_status, Some(TransactionExecutionStatus::Succeeded));
+
+        starknet.create_block().unwrap();
+        let status = starknet.get_transaction_status(transaction_hash).unwrap();
+        assert_eq!(status.finality_status, FinalityStatus::AcceptedOnL2);
+    }
+
+    #[test]
+    fn transactions_are_accumulated_in_pending_block_with_blocks_on_demand() {
+        let config = StarknetConfig { blocks_on_demand: true, ..starknet_config_for_test() };
+        let mut starknet = Starknet::new(&config).unwrap();
+
+        let mut transaction_hashes = vec![];
+        for (nonce, transaction_hash) in [(1, Felt::from(1)), (2, Felt::from(2))] {
+            let mut declare_transaction = dummy_declare_transaction_v1();
+            declare_transaction.transaction_hash = transaction_hash;
+            let transaction =
+                Transaction::Declare(DeclareTransaction::Version1(declare_transaction));
+
+            starknet
+                .state
+                .state
+                .increment_nonce(dummy_contract_address().try_into().unwrap())
+                .unwrap();
+            starknet
+                .handle_accepted_transaction(
+                    &transaction_hash,
+                    &transaction,
+                    TransactionExecutionInfo::default(),
+                )
+                .unwrap();
+
+            assert_eq!(
+                starknet.pending_state_diff.address_to_nonce.get(&dummy_contract_address()),
+                Some(&Felt::from(nonce))
+            );
+            transaction_hashes.push(transaction_hash);
+        }
+
+        // no block is generated until requested
+        starknet.get_latest_block().err().unwrap();
+        assert_eq!(starknet.pending_block().get_transactions(), &transaction_hashes);
+        let pending_block = starknet.get_block(BlockId::Tag(BlockTag::Pending)).unwrap();
+        assert_eq!(pending_block.get_transactions(), &transaction_hashes);
+
+        let block_hash = starknet.create_block().unwrap();
+
+        let latest_block = starknet.get_latest_block().unwrap();
+        assert_eq!(latest_block.block_hash(), block_hash);
+        assert_eq!(latest_block.block_number(), BlockNumber(0));
+        assert_eq!(latest_block.get_transactions(), &transaction_hashes);
+        assert!(starknet.pending_block().get_transactions().is_empty());
+
+        let mut expected_state_diff = StateDiff::default();
+        expected_state_diff.address_to_nonce.insert(dummy_contract_address(), Felt::from(2));
+        assert_eq!(
+            starknet.blocks.num_to_state_diff.get(&BlockNumber(0)),
+            Some(&expected_state_diff)
+        );
+        assert_eq!(starknet.pending_state_diff, StateDiff::default());
+    }
+
+    #[test]
+    fn create_block_generates_empty_block() {
+        let config = starknet_config_for_test();
+        let mut starknet = Starknet::new(&config).unwrap();
+
+        starknet.create_block().unwrap();
+        starknet.create_block().unwrap();
+
+        let latest_block = starknet.get_latest_block().unwrap();
+        assert_eq!(latest_block.block_number(), BlockNumber(1));
+        assert!(latest_block.get_transactions().is_empty());
+    }
+
+    /// Adds a transaction which increments the nonce of the dummy contract and puts it in a new
+    /// block. Returns the hash of the new block.
+    fn generate_block_with_nonce_increment(
+        starknet: &mut Starknet,
+        transaction_hash: Felt,
+    ) -> Felt {
+        let mut declare_transaction = dummy_declare_transaction_v1();
+        declare_transaction.transaction_hash = transaction_hash;
+        let transaction = Transaction::Declare(DeclareTransaction::Version1(declare_transaction));
+
+        starknet.state.state.increment_nonce(dummy_contract_address().try_into().unwrap()).unwrap();
+        starknet
+            .handle_accepted_transaction(
+                &transaction_hash,
+                &transaction,
+                TransactionExecutionInfo::default(),
+            )
+            .unwrap();
+
+        starknet.get_latest_block().unwrap().block_hash()
+    }
+
+    #[test]
+    fn abort_blocks_reverts_state_and_rejects_transactions() {
+        let config = starknet_config_for_test();
+        let mut starknet = Starknet::new(&config).unwrap();
+
+        let first_block_hash = generate_block_with_nonce_increment(&mut starknet, Felt::from(1));
+        let second_block_hash = generate_block_with_nonce_increment(&mut starknet, Felt::from(2));
+        let third_block_hash = generate_block_with_nonce_increment(&mut starknet, Felt::from(3));
+
+        let aborted_blocks = starknet.abort_blocks(second_block_hash).unwrap();
+        assert_eq!(aborted_blocks, vec![second_block_hash, third_block_hash]);
+
+        let latest_block = starknet.get_latest_block().unwrap();
+        assert_eq!(latest_block.block_hash(), first_block_hash);
+        assert!(starknet.get_block(BlockId::Hash(second_block_hash.into())).is_err());
+        assert!(starknet.get_block(BlockId::Number(2)).is_err());
+        assert_eq!(
+            starknet
+                .contract_nonce_at_block(BlockId::Tag(BlockTag::Latest), dummy_contract_address())
+                .unwrap(),
+            Felt::from(1)
+        );
+
+        // the first transaction is untouched, the others are rejected
+        let accepted_transaction = starknet.transactions.get(&Felt::from(1)).unwrap();
+        assert_eq!(accepted_transaction.block_hash, Some(first_block_hash));
+        for transaction_hash in [Felt::from(2), Felt::from(3)] {
+            let aborted_transaction = starknet.transactions.get(&transaction_hash).unwrap();
+            assert_eq!(aborted_transaction.finality_status, None);
+            assert_eq!(aborted_transaction.get_rejection_reason(), Some("Block aborted"));
+            assert_eq!(aborted_transaction.block_hash, None);
+            assert_eq!(aborted_transaction.block_number, None);
+        }
+
+        // the next block continues from the last non-aborted one
+        let new_block_hash = generate_block_with_nonce_increment(&mut starknet, Felt::from(4));
+        let new_block = starknet.get_latest_block().unwrap();
+        assert_eq!(new_block.block_number(), BlockNumber(1));
+        assert_eq!(new_block.parent_hash(), first_block_hash);
+        assert_ne!(new_block_hash, second_block_hash);
+    }
+
+    #[test]
+    fn abort_blocks_from_first_block_reverts_to_initial_state() {
+        let config = starknet_config_for_test();
+        let mut starknet = Starknet::new(&config).unwrap();
+
+        let first_block_hash = generate_block_with_nonce_increment(&mut starknet, Felt::from(1));
+        let aborted_blocks = starknet.abort_blocks(first_block_hash).unwrap();
+        assert_eq!(aborted_blocks, vec![first_block_hash]);
+
+        starknet.get_latest_block().err().unwrap();
+        assert!(
+            starknet.state.state.state.address_to_nonce.get(&dummy_contract_address()).is_none()
+        );
+        assert_eq!(starknet.pending_block().block_number(), BlockNumber(0));
+    }
+
+    #[test]
+    fn abort_blocks_with_nonexistent_hash() {
+        let config = starknet_config_for_test();
+        let mut starknet = Starknet::new(&config).unwrap();
+
+        match starknet.abort_blocks(Felt::from(1)) {
+            Err(Error::NoBlock) => (),
+            unexpected => panic!("Should have failed; got {unexpected:?}"),
+        }
+    }
+
+    #[test]
+    fn restart_resets_blocks_transactions_and_state() {
+        let config = starknet_config_for_test();
+        let mut starknet = Starknet::new(&config).unwrap();
+        let initial_block_number = starknet.block_context.block_number;
+
+        add_executed_declaration(&mut starknet, dummy_declare_transaction_v1());
+        starknet.state.state.increment_nonce(dummy_contract_address().try_into().unwrap()).unwrap();
+        let state_diff = starknet.state.extract_state_diff_from_pending_state().unwrap();
+        starknet.state.apply_state_difference(state_diff.clone()).unwrap();
+        starknet.generate_new_block(state_diff).unwrap();
+        starknet.generate_pending_block().unwrap();
+
+        starknet.restart().unwrap();
+
+        starknet.get_latest_block().err().unwrap();
+        assert!(starknet.blocks.num_to_state.is_empty());
+        assert!(starknet.transactions.iter().next().is_none());
+        assert!(starknet.pending_block().get_transactions().is_empty());
+        assert_eq!(starknet.block_context.block_number, initial_block_number);
+        assert!(
+            starknet.state.state.state.address_to_nonce.get(&dummy_contract_address()).is_none()
+        );
+
+        // predeployed accounts are the same as on startup
+        let predeployed_accounts = starknet.predeployed_accounts.get_accounts().to_vec();
+        for account in predeployed_accounts {
+            let account_balance = account.get_balance(&mut starknet.state, FeeUnit::Wei).unwrap();
+            assert_eq!(config.predeployed_accounts_initial_balance, account_balance);
+        }
+    }
+
+    #[test]
+    fn subscribers_receive_sealed_blocks_after_restart() {
+        let mut starknet = Starknet::new(&starknet_config_for_test()).unwrap();
+        let mut updates = starknet.subscribe_to_updates();
+
+        starknet.create_block().unwrap();
+        starknet.create_block().unwrap();
+        starknet.restart().unwrap();
+        starknet.create_block().unwrap();
+
+        assert_eq!(updates.try_recv().unwrap(), ChainUpdate::NewBlock(BlockNumber(0)));
+        assert_eq!(updates.try_recv().unwrap(), ChainUpdate::NewBlock(BlockNumber(1)));
+        assert_eq!(updates.try_recv().unwrap(), ChainUpdate::NewBlock(BlockNumber(0)));
+        assert!(updates.try_recv().is_err());
+    }
+
+    #[test]
+    fn subscribers_receive_status_changes_of_aborted_transactions() {
+        let mut starknet = Starknet::new(&starknet_config_for_test()).unwrap();
+        generate_block_with_nonce_increment(&mut starknet, Felt::from(1));
+        let second_block_hash = generate_block_with_nonce_increment(&mut starknet, Felt::from(2));
+        let mut updates = starknet.subscribe_to_updates();
+
+        starknet.abort_blocks(second_block_hash).unwrap();
+
+        assert_eq!(updates.try_recv().unwrap(), ChainUpdate::TransactionStatus(Felt::from(2)));
+        assert!(updates.try_recv().is_err());
+    }
 }
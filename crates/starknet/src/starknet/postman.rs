@@ -0,0 +1,198 @@
+use primitive_types::{H160, H256};
+use starknet_api::block::BlockNumber;
+use starknet_types::felt::TransactionHash;
+use starknet_types::rpc::messaging::MessageToL2;
+use starknet_types::rpc::transaction_receipt::MessageToL1;
+use url::Url;
+
+use super::Starknet;
+use crate::error::{DevnetResult, Error};
+use crate::messaging::ethereum::EthereumMessaging;
+use crate::messaging::FlushedMessages;
+use crate::traits::HashIdentified;
+
+impl Starknet {
+    /// Attaches to the messaging contract deployed at `messaging_contract_address` on the L1 node
+    /// at `l1_node_url`, or deploys the mock messaging contract there if no address is provided.
+    /// Messages are exchanged with the contract on each flush. Returns the address of the contract.
+    pub fn configure_messaging(
+        &mut self,
+        l1_node_url: Url,
+        messaging_contract_address: Option<H160>,
+    ) -> DevnetResult<H160> {
+        let ethereum = match messaging_contract_address {
+            Some(address) => EthereumMessaging::new(l1_node_url, address)?,
+            None => EthereumMessaging::deploy(l1_node_url)?,
+        };
+        let messaging_contract_address = ethereum.messaging_contract_address();
+        self.messaging.ethereum = Some(ethereum);
+
+        Ok(messaging_contract_address)
+    }
+
+    /// Returns the URL of the L1 node, if the messaging contract is loaded
+    pub fn messaging_l1_node_url(&self) -> Option<&Url> {
+        self.messaging.ethereum.as_ref().map(EthereumMessaging::node_url)
+    }
+
+    /// Sends the messages to L1 which were sent by the transactions of the blocks created since the
+    /// last flush, then executes the messages sent to L2 since the last flush as L1 handler
+    /// transactions.
+    ///
+    /// Each part is marked as flushed only once it succeeds: messages sent to L1 are not sent
+    /// again if fetching the messages to L2 fails, and messages to L2 are fetched again until all
+    /// of them are executed. A message already executed by a flush which failed on a later one is
+    /// not executed again.
+    pub fn flush_messages(&mut self) -> DevnetResult<FlushedMessages> {
+        let messages_to_l1 = self.collect_messages_to_l1()?;
+        let message_hashes: Vec<_> = messages_to_l1.iter().map(MessageToL1::hash).collect();
+
+        let ethereum = self.messaging.ethereum.as_ref().ok_or_else(|| Error::MessagingError {
+            msg: "Messaging contract is not loaded".into(),
+        })?;
+        ethereum.send_message_hashes_to_l1(&message_hashes)?;
+        // blocks created by the L1 handler transactions below are flushed next time
+        self.messaging.next_block_to_flush = self.block_context.block_number;
+
+        let (messages_to_l2, last_l1_block) = ethereum.fetch_messages_to_l2()?;
+        let mut generated_l2_transactions = vec![];
+        for message in messages_to_l2.iter() {
+            let transaction = message.create_l1_handler_transaction(self.chain_id().to_felt())?;
+            let transaction_hash = transaction.transaction_hash;
+            if self.transactions.get_by_hash(transaction_hash).is_none() {
+                self.add_l1_handler_transaction(transaction)?;
+            }
+            generated_l2_transactions.push(transaction_hash);
+        }
+
+        if let Some(ethereum) = self.messaging.ethereum.as_mut() {
+            ethereum.set_last_fetched_block(last_l1_block);
+        }
+
+        Ok(FlushedMessages { messages_to_l1, messages_to_l2, generated_l2_transactions })
+    }
+
+    /// Executes the message as if it was sent to L2 by the messaging contract, without involving
+    /// L1. Returns the hash of the L1 handler transaction.
+    pub fn send_message_to_l2(&mut self, message: &MessageToL2) -> DevnetResult<TransactionHash> {
+        let transaction = message.create_l1_handler_transaction(self.chain_id().to_felt())?;
+        self.add_l1_handler_transaction(transaction)
+    }
+
+    /// Consumes the message sent to L1 by an accepted transaction, without involving L1. Returns
+    /// the hash of the message.
+    pub fn consume_message_from_l2(&mut self, message: &MessageToL1) -> DevnetResult<H256> {
+        self.messaging.consume_message_from_l2(message)
+    }
+
+    /// Returns the messages sent to L1 by the transactions of the blocks which were not flushed
+    /// yet, in the order in which they were sent
+    fn collect_messages_to_l1(&self) -> DevnetResult<Vec<MessageToL1>> {
+        let mut block_numbers: Vec<BlockNumber> = self
+            .blocks
+            .num_to_block
+            .keys()
+            .filter(|block_number| **block_number >= self.messaging.next_block_to_flush)
+            .copied()
+            .collect();
+        block_numbers.sort();
+
+        let mut messages = vec![];
+        for block_number in block_numbers {
+            let block = self.blocks.num_to_block.get(&block_number).ok_or(Error::NoBlock)?;
+            for transaction_hash in block.get_transactions() {
+                let transaction =
+                    self.transactions.get_by_hash(*transaction_hash).ok_or(Error::NoTransaction)?;
+                messages.extend(transaction.get_l2_to_l1_messages());
+            }
+        }
+
+        Ok(messages)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::Ordering;
+
+    use primitive_types::{H160, H256};
+    use serde_json::json;
+
+    use crate::error::Error;
+    use crate::starknet::Starknet;
+    use crate::utils::test_utils::spawn_mock_json_rpc_server;
+
+    #[test]
+    fn flush_without_loaded_messaging_contract() {
+        match Starknet::default().flush_messages() {
+            Err(Error::MessagingError { .. }) => (),
+            unexpected => panic!("Should have failed; got {unexpected:?}"),
+        }
+    }
+
+    #[test]
+    fn mock_messaging_contract_is_deployed_without_address() {
+        let (url, _) = spawn_mock_json_rpc_server(|method, _| match method {
+            "eth_accounts" => json!({ "result": [H160::from_low_u64_be(1)] }),
+            "eth_sendTransaction" => json!({ "result": H256::from_low_u64_be(0xd) }),
+            "eth_getTransactionReceipt" => json!({ "result": {
+                "status": "0x1",
+                "contractAddress": H160::from_low_u64_be(0x42),
+            } }),
+            _ => json!({ "error": { "code": -32601, "message": "Method not found" } }),
+        });
+
+        let mut starknet = Starknet::default();
+        assert_eq!(
+            starknet.configure_messaging(url.clone(), None).unwrap(),
+            H160::from_low_u64_be(0x42)
+        );
+        assert_eq!(starknet.messaging_l1_node_url(), Some(&url));
+    }
+
+    #[test]
+    fn flush_without_messages() {
+        let (url, request_count) = spawn_mock_json_rpc_server(|method, _| match method {
+            "eth_getCode" => json!({ "result": "0x6080" }),
+            "eth_accounts" => json!({ "result": [H160::from_low_u64_be(1)] }),
+            "eth_blockNumber" => json!({ "result": "0x0" }),
+            "eth_getLogs" => json!({ "result": [] }),
+            _ => json!({ "error": { "code": -32601, "message": "Method not found" } }),
+        });
+
+        let mut starknet = Starknet::default();
+        let messaging_contract_address = H160::from_low_u64_be(0x42);
+        assert_eq!(
+            starknet.configure_messaging(url.clone(), Some(messaging_contract_address)).unwrap(),
+            messaging_contract_address
+        );
+        assert_eq!(starknet.messaging_l1_node_url(), Some(&url));
+
+        let flushed_messages = starknet.flush_messages().unwrap();
+        assert!(flushed_messages.messages_to_l1.is_empty());
+        assert!(flushed_messages.messages_to_l2.is_empty());
+        assert!(flushed_messages.generated_l2_transactions.is_empty());
+
+        // attaching takes two requests and fetching messages two more; nothing is sent to L1
+        assert_eq!(request_count.load(Ordering::SeqCst), 4);
+    }
+
+    #[test]
+    fn messages_to_l2_are_fetched_again_after_failed_flush() {
+        let (url, request_count) = spawn_mock_json_rpc_server(|method, _| match method {
+            "eth_getCode" => json!({ "result": "0x6080" }),
+            "eth_accounts" => json!({ "result": [H160::from_low_u64_be(1)] }),
+            "eth_blockNumber" => json!({ "result": "0x0" }),
+            _ => json!({ "error": { "code": -32000, "message": "Logs unavailable" } }),
+        });
+
+        let mut starknet = Starknet::default();
+        starknet.configure_messaging(url, Some(H160::from_low_u64_be(0x42))).unwrap();
+
+        assert!(starknet.flush_messages().is_err());
+        assert!(starknet.flush_messages().is_err());
+
+        // the logs of block 0 are requested on both flushes, since the first one did not succeed
+        assert_eq!(request_count.load(Ordering::SeqCst), 6);
+    }
+}
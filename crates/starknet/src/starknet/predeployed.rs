@@ -5,7 +5,8 @@ use starknet_types::felt::Felt;
 
 use crate::constants::{
     CHARGEABLE_ACCOUNT_ADDRESS, ERC20_CONTRACT_ADDRESS, ERC20_CONTRACT_CLASS_HASH,
-    ERC20_CONTRACT_PATH, UDC_CONTRACT_ADDRESS, UDC_CONTRACT_CLASS_HASH, UDC_CONTRACT_PATH,
+    ERC20_CONTRACT_PATH, STRK_ERC20_CONTRACT_ADDRESS, UDC_CONTRACT_ADDRESS,
+    UDC_CONTRACT_CLASS_HASH, UDC_CONTRACT_PATH,
 };
 use crate::error::{DevnetResult, Error};
 use crate::state::StarknetState;
@@ -14,6 +15,14 @@ use crate::traits::StateChanger;
 use crate::utils::get_storage_var_address;
 
 pub(crate) fn create_erc20() -> DevnetResult<SystemContract> {
+    create_erc20_at_address(ERC20_CONTRACT_ADDRESS)
+}
+
+pub(crate) fn create_strk_erc20() -> DevnetResult<SystemContract> {
+    create_erc20_at_address(STRK_ERC20_CONTRACT_ADDRESS)
+}
+
+fn create_erc20_at_address(contract_address: &str) -> DevnetResult<SystemContract> {
     let erc20_contract_class_json_str =
         std::fs::read_to_string(ERC20_CONTRACT_PATH).map_err(|err| Error::ReadFileError {
             source: err,
@@ -21,7 +30,7 @@ pub(crate) fn create_erc20() -> DevnetResult<SystemContract> {
         })?;
     let erc20_fee_contract = SystemContract::new(
         ERC20_CONTRACT_CLASS_HASH,
-        ERC20_CONTRACT_ADDRESS,
+        contract_address,
         &erc20_contract_class_json_str,
     )?;
 
@@ -29,13 +38,15 @@ pub(crate) fn create_erc20() -> DevnetResult<SystemContract> {
 }
 
 /// Set initial values of ERC20 contract storage
-pub(crate) fn initialize_erc20(state: &mut StarknetState) -> DevnetResult<()> {
-    let contract_address =
-        ContractAddress::new(Felt::from_prefixed_hex_str(ERC20_CONTRACT_ADDRESS)?)?;
-
+pub(crate) fn initialize_erc20(
+    state: &mut StarknetState,
+    contract_address: ContractAddress,
+    name: &str,
+    symbol: &str,
+) -> DevnetResult<()> {
     for (storage_var_name, storage_value) in [
-        ("ERC20_name", get_selector_from_name("ether").unwrap().into()),
-        ("ERC20_symbol", get_selector_from_name("ETH").unwrap().into()),
+        ("ERC20_name", get_selector_from_name(name).unwrap().into()),
+        ("ERC20_symbol", get_selector_from_name(symbol).unwrap().into()),
         ("ERC20_decimals", 18.into()),
         // necessary to set - otherwise minting txs cannot be executed
         ("Ownable_owner", Felt::from_prefixed_hex_str(CHARGEABLE_ACCOUNT_ADDRESS)?),
@@ -0,0 +1,203 @@
+use std::collections::{BTreeMap, HashMap};
+
+use blockifier::block_context::BlockContext;
+use primitive_types::H256;
+use starknet_api::block::BlockNumber;
+use starknet_types::felt::TransactionHash;
+
+use super::{ChainUpdate, Starknet};
+use crate::blocks::StarknetBlocks;
+use crate::error::{DevnetResult, Error};
+use crate::state::state_diff::StateDiff;
+use crate::state::StarknetState;
+use crate::transactions::StarknetTransactions;
+
+pub type SnapshotId = u64;
+
+/// Copy of the parts of [`Starknet`] which change when transactions are executed, blocks are
+/// created or time is shifted. Blocks, states and transactions are shared with the chain rather
+/// than copied, so taking a snapshot does not copy the history of the chain.
+struct Snapshot {
+    state: StarknetState,
+    block_context: BlockContext,
+    blocks: StarknetBlocks,
+    pending_state_diff: StateDiff,
+    timestamp_shift_in_secs: i64,
+    transactions: StarknetTransactions,
+    l2_to_l1_messages: HashMap<H256, u64>,
+    next_block_to_flush: BlockNumber,
+}
+
+#[derive(Default)]
+pub(crate) struct Snapshots {
+    /// Ids are not reused, even if the snapshots they were assigned to are discarded
+    next_id: SnapshotId,
+    snapshots: BTreeMap<SnapshotId, Snapshot>,
+}
+
+impl Snapshots {
+    /// Returns no snapshots, e.g. for an instance replacing the current one on restart or on load,
+    /// whose chain the snapshots are not part of. Ids keep being assigned after the discarded ones.
+    pub(crate) fn discard_all(&self) -> Self {
+        Self { next_id: self.next_id, snapshots: BTreeMap::new() }
+    }
+}
+
+impl Starknet {
+    /// Captures the current chain in memory. Returns the id with which it can be reverted to.
+    /// Snapshots are discarded on restart and on load.
+    pub fn snapshot(&mut self) -> SnapshotId {
+        let snapshot = Snapshot {
+            state: self.state.clone(),
+            block_context: self.block_context.clone(),
+            blocks: self.blocks.clone(),
+            pending_state_diff: self.pending_state_diff.clone(),
+            timestamp_shift_in_secs: self.timestamp_shift_in_secs,
+            transactions: self.transactions.clone(),
+            l2_to_l1_messages: self.messaging.l2_to_l1_messages.clone(),
+            next_block_to_flush: self.messaging.next_block_to_flush,
+        };
+
+        let snapshot_id = self.snapshots.next_id;
+        self.snapshots.snapshots.insert(snapshot_id, snapshot);
+        self.snapshots.next_id += 1;
+
+        snapshot_id
+    }
+
+    /// Reverts the chain to the snapshot with the given id. Blocks and transactions created after
+    /// it are discarded, as are the snapshots taken after it. The snapshot itself is kept, so it
+    /// can be reverted to again. Subscribers are notified of the block which becomes the latest
+    /// one and of the transactions whose status changes, including the discarded ones.
+    pub fn revert_to_snapshot(&mut self, snapshot_id: SnapshotId) -> DevnetResult<()> {
+        let snapshot =
+            self.snapshots.snapshots.get(&snapshot_id).ok_or(Error::NoSnapshot { snapshot_id })?;
+
+        let latest_block_changes = self.blocks.last_block_hash != snapshot.blocks.last_block_hash;
+        let changed_transactions: Vec<TransactionHash> = self
+            .transactions
+            .iter()
+            .filter(|(transaction_hash, transaction)| {
+                match snapshot.transactions.get(transaction_hash) {
+                    Some(reverted) => {
+                        reverted.finality_status != transaction.finality_status
+                            || reverted.get_rejection_reason() != transaction.get_rejection_reason()
+                    }
+                    None => true,
+                }
+            })
+            .map(|(transaction_hash, _)| *transaction_hash)
+            .collect();
+
+        self.state = snapshot.state.clone();
+        self.block_context = snapshot.block_context.clone();
+        self.blocks = snapshot.blocks.clone();
+        self.pending_state_diff = snapshot.pending_state_diff.clone();
+        self.timestamp_shift_in_secs = snapshot.timestamp_shift_in_secs;
+        self.transactions = snapshot.transactions.clone();
+        self.messaging.l2_to_l1_messages = snapshot.l2_to_l1_messages.clone();
+        self.messaging.next_block_to_flush = snapshot.next_block_to_flush;
+
+        self.snapshots.snapshots.retain(|id, _| *id <= snapshot_id);
+
+        if latest_block_changes {
+            if let Ok(latest_block) = self.get_latest_block() {
+                self.send_update(ChainUpdate::NewBlock(latest_block.block_number()));
+            }
+        }
+        for transaction_hash in changed_transactions {
+            self.send_update(ChainUpdate::TransactionStatus(transaction_hash));
+        }
+
+        self.rewrite_dump_on_transaction()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use blockifier::transaction::objects::TransactionExecutionInfo;
+    use starknet_api::block::BlockNumber;
+    use starknet_types::felt::Felt;
+    use starknet_types::rpc::transactions::{DeclareTransaction, Transaction};
+
+    use crate::error::Error;
+    use crate::starknet::{ChainUpdate, Starknet};
+    use crate::traits::StateChanger;
+    use crate::utils::test_utils::{
+        dummy_contract_storage_key, dummy_declare_transaction_v1, starknet_config_for_test,
+    };
+
+    #[test]
+    fn revert_restores_blocks_and_state() {
+        let mut starknet = Starknet::new(&starknet_config_for_test()).unwrap();
+        let storage_key = dummy_contract_storage_key();
+        starknet.create_block().unwrap();
+        let snapshot_id = starknet.snapshot();
+
+        starknet.create_block().unwrap();
+        starknet.state.change_storage(storage_key, Felt::from(1)).unwrap();
+        assert_eq!(starknet.get_latest_block().unwrap().block_number(), BlockNumber(1));
+
+        starknet.revert_to_snapshot(snapshot_id).unwrap();
+        assert_eq!(starknet.get_latest_block().unwrap().block_number(), BlockNumber(0));
+        assert_eq!(starknet.block_context.block_number, BlockNumber(1));
+        assert!(!starknet.state.state.state.address_to_storage.contains_key(&storage_key));
+
+        // the snapshot can be reverted to again
+        starknet.create_block().unwrap();
+        starknet.revert_to_snapshot(snapshot_id).unwrap();
+        assert_eq!(starknet.get_latest_block().unwrap().block_number(), BlockNumber(0));
+    }
+
+    #[test]
+    fn snapshots_taken_after_the_reverted_one_are_discarded() {
+        let mut starknet = Starknet::new(&starknet_config_for_test()).unwrap();
+        let first_snapshot_id = starknet.snapshot();
+        starknet.create_block().unwrap();
+        let second_snapshot_id = starknet.snapshot();
+
+        starknet.revert_to_snapshot(first_snapshot_id).unwrap();
+        assert!(starknet.get_latest_block().is_err());
+        match starknet.revert_to_snapshot(second_snapshot_id) {
+            Err(Error::NoSnapshot { snapshot_id }) => assert_eq!(snapshot_id, second_snapshot_id),
+            unexpected => panic!("Should have failed; got {unexpected:?}"),
+        }
+
+        // ids of discarded snapshots are not reused
+        assert_ne!(starknet.snapshot(), second_snapshot_id);
+    }
+
+    #[test]
+    fn subscribers_are_notified_of_latest_block_and_discarded_transactions() {
+        let mut starknet = Starknet::new(&starknet_config_for_test()).unwrap();
+        starknet.create_block().unwrap();
+        let snapshot_id = starknet.snapshot();
+
+        let mut transaction = dummy_declare_transaction_v1();
+        transaction.transaction_hash = Felt::from(1);
+        starknet
+            .handle_accepted_transaction(
+                &Felt::from(1),
+                &Transaction::Declare(DeclareTransaction::Version1(transaction)),
+                TransactionExecutionInfo::default(),
+            )
+            .unwrap();
+        let mut updates = starknet.subscribe_to_updates();
+
+        starknet.revert_to_snapshot(snapshot_id).unwrap();
+
+        assert_eq!(updates.try_recv().unwrap(), ChainUpdate::NewBlock(BlockNumber(0)));
+        assert_eq!(updates.try_recv().unwrap(), ChainUpdate::TransactionStatus(Felt::from(1)));
+        assert!(updates.try_recv().is_err());
+    }
+
+    #[test]
+    fn snapshots_are_discarded_on_restart_without_reusing_ids() {
+        let mut starknet = Starknet::new(&starknet_config_for_test()).unwrap();
+        let snapshot_id = starknet.snapshot();
+
+        starknet.restart().unwrap();
+        assert!(matches!(starknet.revert_to_snapshot(snapshot_id), Err(Error::NoSnapshot { .. })));
+        assert_ne!(starknet.snapshot(), snapshot_id);
+    }
+}
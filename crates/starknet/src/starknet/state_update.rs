@@ -1,3 +1,4 @@
+use starknet_api::block::BlockNumber;
 use starknet_rs_core::types::BlockId;
 
 use super::Starknet;
@@ -12,14 +13,22 @@ pub fn state_update_by_block_id(
     let state_diff =
         starknet.blocks.num_to_state_diff.get(&block.block_number()).cloned().unwrap_or_default();
 
-    StateUpdate::new(block.block_hash(), state_diff)
+    // the first block, which has no parent or whose parent is at the origin of a fork, is
+    // created on top of the state on startup
+    let old_root = block
+        .block_number()
+        .0
+        .checked_sub(1)
+        .and_then(|parent_number| starknet.blocks.num_to_block.get(&BlockNumber(parent_number)))
+        .map_or(starknet.genesis_state_root, |parent| parent.new_root());
+
+    StateUpdate::new(block.block_hash(), block.new_root(), old_root, state_diff)
 }
 
 #[cfg(test)]
 mod tests {
     use starknet_api::transaction::Fee;
     use starknet_in_rust::core::contract_address::compute_casm_class_hash;
-    use starknet_in_rust::CasmContractClass;
     use starknet_rs_core::types::{TransactionExecutionStatus, TransactionFinalityStatus};
     use starknet_types::contract_address::ContractAddress;
     use starknet_types::contract_class::{Cairo0Json, ContractClass};
@@ -33,7 +42,9 @@ mod tests {
     use crate::state::state_diff::StateDiff;
     use crate::state::state_update::StateUpdate;
     use crate::traits::{Accounted, Deployed, HashIdentifiedMut};
-    use crate::utils::test_utils::{dummy_cairo_1_contract_class, dummy_felt};
+    use crate::utils::test_utils::{
+        compile_sierra_for_test, dummy_cairo_1_contract_class, dummy_felt,
+    };
 
     #[test]
     /// This test checks that the state update is correct after a declare transaction v2.
@@ -43,8 +54,7 @@ mod tests {
 
         let sierra_class_hash =
             ContractClass::Cairo1(contract_class.clone()).generate_hash().unwrap();
-        let casm_contract_class =
-            CasmContractClass::from_contract_class(contract_class.clone(), true).unwrap();
+        let casm_contract_class = compile_sierra_for_test(contract_class.clone());
         let compiled_class_hash = compute_casm_class_hash(&casm_contract_class).unwrap();
 
         let declare_txn = BroadcastedDeclareTransactionV2::new(
@@ -61,7 +71,7 @@ mod tests {
         let (txn_hash, _) = starknet.add_declare_transaction_v2(declare_txn).unwrap();
         let tx = starknet.transactions.get_by_hash_mut(&txn_hash).unwrap();
         assert_eq!(tx.finality_status, Some(TransactionFinalityStatus::AcceptedOnL2));
-        assert_eq!(tx.execution_result.status(), TransactionExecutionStatus::Succeeded);
+        assert_eq!(tx.get_execution_status(), Some(TransactionExecutionStatus::Succeeded));
 
         let state_update = starknet
             .block_state_update(starknet_rs_core::types::BlockId::Tag(
@@ -80,7 +90,13 @@ mod tests {
             ..Default::default()
         };
 
-        let expected_state_update = StateUpdate::new(Felt::default(), expected_state_diff).unwrap();
+        let expected_state_update = StateUpdate::new(
+            Felt::default(),
+            Felt::default(),
+            Felt::default(),
+            expected_state_diff,
+        )
+        .unwrap();
 
         // check only 3 of the 4 fields, because the inner property has changes to the storage of
         // the ERC20 contract which are hard to be tested correctly, it depends on the fee
@@ -104,6 +120,8 @@ mod tests {
 
         let erc_20_contract = predeployed::create_erc20().unwrap();
         erc_20_contract.deploy(&mut starknet.state).unwrap();
+        let strk_erc_20_contract = predeployed::create_strk_erc20().unwrap();
+        strk_erc_20_contract.deploy(&mut starknet.state).unwrap();
 
         let acc = Account::new(
             Felt::from(100000),
@@ -112,6 +130,7 @@ mod tests {
             contract_class.generate_hash().unwrap(),
             contract_class.into(),
             erc_20_contract.get_address(),
+            strk_erc_20_contract.get_address(),
         )
         .unwrap();
 
@@ -120,8 +139,10 @@ mod tests {
 
         starknet.state.clear_dirty_state();
         starknet.block_context = Starknet::init_block_context(
+            1,
             1,
             constants::ERC20_CONTRACT_ADDRESS,
+            constants::STRK_ERC20_CONTRACT_ADDRESS,
             DEVNET_DEFAULT_CHAIN_ID,
         );
 
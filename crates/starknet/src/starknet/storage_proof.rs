@@ -0,0 +1,130 @@
+use starknet_rs_core::types::{BlockId, BlockTag};
+use starknet_types::contract_address::ContractAddress;
+use starknet_types::felt::{ClassHash, Felt};
+use starknet_types::rpc::storage_proof::{
+    ContractLeafData, ContractStorageKeys, ContractsProof, GlobalRoots, StorageProof,
+};
+
+use super::Starknet;
+use crate::error::{DevnetResult, Error};
+
+/// Returns the proofs of the given classes, contracts and storage entries, against the roots of
+/// the state at `block_id`. Like the state root, the proofs only cover the local state; values of
+/// the origin of a fork, which were not changed locally, are not part of them.
+pub(crate) fn get_storage_proof(
+    starknet: &Starknet,
+    block_id: BlockId,
+    class_hashes: &[ClassHash],
+    contract_addresses: &[ContractAddress],
+    contracts_storage_keys: &[ContractStorageKeys],
+) -> DevnetResult<StorageProof> {
+    if let BlockId::Tag(BlockTag::Pending) = block_id {
+        return Err(Error::UnsupportedAction {
+            msg: "Storage proofs are not available for the pending block".to_string(),
+        });
+    }
+
+    let block = starknet.blocks.get_by_block_id(block_id).ok_or(Error::NoBlock)?;
+    let state = &starknet.get_state_at(&block_id)?.state.state;
+
+    let classes_trie = state.classes_trie();
+    let contracts_trie = state.contracts_trie();
+
+    let contract_keys: Vec<Felt> =
+        contract_addresses.iter().map(|address| Felt::from(*address)).collect();
+    let contract_leaves_data = contract_addresses
+        .iter()
+        .map(|address| ContractLeafData {
+            nonce: state.address_to_nonce.get(address).copied().unwrap_or_default(),
+            class_hash: state.address_to_class_hash.get(address).copied().unwrap_or_default(),
+        })
+        .collect();
+
+    let contracts_storage_proofs = contracts_storage_keys
+        .iter()
+        .map(|storage_keys| {
+            state.storage_trie(&storage_keys.contract_address).proof(&storage_keys.storage_keys)
+        })
+        .collect();
+
+    Ok(StorageProof {
+        classes_proof: classes_trie.proof(class_hashes),
+        contracts_proof: ContractsProof {
+            nodes: contracts_trie.proof(&contract_keys),
+            contract_leaves_data,
+        },
+        contracts_storage_proofs,
+        global_roots: GlobalRoots {
+            contracts_tree_root: contracts_trie.root(),
+            classes_tree_root: classes_trie.root(),
+            block_hash: block.block_hash(),
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use starknet_rs_core::types::{BlockId, BlockTag};
+    use starknet_types::felt::Felt;
+    use starknet_types::rpc::storage_proof::{ContractStorageKeys, MerkleNode};
+
+    use crate::error::Error;
+    use crate::starknet::Starknet;
+    use crate::state::state_diff::StateDiff;
+    use crate::utils::test_utils::{
+        dummy_contract_storage_key, dummy_felt, starknet_config_for_test,
+    };
+
+    #[test]
+    fn proofs_lead_to_the_roots_of_the_latest_state() {
+        let mut starknet = Starknet::new(&starknet_config_for_test()).unwrap();
+        let storage_key = dummy_contract_storage_key();
+        let address = *storage_key.get_contract_address();
+        starknet.state.state.state.address_to_class_hash.insert(address, dummy_felt());
+        starknet.state.state.state.address_to_storage.insert(storage_key, Felt::from(1));
+        let mut state_diff = StateDiff::default();
+        state_diff.address_to_class_hash.insert(address, dummy_felt());
+        state_diff
+            .storage_updates
+            .insert(address, [(*storage_key.get_storage_key(), Felt::from(1))].into());
+        starknet.generate_new_block(state_diff).unwrap();
+
+        let proof = starknet
+            .get_storage_proof(
+                BlockId::Tag(BlockTag::Latest),
+                &[],
+                &[address],
+                &[ContractStorageKeys {
+                    contract_address: address,
+                    storage_keys: vec![dummy_felt()],
+                }],
+            )
+            .unwrap();
+
+        assert_eq!(
+            proof.contracts_proof.nodes[0].node_hash,
+            proof.global_roots.contracts_tree_root
+        );
+        assert_eq!(proof.contracts_proof.contract_leaves_data[0].class_hash, dummy_felt());
+        assert!(proof.classes_proof.is_empty());
+
+        // the only storage entry of the contract is an edge from the root of its storage trie
+        match &proof.contracts_storage_proofs[0][..] {
+            [node] => assert_eq!(
+                node.node,
+                MerkleNode::Edge { path: dummy_felt(), length: 251, child: Felt::from(1) }
+            ),
+            nodes => panic!("Unexpected storage proof: {nodes:?}"),
+        }
+    }
+
+    #[test]
+    fn proofs_are_not_available_for_pending_block() {
+        let starknet = Starknet::new(&starknet_config_for_test()).unwrap();
+
+        match starknet.get_storage_proof(BlockId::Tag(BlockTag::Pending), &[], &[], &[]) {
+            Err(Error::UnsupportedAction { .. }) => (),
+            other => panic!("Unexpected result: {other:?}"),
+        }
+    }
+}
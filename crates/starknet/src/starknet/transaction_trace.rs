@@ -0,0 +1,350 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use blockifier::execution::call_info::CallInfo;
+use blockifier::transaction::objects::TransactionExecutionInfo;
+use starknet_rs_core::types::BlockId;
+use starknet_types::contract_address::ContractAddress;
+use starknet_types::felt::{ClassHash, TransactionHash};
+use starknet_types::rpc::transactions::{
+    BlockTransactionTrace, DeclareTransactionTrace, DeployAccountTransactionTrace,
+    ExecutionInvocation, FunctionInvocation, InvokeTransactionTrace, L1HandlerTransactionTrace,
+    Reversion, TransactionTrace, TransactionType,
+};
+
+use super::Starknet;
+use crate::error::{DevnetResult, Error};
+use crate::traits::HashIdentified;
+use crate::transactions::StarknetTransaction;
+
+/// Creates the trace of a transaction of `transaction_type` from the info of its execution
+pub(crate) fn create_trace(
+    transaction_type: TransactionType,
+    execution_info: &TransactionExecutionInfo,
+    address_to_class_hash: &HashMap<ContractAddress, ClassHash>,
+) -> DevnetResult<TransactionTrace> {
+    let to_invocation = |call_info: &Option<CallInfo>| {
+        call_info
+            .as_ref()
+            .map(|call_info| {
+                FunctionInvocation::try_from_call_info(call_info, address_to_class_hash)
+            })
+            .transpose()
+    };
+
+    let validate_invocation = to_invocation(&execution_info.validate_call_info)?;
+    let fee_transfer_invocation = to_invocation(&execution_info.fee_transfer_call_info)?;
+
+    let trace = match transaction_type {
+        TransactionType::Declare => TransactionTrace::Declare(DeclareTransactionTrace {
+            validate_invocation,
+            fee_transfer_invocation,
+        }),
+        TransactionType::DeployAccount => {
+            TransactionTrace::DeployAccount(DeployAccountTransactionTrace {
+                validate_invocation,
+                constructor_invocation: to_invocation(&execution_info.execute_call_info)?,
+                fee_transfer_invocation,
+            })
+        }
+        TransactionType::Invoke => TransactionTrace::Invoke(InvokeTransactionTrace {
+            validate_invocation,
+            execution_invocation: execution_invocation(execution_info, address_to_class_hash)?,
+            fee_transfer_invocation,
+        }),
+        TransactionType::L1Handler => match to_invocation(&execution_info.execute_call_info)? {
+            Some(function_invocation) => {
+                TransactionTrace::L1Handler(L1HandlerTransactionTrace { function_invocation })
+            }
+            None => {
+                return Err(Error::UnexpectedInternalError {
+                    msg: "Execution of L1 handler transaction contains no call_info".into(),
+                });
+            }
+        },
+        // the trace API has no type for deploy transactions, so they are not traced
+        TransactionType::Deploy => return Err(Error::NoTransactionTrace),
+    };
+
+    Ok(trace)
+}
+
+fn execution_invocation(
+    execution_info: &TransactionExecutionInfo,
+    address_to_class_hash: &HashMap<ContractAddress, ClassHash>,
+) -> DevnetResult<ExecutionInvocation> {
+    match (&execution_info.execute_call_info, &execution_info.revert_error) {
+        (Some(call_info), _) if !call_info.execution.failed => Ok(ExecutionInvocation::Succeeded(
+            FunctionInvocation::try_from_call_info(call_info, address_to_class_hash)?,
+        )),
+        (Some(_), revert_error) => Ok(ExecutionInvocation::Reverted(Reversion {
+            revert_reason: revert_error.clone().unwrap_or("Revert reason not found".into()),
+        })),
+        (None, Some(revert_reason)) => {
+            Ok(ExecutionInvocation::Reverted(Reversion { revert_reason: revert_reason.clone() }))
+        }
+        (None, None) => Err(Error::UnexpectedInternalError {
+            msg: "Execution contains neither call_info nor revert_error".into(),
+        }),
+    }
+}
+
+/// Creates the trace of the transaction right after its execution, once its state changes are
+/// applied, so that the class hashes of the called contracts are resolved as they were on
+/// execution. Returns `None` if the transaction is of a type which is not traced.
+pub(crate) fn trace_of_execution(
+    starknet: &Starknet,
+    transaction: &StarknetTransaction,
+) -> DevnetResult<Option<Arc<TransactionTrace>>> {
+    let execution_info = match &transaction.execution_info {
+        Some(execution_info) => execution_info,
+        None => return Ok(None),
+    };
+    let address_to_class_hash: HashMap<ContractAddress, ClassHash> =
+        starknet.state.state.state.address_to_class_hash.clone().into_iter().collect();
+
+    match create_trace(transaction.inner.get_type(), execution_info, &address_to_class_hash) {
+        Ok(trace) => Ok(Some(Arc::new(trace))),
+        Err(Error::NoTransactionTrace) => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+pub(crate) fn get_transaction_trace(
+    starknet: &Starknet,
+    transaction_hash: TransactionHash,
+) -> DevnetResult<TransactionTrace> {
+    let transaction = starknet.transactions.get(&transaction_hash).ok_or(Error::NoTransaction)?;
+
+    // rejected transactions were not executed
+    transaction.trace.as_deref().cloned().ok_or(Error::NoTransactionTrace)
+}
+
+/// Returns the traces of the transactions in the block. Transactions which have no trace are
+/// skipped.
+pub(crate) fn get_transaction_traces_from_block(
+    starknet: &Starknet,
+    block_id: BlockId,
+) -> DevnetResult<Vec<BlockTransactionTrace>> {
+    let block = starknet.blocks.get_by_block_id(block_id).ok_or(Error::NoBlock)?;
+
+    let mut traces = vec![];
+    for transaction_hash in block.get_transactions() {
+        let transaction =
+            starknet.transactions.get_by_hash(*transaction_hash).ok_or(Error::NoTransaction)?;
+
+        if let Some(trace) = transaction.trace.as_deref() {
+            traces.push(BlockTransactionTrace {
+                transaction_hash: *transaction_hash,
+                trace_root: trace.clone(),
+            });
+        }
+    }
+
+    Ok(traces)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use blockifier::execution::call_info::CallInfo;
+    use blockifier::execution::entry_point::CallEntryPoint;
+    use blockifier::transaction::objects::TransactionExecutionInfo;
+    use starknet_rs_core::types::{BlockId, BlockTag};
+    use starknet_types::felt::Felt;
+    use starknet_types::rpc::transactions::deploy_transaction::DeployTransaction;
+    use starknet_types::rpc::transactions::invoke_transaction_v1::InvokeTransactionV1;
+    use starknet_types::rpc::transactions::{
+        DeclareTransaction, ExecutionInvocation, InvokeTransaction, InvokeTransactionTrace,
+        L1HandlerTransaction, Transaction, TransactionTrace, TransactionType,
+    };
+    use starknet_types::traits::ToHexString;
+
+    use super::create_trace;
+    use crate::error::Error;
+    use crate::starknet::{Starknet, StarknetConfig};
+    use crate::traits::{HashIdentifiedMut, StateChanger};
+    use crate::transactions::StarknetTransaction;
+    use crate::utils::test_utils::{
+        dummy_contract_address, dummy_declare_transaction_v1, dummy_felt, starknet_config_for_test,
+    };
+
+    #[test]
+    fn reverted_invoke_transaction_is_traced_with_revert_reason() {
+        let execution_info = TransactionExecutionInfo {
+            revert_error: Some("reason".to_string()),
+            ..Default::default()
+        };
+
+        match create_trace(TransactionType::Invoke, &execution_info, &HashMap::new()).unwrap() {
+            TransactionTrace::Invoke(trace) => match trace.execution_invocation {
+                ExecutionInvocation::Reverted(reversion) => {
+                    assert_eq!(reversion.revert_reason, "reason")
+                }
+                other => panic!("Unexpected execution invocation: {other:?}"),
+            },
+            other => panic!("Unexpected trace: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn traces_of_block_are_in_order_of_its_transactions() {
+        let mut starknet = starknet_with_blocks_on_demand();
+
+        let mut first_transaction = dummy_declare_transaction_v1();
+        first_transaction.transaction_hash = Felt::from(1);
+        let mut second_transaction = dummy_declare_transaction_v1();
+        second_transaction.transaction_hash = Felt::from(2);
+
+        for transaction in [first_transaction, second_transaction] {
+            let transaction_hash = transaction.transaction_hash;
+            starknet
+                .handle_accepted_transaction(
+                    &transaction_hash,
+                    &Transaction::Declare(DeclareTransaction::Version1(transaction)),
+                    TransactionExecutionInfo::default(),
+                )
+                .unwrap();
+        }
+        starknet.create_block().unwrap();
+
+        let traces =
+            starknet.get_transaction_traces_from_block(BlockId::Tag(BlockTag::Latest)).unwrap();
+        let traced_hashes: Vec<_> = traces.iter().map(|trace| trace.transaction_hash).collect();
+        assert_eq!(
+            &traced_hashes,
+            starknet.get_block(BlockId::Number(0)).unwrap().get_transactions()
+        );
+        assert!(matches!(traces[0].trace_root, TransactionTrace::Declare(_)));
+    }
+
+    #[test]
+    fn class_hashes_are_resolved_on_execution() {
+        let mut starknet = Starknet::new(&starknet_config_for_test()).unwrap();
+        let contract_address = dummy_contract_address();
+        starknet.state.deploy_contract(contract_address, Felt::from(1)).unwrap();
+        starknet.state.clear_dirty_state();
+
+        let transaction_hash = dummy_felt();
+        let execution_info = TransactionExecutionInfo {
+            execute_call_info: Some(CallInfo {
+                call: CallEntryPoint {
+                    storage_address: contract_address.try_into().unwrap(),
+                    class_hash: None,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        starknet
+            .handle_accepted_transaction(
+                &transaction_hash,
+                &Transaction::Invoke(InvokeTransaction::Version1(InvokeTransactionV1 {
+                    transaction_hash,
+                    ..Default::default()
+                })),
+                execution_info,
+            )
+            .unwrap();
+
+        // the class is replaced after the transaction was executed
+        starknet.state.deploy_contract(contract_address, Felt::from(2)).unwrap();
+
+        match starknet.get_transaction_trace(transaction_hash).unwrap() {
+            TransactionTrace::Invoke(InvokeTransactionTrace {
+                execution_invocation: ExecutionInvocation::Succeeded(invocation),
+                ..
+            }) => {
+                let invocation = serde_json::to_value(invocation).unwrap();
+                assert_eq!(invocation["class_hash"], Felt::from(1).to_prefixed_hex_str());
+            }
+            other => panic!("Unexpected trace: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn transaction_without_trace_is_skipped_in_traces_of_block() {
+        let mut starknet = starknet_with_blocks_on_demand();
+
+        for transaction_hash in [Felt::from(1), Felt::from(2)] {
+            let mut transaction = dummy_declare_transaction_v1();
+            transaction.transaction_hash = transaction_hash;
+            starknet
+                .handle_accepted_transaction(
+                    &transaction_hash,
+                    &Transaction::Declare(DeclareTransaction::Version1(transaction)),
+                    TransactionExecutionInfo::default(),
+                )
+                .unwrap();
+        }
+        // e.g. a transaction loaded from a dump without its trace
+        starknet.transactions.get_by_hash_mut(&Felt::from(1)).unwrap().trace = None;
+        starknet.create_block().unwrap();
+
+        let traces =
+            starknet.get_transaction_traces_from_block(BlockId::Tag(BlockTag::Latest)).unwrap();
+        assert_eq!(traces.len(), 1);
+        assert_eq!(traces[0].transaction_hash, Felt::from(2));
+    }
+
+    #[test]
+    fn deploy_transaction_has_no_trace_and_is_skipped_in_traces_of_block() {
+        let mut starknet = starknet_with_blocks_on_demand();
+
+        let mut declare_transaction = dummy_declare_transaction_v1();
+        declare_transaction.transaction_hash = Felt::from(1);
+        let deploy_transaction =
+            DeployTransaction { transaction_hash: Felt::from(2), ..Default::default() };
+        for transaction in [
+            Transaction::Declare(DeclareTransaction::Version1(declare_transaction)),
+            Transaction::Deploy(deploy_transaction),
+        ] {
+            starknet
+                .handle_accepted_transaction(
+                    transaction.get_transaction_hash(),
+                    &transaction,
+                    TransactionExecutionInfo::default(),
+                )
+                .unwrap();
+        }
+        starknet.create_block().unwrap();
+
+        match starknet.get_transaction_trace(Felt::from(2)) {
+            Err(Error::NoTransactionTrace) => (),
+            other => panic!("Unexpected result: {other:?}"),
+        }
+        let traces =
+            starknet.get_transaction_traces_from_block(BlockId::Tag(BlockTag::Latest)).unwrap();
+        assert_eq!(traces.len(), 1);
+        assert_eq!(traces[0].transaction_hash, Felt::from(1));
+    }
+
+    #[test]
+    fn rejected_transaction_has_no_trace() {
+        let mut starknet = Starknet::new(&starknet_config_for_test()).unwrap();
+        let transaction_hash = dummy_felt();
+        starknet.transactions.insert(
+            &transaction_hash,
+            StarknetTransaction::create_rejected(
+                &Transaction::L1Handler(L1HandlerTransaction {
+                    transaction_hash,
+                    ..Default::default()
+                }),
+                "rejected",
+            ),
+        );
+
+        match starknet.get_transaction_trace(transaction_hash) {
+            Err(Error::NoTransactionTrace) => (),
+            other => panic!("Unexpected result: {other:?}"),
+        }
+    }
+
+    /// Transactions are kept in the pending block until a block is created
+    fn starknet_with_blocks_on_demand() -> Starknet {
+        Starknet::new(&StarknetConfig { blocks_on_demand: true, ..starknet_config_for_test() })
+            .unwrap()
+    }
+}
@@ -0,0 +1,262 @@
+use std::collections::{HashMap, HashSet};
+
+use starknet_rs_core::crypto::pedersen_hash;
+use starknet_rs_core::utils::cairo_short_string_to_felt;
+use starknet_rs_crypto::{poseidon_hash, poseidon_hash_many};
+use starknet_rs_ff::FieldElement;
+use starknet_types::contract_address::ContractAddress;
+use starknet_types::felt::{ClassHash, Felt};
+
+use super::patricia_trie::{PatriciaTrie, TrieHashFunction, STATE_TRIE_HEIGHT};
+use super::state_diff::StateDiff;
+use super::DevnetState;
+use crate::error::{DevnetResult, Error};
+
+const GLOBAL_STATE_VERSION: &str = "STARKNET_STATE_V0";
+const CONTRACT_CLASS_LEAF_VERSION: &str = "CONTRACT_CLASS_LEAF_V0";
+
+/// Tries committing to the local state. They are kept next to the state and updated with the
+/// state diff of each block, so that only the changed contracts and classes are rehashed.
+#[derive(Clone)]
+pub(crate) struct StateTries {
+    contracts: PatriciaTrie,
+    classes: PatriciaTrie,
+    storage: im::HashMap<ContractAddress, PatriciaTrie>,
+}
+
+impl Default for StateTries {
+    fn default() -> Self {
+        Self {
+            contracts: PatriciaTrie::empty(STATE_TRIE_HEIGHT, TrieHashFunction::Pedersen),
+            classes: PatriciaTrie::empty(STATE_TRIE_HEIGHT, TrieHashFunction::Poseidon),
+            storage: im::HashMap::new(),
+        }
+    }
+}
+
+impl DevnetState {
+    /// Computes the global state root of the last committed state. It only covers the local
+    /// state; values of the origin of a fork, which were not changed locally, are not part of it.
+    pub(crate) fn state_commitment(&self) -> DevnetResult<Felt> {
+        let contracts_root = self.tries.contracts.root();
+        let classes_root = self.tries.classes.root();
+
+        // before any Cairo 1 class is declared, the state root is the root of the contracts trie
+        if classes_root.is_zero() {
+            return Ok(contracts_root);
+        }
+
+        Ok(poseidon_hash_many(&[
+            short_string_to_felt(GLOBAL_STATE_VERSION)?,
+            contracts_root.into(),
+            classes_root.into(),
+        ])
+        .into())
+    }
+
+    /// Updates the tries with the changes of `state_diff`, which have to be applied to the state
+    /// already, and returns the new global state root
+    pub(crate) fn commit_state_diff(&mut self, state_diff: &StateDiff) -> DevnetResult<Felt> {
+        for (address, storage_updates) in state_diff.storage_updates.iter() {
+            let storage_trie = self.tries.storage.entry(*address).or_insert_with(|| {
+                PatriciaTrie::empty(STATE_TRIE_HEIGHT, TrieHashFunction::Pedersen)
+            });
+            storage_trie.update(
+                storage_updates.iter().map(|(storage_key, value)| (storage_key.to_felt(), *value)),
+            );
+        }
+
+        let changed_addresses: HashSet<ContractAddress> = state_diff
+            .storage_updates
+            .keys()
+            .chain(state_diff.address_to_nonce.keys())
+            .chain(state_diff.address_to_class_hash.keys())
+            .copied()
+            .collect();
+        self.update_contract_leaves(changed_addresses);
+
+        self.update_class_leaves(state_diff.class_hash_to_compiled_class_hash.iter())?;
+
+        self.state_commitment()
+    }
+
+    /// Builds the tries from the whole state and returns the global state root
+    pub(crate) fn rebuild_tries(&mut self) -> DevnetResult<Felt> {
+        let mut storage_by_address: HashMap<ContractAddress, Vec<(Felt, Felt)>> = HashMap::new();
+        for (storage_key, value) in self.address_to_storage.iter() {
+            storage_by_address
+                .entry(*storage_key.get_contract_address())
+                .or_default()
+                .push((storage_key.get_storage_key().to_felt(), *value));
+        }
+
+        let addresses: HashSet<ContractAddress> = self
+            .address_to_class_hash
+            .keys()
+            .chain(self.address_to_nonce.keys())
+            .chain(storage_by_address.keys())
+            .copied()
+            .collect();
+
+        self.tries = StateTries::default();
+        self.tries.storage = storage_by_address
+            .into_iter()
+            .map(|(address, storage)| {
+                (address, PatriciaTrie::new(STATE_TRIE_HEIGHT, storage, TrieHashFunction::Pedersen))
+            })
+            .collect();
+        self.update_contract_leaves(addresses);
+
+        let class_hash_to_compiled_class_hash = self.class_hash_to_compiled_class_hash.clone();
+        self.update_class_leaves(class_hash_to_compiled_class_hash.iter())?;
+
+        self.state_commitment()
+    }
+
+    /// Trie with the hash of the state of each contract, keyed by contract address
+    pub(crate) fn contracts_trie(&self) -> &PatriciaTrie {
+        &self.tries.contracts
+    }
+
+    /// Trie with the storage of the contract at `address`, keyed by storage key
+    pub(crate) fn storage_trie(&self, address: &ContractAddress) -> PatriciaTrie {
+        self.tries
+            .storage
+            .get(address)
+            .cloned()
+            .unwrap_or_else(|| PatriciaTrie::empty(STATE_TRIE_HEIGHT, TrieHashFunction::Pedersen))
+    }
+
+    /// Trie with a leaf for each declared Cairo 1 class, keyed by class hash
+    pub(crate) fn classes_trie(&self) -> &PatriciaTrie {
+        &self.tries.classes
+    }
+
+    /// Sets the leaves of `addresses` in the contracts trie; their storage tries have to be
+    /// up to date
+    fn update_contract_leaves(&mut self, addresses: impl IntoIterator<Item = ContractAddress>) {
+        let leaves: Vec<(Felt, Felt)> = addresses
+            .into_iter()
+            .map(|address| {
+                let storage_root =
+                    self.tries.storage.get(&address).map(PatriciaTrie::root).unwrap_or_default();
+                (Felt::from(address), self.contract_state_hash(&address, storage_root))
+            })
+            .collect();
+        self.tries.contracts.update(leaves);
+    }
+
+    fn update_class_leaves<'a>(
+        &mut self,
+        class_hash_to_compiled_class_hash: impl IntoIterator<Item = (&'a ClassHash, &'a Felt)>,
+    ) -> DevnetResult<()> {
+        let leaf_version = short_string_to_felt(CONTRACT_CLASS_LEAF_VERSION)?;
+        self.tries.classes.update(class_hash_to_compiled_class_hash.into_iter().map(
+            |(class_hash, compiled_class_hash)| {
+                (*class_hash, poseidon_hash(leaf_version, (*compiled_class_hash).into()).into())
+            },
+        ));
+
+        Ok(())
+    }
+
+    fn contract_state_hash(&self, address: &ContractAddress, storage_root: Felt) -> Felt {
+        let class_hash = self.address_to_class_hash.get(address).copied().unwrap_or_default();
+        let nonce = self.address_to_nonce.get(address).copied().unwrap_or_default();
+
+        let hash = pedersen_hash(&class_hash.into(), &storage_root.into());
+        let hash = pedersen_hash(&hash, &nonce.into());
+        // the last element is the version of the contract state
+        pedersen_hash(&hash, &FieldElement::ZERO).into()
+    }
+}
+
+fn short_string_to_felt(short_string: &str) -> DevnetResult<FieldElement> {
+    cairo_short_string_to_felt(short_string).map_err(|_| Error::FormatError)
+}
+
+#[cfg(test)]
+mod tests {
+    use starknet_rs_core::crypto::pedersen_hash;
+    use starknet_rs_ff::FieldElement;
+    use starknet_types::felt::Felt;
+
+    use crate::state::state_diff::StateDiff;
+    use crate::state::DevnetState;
+    use crate::utils::test_utils::{
+        dummy_contract_address, dummy_contract_storage_key, dummy_felt,
+    };
+
+    #[test]
+    fn empty_state_has_zero_root() {
+        assert_eq!(DevnetState::default().rebuild_tries().unwrap(), Felt::default());
+    }
+
+    #[test]
+    fn state_root_without_cairo_1_classes_is_root_of_contracts_trie() {
+        let mut state = DevnetState::default();
+        let address = dummy_contract_address();
+        state.address_to_class_hash.insert(address, dummy_felt());
+        state.address_to_nonce.insert(address, Felt::from(1));
+
+        // the contract has no storage, so its storage root is zero
+        let contract_state_hash = pedersen_hash(
+            &pedersen_hash(
+                &pedersen_hash(&dummy_felt().into(), &FieldElement::ZERO),
+                &FieldElement::ONE,
+            ),
+            &FieldElement::ZERO,
+        );
+        let expected_root = pedersen_hash(&contract_state_hash, &Felt::from(address).into())
+            + FieldElement::from(251_u8);
+
+        assert_eq!(state.rebuild_tries().unwrap(), expected_root.into());
+    }
+
+    #[test]
+    fn state_root_changes_with_storage_and_declared_classes() {
+        let mut state = DevnetState::default();
+        state.address_to_class_hash.insert(dummy_contract_address(), dummy_felt());
+        let initial_root = state.rebuild_tries().unwrap();
+
+        state.address_to_storage.insert(dummy_contract_storage_key(), Felt::from(1));
+        let root_with_storage = state.rebuild_tries().unwrap();
+        assert_ne!(root_with_storage, initial_root);
+
+        state.class_hash_to_compiled_class_hash.insert(dummy_felt(), Felt::from(2));
+        let root_with_class = state.rebuild_tries().unwrap();
+        assert_ne!(root_with_class, root_with_storage);
+        assert_ne!(root_with_class, state.contracts_trie().root());
+    }
+
+    #[test]
+    fn committing_state_diffs_gives_the_root_of_the_whole_state() {
+        let mut state = DevnetState::default();
+        let storage_key = dummy_contract_storage_key();
+        let address = *storage_key.get_contract_address();
+
+        state.address_to_class_hash.insert(address, dummy_felt());
+        state.address_to_storage.insert(storage_key, Felt::from(1));
+        let mut state_diff = StateDiff::default();
+        state_diff.address_to_class_hash.insert(address, dummy_felt());
+        state_diff
+            .storage_updates
+            .insert(address, [(*storage_key.get_storage_key(), Felt::from(1))].into());
+        state.commit_state_diff(&state_diff).unwrap();
+
+        // the second diff clears the storage entry and declares a class
+        state.address_to_storage.insert(storage_key, Felt::default());
+        state.address_to_nonce.insert(address, Felt::from(1));
+        state.class_hash_to_compiled_class_hash.insert(dummy_felt(), Felt::from(2));
+        let mut state_diff = StateDiff::default();
+        state_diff.address_to_nonce.insert(address, Felt::from(1));
+        state_diff
+            .storage_updates
+            .insert(address, [(*storage_key.get_storage_key(), Felt::default())].into());
+        state_diff.class_hash_to_compiled_class_hash.insert(dummy_felt(), Felt::from(2));
+        let committed_root = state.commit_state_diff(&state_diff).unwrap();
+
+        assert_eq!(committed_root, state.clone().rebuild_tries().unwrap());
+        assert_eq!(state.storage_trie(&address).root(), Felt::default());
+    }
+}
@@ -1,23 +1,27 @@
-use std::collections::HashMap;
-
 use blockifier::state::cached_state::CachedState;
 use blockifier::state::state_api::StateReader;
 use starknet_types::cairo_felt::Felt252;
 use starknet_types::contract_address::ContractAddress;
+use starknet_types::contract_class::compilation_cache::CompilationCache;
 use starknet_types::contract_class::ContractClass;
 use starknet_types::contract_storage_key::ContractStorageKey;
 use starknet_types::felt::{ClassHash, CompiledClassHash, Felt};
 
+use self::commitment::StateTries;
+use self::origin::OriginReader;
 use self::state_diff::StateDiff;
 use crate::error::{DevnetResult, Error, StateError};
 use crate::traits::{DevnetStateReader, StateChanger, StateExtractor};
 
+mod commitment;
+pub(crate) mod origin;
+pub(crate) mod patricia_trie;
 pub(crate) mod state_diff;
 pub mod state_update;
 
 pub(crate) struct StarknetState {
     pub state: CachedState<DevnetState>,
-    pub(crate) contract_classes: HashMap<ClassHash, ContractClass>,
+    pub(crate) contract_classes: im::HashMap<ClassHash, ContractClass>,
 }
 
 impl Default for StarknetState {
@@ -29,45 +33,99 @@ impl Default for StarknetState {
     }
 }
 
+impl StarknetState {
+    /// Creates an empty state which falls back to reading from the origin of a fork, if there is
+    /// one, and compiles Sierra classes through `compilation_cache`, if there is one
+    pub(crate) fn new(
+        origin: Option<OriginReader>,
+        compilation_cache: Option<CompilationCache>,
+    ) -> Self {
+        let devnet_state = DevnetState { origin, compilation_cache, ..Default::default() };
+
+        Self {
+            state: CachedState::new(devnet_state, Default::default()),
+            contract_classes: Default::default(),
+        }
+    }
+}
+
+/// The maps are persistent, so clones of the state saved for each block share the entries which
+/// did not change between them
 #[derive(Default, Clone)]
 pub(crate) struct DevnetState {
-    pub address_to_class_hash: HashMap<ContractAddress, ClassHash>,
-    pub address_to_nonce: HashMap<ContractAddress, Felt>,
-    pub address_to_storage: HashMap<ContractStorageKey, Felt>,
-    pub class_hash_to_compiled_class: HashMap<ClassHash, ContractClass>,
-    pub class_hash_to_compiled_class_hash: HashMap<ClassHash, CompiledClassHash>,
+    pub address_to_class_hash: im::HashMap<ContractAddress, ClassHash>,
+    pub address_to_nonce: im::HashMap<ContractAddress, Felt>,
+    pub address_to_storage: im::HashMap<ContractStorageKey, Felt>,
+    pub class_hash_to_compiled_class: im::HashMap<ClassHash, ContractClass>,
+    pub class_hash_to_compiled_class_hash: im::HashMap<ClassHash, CompiledClassHash>,
+    /// If forked, values not present locally are read from the origin
+    pub(crate) origin: Option<OriginReader>,
+    /// Tries committing to the state as of the last block
+    pub(crate) tries: StateTries,
+    /// Cache of the CASM which declared Sierra classes are compiled to for execution
+    pub(crate) compilation_cache: Option<CompilationCache>,
+}
+
+impl DevnetState {
+    pub(crate) fn is_contract_deployed(&self, address: &ContractAddress) -> bool {
+        self.address_to_class_hash.contains_key(address)
+            || self.origin.as_ref().map_or(false, |origin| origin.is_contract_deployed(address))
+    }
+
+    pub(crate) fn is_contract_declared(&self, class_hash: &ClassHash) -> bool {
+        self.class_hash_to_compiled_class_hash.contains_key(class_hash)
+            || self.class_hash_to_compiled_class.contains_key(class_hash)
+            || self.origin.as_ref().map_or(false, |origin| origin.is_class_declared(class_hash))
+    }
 }
 
 impl crate::traits::DevnetStateReader for DevnetState {
-    fn compiled_class_hash_at(&self, class_hash: &ClassHash) -> ClassHash {
-        self.class_hash_to_compiled_class_hash.get(class_hash).cloned().unwrap_or_default()
+    fn compiled_class_hash_at(&self, class_hash: &ClassHash) -> DevnetResult<ClassHash> {
+        // compiled class hashes of classes declared at the origin are not available via JSON-RPC
+        Ok(self.class_hash_to_compiled_class_hash.get(class_hash).cloned().unwrap_or_default())
     }
 
-    fn storage_at(&self, storage_key: &ContractStorageKey) -> Felt {
-        self.address_to_storage.get(storage_key).cloned().unwrap_or_default()
+    fn storage_at(&self, storage_key: &ContractStorageKey) -> DevnetResult<Felt> {
+        match (self.address_to_storage.get(storage_key), &self.origin) {
+            (Some(value), _) => Ok(*value),
+            (None, Some(origin)) => origin.storage_at(storage_key),
+            (None, None) => Ok(Felt::default()),
+        }
     }
 
-    fn nonce_at(&self, address: &ContractAddress) -> Felt {
-        self.address_to_nonce.get(address).cloned().unwrap_or_default()
+    fn nonce_at(&self, address: &ContractAddress) -> DevnetResult<Felt> {
+        match (self.address_to_nonce.get(address), &self.origin) {
+            (Some(nonce), _) => Ok(*nonce),
+            (None, Some(origin)) => origin.nonce_at(address),
+            (None, None) => Ok(Felt::default()),
+        }
     }
 
-    fn class_hash_at(&self, address: &ContractAddress) -> ClassHash {
-        self.address_to_class_hash.get(address).cloned().unwrap_or_default()
+    fn class_hash_at(&self, address: &ContractAddress) -> DevnetResult<ClassHash> {
+        match (self.address_to_class_hash.get(address), &self.origin) {
+            (Some(class_hash), _) => Ok(*class_hash),
+            (None, Some(origin)) => origin.class_hash_at(address),
+            (None, None) => Ok(ClassHash::default()),
+        }
     }
 
     fn contract_class_at(&self, class_hash: &ClassHash) -> DevnetResult<ContractClass> {
         if let Some(deprecated_contract_class) = self.class_hash_to_compiled_class.get(class_hash) {
             Ok(deprecated_contract_class.clone())
-        } else {
-            let compiled_class_hash = self
-                .class_hash_to_compiled_class_hash
-                .get(class_hash)
-                .ok_or(Error::StateError(StateError::NoneCompiledHash(*class_hash)))?;
-
+        } else if let Some(compiled_class_hash) =
+            self.class_hash_to_compiled_class_hash.get(class_hash)
+        {
             self.class_hash_to_compiled_class
                 .get(compiled_class_hash)
                 .ok_or(Error::StateError(StateError::NoneCasmClass(*compiled_class_hash)))
                 .cloned()
+        } else {
+            match &self.origin {
+                Some(origin) => origin
+                    .contract_class_at(class_hash)?
+                    .ok_or(Error::StateError(StateError::NoneCompiledHash(*class_hash))),
+                None => Err(Error::StateError(StateError::NoneCompiledHash(*class_hash))),
+            }
         }
     }
 }
@@ -81,7 +139,8 @@ impl blockifier::state::state_api::StateReader for DevnetState {
         let storage = crate::traits::DevnetStateReader::storage_at(
             self,
             &ContractStorageKey::new(contract_address.into(), key.0.into()),
-        );
+        )
+        .map_err(|err| blockifier::state::errors::StateError::StateReadError(err.to_string()))?;
         Ok(storage.into())
     }
 
@@ -89,7 +148,10 @@ impl blockifier::state::state_api::StateReader for DevnetState {
         &mut self,
         contract_address: starknet_api::core::ContractAddress,
     ) -> blockifier::state::state_api::StateResult<starknet_api::core::Nonce> {
-        let nonce = crate::traits::DevnetStateReader::nonce_at(self, &contract_address.into());
+        let nonce = crate::traits::DevnetStateReader::nonce_at(self, &contract_address.into())
+            .map_err(|err| {
+                blockifier::state::errors::StateError::StateReadError(err.to_string())
+            })?;
         Ok(starknet_api::core::Nonce(nonce.into()))
     }
 
@@ -98,7 +160,10 @@ impl blockifier::state::state_api::StateReader for DevnetState {
         contract_address: starknet_api::core::ContractAddress,
     ) -> blockifier::state::state_api::StateResult<starknet_api::core::ClassHash> {
         let class_hash =
-            crate::traits::DevnetStateReader::class_hash_at(self, &contract_address.into());
+            crate::traits::DevnetStateReader::class_hash_at(self, &contract_address.into())
+                .map_err(|err| {
+                    blockifier::state::errors::StateError::StateReadError(err.to_string())
+                })?;
         Ok(starknet_api::core::ClassHash(class_hash.into()))
     }
 
@@ -110,11 +175,15 @@ impl blockifier::state::state_api::StateReader for DevnetState {
     > {
         let contract_class =
             crate::traits::DevnetStateReader::contract_class_at(self, &class_hash.0.into())
-                .map_err(|_| {
-                    blockifier::state::errors::StateError::UndeclaredClassHash(*class_hash)
+                .map_err(|err| match err {
+                    Error::ForkOriginError { .. } => {
+                        blockifier::state::errors::StateError::StateReadError(err.to_string())
+                    }
+                    _ => blockifier::state::errors::StateError::UndeclaredClassHash(*class_hash),
                 })?;
 
-        blockifier::execution::contract_class::ContractClass::try_from(contract_class)
+        contract_class
+            .to_blockifier_contract_class(self.compilation_cache.as_ref())
             .map_err(|err| blockifier::state::errors::StateError::StateReadError(err.to_string()))
     }
 
@@ -123,7 +192,10 @@ impl blockifier::state::state_api::StateReader for DevnetState {
         class_hash: starknet_api::core::ClassHash,
     ) -> blockifier::state::state_api::StateResult<starknet_api::core::CompiledClassHash> {
         let compiled_class_hash =
-            crate::traits::DevnetStateReader::compiled_class_hash_at(self, &(class_hash.0.into()));
+            crate::traits::DevnetStateReader::compiled_class_hash_at(self, &(class_hash.0.into()))
+                .map_err(|err| {
+                    blockifier::state::errors::StateError::StateReadError(err.to_string())
+                })?;
         Ok(starknet_api::core::CompiledClassHash(compiled_class_hash.into()))
     }
 }
@@ -181,7 +253,7 @@ impl StateChanger for StarknetState {
     }
 
     fn increment_nonce(&mut self, address: ContractAddress) -> DevnetResult<()> {
-        let nonce = self.state.state.nonce_at(&address);
+        let nonce = self.state.state.nonce_at(&address)?;
         let persistent_state = &mut self.state.state;
 
         persistent_state
@@ -217,7 +289,7 @@ impl StateChanger for StarknetState {
         // update class_hash -> compiled_class_hash differences
         old_state
             .class_hash_to_compiled_class_hash
-            .extend(state_diff.class_hash_to_compiled_class_hash.iter());
+            .extend(state_diff.class_hash_to_compiled_class_hash);
 
         // update cairo 1 differences
         state_diff.declared_contracts.iter().try_for_each(|class_hash| -> DevnetResult<()> {
@@ -238,10 +310,10 @@ impl StateChanger for StarknetState {
         })?;
 
         // update deployed contracts
-        old_state.address_to_class_hash.extend(state_diff.address_to_class_hash.iter());
+        old_state.address_to_class_hash.extend(state_diff.address_to_class_hash);
 
         // update accounts nonce
-        old_state.address_to_nonce.extend(state_diff.address_to_nonce.iter());
+        old_state.address_to_nonce.extend(state_diff.address_to_nonce);
 
         Ok(())
     }
@@ -253,18 +325,15 @@ impl StateExtractor for StarknetState {
             return Err(Error::ContractNotFound);
         }
 
-        let data = self.state.state.storage_at(&storage_key);
-
-        Ok(data)
+        self.state.state.storage_at(&storage_key)
     }
 
     fn is_contract_declared(&mut self, class_hash: &ClassHash) -> bool {
-        self.state.state.class_hash_to_compiled_class_hash.contains_key(class_hash)
-            || self.state.state.class_hash_to_compiled_class.contains_key(class_hash)
+        self.state.state.is_contract_declared(class_hash)
     }
 
     fn is_contract_deployed(&self, address: &ContractAddress) -> bool {
-        self.state.state.address_to_class_hash.contains_key(address)
+        self.state.state.is_contract_deployed(address)
     }
 
     fn get_class_hash_at_contract_address(
@@ -286,7 +355,7 @@ impl StateExtractor for StarknetState {
             return Err(Error::ContractNotFound);
         }
 
-        Ok(self.state.state.nonce_at(address))
+        self.state.state.nonce_at(address)
     }
 }
 
@@ -369,7 +438,12 @@ mod tests {
                     err.to_string(),
                 )
             })?;
-            let class_hash = crate::traits::DevnetStateReader::class_hash_at(self, &address);
+            let class_hash = crate::traits::DevnetStateReader::class_hash_at(self, &address)
+                .map_err(|err| {
+                    starknet_in_rust::core::errors::state_errors::StateError::CustomError(
+                        err.to_string(),
+                    )
+                })?;
 
             Ok(class_hash.bytes())
         }
@@ -383,7 +457,12 @@ mod tests {
                     err.to_string(),
                 )
             })?;
-            let nonce = crate::traits::DevnetStateReader::nonce_at(self, &address);
+            let nonce =
+                crate::traits::DevnetStateReader::nonce_at(self, &address).map_err(|err| {
+                    starknet_in_rust::core::errors::state_errors::StateError::CustomError(
+                        err.to_string(),
+                    )
+                })?;
 
             Ok(nonce.into())
         }
@@ -413,7 +492,12 @@ mod tests {
             let storage = crate::traits::DevnetStateReader::storage_at(
                 self,
                 &ContractStorageKey::new(contract_address, storage_key),
-            );
+            )
+            .map_err(|err| {
+                starknet_in_rust::core::errors::state_errors::StateError::CustomError(
+                    err.to_string(),
+                )
+            })?;
             Ok(storage.into())
         }
 
@@ -431,7 +515,12 @@ mod tests {
             })?;
 
             let compiled_class_hash =
-                crate::traits::DevnetStateReader::compiled_class_hash_at(self, &class_hash_as_felt);
+                crate::traits::DevnetStateReader::compiled_class_hash_at(self, &class_hash_as_felt)
+                    .map_err(|err| {
+                        starknet_in_rust::core::errors::state_errors::StateError::CustomError(
+                            err.to_string(),
+                        )
+                    })?;
             if compiled_class_hash == Felt::default() {
                 return Err(
                     starknet_in_rust::core::errors::state_errors::StateError::NoneCompiledHash(
@@ -615,6 +704,23 @@ mod tests {
         assert_eq!(expected_result, generated_result);
     }
 
+    #[test]
+    fn changes_after_cloning_do_not_affect_the_clone() {
+        let (mut state, address) = setup();
+        let storage_key = dummy_contract_storage_key();
+        state.change_storage(storage_key, Felt::from(1)).unwrap();
+        let cloned_state = state.clone();
+
+        state.change_storage(storage_key, Felt::from(2)).unwrap();
+        state.increment_nonce(address).unwrap();
+
+        let cloned_storage = &cloned_state.state.state.address_to_storage;
+        assert_eq!(cloned_storage.get(&storage_key), Some(&Felt::from(1)));
+        assert_eq!(cloned_state.get_nonce(&address).unwrap(), Felt::from(0));
+        assert_eq!(state.state.state.address_to_storage.get(&storage_key), Some(&Felt::from(2)));
+        assert_eq!(state.get_nonce(&address).unwrap(), Felt::from(1));
+    }
+
     #[test]
     fn get_nonce_should_return_error_when_contract_not_deployed() {
         let (state, _) = setup();
@@ -0,0 +1,351 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+use starknet_types::contract_address::ContractAddress;
+use starknet_types::contract_class::{Cairo0ContractClass, ContractClass};
+use starknet_types::contract_storage_key::ContractStorageKey;
+use starknet_types::felt::{ClassHash, Felt};
+use starknet_types::serde_helpers::rpc_sierra_contract_class_to_sierra_contract_class::deserialize_to_sierra_contract_class;
+use url::Url;
+
+use crate::error::{DevnetResult, Error};
+use crate::utils::{create_remote_node_agent, run_blocking, READ_TIMEOUT};
+
+/// JSON-RPC error codes which mean that the requested data does not exist at the origin
+const CONTRACT_NOT_FOUND: i64 = 20;
+const CLASS_HASH_NOT_FOUND: i64 = 28;
+
+/// Values already retrieved from the origin. The origin state at the fork block never changes, so
+/// the cache is shared by all clones of the reader.
+#[derive(Default)]
+struct OriginCache {
+    storage: HashMap<ContractStorageKey, Felt>,
+    nonces: HashMap<ContractAddress, Felt>,
+    class_hashes: HashMap<ContractAddress, ClassHash>,
+    classes: HashMap<ClassHash, Option<ContractClass>>,
+}
+
+/// Reads the state of the Starknet network Devnet was forked from, as it was at the fork block.
+/// Data is requested lazily via JSON-RPC and cached locally.
+#[derive(Clone)]
+pub(crate) struct OriginReader {
+    url: Url,
+    block_number: u64,
+    agent: ureq::Agent,
+    cache: Arc<Mutex<OriginCache>>,
+}
+
+impl OriginReader {
+    /// Creates a reader of the origin available at `url`. If `block_number` is not provided, the
+    /// latest block of the origin is used.
+    pub(crate) fn new(url: Url, block_number: Option<u64>) -> DevnetResult<Self> {
+        Self::with_read_timeout(url, block_number, READ_TIMEOUT)
+    }
+
+    fn with_read_timeout(
+        url: Url,
+        block_number: Option<u64>,
+        read_timeout: Duration,
+    ) -> DevnetResult<Self> {
+        let agent = create_remote_node_agent(read_timeout);
+        let mut reader = Self { url, block_number: 0, agent, cache: Default::default() };
+
+        reader.block_number = match block_number {
+            Some(block_number) => block_number,
+            None => reader.request("starknet_blockNumber", json!([]))?.ok_or_else(|| {
+                Error::ForkOriginError { msg: "Origin did not return a block number".into() }
+            })?,
+        };
+
+        Ok(reader)
+    }
+
+    pub(crate) fn block_number(&self) -> u64 {
+        self.block_number
+    }
+
+    pub(crate) fn storage_at(&self, storage_key: &ContractStorageKey) -> DevnetResult<Felt> {
+        if let Some(value) = self.cache()?.storage.get(storage_key) {
+            return Ok(*value);
+        }
+
+        let value = self
+            .request(
+                "starknet_getStorageAt",
+                json!({
+                    "contract_address": storage_key.get_contract_address(),
+                    "key": storage_key.get_storage_key().0,
+                    "block_id": self.block_id(),
+                }),
+            )?
+            .unwrap_or_default();
+
+        self.cache()?.storage.insert(*storage_key, value);
+        Ok(value)
+    }
+
+    pub(crate) fn nonce_at(&self, address: &ContractAddress) -> DevnetResult<Felt> {
+        if let Some(nonce) = self.cache()?.nonces.get(address) {
+            return Ok(*nonce);
+        }
+
+        let nonce = self
+            .request(
+                "starknet_getNonce",
+                json!({ "block_id": self.block_id(), "contract_address": address }),
+            )?
+            .unwrap_or_default();
+
+        self.cache()?.nonces.insert(*address, nonce);
+        Ok(nonce)
+    }
+
+    /// Returns the class hash of the contract at `address`, or zero if it is not deployed
+    pub(crate) fn class_hash_at(&self, address: &ContractAddress) -> DevnetResult<ClassHash> {
+        if let Some(class_hash) = self.cache()?.class_hashes.get(address) {
+            return Ok(*class_hash);
+        }
+
+        let class_hash = self
+            .request(
+                "starknet_getClassHashAt",
+                json!({ "block_id": self.block_id(), "contract_address": address }),
+            )?
+            .unwrap_or_default();
+
+        self.cache()?.class_hashes.insert(*address, class_hash);
+        Ok(class_hash)
+    }
+
+    /// Returns the class declared with `class_hash`, or `None` if it is not declared
+    pub(crate) fn contract_class_at(
+        &self,
+        class_hash: &ClassHash,
+    ) -> DevnetResult<Option<ContractClass>> {
+        if let Some(contract_class) = self.cache()?.classes.get(class_hash) {
+            return Ok(contract_class.clone());
+        }
+
+        let contract_class = self
+            .request::<Value>(
+                "starknet_getClass",
+                json!({ "block_id": self.block_id(), "class_hash": class_hash }),
+            )?
+            .map(Self::parse_contract_class)
+            .transpose()?;
+
+        self.cache()?.classes.insert(*class_hash, contract_class.clone());
+        Ok(contract_class)
+    }
+
+    /// Returns true if a contract with a non-zero class hash is deployed at `address`. Errors of
+    /// the origin are treated as the contract not being deployed.
+    pub(crate) fn is_contract_deployed(&self, address: &ContractAddress) -> bool {
+        matches!(self.class_hash_at(address), Ok(class_hash) if !class_hash.is_zero())
+    }
+
+    /// Returns true if the class is declared. Errors of the origin are treated as the class not
+    /// being declared.
+    pub(crate) fn is_class_declared(&self, class_hash: &ClassHash) -> bool {
+        matches!(self.contract_class_at(class_hash), Ok(Some(_)))
+    }
+
+    fn block_id(&self) -> Value {
+        json!({ "block_number": self.block_number })
+    }
+
+    fn cache(&self) -> DevnetResult<MutexGuard<OriginCache>> {
+        self.cache.lock().map_err(|_| Error::ForkOriginError { msg: "Cache is poisoned".into() })
+    }
+
+    /// Classes are returned in the RPC format, which for Cairo 1 classes has the ABI as a string
+    fn parse_contract_class(json_class: Value) -> DevnetResult<ContractClass> {
+        let invalid_class_error = |err: serde_json::Error| Error::ForkOriginError {
+            msg: format!("Invalid class: {err}"),
+        };
+
+        if json_class.get("sierra_program").is_some() {
+            let sierra_class =
+                deserialize_to_sierra_contract_class(json_class).map_err(invalid_class_error)?;
+            Ok(ContractClass::Cairo1(sierra_class))
+        } else {
+            let cairo_0_class = serde_json::from_value::<Cairo0ContractClass>(json_class)
+                .map_err(invalid_class_error)?;
+            Ok(ContractClass::Cairo0(cairo_0_class))
+        }
+    }
+
+    /// Sends a JSON-RPC request to the origin. Returns `None` if the origin responded that the
+    /// requested contract or class does not exist.
+    fn request<T: DeserializeOwned>(&self, method: &str, params: Value) -> DevnetResult<Option<T>> {
+        let request_body = json!({
+            "jsonrpc": "2.0",
+            "id": 0,
+            "method": method,
+            "params": params,
+        });
+
+        let mut response: Value = run_blocking(|| {
+            self.agent
+                .post(self.url.as_str())
+                .send_json(request_body)
+                .and_then(|response| response.into_json().map_err(ureq::Error::from))
+        })
+        .map_err(|err| Error::ForkOriginError { msg: format!("{method} failed: {err}") })?;
+
+        if let Some(error) = response.get("error") {
+            return match error.get("code").and_then(Value::as_i64) {
+                Some(CONTRACT_NOT_FOUND | CLASS_HASH_NOT_FOUND) => Ok(None),
+                _ => Err(Error::ForkOriginError { msg: format!("{method} failed: {error}") }),
+            };
+        }
+
+        let result = response.get_mut("result").map(Value::take).ok_or_else(|| {
+            Error::ForkOriginError { msg: format!("{method} response has no result") }
+        })?;
+
+        serde_json::from_value(result).map(Some).map_err(|err| Error::ForkOriginError {
+            msg: format!("{method} returned an invalid result: {err}"),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::TcpListener;
+    use std::sync::atomic::Ordering;
+    use std::time::{Duration, Instant};
+
+    use serde_json::json;
+    use starknet_types::contract_address::ContractAddress;
+    use starknet_types::contract_storage_key::ContractStorageKey;
+    use starknet_types::felt::Felt;
+    use starknet_types::patricia_key::PatriciaKey;
+
+    use super::OriginReader;
+    use crate::error::Error;
+    use crate::utils::test_utils::{
+        mock_origin_handler, spawn_mock_json_rpc_server, MOCK_ORIGIN_BLOCK_NUMBER,
+        MOCK_ORIGIN_CLASS_HASH, MOCK_ORIGIN_CONTRACT_ADDRESS, MOCK_ORIGIN_NONCE,
+        MOCK_ORIGIN_STORAGE_VALUE,
+    };
+
+    fn origin_contract_address() -> ContractAddress {
+        ContractAddress::new(Felt::from(MOCK_ORIGIN_CONTRACT_ADDRESS)).unwrap()
+    }
+
+    fn unknown_contract_address() -> ContractAddress {
+        ContractAddress::new(Felt::from(0x42)).unwrap()
+    }
+
+    #[test]
+    fn latest_block_of_origin_used_if_fork_block_not_provided() {
+        let (url, _) = spawn_mock_json_rpc_server(mock_origin_handler);
+
+        let origin = OriginReader::new(url.clone(), None).unwrap();
+        assert_eq!(origin.block_number(), MOCK_ORIGIN_BLOCK_NUMBER);
+
+        let origin = OriginReader::new(url, Some(3)).unwrap();
+        assert_eq!(origin.block_number(), 3);
+    }
+
+    #[test]
+    fn values_of_deployed_contract_are_read_and_cached() {
+        let (url, request_count) = spawn_mock_json_rpc_server(mock_origin_handler);
+        let origin = OriginReader::new(url, Some(MOCK_ORIGIN_BLOCK_NUMBER)).unwrap();
+        let address = origin_contract_address();
+        let storage_key =
+            ContractStorageKey::new(address, PatriciaKey::new(Felt::from(0x2)).unwrap());
+
+        for _ in 0..2 {
+            assert_eq!(origin.class_hash_at(&address).unwrap(), Felt::from(MOCK_ORIGIN_CLASS_HASH));
+            assert_eq!(origin.nonce_at(&address).unwrap(), Felt::from(MOCK_ORIGIN_NONCE));
+            assert_eq!(
+                origin.storage_at(&storage_key).unwrap(),
+                Felt::from(MOCK_ORIGIN_STORAGE_VALUE)
+            );
+        }
+        assert!(origin.is_contract_deployed(&address));
+
+        // the second round of reads and the deployment check are served from the cache
+        assert_eq!(request_count.load(Ordering::SeqCst), 3);
+
+        // clones share the cache
+        origin.clone().nonce_at(&address).unwrap();
+        assert_eq!(request_count.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn missing_data_is_read_as_default() {
+        let (url, _) = spawn_mock_json_rpc_server(mock_origin_handler);
+        let origin = OriginReader::new(url, Some(MOCK_ORIGIN_BLOCK_NUMBER)).unwrap();
+        let address = unknown_contract_address();
+
+        assert_eq!(origin.class_hash_at(&address).unwrap(), Felt::default());
+        assert_eq!(origin.nonce_at(&address).unwrap(), Felt::default());
+        assert!(!origin.is_contract_deployed(&address));
+
+        assert!(origin.contract_class_at(&Felt::from(MOCK_ORIGIN_CLASS_HASH)).unwrap().is_none());
+        assert!(!origin.is_class_declared(&Felt::from(MOCK_ORIGIN_CLASS_HASH)));
+    }
+
+    #[test]
+    fn origin_errors_are_propagated() {
+        let (url, _) = spawn_mock_json_rpc_server(
+            |_, _| json!({ "error": { "code": 24, "message": "Block not found" } }),
+        );
+
+        match OriginReader::new(url.clone(), None) {
+            Err(Error::ForkOriginError { .. }) => (),
+            unexpected => panic!("Should have failed; got {:?}", unexpected.err()),
+        }
+
+        let origin = OriginReader::new(url, Some(MOCK_ORIGIN_BLOCK_NUMBER)).unwrap();
+        match origin.nonce_at(&origin_contract_address()) {
+            Err(Error::ForkOriginError { .. }) => (),
+            unexpected => panic!("Should have failed; got {unexpected:?}"),
+        }
+        assert!(!origin.is_contract_deployed(&origin_contract_address()));
+    }
+
+    #[test]
+    fn unresponsive_origin_times_out() {
+        // accepts connections, but never responds
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap()).parse().unwrap();
+
+        let start = Instant::now();
+        match OriginReader::with_read_timeout(url, None, Duration::from_millis(200)) {
+            Err(Error::ForkOriginError { .. }) => (),
+            unexpected => panic!("Should have failed; got {:?}", unexpected.err()),
+        }
+        assert!(start.elapsed() < Duration::from_secs(5));
+        drop(listener);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn origin_is_read_from_async_worker() {
+        let (url, _) = spawn_mock_json_rpc_server(mock_origin_handler);
+        let origin = OriginReader::new(url, Some(MOCK_ORIGIN_BLOCK_NUMBER)).unwrap();
+
+        assert_eq!(
+            origin.nonce_at(&origin_contract_address()).unwrap(),
+            Felt::from(MOCK_ORIGIN_NONCE)
+        );
+    }
+
+    #[test]
+    fn unreachable_origin() {
+        let (url, _) = spawn_mock_json_rpc_server(mock_origin_handler);
+        let mut unreachable_url = url.clone();
+        unreachable_url.set_port(Some(1)).unwrap();
+
+        match OriginReader::new(unreachable_url, None) {
+            Err(Error::ForkOriginError { .. }) => (),
+            unexpected => panic!("Should have failed; got {:?}", unexpected.err()),
+        }
+    }
+}
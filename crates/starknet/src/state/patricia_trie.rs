@@ -0,0 +1,407 @@
+use std::collections::HashSet;
+
+use starknet_rs_core::crypto::pedersen_hash;
+use starknet_rs_crypto::poseidon_hash;
+use starknet_rs_ff::FieldElement;
+use starknet_types::felt::Felt;
+use starknet_types::rpc::storage_proof::{MerkleNode, NodeHashToNode};
+
+/// Height of the tries of the Starknet state; keys are at most 251 bits long
+pub(crate) const STATE_TRIE_HEIGHT: usize = 251;
+/// Height of the tries committing to the transactions and events of a block, keyed by index
+pub(crate) const BLOCK_TRIE_HEIGHT: usize = 64;
+
+/// Hash function with which the nodes of a trie are combined
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum TrieHashFunction {
+    Pedersen,
+    Poseidon,
+}
+
+impl TrieHashFunction {
+    pub(crate) fn hash(&self, left: FieldElement, right: FieldElement) -> FieldElement {
+        match self {
+            Self::Pedersen => pedersen_hash(&left, &right),
+            Self::Poseidon => poseidon_hash(left, right),
+        }
+    }
+}
+
+/// Key of a leaf, or of a node with the bits below its height cleared, as big-endian bytes
+type Key = [u8; 32];
+
+/// Binary Merkle-Patricia trie, as defined for the Starknet state and block commitments. Only the
+/// leaves and the hashes of the nodes above them are kept. The trie is persistent: clones share
+/// their leaves and hashes, and an update rehashes only the nodes on the paths to the updated
+/// leaves.
+#[derive(Clone)]
+pub(crate) struct PatriciaTrie {
+    height: usize,
+    /// Leaves with non-zero values
+    leaves: im::OrdMap<Key, FieldElement>,
+    /// Hashes of the subtrees below each height, by the key bits above that height
+    node_hashes: im::HashMap<(usize, Key), FieldElement>,
+    hash_function: TrieHashFunction,
+}
+
+impl PatriciaTrie {
+    pub(crate) fn new(
+        height: usize,
+        leaves: impl IntoIterator<Item = (Felt, Felt)>,
+        hash_function: TrieHashFunction,
+    ) -> Self {
+        let mut trie = Self::empty(height, hash_function);
+        trie.update(leaves);
+
+        trie
+    }
+
+    pub(crate) fn empty(height: usize, hash_function: TrieHashFunction) -> Self {
+        Self { height, leaves: im::OrdMap::new(), node_hashes: im::HashMap::new(), hash_function }
+    }
+
+    /// Sets the values of the given leaves; a zero value removes the leaf
+    pub(crate) fn update(&mut self, leaves: impl IntoIterator<Item = (Felt, Felt)>) {
+        for (key, value) in leaves {
+            let key = key.bytes();
+            if value.is_zero() {
+                self.leaves.remove(&key);
+            } else {
+                self.leaves.insert(key, value.into());
+            }
+
+            for height in 0..=self.height {
+                self.node_hashes.remove(&(height, mask(&key, height)));
+            }
+        }
+
+        // the nodes are rehashed right away, so that reading the trie finds all of them
+        if !self.leaves.is_empty() {
+            let mut node_hashes = std::mem::take(&mut self.node_hashes);
+            self.node_hash(&mut node_hashes, self.height, [0; 32]);
+            self.node_hashes = node_hashes;
+        }
+    }
+
+    /// Returns the hash of the root node, which is zero for an empty trie
+    pub(crate) fn root(&self) -> Felt {
+        if self.leaves.is_empty() {
+            return Felt::default();
+        }
+
+        self.node_hash(&mut self.node_hashes.clone(), self.height, [0; 32]).into()
+    }
+
+    /// Returns the nodes on the paths from the root to the leaves of `keys`, each node once. For a
+    /// key without a leaf, its path ends with the edge from which the key diverges, which proves
+    /// that there is no such leaf.
+    pub(crate) fn proof<'a>(
+        &self,
+        keys: impl IntoIterator<Item = &'a Felt>,
+    ) -> Vec<NodeHashToNode> {
+        let mut node_hashes = self.node_hashes.clone();
+        let mut nodes = vec![];
+        let mut added_node_hashes = HashSet::new();
+        let mut add_node = |node_hash: FieldElement, node: MerkleNode| {
+            if added_node_hashes.insert(node_hash) {
+                nodes.push(NodeHashToNode { node_hash: node_hash.into(), node });
+            }
+        };
+
+        for key in keys {
+            if self.leaves.is_empty() {
+                break;
+            }
+
+            let key = key.bytes();
+            let mut prefix = [0; 32];
+            let mut height = self.height;
+            while height > 0 {
+                let (first_key, edge_length) = self.edge(&prefix, height);
+                if edge_length > 0 {
+                    let child_height = height - edge_length;
+                    let child_prefix = mask(first_key, child_height);
+                    let path = path_bits(first_key, child_height, edge_length);
+                    add_node(
+                        self.node_hash(&mut node_hashes, height, prefix),
+                        MerkleNode::Edge {
+                            path: path.into(),
+                            length: edge_length as u8,
+                            child: self
+                                .node_hash(&mut node_hashes, child_height, child_prefix)
+                                .into(),
+                        },
+                    );
+
+                    if path_bits(&key, child_height, edge_length) != path {
+                        break;
+                    }
+                    // the child is a binary node or the leaf
+                    prefix = child_prefix;
+                    height = child_height;
+                    continue;
+                }
+
+                let right_prefix = with_bit_set(&prefix, height - 1);
+                let left = self.node_hash(&mut node_hashes, height - 1, prefix);
+                let right = self.node_hash(&mut node_hashes, height - 1, right_prefix);
+                add_node(
+                    self.hash_function.hash(left, right),
+                    MerkleNode::Binary { left: left.into(), right: right.into() },
+                );
+
+                if bit_at(&key, height - 1) {
+                    prefix = right_prefix;
+                }
+                height -= 1;
+            }
+        }
+
+        nodes
+    }
+
+    /// Hash of the topmost node below `height` whose key bits above that height are `prefix`. The
+    /// subtree has to contain at least one leaf. If the node is an edge node, its hash commits to
+    /// the path leading down from it. Hashes are looked up in and added to `node_hashes`.
+    fn node_hash(
+        &self,
+        node_hashes: &mut im::HashMap<(usize, Key), FieldElement>,
+        height: usize,
+        prefix: Key,
+    ) -> FieldElement {
+        if height == 0 {
+            return self.leaves.get(&prefix).copied().unwrap_or_default();
+        }
+        if let Some(node_hash) = node_hashes.get(&(height, prefix)) {
+            return *node_hash;
+        }
+
+        let (first_key, edge_length) = self.edge(&prefix, height);
+        let node_hash = if edge_length == 0 {
+            let left = self.node_hash(node_hashes, height - 1, prefix);
+            let right = self.node_hash(node_hashes, height - 1, with_bit_set(&prefix, height - 1));
+            self.hash_function.hash(left, right)
+        } else {
+            let child_height = height - edge_length;
+            let child_hash =
+                self.node_hash(node_hashes, child_height, mask(first_key, child_height));
+            let path = path_bits(first_key, child_height, edge_length);
+            self.hash_function.hash(child_hash, path) + FieldElement::from(edge_length)
+        };
+        node_hashes.insert((height, prefix), node_hash);
+
+        node_hash
+    }
+
+    /// Returns the key of the first leaf below `height` whose key bits above it are `prefix`, and
+    /// for how many levels all such leaves share their key bits, i.e. the length of the edge the
+    /// subtree starts with. The subtree has to contain at least one leaf.
+    fn edge(&self, prefix: &Key, height: usize) -> (&Key, usize) {
+        let mut leaves = self.leaves.range(*prefix..=with_bits_below_set(prefix, height));
+        let (first_key, _) = leaves.next().expect("subtree has no leaves");
+        let last_key = leaves.next_back().map_or(first_key, |(last_key, _)| last_key);
+
+        let edge_length = (0..height)
+            .rev()
+            .take_while(|position| bit_at(first_key, *position) == bit_at(last_key, *position))
+            .count();
+
+        (first_key, edge_length)
+    }
+}
+
+/// Returns whether the bit at `position` of `key` is set; position 0 is the least significant bit
+fn bit_at(key: &Key, position: usize) -> bool {
+    (key[31 - position / 8] >> (position % 8)) & 1 == 1
+}
+
+fn with_bit_set(key: &Key, position: usize) -> Key {
+    let mut key = *key;
+    key[31 - position / 8] |= 1 << (position % 8);
+    key
+}
+
+/// Returns `key` with the bits below `height` cleared
+fn mask(key: &Key, height: usize) -> Key {
+    let mut key = *key;
+    (0..height).for_each(|position| key[31 - position / 8] &= !(1 << (position % 8)));
+    key
+}
+
+/// Returns `key` with the bits below `height` set
+fn with_bits_below_set(key: &Key, height: usize) -> Key {
+    (0..height).fold(*key, |key, position| with_bit_set(&key, position))
+}
+
+/// Returns `length` bits of `key`, starting at `lowest_position`, as a number
+fn path_bits(key: &Key, lowest_position: usize, length: usize) -> FieldElement {
+    (lowest_position..lowest_position + length).rev().fold(FieldElement::ZERO, |path, position| {
+        let bit = if bit_at(key, position) { FieldElement::ONE } else { FieldElement::ZERO };
+        path.double() + bit
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use starknet_rs_core::crypto::pedersen_hash;
+    use starknet_rs_crypto::poseidon_hash;
+    use starknet_rs_ff::FieldElement;
+    use starknet_types::felt::Felt;
+    use starknet_types::rpc::storage_proof::MerkleNode;
+
+    use super::{path_bits, PatriciaTrie, TrieHashFunction, BLOCK_TRIE_HEIGHT, STATE_TRIE_HEIGHT};
+
+    #[test]
+    fn empty_trie_has_zero_root() {
+        let trie = PatriciaTrie::new(
+            STATE_TRIE_HEIGHT,
+            vec![(Felt::from(1), Felt::from(0))],
+            TrieHashFunction::Pedersen,
+        );
+        assert_eq!(trie.root(), Felt::from(0));
+    }
+
+    #[test]
+    fn root_of_single_leaf_is_edge_to_it() {
+        let key = Felt::from(0x123);
+        let value = Felt::from(42);
+
+        let expected_root = pedersen_hash(&value.into(), &key.into()) + FieldElement::from(251_u8);
+        let trie =
+            PatriciaTrie::new(STATE_TRIE_HEIGHT, vec![(key, value)], TrieHashFunction::Pedersen);
+        assert_eq!(trie.root(), expected_root.into());
+    }
+
+    #[test]
+    fn edge_to_single_leaf_spans_the_height_of_the_trie() {
+        let value = FieldElement::from(42_u8);
+
+        let expected_root =
+            pedersen_hash(&value, &FieldElement::ZERO) + FieldElement::from(BLOCK_TRIE_HEIGHT);
+        let trie = PatriciaTrie::new(
+            BLOCK_TRIE_HEIGHT,
+            vec![(Felt::from(0), Felt::from(value))],
+            TrieHashFunction::Pedersen,
+        );
+        assert_eq!(trie.root(), expected_root.into());
+    }
+
+    #[test]
+    fn root_of_sibling_leaves_is_edge_to_binary_node() {
+        let (left, right) = (FieldElement::from(10_u8), FieldElement::from(20_u8));
+        let leaves = vec![(Felt::from(3), Felt::from(right)), (Felt::from(2), Felt::from(left))];
+
+        // keys 2 and 3 share all bits except the lowest one, which is 0 for the left leaf
+        let binary_node = poseidon_hash(left, right);
+        let expected_root =
+            poseidon_hash(binary_node, FieldElement::ONE) + FieldElement::from(250_u8);
+        let trie = PatriciaTrie::new(STATE_TRIE_HEIGHT, leaves, TrieHashFunction::Poseidon);
+        assert_eq!(trie.root(), expected_root.into());
+    }
+
+    #[test]
+    fn updated_trie_has_the_root_of_a_trie_built_from_its_leaves() {
+        let leaves: Vec<(Felt, Felt)> =
+            (1..20_u128).map(|key| (Felt::from(key * 7919), Felt::from(key))).collect();
+        let mut trie =
+            PatriciaTrie::new(STATE_TRIE_HEIGHT, leaves.clone(), TrieHashFunction::Pedersen);
+        let earlier_trie = trie.clone();
+
+        // changes one leaf, removes one and adds one
+        let updates = vec![
+            (Felt::from(7919), Felt::from(100)),
+            (Felt::from(2 * 7919), Felt::from(0)),
+            (Felt::from(0x123), Felt::from(42)),
+        ];
+        trie.update(updates.clone());
+
+        let mut updated_leaves: Vec<(Felt, Felt)> = leaves.clone();
+        updated_leaves.extend(updates);
+        updated_leaves.retain(|(key, _)| *key != Felt::from(7919) && *key != Felt::from(2 * 7919));
+        updated_leaves.push((Felt::from(7919), Felt::from(100)));
+
+        let expected_trie =
+            PatriciaTrie::new(STATE_TRIE_HEIGHT, updated_leaves, TrieHashFunction::Pedersen);
+        assert_eq!(trie.root(), expected_trie.root());
+        assert_eq!(
+            earlier_trie.root(),
+            PatriciaTrie::new(STATE_TRIE_HEIGHT, leaves, TrieHashFunction::Pedersen).root()
+        );
+    }
+
+    #[test]
+    fn root_does_not_depend_on_order_of_leaves() {
+        let leaves: Vec<(Felt, Felt)> =
+            (1..10_u128).map(|i| (Felt::from(i * 0x1000 + i), Felt::from(i))).collect();
+        let reversed_leaves = leaves.iter().rev().copied();
+
+        assert_eq!(
+            PatriciaTrie::new(STATE_TRIE_HEIGHT, leaves.clone(), TrieHashFunction::Pedersen).root(),
+            PatriciaTrie::new(STATE_TRIE_HEIGHT, reversed_leaves, TrieHashFunction::Pedersen)
+                .root()
+        );
+    }
+
+    #[test]
+    fn proof_leads_from_root_to_leaf() {
+        let leaves: Vec<(Felt, Felt)> =
+            (1..10_u128).map(|i| (Felt::from(i * 0x1000 + i), Felt::from(i))).collect();
+        let trie = PatriciaTrie::new(STATE_TRIE_HEIGHT, leaves, TrieHashFunction::Pedersen);
+        let key = Felt::from(3 * 0x1000 + 3);
+
+        let proof = trie.proof([&key]);
+        assert_eq!(proof[0].node_hash, trie.root());
+
+        // each node is the child of the previous one, the last one points to the leaf
+        let mut expected_hash = trie.root();
+        let mut height = STATE_TRIE_HEIGHT;
+        for node in proof {
+            assert_eq!(node.node_hash, expected_hash);
+            match node.node {
+                MerkleNode::Binary { left, right } => {
+                    height -= 1;
+                    let bit = path_bits(&key.bytes(), height, 1);
+                    expected_hash = if bit == FieldElement::ONE { right } else { left };
+                }
+                MerkleNode::Edge { path, length, child } => {
+                    height -= length as usize;
+                    assert_eq!(
+                        path_bits(&key.bytes(), height, length as usize),
+                        FieldElement::from(path)
+                    );
+                    expected_hash = child;
+                }
+            }
+        }
+        assert_eq!(height, 0);
+        assert_eq!(expected_hash, Felt::from(3));
+    }
+
+    #[test]
+    fn proof_of_missing_key_ends_with_diverging_edge() {
+        let key = Felt::from(0x123);
+        let trie = PatriciaTrie::new(
+            STATE_TRIE_HEIGHT,
+            vec![(key, Felt::from(42))],
+            TrieHashFunction::Pedersen,
+        );
+
+        let proof = trie.proof([&Felt::from(0x124)]);
+        assert_eq!(proof.len(), 1);
+        assert_eq!(proof[0].node_hash, trie.root());
+        assert_eq!(
+            proof[0].node,
+            MerkleNode::Edge { path: key, length: 251, child: Felt::from(42) }
+        );
+
+        // nodes shared by several keys are only included once
+        assert_eq!(trie.proof([&key, &Felt::from(0x124)]), proof);
+    }
+
+    #[test]
+    fn path_bits_are_read_from_the_given_position() {
+        let key = Felt::from(0b1011_0100);
+        assert_eq!(path_bits(&key.bytes(), 2, 4), FieldElement::from(0b1101_u8));
+        assert_eq!(path_bits(&key.bytes(), 0, 251), key.into());
+    }
+}
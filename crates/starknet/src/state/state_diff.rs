@@ -2,6 +2,7 @@ use std::collections::HashMap;
 
 use blockifier::state::cached_state::CachedState;
 use blockifier::state::state_api::State;
+use serde::{Deserialize, Serialize};
 use starknet_types::contract_address::ContractAddress;
 use starknet_types::felt::{ClassHash, Felt};
 use starknet_types::patricia_key::{PatriciaKey, StorageKey};
@@ -10,7 +11,7 @@ use super::DevnetState;
 use crate::error::DevnetResult;
 
 /// This struct is used to store the difference between state modifications
-#[derive(PartialEq, Default, Debug, Clone)]
+#[derive(PartialEq, Default, Debug, Clone, Serialize, Deserialize)]
 pub struct StateDiff {
     pub(crate) storage_updates: HashMap<ContractAddress, HashMap<StorageKey, Felt>>,
     pub(crate) address_to_nonce: HashMap<ContractAddress, Felt>,
@@ -43,9 +44,8 @@ impl StateDiff {
         for (class_hash, class) in new_state.global_class_hash_to_class().get_order().iter() {
             let class_hash_as_felt: Felt = class_hash.0.into();
 
-            if !(old_state.class_hash_to_compiled_class.contains_key(&class_hash_as_felt)
-                || old_state.class_hash_to_compiled_class_hash.contains_key(&class_hash_as_felt))
-            {
+            // classes loaded from the origin of a fork are not declared by the transaction
+            if !old_state.is_contract_declared(&class_hash_as_felt) {
                 match class {
                     blockifier::execution::contract_class::ContractClass::V0(_) => {
                         cairo_0_declared_contracts.push(class_hash_as_felt)
@@ -116,17 +116,42 @@ impl StateDiff {
             declared_contracts,
         })
     }
+
+    /// Merges a newer state diff into this one. Values present in both diffs are taken from
+    /// `other`, so the result describes the change from the oldest to the newest state.
+    pub(crate) fn extend(&mut self, other: StateDiff) {
+        for (contract_address, storage) in other.storage_updates {
+            self.storage_updates.entry(contract_address).or_default().extend(storage);
+        }
+        self.address_to_nonce.extend(other.address_to_nonce);
+        self.address_to_class_hash.extend(other.address_to_class_hash);
+        self.class_hash_to_compiled_class_hash.extend(other.class_hash_to_compiled_class_hash);
+
+        for class_hash in other.declared_contracts {
+            if !self.declared_contracts.contains(&class_hash) {
+                self.declared_contracts.push(class_hash);
+            }
+        }
+        for class_hash in other.cairo_0_declared_contracts {
+            if !self.cairo_0_declared_contracts.contains(&class_hash) {
+                self.cairo_0_declared_contracts.push(class_hash);
+            }
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
 
+    use std::collections::HashMap;
+
     use blockifier::state::cached_state::CachedState;
     use blockifier::state::state_api::State;
     use starknet_api::core::ClassHash;
     use starknet_api::hash::StarkFelt;
     use starknet_types::contract_class::{Cairo0ContractClass, ContractClass};
     use starknet_types::felt::Felt;
+    use starknet_types::patricia_key::PatriciaKey;
 
     use super::StateDiff;
     use crate::state::DevnetState;
@@ -246,6 +271,40 @@ mod tests {
         assert_eq!(generated_diff, expected_diff);
     }
 
+    #[test]
+    fn extending_state_diff_keeps_latest_values() {
+        let contract_address = dummy_contract_address();
+        let storage_key = PatriciaKey::new(Felt::from(1)).unwrap();
+        let other_storage_key = PatriciaKey::new(Felt::from(2)).unwrap();
+
+        let mut first_diff = StateDiff::default();
+        first_diff.address_to_nonce.insert(contract_address, Felt::from(1));
+        first_diff
+            .storage_updates
+            .insert(contract_address, HashMap::from([(storage_key, Felt::from(10))]));
+        first_diff.declared_contracts.push(dummy_felt());
+
+        let mut second_diff = StateDiff::default();
+        second_diff.address_to_nonce.insert(contract_address, Felt::from(2));
+        second_diff.storage_updates.insert(
+            contract_address,
+            HashMap::from([(storage_key, Felt::from(20)), (other_storage_key, Felt::from(30))]),
+        );
+        second_diff.declared_contracts.push(dummy_felt());
+
+        first_diff.extend(second_diff);
+
+        let mut expected_diff = StateDiff::default();
+        expected_diff.address_to_nonce.insert(contract_address, Felt::from(2));
+        expected_diff.storage_updates.insert(
+            contract_address,
+            HashMap::from([(storage_key, Felt::from(20)), (other_storage_key, Felt::from(30))]),
+        );
+        expected_diff.declared_contracts.push(dummy_felt());
+
+        assert_eq!(first_diff, expected_diff);
+    }
+
     fn setup() -> (DevnetState, CachedState<DevnetState>) {
         let state = DevnetState::default();
         let cached_state = CachedState::from(state.clone());
@@ -17,7 +17,12 @@ pub struct StateUpdate {
 }
 
 impl StateUpdate {
-    pub fn new(block_hash: Felt, state_diff: StateDiff) -> DevnetResult<Self> {
+    pub fn new(
+        block_hash: Felt,
+        new_root: Felt,
+        old_root: Felt,
+        state_diff: StateDiff,
+    ) -> DevnetResult<Self> {
         // declared classes (class hash, compiled class hash) that are not cairo 0
         let declared_classes = state_diff.class_hash_to_compiled_class_hash.into_iter().collect();
 
@@ -37,11 +42,10 @@ impl StateUpdate {
         // deployed contracts (address -> class hash)
         let deployed_contracts = state_diff.address_to_class_hash.into_iter().collect();
 
-        // TODO new and old root are not computed, they are not part of the MVP
         Ok(Self {
             block_hash,
-            new_root: Felt::default(),
-            old_root: Felt::default(),
+            new_root,
+            old_root,
             declared_classes,
             cairo_0_declared_classes,
             storage_updates,
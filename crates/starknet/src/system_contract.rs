@@ -1,6 +1,7 @@
 use starknet_types::contract_address::ContractAddress;
 use starknet_types::contract_class::{Cairo0Json, ContractClass};
 use starknet_types::felt::{Balance, ClassHash, Felt};
+use starknet_types::rpc::estimate_message_fee::FeeUnit;
 
 use crate::error::DevnetResult;
 use crate::traits::{Accounted, Deployed, StateChanger, StateExtractor};
@@ -52,6 +53,7 @@ impl Accounted for SystemContract {
     fn get_balance(
         &self,
         _state: &mut impl crate::traits::StateExtractor,
+        _unit: FeeUnit,
     ) -> DevnetResult<Balance> {
         Ok(Felt::default())
     }
@@ -2,6 +2,7 @@ use starknet_types::contract_address::ContractAddress;
 use starknet_types::contract_class::ContractClass;
 use starknet_types::contract_storage_key::ContractStorageKey;
 use starknet_types::felt::{Balance, ClassHash, Felt};
+use starknet_types::rpc::estimate_message_fee::FeeUnit;
 
 use crate::error::DevnetResult;
 use crate::state::state_diff::StateDiff;
@@ -30,7 +31,7 @@ pub trait Deployed {
 /// This trait sets the interface for the account
 pub trait Accounted {
     fn set_initial_balance(&self, state: &mut impl StateChanger) -> DevnetResult<()>;
-    fn get_balance(&self, state: &mut impl StateExtractor) -> DevnetResult<Balance>;
+    fn get_balance(&self, state: &mut impl StateExtractor, unit: FeeUnit) -> DevnetResult<Balance>;
 }
 
 /// Interface for modifying the state
@@ -65,10 +66,10 @@ pub trait StateExtractor {
 }
 
 pub trait DevnetStateReader {
-    fn compiled_class_hash_at(&self, class_hash: &ClassHash) -> ClassHash;
-    fn storage_at(&self, storage_key: &ContractStorageKey) -> Felt;
-    fn nonce_at(&self, address: &ContractAddress) -> Felt;
-    fn class_hash_at(&self, address: &ContractAddress) -> ClassHash;
+    fn compiled_class_hash_at(&self, class_hash: &ClassHash) -> DevnetResult<ClassHash>;
+    fn storage_at(&self, storage_key: &ContractStorageKey) -> DevnetResult<Felt>;
+    fn nonce_at(&self, address: &ContractAddress) -> DevnetResult<Felt>;
+    fn class_hash_at(&self, address: &ContractAddress) -> DevnetResult<ClassHash>;
     fn contract_class_at(&self, class_hash: &ClassHash) -> DevnetResult<ContractClass>;
 }
 
@@ -1,33 +1,63 @@
+use std::sync::Arc;
+
 use blockifier::transaction::objects::TransactionExecutionInfo;
 use indexmap::IndexMap;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use starknet_api::block::BlockNumber;
-use starknet_rs_core::types::{ExecutionResult, TransactionFinalityStatus};
+use starknet_rs_core::types::{
+    ExecutionResult, TransactionExecutionStatus, TransactionFinalityStatus,
+};
 use starknet_rs_core::utils::get_selector_from_name;
 use starknet_types::contract_address::ContractAddress;
 use starknet_types::emitted_event::Event;
 use starknet_types::felt::{BlockHash, Felt, TransactionHash};
-use starknet_types::rpc::transaction_receipt::{DeployTransactionReceipt, TransactionReceipt};
-use starknet_types::rpc::transactions::{Transaction, TransactionType};
+use starknet_types::rpc::transaction_receipt::{
+    DeployTransactionReceipt, MessageToL1, TransactionReceipt,
+};
+use starknet_types::rpc::transactions::{Transaction, TransactionTrace, TransactionType};
 
 use crate::constants::UDC_CONTRACT_ADDRESS;
 use crate::error::{DevnetResult, Error};
 use crate::traits::{HashIdentified, HashIdentifiedMut};
 
-#[derive(Debug, Default, Serialize, Deserialize)]
-pub struct StarknetTransactions(IndexMap<TransactionHash, StarknetTransaction>);
+/// Transactions in the order in which they were added. They are shared, so that snapshots of the
+/// chain do not copy them; a transaction is copied only once it is modified.
+#[derive(Debug, Default, Clone)]
+pub struct StarknetTransactions(IndexMap<TransactionHash, Arc<StarknetTransaction>>);
 
 impl StarknetTransactions {
     pub fn insert(&mut self, transaction_hash: &TransactionHash, transaction: StarknetTransaction) {
-        self.0.insert(*transaction_hash, transaction);
+        self.0.insert(*transaction_hash, Arc::new(transaction));
     }
 
     pub fn get(&self, transaction_hash: &TransactionHash) -> Option<&StarknetTransaction> {
-        self.0.get(transaction_hash)
+        self.0.get(transaction_hash).map(Arc::as_ref)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&TransactionHash, &StarknetTransaction)> {
+        self.0
+            .iter()
+            .map(|(transaction_hash, transaction)| (transaction_hash, transaction.as_ref()))
     }
+}
+
+impl Serialize for StarknetTransactions {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.iter())
+    }
+}
 
-    pub fn iter(&self) -> indexmap::map::Iter<'_, Felt, StarknetTransaction> {
-        self.0.iter()
+impl<'de> Deserialize<'de> for StarknetTransactions {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let transactions =
+            IndexMap::<TransactionHash, StarknetTransaction>::deserialize(deserializer)?;
+
+        Ok(Self(
+            transactions
+                .into_iter()
+                .map(|(transaction_hash, transaction)| (transaction_hash, Arc::new(transaction)))
+                .collect(),
+        ))
     }
 }
 
@@ -35,7 +65,7 @@ impl HashIdentifiedMut for StarknetTransactions {
     type Hash = TransactionHash;
     type Element = StarknetTransaction;
     fn get_by_hash_mut(&mut self, hash: &Self::Hash) -> Option<&mut StarknetTransaction> {
-        self.0.get_mut(hash)
+        self.0.get_mut(hash).map(Arc::make_mut)
     }
 }
 
@@ -43,45 +73,64 @@ impl HashIdentified for StarknetTransactions {
     type Hash = TransactionHash;
     type Element = StarknetTransaction;
     fn get_by_hash(&self, hash: Self::Hash) -> Option<&StarknetTransaction> {
-        self.0.get(&hash)
+        self.get(&hash)
     }
 }
 
 #[allow(unused)]
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StarknetTransaction {
     pub inner: Transaction,
     pub(crate) finality_status: Option<TransactionFinalityStatus>,
-    pub(crate) execution_result: ExecutionResult,
+    /// Not present if the transaction was rejected before it was executed
+    pub(crate) execution_result: Option<ExecutionResult>,
+    /// Present if the transaction failed validation or its block was aborted; a rejected
+    /// transaction is not part of any block
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) rejection_reason: Option<String>,
     pub(crate) block_hash: Option<BlockHash>,
     pub(crate) block_number: Option<BlockNumber>,
+    /// Kept apart from the execution info, which is not serialized, so that the receipt can be
+    /// restored from a dump
+    pub(crate) events: Vec<Event>,
+    pub(crate) messages_sent: Vec<MessageToL1>,
+    /// Shared, so that modifying a transaction of a snapshot does not copy it
     #[serde(skip)]
-    pub(crate) execution_info: Option<TransactionExecutionInfo>,
+    pub(crate) execution_info: Option<Arc<TransactionExecutionInfo>>,
+    /// Created once the transaction is executed, so that it is not recreated on each request and
+    /// can be dumped, unlike the execution info. Not present if the transaction was not executed
+    /// or is of a type which is not traced.
+    #[serde(skip)]
+    pub(crate) trace: Option<Arc<TransactionTrace>>,
 }
 
 impl StarknetTransaction {
-    pub fn create_rejected(
-        transaction: &Transaction,
-        finality_status: Option<TransactionFinalityStatus>,
-        execution_error: &str,
-    ) -> Self {
+    /// Creates a transaction which was rejected, because it failed validation, so it was not
+    /// executed
+    pub fn create_rejected(transaction: &Transaction, rejection_reason: &str) -> Self {
         Self {
-            finality_status,
-            execution_result: ExecutionResult::Reverted { reason: execution_error.to_string() },
+            finality_status: None,
+            execution_result: None,
+            rejection_reason: Some(rejection_reason.to_string()),
             inner: transaction.clone(),
             block_hash: None,
             block_number: None,
+            events: vec![],
+            messages_sent: vec![],
             execution_info: None,
+            trace: None,
         }
     }
 
+    /// Creates a transaction which was executed and added to the pending block. It is final, i.e.
+    /// accepted on L2, once the block is sealed.
     pub fn create_accepted(
         transaction: &Transaction,
         execution_info: TransactionExecutionInfo,
     ) -> Self {
         Self {
-            finality_status: Some(TransactionFinalityStatus::AcceptedOnL2),
-            execution_result: match execution_info.is_reverted() {
+            finality_status: None,
+            execution_result: Some(match execution_info.is_reverted() {
                 true => ExecutionResult::Reverted {
                     reason: execution_info
                         .revert_error
@@ -89,15 +138,50 @@ impl StarknetTransaction {
                         .unwrap_or("No revert error".to_string()),
                 },
                 false => ExecutionResult::Succeeded,
-            },
+            }),
+            rejection_reason: None,
             inner: transaction.clone(),
             block_hash: None,
             block_number: None,
-            execution_info: Some(execution_info),
+            events: Self::events_of_execution(&execution_info),
+            messages_sent: Self::messages_of_execution(&execution_info),
+            execution_info: Some(Arc::new(execution_info)),
+            trace: None,
         }
     }
 
+    /// Marks the transaction as rejected, because the block containing it was aborted. The result
+    /// of its execution is kept.
+    pub(crate) fn abort(&mut self) {
+        self.finality_status = None;
+        self.rejection_reason = Some("Block aborted".to_string());
+        self.block_hash = None;
+        self.block_number = None;
+    }
+
+    /// Returns why the transaction was rejected, i.e. why it failed validation or that its block
+    /// was aborted; accepted transactions have no rejection reason
+    pub fn get_rejection_reason(&self) -> Option<&str> {
+        self.rejection_reason.as_deref()
+    }
+
+    /// Returns whether the execution succeeded or was reverted; a transaction rejected before it
+    /// was executed has no execution status
+    pub fn get_execution_status(&self) -> Option<TransactionExecutionStatus> {
+        self.execution_result.as_ref().map(ExecutionResult::status)
+    }
+
     pub fn get_events(&self) -> Vec<Event> {
+        self.events.clone()
+    }
+
+    /// Returns the messages sent to L1 by the execution of the transaction, in the order in which
+    /// they were sent
+    pub fn get_l2_to_l1_messages(&self) -> Vec<MessageToL1> {
+        self.messages_sent.clone()
+    }
+
+    fn events_of_execution(execution_info: &TransactionExecutionInfo) -> Vec<Event> {
         let mut events: Vec<Event> = vec![];
 
         fn get_blockifier_events_recursively(
@@ -123,30 +207,65 @@ impl StarknetTransaction {
             events
         }
 
-        if let Some(execution_info) = self.execution_info.as_ref() {
-            if let Some(validate_call_info) = execution_info.validate_call_info.as_ref() {
-                let mut not_sorted_events = get_blockifier_events_recursively(validate_call_info);
-                not_sorted_events.sort_by_key(|(order, _)| *order);
-                events.extend(not_sorted_events.into_iter().map(|(_, e)| e));
-            }
+        if let Some(validate_call_info) = execution_info.validate_call_info.as_ref() {
+            let mut not_sorted_events = get_blockifier_events_recursively(validate_call_info);
+            not_sorted_events.sort_by_key(|(order, _)| *order);
+            events.extend(not_sorted_events.into_iter().map(|(_, e)| e));
+        }
 
-            if let Some(execution_call_info) = execution_info.execute_call_info.as_ref() {
-                let mut not_sorted_events = get_blockifier_events_recursively(execution_call_info);
-                not_sorted_events.sort_by_key(|(order, _)| *order);
-                events.extend(not_sorted_events.into_iter().map(|(_, e)| e));
-            }
+        if let Some(execution_call_info) = execution_info.execute_call_info.as_ref() {
+            let mut not_sorted_events = get_blockifier_events_recursively(execution_call_info);
+            not_sorted_events.sort_by_key(|(order, _)| *order);
+            events.extend(not_sorted_events.into_iter().map(|(_, e)| e));
+        }
 
-            if let Some(fee_transfer_call_info) = execution_info.fee_transfer_call_info.as_ref() {
-                let mut not_sorted_events =
-                    get_blockifier_events_recursively(fee_transfer_call_info);
-                not_sorted_events.sort_by_key(|(order, _)| *order);
-                events.extend(not_sorted_events.into_iter().map(|(_, e)| e));
-            }
+        if let Some(fee_transfer_call_info) = execution_info.fee_transfer_call_info.as_ref() {
+            let mut not_sorted_events = get_blockifier_events_recursively(fee_transfer_call_info);
+            not_sorted_events.sort_by_key(|(order, _)| *order);
+            events.extend(not_sorted_events.into_iter().map(|(_, e)| e));
         }
 
         events
     }
 
+    fn messages_of_execution(execution_info: &TransactionExecutionInfo) -> Vec<MessageToL1> {
+        fn get_blockifier_messages_recursively(
+            call_info: &blockifier::execution::call_info::CallInfo,
+        ) -> Vec<(usize, MessageToL1)> {
+            let mut messages: Vec<(usize, MessageToL1)> = call_info
+                .execution
+                .l2_to_l1_messages
+                .iter()
+                .map(|m| {
+                    (
+                        m.order,
+                        MessageToL1 {
+                            from_address: call_info.call.storage_address.into(),
+                            to_address: m.message.to_address,
+                            payload: m.message.payload.0.iter().map(|p| (*p).into()).collect(),
+                        },
+                    )
+                })
+                .collect();
+
+            call_info.inner_calls.iter().for_each(|call| {
+                messages.extend(get_blockifier_messages_recursively(call));
+            });
+
+            messages
+        }
+
+        let mut messages = vec![];
+
+        if let Some(execution_call_info) = execution_info.execute_call_info.as_ref() {
+            let mut not_sorted_messages = get_blockifier_messages_recursively(execution_call_info);
+            not_sorted_messages.sort_by_key(|(order, _)| *order);
+            messages.extend(not_sorted_messages.into_iter().map(|(_, m)| m));
+        }
+
+        messages
+    }
+
     /// Scans through events and gets information from Event generated from UDC with specific
     /// ContractDeployed. Returns the contract address
     ///
@@ -174,14 +293,21 @@ impl StarknetTransaction {
         })
     }
 
+    /// Returns the receipt of an accepted transaction. Rejected transactions are not part of the
+    /// chain, so as in the JSON-RPC spec, there is no receipt of them.
     pub fn get_receipt(&self) -> DevnetResult<TransactionReceipt> {
+        let execution_result = match (&self.rejection_reason, &self.execution_result) {
+            (None, Some(execution_result)) => execution_result,
+            _ => return Err(Error::NoTransaction),
+        };
         let transaction_events = self.get_events();
 
         let mut common_receipt = self.inner.create_common_receipt(
             &transaction_events,
+            &self.get_l2_to_l1_messages(),
             self.block_hash.as_ref(),
             self.block_number,
-            &self.execution_result,
+            execution_result,
             self.finality_status,
         );
 
@@ -189,7 +315,13 @@ impl StarknetTransaction {
             Transaction::DeployAccount(deploy_account_transaction) => {
                 Ok(TransactionReceipt::Deploy(DeployTransactionReceipt {
                     common: common_receipt,
-                    contract_address: deploy_account_transaction.contract_address,
+                    contract_address: deploy_account_transaction.get_contract_address(),
+                }))
+            }
+            Transaction::Deploy(deploy_transaction) => {
+                Ok(TransactionReceipt::Deploy(DeployTransactionReceipt {
+                    common: common_receipt,
+                    contract_address: deploy_transaction.get_contract_address()?,
                 }))
             }
             Transaction::Invoke(_) => {
@@ -216,7 +348,7 @@ impl StarknetTransaction {
 #[cfg(test)]
 mod tests {
     use blockifier::transaction::objects::TransactionExecutionInfo;
-    use starknet_rs_core::types::{TransactionExecutionStatus, TransactionFinalityStatus};
+    use starknet_rs_core::types::TransactionExecutionStatus;
     use starknet_types::rpc::transactions::{DeclareTransaction, Transaction};
     use starknet_types::traits::HashProducer;
 
@@ -246,6 +378,25 @@ mod tests {
         assert_eq!(sn_tx.execution_info.is_some(), extracted_tran.execution_info.is_some());
     }
 
+    #[test]
+    fn copies_share_transactions_until_they_are_modified() {
+        let declare_transaction = dummy_declare_transaction_v1();
+        let hash = declare_transaction.generate_hash().unwrap();
+        let tx = Transaction::Declare(DeclareTransaction::Version1(declare_transaction));
+        let mut sn_txs = StarknetTransactions::default();
+        sn_txs.insert(
+            &hash,
+            StarknetTransaction::create_accepted(&tx, TransactionExecutionInfo::default()),
+        );
+
+        let mut copied_txs = sn_txs.clone();
+        assert!(std::ptr::eq(sn_txs.get(&hash).unwrap(), copied_txs.get(&hash).unwrap()));
+
+        copied_txs.get_by_hash_mut(&hash).unwrap().abort();
+        assert!(!std::ptr::eq(sn_txs.get(&hash).unwrap(), copied_txs.get(&hash).unwrap()));
+        assert_eq!(sn_txs.get(&hash).unwrap().get_rejection_reason(), None);
+    }
+
     #[test]
     fn check_correct_rejected_transaction_creation() {
         let tx = Transaction::Declare(DeclareTransaction::Version1(dummy_declare_transaction_v1()));
@@ -262,18 +413,21 @@ mod tests {
         let sn_tran = if is_success {
             let tx =
                 StarknetTransaction::create_accepted(&tran, TransactionExecutionInfo::default());
-            assert_eq!(tx.finality_status, Some(TransactionFinalityStatus::AcceptedOnL2));
-            assert_eq!(tx.execution_result.status(), TransactionExecutionStatus::Succeeded);
+            assert_eq!(tx.finality_status, None);
+            assert_eq!(tx.get_execution_status(), Some(TransactionExecutionStatus::Succeeded));
+            assert_eq!(tx.get_rejection_reason(), None);
 
             tx
         } else {
             let error_str =
                 starknet_in_rust::transaction::error::TransactionError::AttempToUseNoneCodeAddress
                     .to_string();
-            let tx = StarknetTransaction::create_rejected(&tran, None, &error_str);
+            let tx = StarknetTransaction::create_rejected(&tran, &error_str);
 
             assert_eq!(tx.finality_status, None);
-            assert_eq!(tx.execution_result.revert_reason(), Some(error_str.as_str()));
+            assert_eq!(tx.get_execution_status(), None);
+            assert_eq!(tx.get_rejection_reason(), Some(error_str.as_str()));
+            assert!(matches!(tx.get_receipt(), Err(crate::error::Error::NoTransaction)));
 
             tx
         };
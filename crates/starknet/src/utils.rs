@@ -1,12 +1,20 @@
+use std::time::Duration;
+
 use starknet_api::hash::{pedersen_hash, StarkFelt};
 use starknet_in_rust::utils::calculate_sn_keccak;
 use starknet_types::cairo_felt::Felt252;
 use starknet_types::felt::Felt;
 use starknet_types::num_integer::Integer;
 use starknet_types::patricia_key::{PatriciaKey, StorageKey};
+use tokio::runtime::{Handle, RuntimeFlavor};
 
 use crate::error::DevnetResult;
 
+/// Limits of waiting for a remote node (the fork origin or the L1 node), so that an unresponsive
+/// node fails the request instead of stalling Devnet
+pub(crate) const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+pub(crate) const READ_TIMEOUT: Duration = Duration::from_secs(30);
+
 pub(crate) fn generate_u128_random_numbers(
     seed: u32,
     random_numbers_count: u8,
@@ -33,12 +41,39 @@ pub(crate) fn get_storage_var_address(
     Ok(PatriciaKey::new(Felt::new(storage_key.to_be_bytes())?)?)
 }
 
+/// Creates the agent for JSON-RPC requests to a remote node, with the connection limited by
+/// [`CONNECT_TIMEOUT`] and each read by `read_timeout`
+pub(crate) fn create_remote_node_agent(read_timeout: Duration) -> ureq::Agent {
+    ureq::AgentBuilder::new().timeout_connect(CONNECT_TIMEOUT).timeout_read(read_timeout).build()
+}
+
+/// Runs `f`, which blocks on I/O. Remote nodes are requested while Starknet is being read or
+/// modified, which happens on the workers of the async runtime; the other tasks of the worker are
+/// meanwhile handed over to other workers, so that they are not stalled by a slow node.
+pub(crate) fn run_blocking<T>(f: impl FnOnce() -> T) -> T {
+    match Handle::try_current() {
+        Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::MultiThread => {
+            tokio::task::block_in_place(f)
+        }
+        _ => f(),
+    }
+}
+
 #[cfg(test)]
 pub(crate) mod test_utils {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use serde_json::json;
     use starknet_api::transaction::Fee;
     use starknet_in_rust::core::contract_address::compute_casm_class_hash;
     use starknet_in_rust::{CasmContractClass, SierraContractClass};
     use starknet_types::contract_address::ContractAddress;
+    use starknet_types::contract_class::compilation_cache::{
+        compile_sierra_to_casm, CompilationCache,
+    };
     use starknet_types::contract_class::{Cairo0ContractClass, Cairo0Json, ContractClass};
     use starknet_types::contract_storage_key::ContractStorageKey;
     use starknet_types::felt::Felt;
@@ -47,15 +82,22 @@ pub(crate) mod test_utils {
     use starknet_types::rpc::transactions::broadcasted_declare_transaction_v2::BroadcastedDeclareTransactionV2;
     use starknet_types::rpc::transactions::declare_transaction_v0v1::DeclareTransactionV0V1;
     use starknet_types::traits::HashProducer;
+    use url::Url;
 
     use crate::constants::{
         DEVNET_DEFAULT_CHAIN_ID, DEVNET_DEFAULT_GAS_PRICE, DEVNET_DEFAULT_HOST,
-        DEVNET_DEFAULT_INITIAL_BALANCE, DEVNET_DEFAULT_PORT, DEVNET_DEFAULT_TEST_SEED,
-        DEVNET_DEFAULT_TIMEOUT, DEVNET_DEFAULT_TOTAL_ACCOUNTS,
+        DEVNET_DEFAULT_INITIAL_BALANCE, DEVNET_DEFAULT_PORT, DEVNET_DEFAULT_STRK_GAS_PRICE,
+        DEVNET_DEFAULT_TEST_SEED, DEVNET_DEFAULT_TIMEOUT, DEVNET_DEFAULT_TOTAL_ACCOUNTS,
     };
-    use crate::starknet::StarknetConfig;
+    use crate::starknet::{ForkConfig, StarknetConfig};
     use crate::utils::exported_test_utils::dummy_cairo_0_contract_class;
 
+    pub(crate) const MOCK_ORIGIN_BLOCK_NUMBER: u64 = 7;
+    pub(crate) const MOCK_ORIGIN_CONTRACT_ADDRESS: u128 = 0x1;
+    pub(crate) const MOCK_ORIGIN_CLASS_HASH: u128 = 0x10;
+    pub(crate) const MOCK_ORIGIN_NONCE: u128 = 0x5;
+    pub(crate) const MOCK_ORIGIN_STORAGE_VALUE: u128 = 0x3;
+
     pub fn starknet_config_for_test() -> StarknetConfig {
         StarknetConfig {
             seed: DEVNET_DEFAULT_TEST_SEED,
@@ -65,9 +107,14 @@ pub(crate) mod test_utils {
             port: DEVNET_DEFAULT_PORT,
             timeout: DEVNET_DEFAULT_TIMEOUT,
             gas_price: DEVNET_DEFAULT_GAS_PRICE,
+            strk_gas_price: DEVNET_DEFAULT_STRK_GAS_PRICE,
             chain_id: DEVNET_DEFAULT_CHAIN_ID,
             dump_on: None,
             dump_path: None,
+            blocks_on_demand: false,
+            block_time: None,
+            fork_config: ForkConfig::default(),
+            compilation_cache_dir: None,
         }
     }
 
@@ -92,6 +139,15 @@ pub(crate) mod test_utils {
         ContractClass::cairo_1_from_sierra_json_str(&json_str).unwrap()
     }
 
+    /// Compiles `sierra` to CASM through a cache shared by all test runs, so that each test class
+    /// is compiled only once
+    pub(crate) fn compile_sierra_for_test(sierra: SierraContractClass) -> CasmContractClass {
+        let cache =
+            CompilationCache::new(std::env::temp_dir().join("devnet-test-compilation-cache"))
+                .unwrap();
+        compile_sierra_to_casm(sierra, Some(&cache)).unwrap()
+    }
+
     pub(crate) fn dummy_contract_address() -> ContractAddress {
         ContractAddress::new(Felt::from_prefixed_hex_str("0xADD4E55").unwrap()).unwrap()
     }
@@ -119,10 +175,8 @@ pub(crate) mod test_utils {
     ) -> BroadcastedDeclareTransactionV2 {
         let contract_class = dummy_cairo_1_contract_class();
 
-        let compiled_class_hash = compute_casm_class_hash(
-            &CasmContractClass::from_contract_class(contract_class.clone(), true).unwrap(),
-        )
-        .unwrap();
+        let compiled_class_hash =
+            compute_casm_class_hash(&compile_sierra_for_test(contract_class.clone())).unwrap();
 
         BroadcastedDeclareTransactionV2::new(
             &contract_class,
@@ -144,6 +198,93 @@ pub(crate) mod test_utils {
         Cairo0Json::raw_json_from_path(account_json_path).unwrap().into()
     }
 
+    /// Spawns a minimal JSON-RPC server listening on a random local port. Each request is answered
+    /// with the `result` or `error` object returned by `handler` for the requested method and
+    /// params. Returns the URL of the server and the counter of received requests.
+    pub(crate) fn spawn_mock_json_rpc_server(
+        handler: fn(&str, &serde_json::Value) -> serde_json::Value,
+    ) -> (Url, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = Url::parse(&format!("http://{}", listener.local_addr().unwrap())).unwrap();
+        let request_count = Arc::new(AtomicUsize::new(0));
+
+        let counter = request_count.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                let mut content_length = 0;
+                loop {
+                    let mut header_line = String::new();
+                    reader.read_line(&mut header_line).unwrap();
+                    if header_line.trim().is_empty() {
+                        break;
+                    }
+                    if let Some((name, value)) = header_line.split_once(':') {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            content_length = value.trim().parse().unwrap();
+                        }
+                    }
+                }
+
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+                let request: serde_json::Value = serde_json::from_slice(&body).unwrap();
+                counter.fetch_add(1, Ordering::SeqCst);
+
+                let mut response = handler(request["method"].as_str().unwrap(), &request["params"]);
+                response["jsonrpc"] = "2.0".into();
+                response["id"] = request["id"].clone();
+                let response = response.to_string();
+
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: \
+                     {}\r\nConnection: close\r\n\r\n{}",
+                    response.len(),
+                    response
+                )
+                .unwrap();
+            }
+        });
+
+        (url, request_count)
+    }
+
+    /// Handler of [`spawn_mock_json_rpc_server`] imitating a forking origin at block
+    /// `MOCK_ORIGIN_BLOCK_NUMBER`, with a single contract deployed at
+    /// `MOCK_ORIGIN_CONTRACT_ADDRESS`
+    pub(crate) fn mock_origin_handler(
+        method: &str,
+        params: &serde_json::Value,
+    ) -> serde_json::Value {
+        let contract_not_found =
+            json!({ "error": { "code": 20, "message": "Contract not found" } });
+        let is_origin_contract = serde_json::from_value::<Felt>(params["contract_address"].clone())
+            .map_or(false, |address| address == Felt::from(MOCK_ORIGIN_CONTRACT_ADDRESS));
+
+        match method {
+            "starknet_blockNumber" => json!({ "result": MOCK_ORIGIN_BLOCK_NUMBER }),
+            "starknet_getClassHashAt" if is_origin_contract => {
+                json!({ "result": Felt::from(MOCK_ORIGIN_CLASS_HASH) })
+            }
+            "starknet_getNonce" if is_origin_contract => {
+                json!({ "result": Felt::from(MOCK_ORIGIN_NONCE) })
+            }
+            "starknet_getStorageAt" if is_origin_contract => {
+                json!({ "result": Felt::from(MOCK_ORIGIN_STORAGE_VALUE) })
+            }
+            "starknet_getClassHashAt" | "starknet_getNonce" | "starknet_getStorageAt" => {
+                contract_not_found
+            }
+            "starknet_getClass" => {
+                json!({ "error": { "code": 28, "message": "Class hash not found" } })
+            }
+            _ => json!({ "error": { "code": -32601, "message": "Method not found" } }),
+        }
+    }
+
     pub(crate) fn get_bytes_from_u32(num: u32) -> [u8; 32] {
         let num_bytes = num.to_be_bytes();
         let mut result = [0u8; 32];
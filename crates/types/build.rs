@@ -0,0 +1,19 @@
+use std::path::Path;
+use std::{env, fs};
+
+/// Exposes the version of cairo-lang-starknet, which compiles Sierra to CASM, as the
+/// `SIERRA_COMPILER_VERSION` environment variable, so that it follows the locked dependency
+fn main() {
+    let lock_path = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("../../Cargo.lock");
+    println!("cargo:rerun-if-changed={}", lock_path.display());
+
+    let lock = fs::read_to_string(&lock_path).expect("Cargo.lock should be readable");
+    let version = lock
+        .split("[[package]]")
+        .find(|package| package.lines().any(|line| line == r#"name = "cairo-lang-starknet""#))
+        .and_then(|package| package.lines().find_map(|line| line.strip_prefix("version = ")))
+        .map(|version| version.trim_matches('"'))
+        .expect("cairo-lang-starknet should be locked");
+
+    println!("cargo:rustc-env=SIERRA_COMPILER_VERSION={version}");
+}
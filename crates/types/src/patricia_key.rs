@@ -1,7 +1,11 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use starknet_api::core::{CONTRACT_ADDRESS_DOMAIN_SIZE, PATRICIA_KEY_UPPER_BOUND};
 
 use crate::error::{DevnetResult, Error};
 use crate::felt::Felt;
+use crate::serde_helpers::hex_string::{
+    deserialize_to_prefixed_patricia_key, serialize_patricia_key_to_prefixed_hex,
+};
 
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct PatriciaKey(pub(crate) Felt);
@@ -22,6 +26,24 @@ impl PatriciaKey {
     }
 }
 
+impl Serialize for PatriciaKey {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_patricia_key_to_prefixed_hex(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for PatriciaKey {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_to_prefixed_patricia_key(deserializer)
+    }
+}
+
 impl From<starknet_api::core::PatriciaKey> for PatriciaKey {
     fn from(value: starknet_api::core::PatriciaKey) -> Self {
         Self(value.into())
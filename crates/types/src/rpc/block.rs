@@ -5,6 +5,9 @@ use starknet_rs_core::types::{BlockId as ImportedBlockId, BlockTag as ImportedBl
 use crate::contract_address::ContractAddress;
 use crate::felt::{BlockHash, Felt};
 use crate::rpc::transactions::Transactions;
+use crate::serde_helpers::hex_string::{
+    deserialize_prefixed_hex_string_to_u128, serialize_number_to_prefixed_hex,
+};
 pub type GlobalRootHex = Felt;
 
 #[derive(Copy, Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -92,6 +95,22 @@ pub struct BlockHeader {
     pub sequencer_address: ContractAddress,
     pub new_root: GlobalRootHex,
     pub timestamp: BlockTimestamp,
+    pub l1_gas_price: ResourcePrice,
+}
+
+/// Price of a unit of a resource, in both fee tokens
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct ResourcePrice {
+    #[serde(
+        serialize_with = "serialize_number_to_prefixed_hex",
+        deserialize_with = "deserialize_prefixed_hex_string_to_u128"
+    )]
+    pub price_in_wei: u128,
+    #[serde(
+        serialize_with = "serialize_number_to_prefixed_hex",
+        deserialize_with = "deserialize_prefixed_hex_string_to_u128"
+    )]
+    pub price_in_fri: u128,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
@@ -12,10 +12,12 @@ use starknet_rs_core::types::{
 };
 use starknet_rs_ff::FieldElement;
 
+use self::compilation_cache::{compile_sierra_to_casm, CompilationCache};
 use crate::error::{DevnetResult, Error, JsonError};
 use crate::felt::Felt;
 use crate::traits::HashProducer;
 
+pub mod compilation_cache;
 pub mod deprecated;
 pub use deprecated::json_contract_class::Cairo0Json;
 pub use deprecated::rpc_contract_class::DeprecatedContractClass;
@@ -46,6 +48,31 @@ impl ContractClass {
 
         Ok(sierra_contract_class)
     }
+
+    /// Converts the class to the one executed by blockifier. Cairo 1 classes are compiled to CASM
+    /// through `compilation_cache`, if there is one.
+    pub fn to_blockifier_contract_class(
+        self,
+        compilation_cache: Option<&CompilationCache>,
+    ) -> DevnetResult<blockifier::execution::contract_class::ContractClass> {
+        match self {
+            ContractClass::Cairo0(deprecated_contract_class) => {
+                Ok(blockifier::execution::contract_class::ContractClass::V0(
+                    deprecated_contract_class.try_into()?,
+                ))
+            }
+            ContractClass::Cairo1(sierra_contract_class) => {
+                let casm_contract_class =
+                    compile_sierra_to_casm(sierra_contract_class, compilation_cache)?;
+                let blockifier_contract_class: blockifier::execution::contract_class::ContractClassV1 =
+                    casm_contract_class.try_into().map_err(|_| Error::ProgramError)?;
+
+                Ok(blockifier::execution::contract_class::ContractClass::V1(
+                    blockifier_contract_class,
+                ))
+            }
+        }
+    }
 }
 
 impl From<Cairo0ContractClass> for ContractClass {
@@ -114,8 +141,7 @@ impl TryFrom<ContractClass> for starknet_in_rust::CasmContractClass {
     fn try_from(value: ContractClass) -> Result<Self, Self::Error> {
         match value {
             ContractClass::Cairo1(sierra_contract_class) => {
-                CasmContractClass::from_contract_class(sierra_contract_class, true)
-                    .map_err(|err| Error::SierraCompilationError { reason: err.to_string() })
+                compile_sierra_to_casm(sierra_contract_class, None)
             }
             _ => Err(Error::ConversionError(crate::error::ConversionError::InvalidFormat)),
         }
@@ -139,22 +165,7 @@ impl TryFrom<ContractClass> for blockifier::execution::contract_class::ContractC
     type Error = Error;
 
     fn try_from(value: ContractClass) -> Result<Self, Self::Error> {
-        match value {
-            ContractClass::Cairo0(deprecated_contract_class) => {
-                Ok(blockifier::execution::contract_class::ContractClass::V0(
-                    deprecated_contract_class.try_into()?,
-                ))
-            }
-            ContractClass::Cairo1(_) => {
-                let casm_contract_class = CasmContractClass::try_from(value.clone())?;
-                let blockifier_contract_class: blockifier::execution::contract_class::ContractClassV1 =
-                    casm_contract_class.try_into().map_err(|_| Error::ProgramError)?;
-
-                Ok(blockifier::execution::contract_class::ContractClass::V1(
-                    blockifier_contract_class,
-                ))
-            }
-        }
+        value.to_blockifier_contract_class(None)
     }
 }
 
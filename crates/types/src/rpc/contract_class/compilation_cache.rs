@@ -0,0 +1,137 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use starknet_in_rust::core::contract_address::compute_sierra_class_hash;
+use starknet_in_rust::{CasmContractClass, SierraContractClass};
+use tracing::warn;
+
+use crate::error::{DevnetResult, Error, JsonError};
+use crate::felt::{ClassHash, Felt};
+use crate::traits::ToHexString;
+
+/// Version of cairo-lang-starknet used for compiling Sierra to CASM, read from Cargo.lock by the
+/// build script. Part of the cache key, so that classes compiled by a different compiler are not
+/// reused.
+pub const SIERRA_COMPILER_VERSION: &str = env!("SIERRA_COMPILER_VERSION");
+
+/// Directory of CASM classes, each stored in a file named after the hash of the Sierra class it
+/// was compiled from and the compiler version.
+#[derive(Debug, Clone)]
+pub struct CompilationCache {
+    dir: PathBuf,
+}
+
+impl CompilationCache {
+    pub fn new(dir: impl AsRef<Path>) -> DevnetResult<Self> {
+        fs::create_dir_all(dir.as_ref())?;
+        Ok(Self { dir: dir.as_ref().to_path_buf() })
+    }
+
+    fn entry_path(&self, class_hash: ClassHash) -> PathBuf {
+        self.dir.join(format!(
+            "{}-{SIERRA_COMPILER_VERSION}.casm.json",
+            class_hash.to_prefixed_hex_str()
+        ))
+    }
+
+    /// Returns the cached CASM of `class_hash`, if present. Unreadable entries are treated as
+    /// missing, so that they get overwritten by a fresh compilation.
+    pub fn get(&self, class_hash: ClassHash) -> Option<CasmContractClass> {
+        let serialized = fs::read(self.entry_path(class_hash)).ok()?;
+        serde_json::from_slice(&serialized).ok()
+    }
+
+    /// Stores the CASM of `class_hash`. The entry is written to a temporary file first and then
+    /// renamed, so that concurrent Devnet instances sharing the directory never read a partial
+    /// entry.
+    pub fn insert(&self, class_hash: ClassHash, casm: &CasmContractClass) -> DevnetResult<()> {
+        let serialized = serde_json::to_vec(casm).map_err(JsonError::SerdeJsonError)?;
+
+        let entry_path = self.entry_path(class_hash);
+        let tmp_path = entry_path.with_extension(format!("tmp{}", std::process::id()));
+        fs::write(&tmp_path, serialized)?;
+        fs::rename(&tmp_path, entry_path)?;
+
+        Ok(())
+    }
+
+    /// Returns the CASM of `sierra`, compiling it only on a cache miss.
+    pub fn get_or_compile(&self, sierra: SierraContractClass) -> DevnetResult<CasmContractClass> {
+        let class_hash = Felt::from(compute_sierra_class_hash(&sierra)?);
+        if let Some(casm) = self.get(class_hash) {
+            return Ok(casm);
+        }
+
+        let casm = compile(sierra)?;
+        // failing to cache only costs a recompilation next time
+        if let Err(err) = self.insert(class_hash, &casm) {
+            warn!(
+                "Failed caching the compiled class {} in {}: {err}",
+                class_hash.to_prefixed_hex_str(),
+                self.dir.display()
+            );
+        }
+
+        Ok(casm)
+    }
+}
+
+/// Compiles `sierra` to CASM, going through `compilation_cache` if there is one.
+pub fn compile_sierra_to_casm(
+    sierra: SierraContractClass,
+    compilation_cache: Option<&CompilationCache>,
+) -> DevnetResult<CasmContractClass> {
+    match compilation_cache {
+        Some(cache) => cache.get_or_compile(sierra),
+        None => compile(sierra),
+    }
+}
+
+fn compile(sierra: SierraContractClass) -> DevnetResult<CasmContractClass> {
+    CasmContractClass::from_contract_class(sierra, true)
+        .map_err(|err| Error::SierraCompilationError { reason: err.to_string() })
+}
+
+#[cfg(test)]
+mod tests {
+    use starknet_in_rust::core::contract_address::compute_sierra_class_hash;
+
+    use super::{CompilationCache, SIERRA_COMPILER_VERSION};
+    use crate::contract_class::ContractClass;
+    use crate::felt::Felt;
+
+    fn dummy_sierra() -> starknet_in_rust::SierraContractClass {
+        let sierra_contract_path =
+            concat!(env!("CARGO_MANIFEST_DIR"), "/test_data/events_cairo1.sierra");
+        ContractClass::cairo_1_from_sierra_json_str(
+            &std::fs::read_to_string(sierra_contract_path).unwrap(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn compiled_class_is_cached_under_sierra_class_hash() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = CompilationCache::new(dir.path()).unwrap();
+        let sierra = dummy_sierra();
+        let class_hash = Felt::from(compute_sierra_class_hash(&sierra).unwrap());
+        assert!(cache.get(class_hash).is_none());
+
+        let casm = cache.get_or_compile(sierra.clone()).unwrap();
+        assert_eq!(casm.compiler_version, SIERRA_COMPILER_VERSION);
+        assert_eq!(cache.get(class_hash).unwrap(), casm);
+        assert_eq!(cache.get_or_compile(sierra).unwrap(), casm);
+    }
+
+    #[test]
+    fn corrupted_entry_is_recompiled() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = CompilationCache::new(dir.path()).unwrap();
+        let sierra = dummy_sierra();
+        let class_hash = Felt::from(compute_sierra_class_hash(&sierra).unwrap());
+        std::fs::write(cache.entry_path(class_hash), "not CASM").unwrap();
+
+        let casm = cache.get_or_compile(sierra).unwrap();
+        assert_eq!(cache.get(class_hash).unwrap(), casm);
+    }
+}
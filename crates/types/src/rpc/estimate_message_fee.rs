@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use blockifier::transaction::transactions::L1HandlerTransaction;
+use serde::{Deserialize, Serialize};
 use starknet_api::core::EntryPointSelector;
 use starknet_api::transaction::Calldata;
 use starknet_rs_core::types::requests::EstimateMessageFeeRequest;
@@ -14,17 +15,38 @@ use crate::rpc::block::BlockId;
 use crate::rpc::eth_address::EthAddressWrapper;
 use crate::{impl_wrapper_deserialize, impl_wrapper_serialize};
 
-#[derive(Debug, Clone)]
+/// The unit in which a fee or a balance is denominated: WEI of ETH or FRI of STRK
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum FeeUnit {
+    #[default]
+    Wei,
+    Fri,
+}
+
+impl std::fmt::Display for FeeUnit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FeeUnit::Wei => write!(f, "WEI"),
+            FeeUnit::Fri => write!(f, "FRI"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct FeeEstimateWrapper {
+    #[serde(flatten)]
     inner: FeeEstimate,
+    unit: FeeUnit,
 }
 
-impl_wrapper_serialize!(FeeEstimateWrapper);
-impl_wrapper_deserialize!(FeeEstimateWrapper, FeeEstimate);
-
 impl FeeEstimateWrapper {
-    pub fn new(gas_consumed: u64, gas_price: u64, overall_fee: u64) -> Self {
-        FeeEstimateWrapper { inner: FeeEstimate { gas_consumed, gas_price, overall_fee } }
+    pub fn new(gas_consumed: u64, gas_price: u64, overall_fee: u64, unit: FeeUnit) -> Self {
+        FeeEstimateWrapper { inner: FeeEstimate { gas_consumed, gas_price, overall_fee }, unit }
+    }
+
+    pub fn unit(&self) -> FeeUnit {
+        self.unit
     }
 }
 
@@ -0,0 +1,141 @@
+use primitive_types::H256;
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Keccak256};
+use starknet_api::transaction::Fee;
+
+use crate::contract_address::ContractAddress;
+use crate::error::DevnetResult;
+use crate::felt::{Calldata, EntryPointSelector, Felt, Nonce};
+use crate::rpc::eth_address::EthAddressWrapper;
+use crate::rpc::transaction_receipt::MessageToL1;
+use crate::rpc::transactions::L1HandlerTransaction;
+
+/// An L1 to L2 message, as sent to the Starknet messaging contract on L1
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MessageToL2 {
+    pub l1_contract_address: EthAddressWrapper,
+    pub l2_contract_address: ContractAddress,
+    pub entry_point_selector: EntryPointSelector,
+    pub payload: Calldata,
+    pub paid_fee_on_l1: Fee,
+    pub nonce: Nonce,
+}
+
+impl MessageToL2 {
+    /// Computes the hash of the message the same way the Starknet messaging contract does
+    pub fn hash(&self) -> H256 {
+        let header = [
+            Felt::from(self.l1_contract_address.clone()),
+            Felt::from(self.l2_contract_address),
+            self.nonce,
+            self.entry_point_selector,
+        ];
+
+        keccak_of_words(header.iter().map(Felt::bytes), &self.payload)
+    }
+
+    /// Creates the transaction which executes the L1 handler the message is sent to. The L1 sender
+    /// address is prepended to the payload, as expected by L1 handlers.
+    pub fn create_l1_handler_transaction(
+        &self,
+        chain_id: Felt,
+    ) -> DevnetResult<L1HandlerTransaction> {
+        let calldata =
+            [&[Felt::from(self.l1_contract_address.clone())], self.payload.as_slice()].concat();
+
+        L1HandlerTransaction::new(
+            self.l2_contract_address,
+            self.entry_point_selector,
+            calldata,
+            self.nonce,
+            self.paid_fee_on_l1,
+            chain_id,
+        )
+    }
+}
+
+impl MessageToL1 {
+    /// Computes the hash of the message the same way the Starknet messaging contract does. The
+    /// message is consumed on L1 by this hash.
+    pub fn hash(&self) -> H256 {
+        let mut to_address = [0u8; 32];
+        to_address[12..].copy_from_slice(self.to_address.0.as_bytes());
+
+        keccak_of_words([Felt::from(self.from_address).bytes(), to_address], &self.payload)
+    }
+}
+
+/// Keccak of the 32-byte words of `header`, followed by the length of `payload` and its elements,
+/// i.e. the equivalent of Solidity's `keccak256(abi.encodePacked(...))` used for message hashes
+fn keccak_of_words(header: impl IntoIterator<Item = [u8; 32]>, payload: &[Felt]) -> H256 {
+    let mut hasher = Keccak256::new();
+    header.into_iter().for_each(|word| hasher.update(word));
+    hasher.update(H256::from_low_u64_be(payload.len() as u64));
+    payload.iter().for_each(|felt| hasher.update(felt.bytes()));
+
+    H256::from_slice(&hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use primitive_types::H160;
+    use starknet_api::core::EthAddress;
+    use starknet_api::transaction::Fee;
+
+    use super::MessageToL2;
+    use crate::contract_address::ContractAddress;
+    use crate::felt::Felt;
+    use crate::rpc::eth_address::EthAddressWrapper;
+    use crate::rpc::transaction_receipt::MessageToL1;
+
+    fn dummy_message_to_l2() -> MessageToL2 {
+        MessageToL2 {
+            l1_contract_address: EthAddressWrapper::from_str(
+                "0x0000000000000000000000000000000000000002",
+            )
+            .unwrap(),
+            l2_contract_address: ContractAddress::new(Felt::from(1)).unwrap(),
+            entry_point_selector: Felt::from(6),
+            payload: vec![Felt::from(3), Felt::from(4)],
+            paid_fee_on_l1: Fee(1),
+            nonce: Felt::from(5),
+        }
+    }
+
+    #[test]
+    fn message_to_l1_hash() {
+        let message = MessageToL1 {
+            from_address: ContractAddress::new(Felt::from(1)).unwrap(),
+            to_address: EthAddress(H160::from_low_u64_be(2)),
+            payload: vec![Felt::from(3), Felt::from(4)],
+        };
+
+        assert_eq!(
+            format!("{:#x}", message.hash()),
+            "0x2cac3db3b1d4d30a6799a472c477b4a01a3a4bc43fd92f1e6506ce82d7d810dd"
+        );
+    }
+
+    #[test]
+    fn message_to_l2_hash() {
+        assert_eq!(
+            format!("{:#x}", dummy_message_to_l2().hash()),
+            "0xa2eb1e7b72fdecaf3ef42a6a827b178a8e392a2e4f17a8964d7c0805d12dfaec"
+        );
+    }
+
+    #[test]
+    fn l1_handler_transaction_of_message_to_l2() {
+        let message = dummy_message_to_l2();
+        let transaction = message.create_l1_handler_transaction(Felt::from(1)).unwrap();
+
+        assert_eq!(transaction.contract_address, message.l2_contract_address);
+        assert_eq!(transaction.entry_point_selector, message.entry_point_selector);
+        assert_eq!(transaction.nonce, message.nonce);
+        assert_eq!(transaction.paid_fee_on_l1, message.paid_fee_on_l1);
+        assert_eq!(transaction.calldata, vec![Felt::from(2), Felt::from(3), Felt::from(4)]);
+        assert!(!transaction.transaction_hash.is_zero());
+    }
+}
@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+
+use crate::contract_address::ContractAddress;
+use crate::felt::{BlockHash, ClassHash, Felt, Nonce};
+
+/// Inner node of a Merkle-Patricia trie of the Starknet state
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum MerkleNode {
+    Binary { left: Felt, right: Felt },
+    Edge { path: Felt, length: u8, child: Felt },
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct NodeHashToNode {
+    pub node_hash: Felt,
+    pub node: MerkleNode,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ContractStorageKeys {
+    pub contract_address: ContractAddress,
+    pub storage_keys: Vec<Felt>,
+}
+
+/// The values from which the leaf of a contract in the contracts trie is hashed, apart from the
+/// root of its storage trie
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ContractLeafData {
+    pub nonce: Nonce,
+    pub class_hash: ClassHash,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ContractsProof {
+    pub nodes: Vec<NodeHashToNode>,
+    pub contract_leaves_data: Vec<ContractLeafData>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct GlobalRoots {
+    pub contracts_tree_root: Felt,
+    pub classes_tree_root: Felt,
+    pub block_hash: BlockHash,
+}
+
+/// Merkle proofs of the requested classes, contracts and storage entries at a block. Each proof
+/// consists of the nodes on the paths from the root of the trie to the requested leaves.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct StorageProof {
+    pub classes_proof: Vec<NodeHashToNode>,
+    pub contracts_proof: ContractsProof,
+    /// In the order in which the contracts of the storage keys were requested
+    pub contracts_storage_proofs: Vec<Vec<NodeHashToNode>>,
+    pub global_roots: GlobalRoots,
+}
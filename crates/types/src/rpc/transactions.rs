@@ -1,44 +1,72 @@
 use std::collections::HashMap;
+use std::sync::Arc;
 
 use blockifier::transaction::account_transaction::AccountTransaction;
 use broadcasted_declare_transaction_v1::BroadcastedDeclareTransactionV1;
 use broadcasted_declare_transaction_v2::BroadcastedDeclareTransactionV2;
-use broadcasted_deploy_account_transaction::BroadcastedDeployAccountTransaction;
-use broadcasted_invoke_transaction::BroadcastedInvokeTransaction;
+use broadcasted_declare_transaction_v3::BroadcastedDeclareTransactionV3;
+use broadcasted_deploy_account_transaction_v1::BroadcastedDeployAccountTransactionV1;
+use broadcasted_deploy_account_transaction_v3::BroadcastedDeployAccountTransactionV3;
+use broadcasted_invoke_transaction_v1::BroadcastedInvokeTransactionV1;
+use broadcasted_invoke_transaction_v3::BroadcastedInvokeTransactionV3;
+use cairo_felt::Felt252;
 use declare_transaction_v0v1::DeclareTransactionV0V1;
 use declare_transaction_v2::DeclareTransactionV2;
-use deploy_account_transaction::DeployAccountTransaction;
+use declare_transaction_v3::DeclareTransactionV3;
+use deploy_account_transaction_v1::DeployAccountTransactionV1;
+use deploy_account_transaction_v3::DeployAccountTransactionV3;
 use deploy_transaction::DeployTransaction;
 use invoke_transaction_v1::InvokeTransactionV1;
-use serde::{Deserialize, Serialize};
+use invoke_transaction_v3::InvokeTransactionV3;
+use serde::{Deserialize, Deserializer, Serialize};
 use starknet_api::block::BlockNumber;
 use starknet_api::deprecated_contract_class::EntryPointType;
+use starknet_api::hash::StarkFelt;
 use starknet_api::transaction::Fee;
-use starknet_rs_core::types::{BlockId, ExecutionResult, TransactionFinalityStatus};
+use starknet_in_rust::core::transaction_hash::{
+    calculate_transaction_hash_common, TransactionHashPrefix,
+};
+use starknet_rs_core::types::{
+    BlockId, ExecutionResult, TransactionExecutionStatus, TransactionFinalityStatus,
+};
+use starknet_rs_crypto::poseidon_hash_many;
+use starknet_rs_ff::FieldElement;
 
-use super::estimate_message_fee::FeeEstimateWrapper;
+use super::estimate_message_fee::{FeeEstimateWrapper, FeeUnit};
 use super::transaction_receipt::MessageToL1;
 use crate::contract_address::ContractAddress;
+use crate::contract_class::compilation_cache::CompilationCache;
 use crate::emitted_event::Event;
 use crate::error::{ConversionError, DevnetResult};
 use crate::felt::{
-    BlockHash, Calldata, EntryPointSelector, Felt, Nonce, TransactionHash, TransactionSignature,
-    TransactionVersion,
+    BlockHash, Calldata, ClassHash, EntryPointSelector, Felt, Nonce, TransactionHash,
+    TransactionSignature, TransactionVersion,
 };
 use crate::rpc::transaction_receipt::{
     CommonTransactionReceipt, MaybePendingProperties, TransactionOutput,
 };
+use crate::serde_helpers::hex_string::{
+    deserialize_prefixed_hex_string_to_u128, deserialize_prefixed_hex_string_to_u64,
+    serialize_number_to_prefixed_hex,
+};
+use crate::traits::ToHexString;
 
 pub mod broadcasted_declare_transaction_v1;
 pub mod broadcasted_declare_transaction_v2;
-pub mod broadcasted_deploy_account_transaction;
-pub mod broadcasted_invoke_transaction;
+pub mod broadcasted_declare_transaction_v3;
+pub mod broadcasted_deploy_account_transaction_v1;
+pub mod broadcasted_deploy_account_transaction_v3;
+pub mod broadcasted_invoke_transaction_v1;
+pub mod broadcasted_invoke_transaction_v3;
 
 pub mod declare_transaction_v0v1;
 pub mod declare_transaction_v2;
-pub mod deploy_account_transaction;
+pub mod declare_transaction_v3;
+pub mod deploy_account_transaction_v1;
+pub mod deploy_account_transaction_v3;
 pub mod deploy_transaction;
 pub mod invoke_transaction_v1;
+pub mod invoke_transaction_v3;
 
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(untagged)]
@@ -108,9 +136,20 @@ impl Transaction {
         }
     }
 
+    /// Deploy and L1 handler transactions are not signed, so their signature is empty
+    pub fn get_signature(&self) -> &[Felt] {
+        match self {
+            Transaction::Declare(tx) => tx.get_signature(),
+            Transaction::DeployAccount(tx) => tx.get_signature(),
+            Transaction::Invoke(tx) => tx.get_signature(),
+            Transaction::Deploy(_) | Transaction::L1Handler(_) => &[],
+        }
+    }
+
     pub fn create_common_receipt(
         &self,
         transaction_events: &[Event],
+        transaction_messages: &[MessageToL1],
         block_hash: Option<&BlockHash>,
         block_number: Option<BlockNumber>,
         execution_result: &ExecutionResult,
@@ -120,7 +159,7 @@ impl Transaction {
 
         let output = TransactionOutput {
             actual_fee: self.get_max_fee(),
-            messages_sent: Vec::new(),
+            messages_sent: transaction_messages.to_vec(),
             events: transaction_events.to_vec(),
         };
 
@@ -140,12 +179,13 @@ impl Transaction {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
 #[serde(untagged)]
 pub enum DeclareTransaction {
     Version0(DeclareTransactionV0V1),
     Version1(DeclareTransactionV0V1),
     Version2(DeclareTransactionV2),
+    Version3(DeclareTransactionV3),
 }
 
 impl DeclareTransaction {
@@ -154,6 +194,7 @@ impl DeclareTransaction {
             DeclareTransaction::Version0(tx) => tx.get_max_fee(),
             DeclareTransaction::Version1(tx) => tx.get_max_fee(),
             DeclareTransaction::Version2(tx) => tx.get_max_fee(),
+            DeclareTransaction::Version3(tx) => tx.get_max_fee(),
         }
     }
 
@@ -162,6 +203,94 @@ impl DeclareTransaction {
             DeclareTransaction::Version0(tx) => tx.get_transaction_hash(),
             DeclareTransaction::Version1(tx) => tx.get_transaction_hash(),
             DeclareTransaction::Version2(tx) => tx.get_transaction_hash(),
+            DeclareTransaction::Version3(tx) => tx.get_transaction_hash(),
+        }
+    }
+
+    pub fn get_signature(&self) -> &[Felt] {
+        match self {
+            DeclareTransaction::Version0(tx) | DeclareTransaction::Version1(tx) => &tx.signature,
+            DeclareTransaction::Version2(tx) => &tx.signature,
+            DeclareTransaction::Version3(tx) => &tx.signature,
+        }
+    }
+}
+
+/// Versions 0 and 1 share the same fields, so the variant is chosen by the version, instead of
+/// by the first variant the fields fit into
+impl<'de> Deserialize<'de> for DeclareTransaction {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let json_obj = serde_json::Value::deserialize(deserializer)?;
+        let version: Felt = match json_obj.get("version") {
+            Some(version) => {
+                serde_json::from_value(version.clone()).map_err(serde::de::Error::custom)?
+            }
+            None => return Err(serde::de::Error::missing_field("version")),
+        };
+
+        let transaction = if version == Felt::from(0) {
+            DeclareTransaction::Version0(
+                serde_json::from_value(json_obj).map_err(serde::de::Error::custom)?,
+            )
+        } else if version == Felt::from(1) {
+            DeclareTransaction::Version1(
+                serde_json::from_value(json_obj).map_err(serde::de::Error::custom)?,
+            )
+        } else if version == Felt::from(2) {
+            DeclareTransaction::Version2(
+                serde_json::from_value(json_obj).map_err(serde::de::Error::custom)?,
+            )
+        } else if version == Felt::from(3) {
+            DeclareTransaction::Version3(
+                serde_json::from_value(json_obj).map_err(serde::de::Error::custom)?,
+            )
+        } else {
+            return Err(serde::de::Error::custom(format!(
+                "Unsupported declare transaction version: {}",
+                version.to_prefixed_hex_str()
+            )));
+        };
+
+        Ok(transaction)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum DeployAccountTransaction {
+    Version1(DeployAccountTransactionV1),
+    Version3(DeployAccountTransactionV3),
+}
+
+impl DeployAccountTransaction {
+    pub fn get_max_fee(&self) -> Fee {
+        match self {
+            DeployAccountTransaction::Version1(tx) => tx.get_max_fee(),
+            DeployAccountTransaction::Version3(tx) => tx.get_max_fee(),
+        }
+    }
+
+    pub fn get_transaction_hash(&self) -> &TransactionHash {
+        match self {
+            DeployAccountTransaction::Version1(tx) => tx.get_transaction_hash(),
+            DeployAccountTransaction::Version3(tx) => tx.get_transaction_hash(),
+        }
+    }
+
+    pub fn get_signature(&self) -> &[Felt] {
+        match self {
+            DeployAccountTransaction::Version1(tx) => &tx.signature,
+            DeployAccountTransaction::Version3(tx) => &tx.signature,
+        }
+    }
+
+    pub fn get_contract_address(&self) -> ContractAddress {
+        match self {
+            DeployAccountTransaction::Version1(tx) => tx.contract_address,
+            DeployAccountTransaction::Version3(tx) => tx.contract_address,
         }
     }
 }
@@ -185,6 +314,29 @@ impl InvokeTransactionV0 {
     pub fn get_transaction_hash(&self) -> &TransactionHash {
         &self.transaction_hash
     }
+
+    pub fn create_blockifier_invoke_transaction(
+        &self,
+    ) -> DevnetResult<blockifier::transaction::transactions::InvokeTransaction> {
+        let sn_api_transaction = starknet_api::transaction::InvokeTransactionV0 {
+            max_fee: self.max_fee,
+            signature: starknet_api::transaction::TransactionSignature(
+                self.signature.iter().map(|f| f.into()).collect(),
+            ),
+            contract_address: self.contract_address.try_into()?,
+            entry_point_selector: starknet_api::core::EntryPointSelector(
+                self.entry_point_selector.into(),
+            ),
+            calldata: starknet_api::transaction::Calldata(Arc::new(
+                self.calldata.iter().map(StarkFelt::from).collect(),
+            )),
+        };
+
+        Ok(blockifier::transaction::transactions::InvokeTransaction {
+            tx: starknet_api::transaction::InvokeTransaction::V0(sn_api_transaction),
+            tx_hash: starknet_api::transaction::TransactionHash(self.transaction_hash.into()),
+        })
+    }
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
@@ -192,6 +344,7 @@ impl InvokeTransactionV0 {
 pub enum InvokeTransaction {
     Version0(InvokeTransactionV0),
     Version1(InvokeTransactionV1),
+    Version3(InvokeTransactionV3),
 }
 
 impl InvokeTransaction {
@@ -199,6 +352,7 @@ impl InvokeTransaction {
         match self {
             InvokeTransaction::Version0(tx) => tx.get_max_fee(),
             InvokeTransaction::Version1(tx) => tx.get_max_fee(),
+            InvokeTransaction::Version3(tx) => tx.get_max_fee(),
         }
     }
 
@@ -206,6 +360,15 @@ impl InvokeTransaction {
         match self {
             InvokeTransaction::Version0(tx) => tx.get_transaction_hash(),
             InvokeTransaction::Version1(tx) => tx.get_transaction_hash(),
+            InvokeTransaction::Version3(tx) => tx.get_transaction_hash(),
+        }
+    }
+
+    pub fn get_signature(&self) -> &[Felt] {
+        match self {
+            InvokeTransaction::Version0(tx) => &tx.signature,
+            InvokeTransaction::Version1(tx) => &tx.signature,
+            InvokeTransaction::Version3(tx) => &tx.signature,
         }
     }
 }
@@ -218,9 +381,65 @@ pub struct L1HandlerTransaction {
     pub contract_address: ContractAddress,
     pub entry_point_selector: EntryPointSelector,
     pub calldata: Calldata,
+    /// Fee paid on L1 for the execution of the handler; it is not part of the transaction hash
+    pub paid_fee_on_l1: Fee,
 }
 
 impl L1HandlerTransaction {
+    /// Creates a transaction of version 0 with its hash computed for the network of `chain_id`
+    pub fn new(
+        contract_address: ContractAddress,
+        entry_point_selector: EntryPointSelector,
+        calldata: Calldata,
+        nonce: Nonce,
+        paid_fee_on_l1: Fee,
+        chain_id: Felt,
+    ) -> DevnetResult<Self> {
+        let version = Felt::from(0);
+
+        let transaction_hash: Felt = calculate_transaction_hash_common(
+            TransactionHashPrefix::L1Handler,
+            version.into(),
+            &contract_address.into(),
+            entry_point_selector.into(),
+            calldata.iter().map(Felt252::from).collect::<Vec<Felt252>>().as_slice(),
+            0,
+            chain_id.into(),
+            &[Felt252::from(nonce)],
+        )?
+        .into();
+
+        Ok(Self {
+            transaction_hash,
+            version,
+            nonce,
+            contract_address,
+            entry_point_selector,
+            calldata,
+            paid_fee_on_l1,
+        })
+    }
+
+    pub fn create_blockifier_transaction(
+        &self,
+    ) -> DevnetResult<blockifier::transaction::transactions::L1HandlerTransaction> {
+        Ok(blockifier::transaction::transactions::L1HandlerTransaction {
+            tx: starknet_api::transaction::L1HandlerTransaction {
+                version: starknet_api::transaction::TransactionVersion(self.version.into()),
+                nonce: starknet_api::core::Nonce(self.nonce.into()),
+                contract_address: self.contract_address.try_into()?,
+                entry_point_selector: starknet_api::core::EntryPointSelector(
+                    self.entry_point_selector.into(),
+                ),
+                calldata: starknet_api::transaction::Calldata(Arc::new(
+                    self.calldata.iter().map(StarkFelt::from).collect(),
+                )),
+            },
+            tx_hash: starknet_api::transaction::TransactionHash(self.transaction_hash.into()),
+            paid_fee_on_l1: self.paid_fee_on_l1,
+        })
+    }
+
     pub fn get_max_fee(&self) -> Fee {
         Fee(0)
     }
@@ -240,6 +459,39 @@ pub struct EventFilter {
     pub chunk_size: usize,
 }
 
+/// The finality of a transaction, which may also have been received, but not accepted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FinalityStatus {
+    #[serde(rename = "RECEIVED")]
+    Received,
+    #[serde(rename = "REJECTED")]
+    Rejected,
+    #[serde(rename = "ACCEPTED_ON_L2")]
+    AcceptedOnL2,
+    #[serde(rename = "ACCEPTED_ON_L1")]
+    AcceptedOnL1,
+}
+
+impl From<TransactionFinalityStatus> for FinalityStatus {
+    fn from(value: TransactionFinalityStatus) -> Self {
+        match value {
+            TransactionFinalityStatus::AcceptedOnL2 => FinalityStatus::AcceptedOnL2,
+            TransactionFinalityStatus::AcceptedOnL1 => FinalityStatus::AcceptedOnL1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TransactionStatus {
+    pub finality_status: FinalityStatus,
+    /// Not present if the transaction was not executed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub execution_status: Option<TransactionExecutionStatus>,
+    /// Only present if the transaction was rejected
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failure_reason: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct EventsChunk {
     pub events: Vec<crate::emitted_event::EmittedEvent>,
@@ -262,6 +514,243 @@ pub struct BroadcastedTransactionCommon {
     pub nonce: Nonce,
 }
 
+/// The most a V3 transaction may consume of a resource and pay for one unit of it
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct ResourceBounds {
+    #[serde(
+        serialize_with = "serialize_number_to_prefixed_hex",
+        deserialize_with = "deserialize_prefixed_hex_string_to_u64"
+    )]
+    pub max_amount: u64,
+    #[serde(
+        serialize_with = "serialize_number_to_prefixed_hex",
+        deserialize_with = "deserialize_prefixed_hex_string_to_u128"
+    )]
+    pub max_price_per_unit: u128,
+}
+
+impl ResourceBounds {
+    /// Encodes the bounds together with the short string `resource_name` into a single felt, as
+    /// they are hashed: 64 bits of the name, 64 bits of the amount and 128 bits of the price
+    fn to_hashed_felt(self, resource_name: &[u8]) -> DevnetResult<FieldElement> {
+        let mut bytes = [0u8; 32];
+        bytes[8 - resource_name.len()..8].copy_from_slice(resource_name);
+        bytes[8..16].copy_from_slice(&self.max_amount.to_be_bytes());
+        bytes[16..].copy_from_slice(&self.max_price_per_unit.to_be_bytes());
+
+        Ok(FieldElement::from_bytes_be(&bytes)
+            .map_err(|_| ConversionError::InvalidInternalStructure("resource bounds".into()))?)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct ResourceBoundsMapping {
+    pub l1_gas: ResourceBounds,
+    pub l2_gas: ResourceBounds,
+}
+
+impl ResourceBoundsMapping {
+    /// The fee of a V3 transaction is bounded only by its L1 gas, as L2 gas is not charged yet.
+    /// The product saturates, a bound that big can't be covered by any balance anyway.
+    pub fn max_fee(&self) -> Fee {
+        Fee(u128::from(self.l1_gas.max_amount).saturating_mul(self.l1_gas.max_price_per_unit))
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct Tip(
+    #[serde(
+        serialize_with = "serialize_number_to_prefixed_hex",
+        deserialize_with = "deserialize_prefixed_hex_string_to_u64"
+    )]
+    pub u64,
+);
+
+/// The layer on which the data of the nonce or of the fee of a V3 transaction is kept
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub enum DataAvailabilityMode {
+    #[default]
+    L1,
+    L2,
+}
+
+impl DataAvailabilityMode {
+    fn to_u64(self) -> u64 {
+        match self {
+            DataAvailabilityMode::L1 => 0,
+            DataAvailabilityMode::L2 => 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct BroadcastedTransactionCommonV3 {
+    pub version: TransactionVersion,
+    pub signature: TransactionSignature,
+    pub nonce: Nonce,
+    pub resource_bounds: ResourceBoundsMapping,
+    pub tip: Tip,
+    pub paymaster_data: Vec<Felt>,
+    pub nonce_data_availability_mode: DataAvailabilityMode,
+    pub fee_data_availability_mode: DataAvailabilityMode,
+}
+
+impl BroadcastedTransactionCommonV3 {
+    /// Calculates the Poseidon hash of a V3 transaction: the fields common to all V3
+    /// transactions, starting with `prefix`, are followed by `type_specific_fields`
+    pub(crate) fn calculate_transaction_hash(
+        &self,
+        prefix: &[u8],
+        address: ContractAddress,
+        chain_id: Felt,
+        type_specific_fields: &[FieldElement],
+    ) -> DevnetResult<TransactionHash> {
+        let fee_fields_hash = poseidon_hash_many(&[
+            FieldElement::from(self.tip.0),
+            self.resource_bounds.l1_gas.to_hashed_felt(b"L1_GAS")?,
+            self.resource_bounds.l2_gas.to_hashed_felt(b"L2_GAS")?,
+        ]);
+        let data_availability_modes = FieldElement::from(
+            (self.nonce_data_availability_mode.to_u64() << 32)
+                | self.fee_data_availability_mode.to_u64(),
+        );
+
+        let mut hashed_fields = vec![
+            FieldElement::from_byte_slice_be(prefix)?,
+            self.version.into(),
+            Felt::from(address).into(),
+            fee_fields_hash,
+            poseidon_hash_felts(&self.paymaster_data),
+            chain_id.into(),
+            self.nonce.into(),
+            data_availability_modes,
+        ];
+        hashed_fields.extend_from_slice(type_specific_fields);
+
+        Ok(poseidon_hash_many(&hashed_fields).into())
+    }
+
+    /// V3 transactions are executed as V1 transactions by the execution engine, which predates
+    /// them, with the fee bounded by [`ResourceBoundsMapping::max_fee`]
+    pub(crate) fn get_max_fee(&self) -> Fee {
+        self.resource_bounds.max_fee()
+    }
+}
+
+pub(crate) fn poseidon_hash_felts(felts: &[Felt]) -> FieldElement {
+    poseidon_hash_many(&felts.iter().map(|&felt| felt.into()).collect::<Vec<FieldElement>>())
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum BroadcastedInvokeTransaction {
+    V1(BroadcastedInvokeTransactionV1),
+    V3(BroadcastedInvokeTransactionV3),
+}
+
+impl BroadcastedInvokeTransaction {
+    pub fn get_max_fee(&self) -> Fee {
+        match self {
+            BroadcastedInvokeTransaction::V1(tx) => tx.common.max_fee,
+            BroadcastedInvokeTransaction::V3(tx) => tx.common.get_max_fee(),
+        }
+    }
+
+    pub fn get_fee_unit(&self) -> FeeUnit {
+        match self {
+            BroadcastedInvokeTransaction::V1(_) => FeeUnit::Wei,
+            BroadcastedInvokeTransaction::V3(_) => FeeUnit::Fri,
+        }
+    }
+
+    pub fn create_blockifier_invoke_transaction(
+        &self,
+        chain_id: Felt,
+    ) -> DevnetResult<blockifier::transaction::transactions::InvokeTransaction> {
+        match self {
+            BroadcastedInvokeTransaction::V1(tx) => {
+                tx.create_blockifier_invoke_transaction(chain_id)
+            }
+            BroadcastedInvokeTransaction::V3(tx) => {
+                tx.create_blockifier_invoke_transaction(chain_id)
+            }
+        }
+    }
+
+    pub fn create_invoke_transaction(
+        &self,
+        transaction_hash: TransactionHash,
+    ) -> InvokeTransaction {
+        match self {
+            BroadcastedInvokeTransaction::V1(tx) => {
+                InvokeTransaction::Version1(tx.create_invoke_transaction(transaction_hash))
+            }
+            BroadcastedInvokeTransaction::V3(tx) => {
+                InvokeTransaction::Version3(tx.create_invoke_transaction(transaction_hash))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum BroadcastedDeployAccountTransaction {
+    V1(BroadcastedDeployAccountTransactionV1),
+    V3(BroadcastedDeployAccountTransactionV3),
+}
+
+impl BroadcastedDeployAccountTransaction {
+    pub fn get_max_fee(&self) -> Fee {
+        match self {
+            BroadcastedDeployAccountTransaction::V1(tx) => tx.common.max_fee,
+            BroadcastedDeployAccountTransaction::V3(tx) => tx.common.get_max_fee(),
+        }
+    }
+
+    pub fn get_fee_unit(&self) -> FeeUnit {
+        match self {
+            BroadcastedDeployAccountTransaction::V1(_) => FeeUnit::Wei,
+            BroadcastedDeployAccountTransaction::V3(_) => FeeUnit::Fri,
+        }
+    }
+
+    pub fn get_class_hash(&self) -> &ClassHash {
+        match self {
+            BroadcastedDeployAccountTransaction::V1(tx) => &tx.class_hash,
+            BroadcastedDeployAccountTransaction::V3(tx) => &tx.class_hash,
+        }
+    }
+
+    pub fn create_blockifier_deploy_account(
+        &self,
+        chain_id: Felt,
+    ) -> DevnetResult<blockifier::transaction::transactions::DeployAccountTransaction> {
+        match self {
+            BroadcastedDeployAccountTransaction::V1(tx) => {
+                tx.create_blockifier_deploy_account(chain_id)
+            }
+            BroadcastedDeployAccountTransaction::V3(tx) => {
+                tx.create_blockifier_deploy_account(chain_id)
+            }
+        }
+    }
+
+    pub fn compile_deploy_account_transaction(
+        &self,
+        transaction_hash: &TransactionHash,
+        contract_address: ContractAddress,
+    ) -> DeployAccountTransaction {
+        match self {
+            BroadcastedDeployAccountTransaction::V1(tx) => DeployAccountTransaction::Version1(
+                tx.compile_deploy_account_transaction(transaction_hash, contract_address),
+            ),
+            BroadcastedDeployAccountTransaction::V3(tx) => DeployAccountTransaction::Version3(
+                tx.compile_deploy_account_transaction(transaction_hash, contract_address),
+            ),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(tag = "type")]
 pub enum BroadcastedTransaction {
@@ -274,9 +763,20 @@ pub enum BroadcastedTransaction {
 }
 
 impl BroadcastedTransaction {
+    /// Fees of V3 transactions are paid in FRI, fees of the older versions in WEI
+    pub fn get_fee_unit(&self) -> FeeUnit {
+        match self {
+            BroadcastedTransaction::Invoke(invoke_txn) => invoke_txn.get_fee_unit(),
+            BroadcastedTransaction::Declare(BroadcastedDeclareTransaction::V3(_)) => FeeUnit::Fri,
+            BroadcastedTransaction::Declare(_) => FeeUnit::Wei,
+            BroadcastedTransaction::DeployAccount(deploy_account) => deploy_account.get_fee_unit(),
+        }
+    }
+
     pub fn to_blockifier_account_transaction(
         &self,
         chain_id: Felt,
+        compilation_cache: Option<&CompilationCache>,
     ) -> DevnetResult<blockifier::transaction::account_transaction::AccountTransaction> {
         let blockifier_transaction = match self {
             BroadcastedTransaction::Invoke(invoke_txn) => {
@@ -293,7 +793,14 @@ impl BroadcastedTransaction {
                 )
             }
             BroadcastedTransaction::Declare(BroadcastedDeclareTransaction::V2(declare_v2)) => {
-                AccountTransaction::Declare(declare_v2.create_blockifier_declare(chain_id)?)
+                AccountTransaction::Declare(
+                    declare_v2.create_blockifier_declare(chain_id, compilation_cache)?,
+                )
+            }
+            BroadcastedTransaction::Declare(BroadcastedDeclareTransaction::V3(declare_v3)) => {
+                AccountTransaction::Declare(
+                    declare_v3.create_blockifier_declare(chain_id, compilation_cache)?,
+                )
             }
             BroadcastedTransaction::DeployAccount(deploy_account) => {
                 AccountTransaction::DeployAccount(
@@ -311,6 +818,7 @@ impl BroadcastedTransaction {
 pub enum BroadcastedDeclareTransaction {
     V1(Box<BroadcastedDeclareTransactionV1>),
     V2(Box<BroadcastedDeclareTransactionV2>),
+    V3(Box<BroadcastedDeclareTransactionV3>),
 }
 
 /// Flags that indicate how to simulate a given transaction.
@@ -325,7 +833,7 @@ pub enum SimulationFlag {
     SkipFeeCharge,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum CallType {
     #[serde(rename = "LIBRARY_CALL")]
     LibraryCall,
@@ -333,7 +841,7 @@ pub enum CallType {
     Call,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FunctionInvocation {
     #[serde(flatten)]
     function_call: FunctionCall,
@@ -353,6 +861,7 @@ pub enum TransactionTrace {
     Invoke(InvokeTransactionTrace),
     Declare(DeclareTransactionTrace),
     DeployAccount(DeployAccountTransactionTrace),
+    L1Handler(L1HandlerTransactionTrace),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -360,32 +869,45 @@ pub struct Reversion {
     pub revert_reason: String,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum ExecutionInvocation {
     Succeeded(FunctionInvocation),
     Reverted(Reversion),
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InvokeTransactionTrace {
     pub validate_invocation: Option<FunctionInvocation>,
     pub execution_invocation: ExecutionInvocation,
+    pub fee_transfer_invocation: Option<FunctionInvocation>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DeclareTransactionTrace {
     pub validate_invocation: Option<FunctionInvocation>,
     pub fee_transfer_invocation: Option<FunctionInvocation>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DeployAccountTransactionTrace {
     pub validate_invocation: Option<FunctionInvocation>,
     pub constructor_invocation: Option<FunctionInvocation>,
     pub fee_transfer_invocation: Option<FunctionInvocation>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct L1HandlerTransactionTrace {
+    pub function_invocation: FunctionInvocation,
+}
+
+/// Trace of a transaction included in a block
+#[derive(Debug, Clone, Serialize)]
+pub struct BlockTransactionTrace {
+    pub transaction_hash: TransactionHash,
+    pub trace_root: TransactionTrace,
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct SimulatedTransaction {
     pub transaction_trace: TransactionTrace,
@@ -394,44 +916,41 @@ pub struct SimulatedTransaction {
 
 impl FunctionInvocation {
     pub fn try_from_call_info(
-        mut call_info: blockifier::execution::call_info::CallInfo,
+        call_info: &blockifier::execution::call_info::CallInfo,
         address_to_class_hash: &HashMap<ContractAddress, Felt>,
     ) -> DevnetResult<Self> {
         let mut internal_calls: Vec<FunctionInvocation> = vec![];
-        for internal_call in call_info.inner_calls {
+        for internal_call in call_info.inner_calls.iter() {
             internal_calls.push(FunctionInvocation::try_from_call_info(
                 internal_call,
                 address_to_class_hash,
             )?);
         }
 
-        // the logic for getting the sorted l2-l1 messages
-        // is creating an array with enough room for all objects + 1
-        // then based on the order we use this index
+        // the call info is not modified, as it may be stored with the transaction, so its
+        // l2-l1 messages and events are sorted by their order after being collected
 
-        call_info.execution.l2_to_l1_messages.sort_by_key(|msg| msg.order);
+        let mut ordered_messages: Vec<_> = call_info.execution.l2_to_l1_messages.iter().collect();
+        ordered_messages.sort_by_key(|msg| msg.order);
 
-        let messages: Vec<MessageToL1> = call_info
-            .execution
-            .l2_to_l1_messages
+        let messages: Vec<MessageToL1> = ordered_messages
             .into_iter()
             .map(|msg| MessageToL1 {
                 from_address: call_info.call.caller_address.into(),
                 to_address: msg.message.to_address,
-                payload: msg.message.payload.0.into_iter().map(Felt::from).collect(),
+                payload: msg.message.payload.0.iter().map(|p| Felt::from(*p)).collect(),
             })
             .collect();
 
-        call_info.execution.events.sort_by_key(|event| event.order);
+        let mut ordered_events: Vec<_> = call_info.execution.events.iter().collect();
+        ordered_events.sort_by_key(|event| event.order);
 
-        let events: Vec<Event> = call_info
-            .execution
-            .events
+        let events: Vec<Event> = ordered_events
             .into_iter()
             .map(|event| Event {
                 from_address: call_info.call.storage_address.into(),
-                keys: event.event.keys.into_iter().map(|key| Felt::from(key.0)).collect(),
-                data: event.event.data.0.into_iter().map(Felt::from).collect(),
+                keys: event.event.keys.iter().map(|key| Felt::from(key.0)).collect(),
+                data: event.event.data.0.iter().map(|d| Felt::from(*d)).collect(),
             })
             .collect();
 
@@ -463,7 +982,7 @@ impl FunctionInvocation {
                 blockifier::execution::entry_point::CallType::Call => CallType::Call,
                 blockifier::execution::entry_point::CallType::Delegate => CallType::LibraryCall,
             },
-            result: call_info.execution.retdata.0.into_iter().map(Felt::from).collect(),
+            result: call_info.execution.retdata.0.iter().map(|r| Felt::from(*r)).collect(),
             calls: internal_calls,
             events,
             messages,
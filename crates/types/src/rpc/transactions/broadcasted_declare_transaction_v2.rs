@@ -6,6 +6,7 @@ use starknet_in_rust::core::transaction_hash::calculate_declare_v2_transaction_h
 use starknet_in_rust::SierraContractClass;
 
 use crate::contract_address::ContractAddress;
+use crate::contract_class::compilation_cache::CompilationCache;
 use crate::contract_class::ContractClass;
 use crate::error::DevnetResult;
 use crate::felt::{
@@ -67,7 +68,11 @@ impl BroadcastedDeclareTransactionV2 {
         }
     }
 
-    pub fn create_blockifier_declare(&self, chain_id: Felt) -> DevnetResult<DeclareTransaction> {
+    pub fn create_blockifier_declare(
+        &self,
+        chain_id: Felt,
+        compilation_cache: Option<&CompilationCache>,
+    ) -> DevnetResult<DeclareTransaction> {
         let sierra_class_hash: Felt = compute_sierra_class_hash(&self.contract_class)?.into();
 
         let sn_api_declare = starknet_api::transaction::DeclareTransaction::V2(
@@ -97,7 +102,8 @@ impl BroadcastedDeclareTransactionV2 {
         Ok(DeclareTransaction::new(
             sn_api_declare,
             starknet_api::transaction::TransactionHash(txn_hash.into()),
-            ContractClass::Cairo1(self.contract_class.clone()).try_into()?,
+            ContractClass::Cairo1(self.contract_class.clone())
+                .to_blockifier_contract_class(compilation_cache)?,
         )?)
     }
 }
@@ -185,7 +191,7 @@ mod tests {
         );
 
         let blockifier_declare_transaction = broadcasted_declare_transaction
-            .create_blockifier_declare(ChainId::TestNet.to_felt())
+            .create_blockifier_declare(ChainId::TestNet.to_felt(), None)
             .unwrap();
 
         assert_eq!(
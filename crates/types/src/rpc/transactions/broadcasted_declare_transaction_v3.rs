@@ -0,0 +1,106 @@
+use blockifier::transaction::transactions::DeclareTransaction;
+use serde::{Deserialize, Serialize};
+use starknet_in_rust::core::contract_address::compute_sierra_class_hash;
+use starknet_in_rust::SierraContractClass;
+
+use crate::contract_address::ContractAddress;
+use crate::contract_class::compilation_cache::CompilationCache;
+use crate::contract_class::ContractClass;
+use crate::error::DevnetResult;
+use crate::felt::{ClassHash, CompiledClassHash, Felt, TransactionHash};
+use crate::rpc::transactions::declare_transaction_v3::DeclareTransactionV3;
+use crate::rpc::transactions::{poseidon_hash_felts, BroadcastedTransactionCommonV3};
+use crate::serde_helpers::rpc_sierra_contract_class_to_sierra_contract_class::deserialize_to_sierra_contract_class;
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct BroadcastedDeclareTransactionV3 {
+    #[serde(flatten)]
+    pub common: BroadcastedTransactionCommonV3,
+    #[serde(deserialize_with = "deserialize_to_sierra_contract_class")]
+    pub contract_class: SierraContractClass,
+    pub sender_address: ContractAddress,
+    pub compiled_class_hash: CompiledClassHash,
+    pub account_deployment_data: Vec<Felt>,
+}
+
+impl BroadcastedDeclareTransactionV3 {
+    pub fn generate_class_hash(&self) -> DevnetResult<ClassHash> {
+        Ok(compute_sierra_class_hash(&self.contract_class)?.into())
+    }
+
+    pub fn calculate_transaction_hash(
+        &self,
+        chain_id: Felt,
+        class_hash: ClassHash,
+    ) -> DevnetResult<TransactionHash> {
+        self.common.calculate_transaction_hash(
+            b"declare",
+            self.sender_address,
+            chain_id,
+            &[
+                poseidon_hash_felts(&self.account_deployment_data),
+                class_hash.into(),
+                self.compiled_class_hash.into(),
+            ],
+        )
+    }
+
+    pub fn create_declare(
+        &self,
+        class_hash: ClassHash,
+        transaction_hash: TransactionHash,
+    ) -> DeclareTransactionV3 {
+        DeclareTransactionV3 {
+            class_hash,
+            compiled_class_hash: self.compiled_class_hash,
+            contract_class: self.contract_class.clone(),
+            sender_address: self.sender_address,
+            nonce: self.common.nonce,
+            version: self.common.version,
+            transaction_hash,
+            signature: self.common.signature.clone(),
+            resource_bounds: self.common.resource_bounds,
+            tip: self.common.tip,
+            paymaster_data: self.common.paymaster_data.clone(),
+            account_deployment_data: self.account_deployment_data.clone(),
+            nonce_data_availability_mode: self.common.nonce_data_availability_mode,
+            fee_data_availability_mode: self.common.fee_data_availability_mode,
+        }
+    }
+
+    /// Creates the transaction executed by the execution engine, which does not support V3 yet.
+    /// It is emulated by a declare V2 with the same hash, sender, class and compiled class hash,
+    /// whose max fee is [`ResourceBoundsMapping::max_fee`]. As for invoke V3, the fee is
+    /// charged in FRI only because of the block context the transaction is executed in, and the
+    /// other V3 fields are only hashed.
+    ///
+    /// [`ResourceBoundsMapping::max_fee`]: crate::rpc::transactions::ResourceBoundsMapping::max_fee
+    pub fn create_blockifier_declare(
+        &self,
+        chain_id: Felt,
+        compilation_cache: Option<&CompilationCache>,
+    ) -> DevnetResult<DeclareTransaction> {
+        let class_hash = self.generate_class_hash()?;
+        let txn_hash = self.calculate_transaction_hash(chain_id, class_hash)?;
+
+        let sn_api_declare = starknet_api::transaction::DeclareTransaction::V2(
+            starknet_api::transaction::DeclareTransactionV2 {
+                max_fee: self.common.get_max_fee(),
+                signature: starknet_api::transaction::TransactionSignature(
+                    self.common.signature.iter().map(|&felt| felt.into()).collect(),
+                ),
+                nonce: starknet_api::core::Nonce(self.common.nonce.into()),
+                class_hash: class_hash.into(),
+                compiled_class_hash: self.compiled_class_hash.into(),
+                sender_address: self.sender_address.try_into()?,
+            },
+        );
+
+        Ok(DeclareTransaction::new(
+            sn_api_declare,
+            starknet_api::transaction::TransactionHash(txn_hash.into()),
+            ContractClass::Cairo1(self.contract_class.clone())
+                .to_blockifier_contract_class(compilation_cache)?,
+        )?)
+    }
+}
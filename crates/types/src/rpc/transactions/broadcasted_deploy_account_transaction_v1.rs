@@ -11,11 +11,11 @@ use crate::felt::{
     Calldata, ClassHash, ContractAddressSalt, Felt, Nonce, TransactionHash, TransactionSignature,
     TransactionVersion,
 };
-use crate::rpc::transactions::deploy_account_transaction::DeployAccountTransaction;
+use crate::rpc::transactions::deploy_account_transaction_v1::DeployAccountTransactionV1;
 use crate::rpc::transactions::BroadcastedTransactionCommon;
 
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
-pub struct BroadcastedDeployAccountTransaction {
+pub struct BroadcastedDeployAccountTransactionV1 {
     #[serde(flatten)]
     pub common: BroadcastedTransactionCommon,
     pub contract_address_salt: ContractAddressSalt,
@@ -23,7 +23,7 @@ pub struct BroadcastedDeployAccountTransaction {
     pub class_hash: ClassHash,
 }
 
-impl BroadcastedDeployAccountTransaction {
+impl BroadcastedDeployAccountTransactionV1 {
     pub fn new(
         constructor_calldata: &Calldata,
         max_fee: Fee,
@@ -98,8 +98,8 @@ impl BroadcastedDeployAccountTransaction {
         &self,
         transaction_hash: &TransactionHash,
         contract_address: ContractAddress,
-    ) -> DeployAccountTransaction {
-        DeployAccountTransaction {
+    ) -> DeployAccountTransactionV1 {
+        DeployAccountTransactionV1 {
             transaction_hash: *transaction_hash,
             max_fee: self.common.max_fee,
             version: self.common.version,
@@ -121,7 +121,7 @@ mod tests {
     use crate::chain_id::ChainId;
     use crate::contract_address::ContractAddress;
     use crate::felt::Felt;
-    use crate::rpc::transactions::broadcasted_deploy_account_transaction::BroadcastedDeployAccountTransaction;
+    use crate::rpc::transactions::broadcasted_deploy_account_transaction_v1::BroadcastedDeployAccountTransactionV1;
     use crate::traits::ToHexString;
 
     #[derive(Deserialize)]
@@ -150,7 +150,7 @@ mod tests {
         let feeder_gateway_transaction: FeederGatewayDeployAccountTransaction =
             serde_json::from_value(json_obj.get("transaction").unwrap().clone()).unwrap();
 
-        let broadcasted_tx = BroadcastedDeployAccountTransaction::new(
+        let broadcasted_tx = BroadcastedDeployAccountTransactionV1::new(
             &feeder_gateway_transaction.constructor_calldata,
             Fee(u128::from_str_radix(
                 &feeder_gateway_transaction.max_fee.to_nonprefixed_hex_str(),
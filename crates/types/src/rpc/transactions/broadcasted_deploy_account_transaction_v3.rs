@@ -0,0 +1,161 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use starknet_api::core::calculate_contract_address;
+
+use crate::contract_address::ContractAddress;
+use crate::error::DevnetResult;
+use crate::felt::{Calldata, ClassHash, ContractAddressSalt, Felt, TransactionHash};
+use crate::rpc::transactions::deploy_account_transaction_v3::DeployAccountTransactionV3;
+use crate::rpc::transactions::{poseidon_hash_felts, BroadcastedTransactionCommonV3};
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct BroadcastedDeployAccountTransactionV3 {
+    #[serde(flatten)]
+    pub common: BroadcastedTransactionCommonV3,
+    pub contract_address_salt: ContractAddressSalt,
+    pub constructor_calldata: Calldata,
+    pub class_hash: ClassHash,
+}
+
+impl BroadcastedDeployAccountTransactionV3 {
+    pub fn calculate_transaction_hash(
+        &self,
+        chain_id: Felt,
+        contract_address: ContractAddress,
+    ) -> DevnetResult<TransactionHash> {
+        self.common.calculate_transaction_hash(
+            b"deploy_account",
+            contract_address,
+            chain_id,
+            &[
+                poseidon_hash_felts(&self.constructor_calldata),
+                self.class_hash.into(),
+                self.contract_address_salt.into(),
+            ],
+        )
+    }
+
+    /// Creates the transaction executed by the execution engine, which does not support V3 yet.
+    /// It is emulated by a deploy account V1 with the same hash, class, salt and constructor
+    /// calldata, whose max fee is [`ResourceBoundsMapping::max_fee`]. As for invoke V3, the fee is
+    /// charged in FRI only because of the block context the transaction is executed in, and the
+    /// other V3 fields are only hashed.
+    ///
+    /// [`ResourceBoundsMapping::max_fee`]: crate::rpc::transactions::ResourceBoundsMapping::max_fee
+    pub fn create_blockifier_deploy_account(
+        &self,
+        chain_id: Felt,
+    ) -> DevnetResult<blockifier::transaction::transactions::DeployAccountTransaction> {
+        let constructor_calldata = starknet_api::transaction::Calldata(Arc::new(
+            self.constructor_calldata.iter().map(|felt| felt.into()).collect(),
+        ));
+        let contract_address = calculate_contract_address(
+            starknet_api::transaction::ContractAddressSalt(self.contract_address_salt.into()),
+            starknet_api::core::ClassHash(self.class_hash.into()),
+            &constructor_calldata,
+            starknet_api::core::ContractAddress::from(0u8),
+        )?;
+
+        let transaction_hash =
+            self.calculate_transaction_hash(chain_id, contract_address.into())?;
+
+        let sn_api_transaction = starknet_api::transaction::DeployAccountTransactionV1 {
+            max_fee: self.common.get_max_fee(),
+            signature: starknet_api::transaction::TransactionSignature(
+                self.common.signature.iter().map(|felt| felt.into()).collect(),
+            ),
+            nonce: starknet_api::core::Nonce(self.common.nonce.into()),
+            class_hash: self.class_hash.into(),
+            contract_address_salt: starknet_api::transaction::ContractAddressSalt(
+                self.contract_address_salt.into(),
+            ),
+            constructor_calldata,
+        };
+
+        Ok(blockifier::transaction::transactions::DeployAccountTransaction {
+            tx: starknet_api::transaction::DeployAccountTransaction::V1(sn_api_transaction),
+            tx_hash: starknet_api::transaction::TransactionHash(transaction_hash.into()),
+            contract_address,
+        })
+    }
+
+    pub fn compile_deploy_account_transaction(
+        &self,
+        transaction_hash: &TransactionHash,
+        contract_address: ContractAddress,
+    ) -> DeployAccountTransactionV3 {
+        DeployAccountTransactionV3 {
+            transaction_hash: *transaction_hash,
+            version: self.common.version,
+            signature: self.common.signature.clone(),
+            nonce: self.common.nonce,
+            resource_bounds: self.common.resource_bounds,
+            tip: self.common.tip,
+            paymaster_data: self.common.paymaster_data.clone(),
+            nonce_data_availability_mode: self.common.nonce_data_availability_mode,
+            fee_data_availability_mode: self.common.fee_data_availability_mode,
+            class_hash: self.class_hash,
+            contract_address_salt: self.contract_address_salt,
+            constructor_calldata: self.constructor_calldata.clone(),
+            contract_address,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::chain_id::ChainId;
+    use crate::contract_address::ContractAddress;
+    use crate::rpc::transactions::{
+        BroadcastedDeployAccountTransaction, BroadcastedTransaction, DeployAccountTransaction,
+    };
+
+    #[test]
+    fn v3_is_hashed_with_the_address_it_deploys_to() {
+        let transaction: BroadcastedTransaction = serde_json::from_value(json!({
+            "type": "DEPLOY_ACCOUNT",
+            "version": "0x3",
+            "signature": [],
+            "nonce": "0x0",
+            "resource_bounds": {
+                "l1_gas": { "max_amount": "0x1", "max_price_per_unit": "0x1" },
+                "l2_gas": { "max_amount": "0x0", "max_price_per_unit": "0x0" }
+            },
+            "tip": "0x0",
+            "paymaster_data": [],
+            "nonce_data_availability_mode": "L1",
+            "fee_data_availability_mode": "L2",
+            "contract_address_salt": "0x1",
+            "constructor_calldata": ["0x2"],
+            "class_hash": "0x3"
+        }))
+        .unwrap();
+
+        let deploy_account = match transaction {
+            BroadcastedTransaction::DeployAccount(BroadcastedDeployAccountTransaction::V3(
+                deploy_account,
+            )) => deploy_account,
+            other => panic!("Unexpected transaction: {other:?}"),
+        };
+
+        let chain_id = ChainId::TestNet.to_felt();
+        let blockifier_transaction =
+            deploy_account.create_blockifier_deploy_account(chain_id).unwrap();
+        let contract_address = ContractAddress::from(blockifier_transaction.contract_address);
+        assert_eq!(
+            deploy_account.calculate_transaction_hash(chain_id, contract_address).unwrap(),
+            blockifier_transaction.tx_hash.0.into()
+        );
+
+        let stored =
+            DeployAccountTransaction::Version3(deploy_account.compile_deploy_account_transaction(
+                &blockifier_transaction.tx_hash.0.into(),
+                contract_address,
+            ));
+        assert_eq!(stored.get_contract_address(), contract_address);
+        assert_eq!(stored.get_max_fee().0, 1);
+    }
+}
@@ -0,0 +1,200 @@
+use std::sync::Arc;
+
+use blockifier::transaction::transactions::InvokeTransaction;
+use serde::{Deserialize, Serialize};
+use starknet_api::hash::StarkFelt;
+
+use crate::contract_address::ContractAddress;
+use crate::error::DevnetResult;
+use crate::felt::{Calldata, Felt, TransactionHash};
+use crate::rpc::transactions::invoke_transaction_v3::InvokeTransactionV3;
+use crate::rpc::transactions::{poseidon_hash_felts, BroadcastedTransactionCommonV3};
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct BroadcastedInvokeTransactionV3 {
+    #[serde(flatten)]
+    pub common: BroadcastedTransactionCommonV3,
+    pub sender_address: ContractAddress,
+    pub calldata: Calldata,
+    pub account_deployment_data: Vec<Felt>,
+}
+
+impl BroadcastedInvokeTransactionV3 {
+    pub fn calculate_transaction_hash(&self, chain_id: Felt) -> DevnetResult<TransactionHash> {
+        self.common.calculate_transaction_hash(
+            b"invoke",
+            self.sender_address,
+            chain_id,
+            &[
+                poseidon_hash_felts(&self.account_deployment_data),
+                poseidon_hash_felts(&self.calldata),
+            ],
+        )
+    }
+
+    /// Creates the transaction executed by the execution engine, which does not support V3 yet.
+    /// It is emulated by an invoke V1 with the same hash, signature, nonce and calldata, whose max
+    /// fee is [`ResourceBoundsMapping::max_fee`]. The fee is charged in FRI only because the
+    /// transaction is executed in a block context in which STRK takes the place of ETH. The tip,
+    /// paymaster data, account deployment data and data availability modes are only hashed.
+    ///
+    /// [`ResourceBoundsMapping::max_fee`]: crate::rpc::transactions::ResourceBoundsMapping::max_fee
+    pub fn create_blockifier_invoke_transaction(
+        &self,
+        chain_id: Felt,
+    ) -> DevnetResult<InvokeTransaction> {
+        let txn_hash = self.calculate_transaction_hash(chain_id)?;
+
+        let sn_api_transaction = starknet_api::transaction::InvokeTransactionV1 {
+            max_fee: self.common.get_max_fee(),
+            signature: starknet_api::transaction::TransactionSignature(
+                self.common.signature.iter().map(|f| f.into()).collect(),
+            ),
+            nonce: starknet_api::core::Nonce(self.common.nonce.into()),
+            sender_address: self.sender_address.try_into()?,
+            calldata: starknet_api::transaction::Calldata(Arc::new(
+                self.calldata.iter().map(StarkFelt::from).collect::<Vec<StarkFelt>>(),
+            )),
+        };
+
+        Ok(InvokeTransaction {
+            tx: starknet_api::transaction::InvokeTransaction::V1(sn_api_transaction),
+            tx_hash: starknet_api::transaction::TransactionHash(txn_hash.into()),
+        })
+    }
+
+    pub fn create_invoke_transaction(
+        &self,
+        transaction_hash: TransactionHash,
+    ) -> InvokeTransactionV3 {
+        InvokeTransactionV3 {
+            transaction_hash,
+            version: self.common.version,
+            signature: self.common.signature.clone(),
+            nonce: self.common.nonce,
+            resource_bounds: self.common.resource_bounds,
+            tip: self.common.tip,
+            paymaster_data: self.common.paymaster_data.clone(),
+            account_deployment_data: self.account_deployment_data.clone(),
+            nonce_data_availability_mode: self.common.nonce_data_availability_mode,
+            fee_data_availability_mode: self.common.fee_data_availability_mode,
+            sender_address: self.sender_address,
+            calldata: self.calldata.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use starknet_rs_crypto::poseidon_hash_many;
+    use starknet_rs_ff::FieldElement;
+
+    use crate::chain_id::ChainId;
+    use crate::felt::Felt;
+    use crate::rpc::transactions::broadcasted_invoke_transaction_v3::BroadcastedInvokeTransactionV3;
+    use crate::rpc::transactions::{
+        BroadcastedInvokeTransaction, DataAvailabilityMode, InvokeTransaction,
+    };
+
+    fn broadcasted_invoke_transaction_v3() -> serde_json::Value {
+        json!({
+            "type": "INVOKE",
+            "version": "0x3",
+            "signature": ["0x1", "0x2"],
+            "nonce": "0x5",
+            "resource_bounds": {
+                "l1_gas": { "max_amount": "0x186a0", "max_price_per_unit": "0x5af3107a4000" },
+                "l2_gas": { "max_amount": "0x0", "max_price_per_unit": "0x0" }
+            },
+            "tip": "0x0",
+            "paymaster_data": [],
+            "nonce_data_availability_mode": "L1",
+            "fee_data_availability_mode": "L1",
+            "sender_address": "0x3f47d3911396b6d579fd7848cf576286ab6f96dda977915d6c7b10f3dd2315b",
+            "calldata": ["0xa", "0xb"],
+            "account_deployment_data": []
+        })
+    }
+
+    #[test]
+    fn v3_is_deserialized_from_the_json_of_the_spec() {
+        let transaction: BroadcastedInvokeTransaction =
+            serde_json::from_value(broadcasted_invoke_transaction_v3()).unwrap();
+
+        match transaction {
+            BroadcastedInvokeTransaction::V3(transaction) => {
+                assert_eq!(transaction.common.resource_bounds.l1_gas.max_amount, 100_000);
+                assert_eq!(
+                    transaction.common.resource_bounds.l1_gas.max_price_per_unit,
+                    100_000_000_000_000
+                );
+                assert_eq!(transaction.common.fee_data_availability_mode, DataAvailabilityMode::L1);
+            }
+            other => panic!("Unexpected transaction: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn transaction_hash_is_poseidon_hash_of_the_fields_in_the_order_of_the_spec() {
+        let transaction: BroadcastedInvokeTransactionV3 =
+            serde_json::from_value(broadcasted_invoke_transaction_v3()).unwrap();
+        let chain_id = ChainId::TestNet.to_felt();
+
+        // resource name, max amount and max price per unit are packed into one felt
+        let l1_gas_bounds = FieldElement::from_hex_be(
+            "0x4c315f47415300000000000186a0000000000000000000005af3107a4000",
+        )
+        .unwrap();
+        let l2_gas_bounds = FieldElement::from_hex_be(
+            "0x4c325f474153000000000000000000000000000000000000000000000000",
+        )
+        .unwrap();
+        let expected_hash = poseidon_hash_many(&[
+            FieldElement::from_byte_slice_be(b"invoke").unwrap(),
+            FieldElement::THREE,
+            Felt::from(transaction.sender_address).into(),
+            poseidon_hash_many(&[FieldElement::ZERO, l1_gas_bounds, l2_gas_bounds]),
+            poseidon_hash_many(&[]),
+            chain_id.into(),
+            FieldElement::from(5_u8),
+            FieldElement::ZERO,
+            poseidon_hash_many(&[]),
+            poseidon_hash_many(&[FieldElement::from(10_u8), FieldElement::from(11_u8)]),
+        ]);
+
+        assert_eq!(
+            transaction.calculate_transaction_hash(chain_id).unwrap(),
+            Felt::from(expected_hash)
+        );
+    }
+
+    #[test]
+    fn transaction_hash_depends_on_tip_and_data_availability_modes() {
+        let transaction: BroadcastedInvokeTransactionV3 =
+            serde_json::from_value(broadcasted_invoke_transaction_v3()).unwrap();
+        let chain_id = ChainId::TestNet.to_felt();
+        let transaction_hash = transaction.calculate_transaction_hash(chain_id).unwrap();
+
+        let mut with_tip = transaction.clone();
+        with_tip.common.tip.0 = 1;
+        assert_ne!(with_tip.calculate_transaction_hash(chain_id).unwrap(), transaction_hash);
+
+        let mut with_l2_nonce = transaction;
+        with_l2_nonce.common.nonce_data_availability_mode = DataAvailabilityMode::L2;
+        assert_ne!(with_l2_nonce.calculate_transaction_hash(chain_id).unwrap(), transaction_hash);
+    }
+
+    #[test]
+    fn stored_transaction_is_serialized_and_deserialized_as_v3() {
+        let transaction: BroadcastedInvokeTransactionV3 =
+            serde_json::from_value(broadcasted_invoke_transaction_v3()).unwrap();
+        let stored =
+            InvokeTransaction::Version3(transaction.create_invoke_transaction(Felt::from(1)));
+
+        let serialized = serde_json::to_value(&stored).unwrap();
+        assert_eq!(serialized["resource_bounds"]["l1_gas"]["max_amount"], "0x186a0");
+        assert_eq!(serialized["tip"], "0x0");
+        assert_eq!(serde_json::from_value::<InvokeTransaction>(serialized).unwrap(), stored);
+    }
+}
@@ -1,3 +1,4 @@
+use blockifier::transaction::transactions::DeclareTransaction;
 use serde::{Deserialize, Serialize};
 use starknet_api::transaction::Fee;
 
@@ -30,6 +31,32 @@ impl DeclareTransactionV0V1 {
     pub fn get_transaction_hash(&self) -> &TransactionHash {
         &self.transaction_hash
     }
+
+    /// Creates the blockifier declare transaction of the same version, which is either 0 or 1
+    pub fn create_blockifier_declare(&self) -> DevnetResult<DeclareTransaction> {
+        let sn_api_declare = starknet_api::transaction::DeclareTransactionV0V1 {
+            class_hash: self.class_hash.into(),
+            sender_address: self.sender_address.try_into()?,
+            nonce: starknet_api::core::Nonce(self.nonce.into()),
+            max_fee: self.max_fee,
+            signature: starknet_api::transaction::TransactionSignature(
+                self.signature.iter().map(|&felt| felt.into()).collect(),
+            ),
+        };
+        let sn_api_declare = if self.version == Felt::from(0) {
+            starknet_api::transaction::DeclareTransaction::V0(sn_api_declare)
+        } else {
+            starknet_api::transaction::DeclareTransaction::V1(sn_api_declare)
+        };
+
+        Ok(DeclareTransaction::new(
+            sn_api_declare,
+            starknet_api::transaction::TransactionHash(self.transaction_hash.into()),
+            blockifier::execution::contract_class::ContractClass::V0(
+                self.contract_class.clone().try_into()?,
+            ),
+        )?)
+    }
 }
 
 impl HashProducer for DeclareTransactionV0V1 {
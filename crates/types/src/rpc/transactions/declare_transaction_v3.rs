@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+use starknet_api::transaction::Fee;
+use starknet_in_rust::SierraContractClass;
+
+use crate::contract_address::ContractAddress;
+use crate::felt::{
+    ClassHash, CompiledClassHash, Felt, Nonce, TransactionHash, TransactionSignature,
+    TransactionVersion,
+};
+use crate::rpc::transactions::{DataAvailabilityMode, ResourceBoundsMapping, Tip};
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct DeclareTransactionV3 {
+    pub class_hash: ClassHash,
+    pub compiled_class_hash: CompiledClassHash,
+    pub contract_class: SierraContractClass,
+    pub sender_address: ContractAddress,
+    pub nonce: Nonce,
+    pub version: TransactionVersion,
+    pub transaction_hash: TransactionHash,
+    pub signature: TransactionSignature,
+    pub resource_bounds: ResourceBoundsMapping,
+    pub tip: Tip,
+    pub paymaster_data: Vec<Felt>,
+    pub account_deployment_data: Vec<Felt>,
+    pub nonce_data_availability_mode: DataAvailabilityMode,
+    pub fee_data_availability_mode: DataAvailabilityMode,
+}
+
+impl DeclareTransactionV3 {
+    pub fn get_max_fee(&self) -> Fee {
+        self.resource_bounds.max_fee()
+    }
+
+    pub fn get_transaction_hash(&self) -> &TransactionHash {
+        &self.transaction_hash
+    }
+}
@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+use starknet_api::transaction::Fee;
+
+use crate::contract_address::ContractAddress;
+use crate::error::{DevnetResult, Error};
+use crate::felt::{
+    Calldata, ClassHash, ContractAddressSalt, Felt, Nonce, TransactionHash, TransactionSignature,
+    TransactionVersion,
+};
+use crate::rpc::transactions::{DataAvailabilityMode, ResourceBoundsMapping, Tip};
+use crate::traits::HashProducer;
+
+#[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct DeployAccountTransactionV3 {
+    pub transaction_hash: TransactionHash,
+    pub version: TransactionVersion,
+    pub signature: TransactionSignature,
+    pub nonce: Nonce,
+    pub resource_bounds: ResourceBoundsMapping,
+    pub tip: Tip,
+    pub paymaster_data: Vec<Felt>,
+    pub nonce_data_availability_mode: DataAvailabilityMode,
+    pub fee_data_availability_mode: DataAvailabilityMode,
+    pub class_hash: ClassHash,
+    pub contract_address_salt: ContractAddressSalt,
+    pub constructor_calldata: Calldata,
+    pub contract_address: ContractAddress,
+}
+
+impl DeployAccountTransactionV3 {
+    pub fn get_max_fee(&self) -> Fee {
+        self.resource_bounds.max_fee()
+    }
+
+    pub fn get_transaction_hash(&self) -> &TransactionHash {
+        &self.transaction_hash
+    }
+}
+
+impl HashProducer for DeployAccountTransactionV3 {
+    type Error = Error;
+    fn generate_hash(&self) -> DevnetResult<Felt> {
+        Ok(self.transaction_hash)
+    }
+}
@@ -1,6 +1,11 @@
+use std::sync::Arc;
+
 use serde::{Deserialize, Serialize};
+use starknet_api::core::calculate_contract_address;
 use starknet_api::transaction::Fee;
 
+use crate::contract_address::ContractAddress;
+use crate::error::DevnetResult;
 use crate::felt::{Calldata, ClassHash, ContractAddressSalt, TransactionHash, TransactionVersion};
 
 #[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
@@ -20,4 +25,19 @@ impl DeployTransaction {
     pub fn get_transaction_hash(&self) -> &TransactionHash {
         &self.transaction_hash
     }
+
+    /// The address of the deployed contract. Deploy transactions are not sent by an account, so
+    /// the deployer address is 0.
+    pub fn get_contract_address(&self) -> DevnetResult<ContractAddress> {
+        let contract_address = calculate_contract_address(
+            starknet_api::transaction::ContractAddressSalt(self.contract_address_salt.into()),
+            starknet_api::core::ClassHash(self.class_hash.into()),
+            &starknet_api::transaction::Calldata(Arc::new(
+                self.constructor_calldata.iter().map(|felt| felt.into()).collect(),
+            )),
+            starknet_api::core::ContractAddress::from(0u8),
+        )?;
+
+        Ok(ContractAddress::from(contract_address))
+    }
 }
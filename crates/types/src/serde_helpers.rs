@@ -51,6 +51,8 @@ pub mod rpc_sierra_contract_class_to_sierra_contract_class {
 }
 
 pub mod hex_string {
+    use std::fmt::LowerHex;
+
     use serde::{Deserialize, Deserializer, Serializer};
 
     use crate::contract_address::ContractAddress;
@@ -128,6 +130,40 @@ pub mod hex_string {
         Felt::from_prefixed_hex_str(&buf).map_err(serde::de::Error::custom)
     }
 
+    pub fn serialize_number_to_prefixed_hex<S, T>(number: &T, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: LowerHex,
+    {
+        s.serialize_str(format!("{number:#x}").as_str())
+    }
+
+    pub fn deserialize_prefixed_hex_string_to_u64<'de, D>(deserializer: D) -> Result<u64, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let buf = String::deserialize(deserializer)?;
+
+        u64::from_str_radix(strip_hex_prefix(&buf)?, 16).map_err(serde::de::Error::custom)
+    }
+
+    pub fn deserialize_prefixed_hex_string_to_u128<'de, D>(
+        deserializer: D,
+    ) -> Result<u128, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let buf = String::deserialize(deserializer)?;
+
+        u128::from_str_radix(strip_hex_prefix(&buf)?, 16).map_err(serde::de::Error::custom)
+    }
+
+    fn strip_hex_prefix<E: serde::de::Error>(hex_str: &str) -> Result<&str, E> {
+        hex_str
+            .strip_prefix("0x")
+            .ok_or_else(|| E::custom(format!("Expected a 0x-prefixed hex string, got {hex_str}")))
+    }
+
     #[cfg(test)]
     mod tests {
         use serde::{Deserialize, Serialize};
@@ -139,8 +175,9 @@ pub mod hex_string {
         use crate::patricia_key::PatriciaKey;
         use crate::serde_helpers::hex_string::{
             deserialize_non_prefixed_hex_string_to_felt, deserialize_prefixed_hex_string_to_felt,
-            deserialize_to_prefixed_contract_address, deserialize_to_prefixed_patricia_key,
-            serialize_contract_address_to_prefixed_hex, serialize_to_prefixed_hex,
+            deserialize_prefixed_hex_string_to_u64, deserialize_to_prefixed_contract_address,
+            deserialize_to_prefixed_patricia_key, serialize_contract_address_to_prefixed_hex,
+            serialize_number_to_prefixed_hex, serialize_to_prefixed_hex,
         };
 
         #[test]
@@ -237,6 +274,28 @@ pub mod hex_string {
             assert_eq!(serde_json::to_string(&felt).unwrap(), r#"{"felt":"0x100"}"#);
         }
 
+        #[test]
+        fn number_serialization_round_trip() {
+            #[derive(Serialize, Deserialize)]
+            struct TestNumber {
+                #[serde(
+                    serialize_with = "serialize_number_to_prefixed_hex",
+                    deserialize_with = "deserialize_prefixed_hex_string_to_u64"
+                )]
+                number: u64,
+            }
+
+            let serialized = serde_json::to_value(TestNumber { number: 255 }).unwrap();
+            assert_eq!(serialized, json!({ "number": "0xff" }));
+            assert_eq!(serde_json::from_value::<TestNumber>(serialized).unwrap().number, 255);
+
+            assert!(serde_json::from_value::<TestNumber>(json!({ "number": "ff" })).is_err());
+            assert!(
+                serde_json::from_value::<TestNumber>(json!({ "number": "0x10000000000000000" }))
+                    .is_err()
+            );
+        }
+
         fn check_prefixed_hex_string_and_expected_result(hex_str: &str, is_correct: bool) {
             #[derive(Deserialize)]
             struct TestDeserialization {